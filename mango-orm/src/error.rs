@@ -0,0 +1,355 @@
+//! # Errors
+//!
+//! `Error` - Error type of Mango-ORM, with the context of the Model and field.
+//! `Result` - Result type with `mango_orm::Error`.
+//!
+
+use std::fmt;
+
+/// Result type with `mango_orm::Error`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error type of Mango-ORM.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// match user_profile.delete(None) {
+///     Err(mango_orm::Error::DocumentNotFound { model, .. }) => println!("{}", model),
+///     Err(err) => println!("{}", err),
+///     Ok(output_data) => println!("{}", output_data.is_valid()),
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub enum Error {
+    /// An empty `hash` field is not allowed for the operation.
+    MissingHash { model: String, method: String },
+    /// There is no document in the database for the current `hash` value.
+    DocumentNotFound { model: String, method: String },
+    /// The field is missing from the Model or from the document.
+    MissingField {
+        model: String,
+        field: String,
+        method: String,
+    },
+    /// The widget value (or default value) is not a valid json-line.
+    InvalidWidgetJson {
+        model: String,
+        field: String,
+        message: String,
+    },
+    /// The widget type is not supported for this field.
+    UnsupportedWidget {
+        model: String,
+        field: String,
+        widget: String,
+    },
+    /// The file (or image) is missing on the file system.
+    FileNotFound {
+        model: String,
+        field: String,
+        path: String,
+    },
+    /// Operation is forbidden by Model parameters
+    /// (`is_add_docs`, `is_up_docs`, `is_del_docs`, `is_soft_delete`).
+    PermissionDenied { model: String, operation: String },
+    /// The uploaded file is rejected ( size or MIME type ).
+    InvalidUpload {
+        model: String,
+        field: String,
+        message: String,
+    },
+    /// There is no MongoDB client in the client store.
+    ClientNotFound { model: String, client: String },
+    /// There is no file storage in the storage store.
+    StorageNotFound { model: String, storage: String },
+    /// File storage error.
+    Storage { key: String, message: String },
+    /// Failed to get Model data from cache.
+    Cache { model: String, method: String },
+    /// The value of the field did not pass validation ( the message for the widget ).
+    InvalidValue(String),
+    /// The argument of the method is invalid.
+    InvalidArgument {
+        model: String,
+        method: String,
+        message: String,
+    },
+    /// Invalid parameters of the Monitor, Model, Form or migration step.
+    /// ( `context` - Example: "Model: `UserProfile` > Field: `birthday`" )
+    InvalidConfig { context: String, message: String },
+    /// The technical database of the project has not been created ( `Monitor::migrat` ).
+    MissingTechDatabase { database: String },
+    /// There are collections without a model ( napalm policy `Refuse` ).
+    OrphanedCollections { collections: Vec<String> },
+    /// The archived collection can not be restored.
+    Archive { archive: String, message: String },
+    /// Unknown time zone ( not `UTC` and not from the IANA database ).
+    UnknownTimezone { timezone: String },
+    /// Keyset pagination error ( invalid sort or cursor ).
+    Pagination(String),
+    /// File I/O error.
+    Io(std::io::Error),
+    /// MongoDB driver error.
+    Driver(mongodb::error::Error),
+    /// BSON conversion error.
+    Bson(String),
+    /// JSON conversion error.
+    Json(serde_json::Error),
+    /// Other errors.
+    Other(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHash { model, method } => write!(
+                f,
+                "Model: `{}` > Field: `hash` > Method: `{}` : \
+                 An empty `hash` field is not allowed.",
+                model, method
+            ),
+            Self::DocumentNotFound { model, method } => write!(
+                f,
+                "Model: `{}` > Method: `{}` : Document not found.",
+                model, method
+            ),
+            Self::MissingField {
+                model,
+                field,
+                method,
+            } => write!(
+                f,
+                "Model: `{}` > Field: `{}` > Method: `{}` : This field is missing.",
+                model, field, method
+            ),
+            Self::InvalidWidgetJson {
+                model,
+                field,
+                message,
+            } => write!(
+                f,
+                "Model: `{}` > Field: `{}` : Invalid json-line - {}",
+                model, field, message
+            ),
+            Self::UnsupportedWidget {
+                model,
+                field,
+                widget,
+            } => write!(
+                f,
+                "Model: `{}` > Field: `{}` : Unsupported widget type - `{}`.",
+                model, field, widget
+            ),
+            Self::FileNotFound { model, field, path } => write!(
+                f,
+                "Model: `{}` > Field: `{}` : File is missing - {}",
+                model, field, path
+            ),
+            Self::PermissionDenied { model, operation } => write!(
+                f,
+                "Model: `{}` : It is forbidden to perform {}.",
+                model, operation
+            ),
+            Self::InvalidUpload {
+                model,
+                field,
+                message,
+            } => write!(
+                f,
+                "Model: `{}` > Field: `{}` : Upload rejected - {}",
+                model, field, message
+            ),
+            Self::ClientNotFound { model, client } => write!(
+                f,
+                "Model: `{}` : There is no client `{}` in the client store.",
+                model, client
+            ),
+            Self::StorageNotFound { model, storage } => write!(
+                f,
+                "Model: `{}` : There is no storage `{}` in the storage store.",
+                model, storage
+            ),
+            Self::Storage { key, message } => {
+                write!(f, "Storage > Key: `{}` : {}", key, message)
+            }
+            Self::Cache { model, method } => write!(
+                f,
+                "Model: `{}` -> Method: `{}` : Failed to get data from cache.",
+                model, method
+            ),
+            Self::InvalidValue(msg) => write!(f, "{}", msg),
+            Self::InvalidArgument {
+                model,
+                method,
+                message,
+            } => write!(f, "Model: `{}` > Method: `{}` : {}", model, method, message),
+            Self::InvalidConfig { context, message } => write!(f, "{} : {}", context, message),
+            Self::MissingTechDatabase { database } => write!(
+                f,
+                "The technical database `{}` has not been created for the project.",
+                database
+            ),
+            Self::OrphanedCollections { collections } => write!(
+                f,
+                "Collections without a model: {} ; Napalm policy - `Refuse`. \
+                 Register the models or change the policy.",
+                collections
+                    .iter()
+                    .map(|item| format!("`{}`", item))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Archive { archive, message } => {
+                write!(f, "Archive: `{}` : {}", archive, message)
+            }
+            Self::UnknownTimezone { timezone } => write!(
+                f,
+                "Unknown time zone `{}`. \
+                 Use `UTC` or the name from the IANA database ( Europe/Berlin ).",
+                timezone
+            ),
+            Self::Pagination(msg) => write!(f, "Keyset pagination - {}", msg),
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Driver(err) => write!(f, "{}", err),
+            Self::Bson(msg) => write!(f, "{}", msg),
+            Self::Json(err) => write!(f, "{}", err),
+            Self::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Driver(err) => Some(err),
+            Self::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// Conversion of errors from dependencies.
+// *************************************************************************************************
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<mongodb::error::Error> for Error {
+    fn from(err: mongodb::error::Error) -> Self {
+        Self::Driver(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<mongodb::bson::oid::Error> for Error {
+    fn from(err: mongodb::bson::oid::Error) -> Self {
+        Self::Bson(err.to_string())
+    }
+}
+
+impl From<mongodb::bson::document::ValueAccessError> for Error {
+    fn from(err: mongodb::bson::document::ValueAccessError) -> Self {
+        Self::Bson(err.to_string())
+    }
+}
+
+impl From<mongodb::bson::de::Error> for Error {
+    fn from(err: mongodb::bson::de::Error) -> Self {
+        Self::Bson(err.to_string())
+    }
+}
+
+impl From<mongodb::bson::ser::Error> for Error {
+    fn from(err: mongodb::bson::ser::Error) -> Self {
+        Self::Bson(err.to_string())
+    }
+}
+
+impl From<chrono::ParseError> for Error {
+    fn from(err: chrono::ParseError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(err: std::num::ParseIntError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for Error {
+    fn from(err: std::num::ParseFloatError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<std::str::ParseBoolError> for Error {
+    fn from(err: std::str::ParseBoolError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<std::num::TryFromIntError> for Error {
+    fn from(err: std::num::TryFromIntError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(err: regex::Error) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<argon2::Error> for Error {
+    fn from(err: argon2::Error) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for Error {
+    fn from(err: std::sync::PoisonError<T>) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+// Errors of user code ( migration steps, etc. ) and messages.
+// Hint: `mango_orm::Error` returned by user code is passed as is.
+// -------------------------------------------------------------------------------------------------
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Self {
+        match err.downcast::<Self>() {
+            Ok(err) => *err,
+            Err(err) => Self::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Self {
+        Self::Other(msg)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Self {
+        Self::Other(msg.to_string())
+    }
+}
@@ -2,6 +2,12 @@
 //! To create form of search, form of recover password, combine multiple models, etc.
 //!
 //! `ToForm` - Define form settings for models (widgets, html).
+//! `EmbeddedDoc` - Structure of an embedded document ( Form nested in a Model ).
+//! `TimeRange` - Data structure for `inputTime` and `inputTimeRange` widgets.
+//! `DateTimeZone` - Time zone of `inputDate` and `inputDateTime` widgets.
+//! `Decimal` - Data structure for `numberDecimal`, `rangeDecimal` and `hiddenDecimal` widgets.
+//! `GeoPoint` - Data structure for `inputGeoPoint` widget.
+//! `ListItems` - Data structure for `listText`, `listI32`, `listU32`, `listI64` and `listF64` widgets.
 //! `Widget` - Form controls parameters.
 //! `OutputData` - Output data for the `check()` and `save()` methods.
 //! `TransMapWidgetType` - For transporting of Widget types map to implementation of methods.
@@ -11,10 +17,13 @@
 //!
 
 pub mod caching;
+pub mod embedded;
 pub mod html_controls;
 pub mod output_data;
 pub mod validation;
 
+use crate::{error::Error, forms::embedded::EmbeddedDoc};
+
 // FORMS
 // #################################################################################################
 /// Data structures for `inputFile` and `inputImage` widgets.
@@ -63,6 +72,701 @@ pub struct ImageData {
     pub height: u32, // in pixels
 }
 
+/// Data structure for `inputTime` and `inputTimeRange` widgets.
+/// Time is stored in minutes since midnight ( `inputTime` - Int32, `inputTimeRange` - document ).
+/// Hint: Field value - "09:00" or "09:00-18:00" ( if `end` < `start`, the range crosses midnight ).
+// *************************************************************************************************
+#[derive(Default, serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug)]
+pub struct TimeRange {
+    pub start: i32, // in minutes since midnight
+    pub end: i32,   // in minutes since midnight
+}
+
+impl TimeRange {
+    /// Convert time "HH:MM" to minutes since midnight.
+    // ---------------------------------------------------------------------------------------------
+    pub fn time_to_minutes(value: &str) -> Option<i32> {
+        if !crate::store::REGEX_IS_TIME.is_match(value) {
+            return None;
+        }
+        let hours: i32 = value[..2].parse().ok()?;
+        let minutes: i32 = value[3..].parse().ok()?;
+        Some(hours * 60 + minutes)
+    }
+
+    /// Convert minutes since midnight to time "HH:MM".
+    // ---------------------------------------------------------------------------------------------
+    pub fn minutes_to_time(minutes: i32) -> String {
+        format!("{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+
+    /// Parse the range "HH:MM-HH:MM".
+    // ---------------------------------------------------------------------------------------------
+    pub fn parse(value: &str) -> Option<Self> {
+        if !crate::store::REGEX_IS_TIME_RANGE.is_match(value) {
+            return None;
+        }
+        Some(TimeRange {
+            start: Self::time_to_minutes(&value[..5])?,
+            end: Self::time_to_minutes(&value[6..])?,
+        })
+    }
+
+    /// Get the range in "HH:MM-HH:MM" format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_value(&self) -> String {
+        format!(
+            "{}-{}",
+            Self::minutes_to_time(self.start),
+            Self::minutes_to_time(self.end)
+        )
+    }
+
+    /// Get the range in BSON format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_bson(&self) -> mongodb::bson::Bson {
+        mongodb::bson::Bson::Document(mongodb::bson::doc! {
+            "start": self.start,
+            "end": self.end
+        })
+    }
+
+    /// Get the range from BSON format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_bson(value: &mongodb::bson::Bson) -> Option<Self> {
+        let doc = value.as_document()?;
+        Some(TimeRange {
+            start: doc.get_i32("start").ok()?,
+            end: doc.get_i32("end").ok()?,
+        })
+    }
+
+    /// Validation of the value of `inputTime` or `inputTimeRange` widget
+    /// ( format, range between `min` and `max`, `step` in seconds ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// Returns: value in BSON format or an error message for the client.
+    ///
+    pub fn check_value(widget: &Widget, value: &str) -> Result<mongodb::bson::Bson, Error> {
+        let (times, bson_value): (Vec<i32>, mongodb::bson::Bson) =
+            if widget.widget == "inputTimeRange" {
+                let range: TimeRange = Self::parse(value).ok_or_else(|| {
+                    Error::InvalidValue(
+                        "Incorrect time range format.<br>Example: 09:00-18:00".to_string(),
+                    )
+                })?;
+                if range.start == range.end {
+                    Err(Error::InvalidValue(
+                        "The start and the end of the range must be different.".to_string(),
+                    ))?
+                }
+                (vec![range.start, range.end], range.to_bson())
+            } else {
+                let minutes: i32 = Self::time_to_minutes(value).ok_or_else(|| {
+                    Error::InvalidValue("Incorrect time format.<br>Example: 09:00".to_string())
+                })?;
+                (vec![minutes], mongodb::bson::Bson::Int32(minutes))
+            };
+        // Validation of range (`min` <> `max`).
+        let min: Option<i32> = Self::time_to_minutes(widget.min.as_str());
+        let max: Option<i32> = Self::time_to_minutes(widget.max.as_str());
+        if times.iter().any(|time| {
+            min.map_or(false, |min| *time < min) || max.map_or(false, |max| *time > max)
+        }) {
+            Err(Error::InvalidValue(
+                "Time out of range between `min` and` max`.".to_string(),
+            ))?
+        }
+        // Validation of `step` ( in seconds, counted from `min` ).
+        let step: i32 = widget.step.parse().unwrap_or_default();
+        if step > 60
+            && times
+                .iter()
+                .any(|time| (time - min.unwrap_or_default()) * 60 % step != 0)
+        {
+            Err(Error::InvalidValue(format!(
+                "Time does not match the step of {} seconds.",
+                step
+            )))?
+        }
+        Ok(bson_value)
+    }
+}
+
+/// Time zone of `inputDate` and `inputDateTime` widgets.
+/// Dates are stored in UTC, the time zone is used to parse the values and to render them.
+/// Hint: Time zone - "UTC" or the name from the IANA database ( "Europe/Berlin" ).
+/// Value of `inputDateTime` - "1970-02-28T09:30" or "1970-02-28T09:30:15" ( in the time zone ),
+/// or RFC 3339 with offset - "1970-02-28T09:30:15+03:00", "1970-02-28T06:30:15Z".
+// *************************************************************************************************
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DateTimeZone(pub chrono_tz::Tz);
+
+impl Default for DateTimeZone {
+    fn default() -> Self {
+        DateTimeZone(chrono_tz::Tz::UTC)
+    }
+}
+
+impl DateTimeZone {
+    /// Get the time zone by name ( None - unknown time zone ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(name: &str) -> Option<Self> {
+        name.trim().parse::<chrono_tz::Tz>().ok().map(DateTimeZone)
+    }
+
+    /// Get the time zone of the field.
+    /// Hint: `map_timezones` - <field name, time zone> ( UTC, if the field is missing ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn of_field(
+        map_timezones: &std::collections::HashMap<String, String>,
+        field_name: &str,
+    ) -> Self {
+        map_timezones
+            .get(field_name)
+            .and_then(|name| Self::new(name))
+            .unwrap_or_default()
+    }
+
+    /// Parse the value of `inputDate` or `inputDateTime` widget.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// Returns: date and time in UTC or an error message for the client.
+    ///
+    pub fn parse(
+        &self,
+        widget_type: &str,
+        value: &str,
+    ) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+        use chrono::TimeZone;
+        let naive_dt: chrono::NaiveDateTime = if widget_type == "inputDate" {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|_| {
+                    Error::InvalidValue("Incorrect date format.<br>Example: 1970-02-28".to_string())
+                })?
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        } else {
+            // Seconds are optional.
+            let value: String = match (value.get(..16), value.get(16..)) {
+                (Some(head), Some(tail)) if !tail.starts_with(':') => {
+                    format!("{}:00{}", head, tail)
+                }
+                _ => value.to_string(),
+            };
+            // The offset of the value takes precedence over the time zone.
+            if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value.as_str()) {
+                return Ok(dt.with_timezone(&chrono::Utc));
+            }
+            chrono::NaiveDateTime::parse_from_str(value.as_str(), "%Y-%m-%dT%H:%M:%S%.f").map_err(
+                |_| {
+                    Error::InvalidValue(
+                        "Incorrect date and time format.<br>Example: 1970-02-28T00:00".to_string(),
+                    )
+                },
+            )?
+        };
+        self.0
+            .from_local_datetime(&naive_dt)
+            .earliest()
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .ok_or_else(|| {
+                Error::InvalidValue(format!(
+                    "The time does not exist in the time zone `{}`.",
+                    self.0.name()
+                ))
+            })
+    }
+
+    /// Render the date in the format of `inputDate` or `inputDateTime` widget.
+    /// ( Seconds are added only if they are not equal to zero )
+    // ---------------------------------------------------------------------------------------------
+    pub fn render(&self, widget_type: &str, dt: &chrono::DateTime<chrono::Utc>) -> String {
+        use chrono::Timelike;
+        let dt = dt.with_timezone(&self.0);
+        let fmt: &str = if widget_type == "inputDate" {
+            "%Y-%m-%d"
+        } else if dt.second() != 0 {
+            "%Y-%m-%dT%H:%M:%S"
+        } else {
+            "%Y-%m-%dT%H:%M"
+        };
+        dt.format(fmt).to_string()
+    }
+}
+
+/// Data structure for `numberDecimal`, `rangeDecimal` and `hiddenDecimal` widgets.
+/// The number is stored in Decimal128 ( prices, amounts of money, etc. ).
+/// Hint: Field value - "1234.50" ( `precision` - maximum number of digits,
+/// `scale` - number of digits after the decimal point ).
+// *************************************************************************************************
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct Decimal {
+    pub units: i128, // The number multiplied by 10^scale
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// Parse the number "-1234.50" or "1.2345E+3" ( the scale is taken from the value ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn parse(value: &str) -> Option<Self> {
+        let value: &str = value.trim();
+        if !crate::store::REGEX_IS_DECIMAL.is_match(value) {
+            return None;
+        }
+        let (mantissa, exponent): (&str, i32) = match value.find(|c| c == 'e' || c == 'E') {
+            Some(idx) => (&value[..idx], value[idx + 1..].parse().ok()?),
+            None => (value, 0),
+        };
+        let (is_negative, mantissa): (bool, &str) = match mantissa.strip_prefix('-') {
+            Some(mantissa) => (true, mantissa),
+            None => (false, mantissa.trim_start_matches('+')),
+        };
+        let (int_part, frac_part): (&str, &str) = match mantissa.find('.') {
+            Some(idx) => (&mantissa[..idx], &mantissa[idx + 1..]),
+            None => (mantissa, ""),
+        };
+        let mut digits: String = format!("{}{}", int_part, frac_part);
+        let mut scale: i32 = frac_part.len() as i32 - exponent;
+        if !(-34..=34).contains(&scale) {
+            return None;
+        }
+        if scale < 0 {
+            digits.push_str("0".repeat(-scale as usize).as_str());
+            scale = 0;
+        }
+        // Decimal128 holds up to 34 digits.
+        let digits: &str = digits.trim_start_matches('0');
+        if digits.len() > 34 {
+            return None;
+        }
+        let units: i128 = if digits.is_empty() {
+            0
+        } else {
+            digits.parse().ok()?
+        };
+        Some(Decimal {
+            units: if is_negative { -units } else { units },
+            scale: scale as u32,
+        })
+    }
+
+    /// Change the scale ( None - if the digits after the decimal point are lost ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn with_scale(&self, scale: u32) -> Option<Self> {
+        let units: i128 = if scale >= self.scale {
+            self.units
+                .checked_mul(10_i128.checked_pow(scale - self.scale)?)?
+        } else {
+            let divisor: i128 = 10_i128.checked_pow(self.scale - scale)?;
+            if self.units % divisor != 0 {
+                return None;
+            }
+            self.units / divisor
+        };
+        Some(Decimal { units, scale })
+    }
+
+    /// Get the number of digits ( the digits after the decimal point are included ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn digits(&self) -> u32 {
+        self.units.unsigned_abs().to_string().len() as u32
+    }
+
+    /// Get the number in "-1234.50" format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_value(&self) -> String {
+        let sign: &str = if self.units < 0 { "-" } else { "" };
+        let units: u128 = self.units.unsigned_abs();
+        if self.scale == 0 {
+            return format!("{}{}", sign, units);
+        }
+        let divisor: u128 = 10_u128.pow(self.scale);
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            units / divisor,
+            units % divisor,
+            width = self.scale as usize
+        )
+    }
+
+    /// Get the number in BSON format ( Decimal128 ).
+    /// Hint: The number is encoded in the binary integer decimal format ( IEEE 754-2008 )
+    /// without the `decimal128` feature of bson - it depends on the C library `decNumber`.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_bson(&self) -> mongodb::bson::Bson {
+        // Sign - bit 127, exponent with the bias 6176 - bits 113-126, coefficient - bits 0-112.
+        let exponent: u128 = (6176 - self.scale as i32) as u128;
+        let mut bits: u128 = (exponent << 113) | self.units.unsigned_abs();
+        if self.units < 0 {
+            bits |= 1 << 127;
+        }
+        // Without the feature, Decimal128 can only be read from a document.
+        let mut buffer: Vec<u8> = Vec::with_capacity(24);
+        buffer.extend_from_slice(&24_i32.to_le_bytes());
+        buffer.extend_from_slice(&[0x13, b'v', 0]);
+        buffer.extend_from_slice(&bits.to_le_bytes());
+        buffer.push(0);
+        mongodb::bson::document::Document::from_reader(&mut buffer.as_slice())
+            .ok()
+            .and_then(|doc| doc.get("v").cloned())
+            .expect("The document with one Decimal128 value is always valid.")
+    }
+
+    /// Get the number from Decimal128 ( None - infinity, NaN or out of range ).
+    // ---------------------------------------------------------------------------------------------
+    fn from_decimal128(number: &mongodb::bson::Decimal128) -> Option<Self> {
+        // Without the feature, the bytes of Decimal128 can only be read from a document.
+        let mut buffer: Vec<u8> = Vec::with_capacity(24);
+        mongodb::bson::doc! {"v": mongodb::bson::Bson::Decimal128(number.clone())}
+            .to_writer(&mut buffer)
+            .ok()?;
+        let mut bytes: [u8; 16] = [0; 16];
+        bytes.copy_from_slice(buffer.get(7..23)?);
+        let bits: u128 = u128::from_le_bytes(bytes);
+        // Infinity, NaN and coefficients in the non-canonical form are not used.
+        if (bits >> 125) & 0b11 == 0b11 {
+            return None;
+        }
+        let sign: &str = if bits >> 127 == 1 { "-" } else { "" };
+        let exponent: i32 = ((bits >> 113) & 0x3FFF) as i32 - 6176;
+        let coefficient: u128 = bits & ((1 << 113) - 1);
+        Self::parse(format!("{}{}E{}", sign, coefficient, exponent).as_str())
+    }
+
+    /// Get the number from BSON format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_bson(value: &mongodb::bson::Bson) -> Option<Self> {
+        match value {
+            mongodb::bson::Bson::Decimal128(number) => Self::from_decimal128(number),
+            mongodb::bson::Bson::Int32(number) => Self::parse(number.to_string().as_str()),
+            mongodb::bson::Bson::Int64(number) => Self::parse(number.to_string().as_str()),
+            mongodb::bson::Bson::Double(number) => Self::parse(number.to_string().as_str()),
+            _ => None,
+        }
+    }
+
+    /// Validation of the value of `numberDecimal`, `rangeDecimal` or `hiddenDecimal` widget
+    /// ( format, `precision`, `scale`, range between `min` and `max`, `step` counted from `min` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// Returns: the number in the scale of the widget or an error message for the client.
+    ///
+    pub fn check_value(widget: &Widget, value: &str) -> Result<Self, Error> {
+        let number: Decimal = Self::parse(value)
+            .ok_or_else(|| {
+                Error::InvalidValue("Incorrect number format.<br>Example: 1234.56".to_string())
+            })?
+            .with_scale(widget.scale)
+            .ok_or_else(|| {
+                Error::InvalidValue(format!(
+                    "No more than {} digits after the decimal point.",
+                    widget.scale
+                ))
+            })?;
+        if number.digits() > widget.precision {
+            Err(Error::InvalidValue(format!(
+                "No more than {} digits.",
+                widget.precision
+            )))?
+        }
+        // Validation of range (`min` <> `max`).
+        let to_units = |value: &str| -> Option<i128> {
+            Some(Self::parse(value)?.with_scale(widget.scale)?.units)
+        };
+        let min: Option<i128> = to_units(widget.min.as_str());
+        let max: Option<i128> = to_units(widget.max.as_str());
+        if min.map_or(false, |min| number.units < min)
+            || max.map_or(false, |max| number.units > max)
+        {
+            Err(Error::InvalidValue(format!(
+                "Number {} is out of range (min={} <> max={}).",
+                number.to_value(),
+                widget.min,
+                widget.max
+            )))?
+        }
+        // Validation of `step`.
+        if let Some(step) = to_units(widget.step.as_str()).filter(|step| *step > 0) {
+            if (number.units - min.unwrap_or_default()) % step != 0 {
+                Err(Error::InvalidValue(format!(
+                    "Number {} does not match the step of {}.",
+                    number.to_value(),
+                    widget.step
+                )))?
+            }
+        }
+        Ok(number)
+    }
+}
+
+/// Data structure for `inputGeoPoint` widget.
+/// The point is stored in GeoJSON format - { type: "Point", coordinates: [lng, lat] },
+/// the field is backed by a `2dsphere` index.
+/// Hint: Field value - "52.520008,13.404954" ( latitude, longitude ).
+// *************************************************************************************************
+#[derive(Default, serde::Serialize, serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct GeoPoint {
+    pub lat: f64, // from -90 to 90
+    pub lng: f64, // from -180 to 180
+}
+
+impl GeoPoint {
+    /// Create a point ( None - if the coordinates are out of range ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(lat: f64, lng: f64) -> Option<Self> {
+        if !(-90_f64..=90_f64).contains(&lat) || !(-180_f64..=180_f64).contains(&lng) {
+            return None;
+        }
+        Some(GeoPoint { lat, lng })
+    }
+
+    /// Parse the point "lat,lng" ( without validation of range ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut coordinates = value.split(',').map(|item| item.trim().parse::<f64>());
+        let lat: f64 = coordinates.next()?.ok()?;
+        let lng: f64 = coordinates.next()?.ok()?;
+        if coordinates.next().is_some() || !lat.is_finite() || !lng.is_finite() {
+            return None;
+        }
+        Some(GeoPoint { lat, lng })
+    }
+
+    /// Get the point in "lat,lng" format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_value(&self) -> String {
+        format!("{},{}", self.lat, self.lng)
+    }
+
+    /// Get the point in BSON format ( GeoJSON ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_bson(&self) -> mongodb::bson::Bson {
+        mongodb::bson::Bson::Document(mongodb::bson::doc! {
+            "type": "Point",
+            "coordinates": [self.lng, self.lat]
+        })
+    }
+
+    /// Get the point from BSON format ( GeoJSON ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_bson(value: &mongodb::bson::Bson) -> Option<Self> {
+        let coordinates = value.as_document()?.get_array("coordinates").ok()?;
+        let coordinate = |idx: usize| -> Option<f64> {
+            match coordinates.get(idx)? {
+                mongodb::bson::Bson::Double(number) => Some(*number),
+                mongodb::bson::Bson::Int32(number) => Some(*number as f64),
+                mongodb::bson::Bson::Int64(number) => Some(*number as f64),
+                _ => None,
+            }
+        };
+        Some(GeoPoint {
+            lat: coordinate(1)?,
+            lng: coordinate(0)?,
+        })
+    }
+
+    /// Get a polygon in GeoJSON format, for the `find_within` method.
+    /// ( The ring of the polygon is closed automatically )
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let area = GeoPoint::polygon(&[
+    ///     GeoPoint { lat: 52.6, lng: 13.2 },
+    ///     GeoPoint { lat: 52.6, lng: 13.6 },
+    ///     GeoPoint { lat: 52.4, lng: 13.6 },
+    ///     GeoPoint { lat: 52.4, lng: 13.2 },
+    /// ]);
+    /// ```
+    ///
+    pub fn polygon(points: &[GeoPoint]) -> mongodb::bson::document::Document {
+        let mut ring: Vec<mongodb::bson::Bson> = points
+            .iter()
+            .map(|point| mongodb::bson::bson!([point.lng, point.lat]))
+            .collect();
+        if points.first() != points.last() {
+            ring.push(ring[0].clone());
+        }
+        mongodb::bson::doc! {
+            "type": "Polygon",
+            "coordinates": [ring]
+        }
+    }
+
+    /// Validation of the value of `inputGeoPoint` widget ( format, range of coordinates ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// Returns: the point or an error message for the client.
+    ///
+    pub fn check_value(value: &str) -> Result<Self, Error> {
+        let point: GeoPoint = Self::parse(value).ok_or_else(|| {
+            Error::InvalidValue(
+                "Incorrect coordinates format.<br>Example: 52.520008,13.404954".to_string(),
+            )
+        })?;
+        if !(-90_f64..=90_f64).contains(&point.lat) {
+            Err(Error::InvalidValue(
+                "The latitude must be in the range from -90 to 90.".to_string(),
+            ))?
+        }
+        if !(-180_f64..=180_f64).contains(&point.lng) {
+            Err(Error::InvalidValue(
+                "The longitude must be in the range from -180 to 180.".to_string(),
+            ))?
+        }
+        Ok(point)
+    }
+}
+
+/// Data structure for `listText`, `listI32`, `listU32`, `listI64` and `listF64` widgets.
+/// Free-form list - the value of the widget is a json-line of the array ( `["rust","mongodb"]` ).
+/// Hint: Items of `listText` - `minlength`, `maxlength` and `pattern`,
+/// items of numeric lists - `min` and `max`, the list - `min_items`, `max_items` and `unique_items`.
+// *************************************************************************************************
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct ListItems(pub Vec<serde_json::Value>);
+
+impl ListItems {
+    /// Get the items of the list according to the widget type
+    /// ( text items are trimmed, None - if the items are of a different type ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn parse(widget_type: &str, value: &serde_json::Value) -> Option<Self> {
+        let mut items: Vec<serde_json::Value> = Vec::new();
+        for item in value.as_array()?.iter() {
+            items.push(match widget_type {
+                "listText" => serde_json::Value::from(item.as_str()?.trim()),
+                "listI32" => {
+                    let number: i64 = item.as_i64()?;
+                    if number < i32::MIN as i64 || number > i32::MAX as i64 {
+                        return None;
+                    }
+                    serde_json::Value::from(number)
+                }
+                "listU32" => {
+                    let number: u64 = item.as_u64()?;
+                    if number > u32::MAX as u64 {
+                        return None;
+                    }
+                    serde_json::Value::from(number)
+                }
+                "listI64" => serde_json::Value::from(item.as_i64()?),
+                "listF64" => serde_json::Value::from(item.as_f64()?),
+                _ => return None,
+            });
+        }
+        Some(ListItems(items))
+    }
+
+    /// Get the json-line of the list.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_value(&self) -> String {
+        serde_json::to_string(&self.0).unwrap_or_default()
+    }
+
+    /// Get the list in BSON format.
+    /// ( `listI32` - Int32, `listU32` and `listI64` - Int64, `listF64` - Double )
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_bson(&self, widget_type: &str) -> mongodb::bson::Bson {
+        mongodb::bson::Bson::Array(
+            self.0
+                .iter()
+                .map(|item| match widget_type {
+                    "listText" => mongodb::bson::Bson::String(item.as_str().unwrap().to_string()),
+                    "listI32" => mongodb::bson::Bson::Int32(item.as_i64().unwrap() as i32),
+                    "listF64" => mongodb::bson::Bson::Double(item.as_f64().unwrap()),
+                    _ => mongodb::bson::Bson::Int64(item.as_i64().unwrap()),
+                })
+                .collect(),
+        )
+    }
+
+    /// Validation of the value of the list widget ( type of items, number of items,
+    /// `minlength`, `maxlength` and `pattern` or `min` and `max` of items, `unique_items` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// Returns: the list or an error message for the client.
+    ///
+    pub fn check_value(widget: &Widget, value: &serde_json::Value) -> Result<Self, Error> {
+        let is_text: bool = widget.widget == "listText";
+        let list: ListItems = Self::parse(widget.widget.as_str(), value).ok_or_else(|| {
+            Error::InvalidValue(if is_text {
+                "Incorrect list format.<br>Example: [\"rust\",\"mongodb\"]".to_string()
+            } else {
+                "Incorrect list format.<br>Example: [1,2,3]".to_string()
+            })
+        })?;
+        // Validation of the number of items.
+        let count: u32 = list.0.len() as u32;
+        if count < widget.min_items {
+            Err(Error::InvalidValue(format!(
+                "At least {} items.",
+                widget.min_items
+            )))?
+        }
+        if widget.max_items > 0 && count > widget.max_items {
+            Err(Error::InvalidValue(format!(
+                "No more than {} items.",
+                widget.max_items
+            )))?
+        }
+        // Validation of items.
+        let re_pattern: Option<regex::Regex> = if is_text && !widget.pattern.is_empty() {
+            Some(regex::Regex::new(
+                format!("^(?:{})$", widget.pattern).as_str(),
+            )?)
+        } else {
+            None
+        };
+        let min: Option<f64> = widget.min.parse().ok();
+        let max: Option<f64> = widget.max.parse().ok();
+        for (idx, item) in list.0.iter().enumerate() {
+            if let Some(text) = item.as_str() {
+                let len: usize = text.encode_utf16().count();
+                if len == 0 {
+                    Err(Error::InvalidValue(format!("Item {} is empty.", idx + 1)))?
+                }
+                if len < widget.minlength || (widget.maxlength > 0 && len > widget.maxlength) {
+                    Err(Error::InvalidValue(format!(
+                        "Item {}: Length {} is out of range (min={} <> max={}).",
+                        idx + 1,
+                        len,
+                        widget.minlength,
+                        widget.maxlength
+                    )))?
+                }
+                if re_pattern.as_ref().map_or(false, |re| !re.is_match(text)) {
+                    Err(Error::InvalidValue(format!(
+                        "Item {} does not match the pattern.",
+                        idx + 1
+                    )))?
+                }
+            } else {
+                let number: f64 = item.as_f64().unwrap_or_default();
+                if min.map_or(false, |min| number < min) || max.map_or(false, |max| number > max) {
+                    Err(Error::InvalidValue(format!(
+                        "Item {}: Number {} is out of range (min={} <> max={}).",
+                        idx + 1,
+                        item,
+                        widget.min,
+                        widget.max
+                    )))?
+                }
+            }
+            // Validation of `unique_items`.
+            if widget.unique_items && list.0[..idx].contains(item) {
+                Err(Error::InvalidValue(format!(
+                    "Item {} is repeated.",
+                    idx + 1
+                )))?
+            }
+        }
+        Ok(list)
+    }
+}
+
 /// Widget.
 /// ( Form controls parameters )
 // *************************************************************************************************
@@ -89,6 +793,12 @@ pub struct Widget {
     pub max: String,
     pub options: Vec<(String, String)>, // Hint: <value, Title> - <option value="value1">Title 1</option>
     pub thumbnails: Vec<(String, u32)>,
+    pub maxsize: u32,        // Maximum file size in bytes ( 0 - without limit )
+    pub precision: u32,      // Maximum number of digits of the decimal number ( 1-34 )
+    pub scale: u32,          // Number of digits after the decimal point
+    pub min_items: u32,      // Minimum number of items of the list ( 0 - without limit )
+    pub max_items: u32,      // Maximum number of items of the list ( 0 - without limit )
+    pub unique_items: bool,  // The items of the list must not be repeated
     pub other_attrs: String, // "autofocus tabindex=\"some number\" size=\"some number\" ..."
     pub css_classes: String, // Hint: "class-name class-name ..."
     pub hint: String,
@@ -121,6 +831,12 @@ impl Default for Widget {
             max: String::new(),
             options: Vec::new(),
             thumbnails: Vec::new(),
+            maxsize: 0_u32,
+            precision: 34_u32,
+            scale: 2_u32,
+            min_items: 0_u32,
+            max_items: 0_u32,
+            unique_items: false,
             other_attrs: String::new(),
             css_classes: String::new(),
             hint: String::new(),
@@ -152,14 +868,57 @@ pub trait ToForm {
 
     /// Get fields name list.
     // ---------------------------------------------------------------------------------------------
-    fn fields_name() -> Result<Vec<String>, Box<dyn std::error::Error>>;
+    fn fields_name() -> Result<Vec<String>, Error>;
 
     /// Get map of widgets for Form fields.
     /// Hint: Vec<field name, Widget>
     // ---------------------------------------------------------------------------------------------
-    fn widgets() -> Result<std::collections::HashMap<String, Widget>, Box<dyn std::error::Error>>;
+    fn widgets() -> Result<std::collections::HashMap<String, Widget>, Error>;
+
+    /// Get map of embedded documents for Form fields.
+    /// Hint: <field name, EmbeddedDoc> - For widgets `embeddedDoc` and `embeddedDocMult`.
+    // ---------------------------------------------------------------------------------------------
+    fn embedded_docs() -> Result<std::collections::HashMap<String, EmbeddedDoc>, Error>;
+
+    /// Get the structure of the Form as an embedded document.
+    /// ( Passwords, files, images and `unique` fields are not supported in embedded documents )
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let embedded_doc = Address::embedded_doc()?;
+    /// println!("{:?}", embedded_doc);
+    /// ```
+    ///
+    fn embedded_doc() -> Result<EmbeddedDoc, Error> {
+        let form_name: String = Self::form_name();
+        let map_widgets: std::collections::HashMap<String, Widget> = Self::widgets()?;
+        // Check the widgets for compatibility with embedded documents.
+        for (field_name, widget) in map_widgets.iter() {
+            match widget.widget.as_str() {
+                "inputPassword" | "inputFile" | "inputImage" => Err(Error::UnsupportedWidget {
+                    model: form_name.clone(),
+                    field: field_name.clone(),
+                    widget: widget.widget.clone(),
+                })?,
+                _ if widget.unique => Err(Error::InvalidConfig {
+                    context: format!("Form: `{}` > Field: `{}`", form_name, field_name),
+                    message: "The `unique` parameter is not supported in embedded documents."
+                        .to_string(),
+                })?,
+                _ => {}
+            }
+        }
+        Ok(EmbeddedDoc {
+            form_name,
+            fields_name: Self::fields_name()?,
+            map_widgets,
+            map_embedded: Self::embedded_docs()?,
+        })
+    }
 
     /// Serialize Form to json-line.
     // ---------------------------------------------------------------------------------------------
-    fn self_to_json(&self) -> Result<serde_json::value::Value, Box<dyn std::error::Error>>;
+    fn self_to_json(&self) -> Result<serde_json::value::Value, Error>;
 }
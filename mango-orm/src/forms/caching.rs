@@ -13,6 +13,7 @@
 //!
 
 use crate::{
+    error::Error,
     forms::{html_controls::HtmlControls, ToForm, Widget},
     store::{FormCache, FORM_STORE},
 };
@@ -22,7 +23,7 @@ use crate::{
 pub trait CachingForm: ToForm + HtmlControls {
     /// Add map of widgets to cache.
     // ---------------------------------------------------------------------------------------------
-    fn widgets_to_cache() -> Result<(), Box<dyn std::error::Error>> {
+    fn widgets_to_cache() -> Result<(), Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get write access in cache.
@@ -49,7 +50,7 @@ pub trait CachingForm: ToForm + HtmlControls {
     /// println!("{:?}", widgets_map);
     /// ```
     ///
-    fn form_wig() -> Result<std::collections::HashMap<String, Widget>, Box<dyn std::error::Error>> {
+    fn form_wig() -> Result<std::collections::HashMap<String, Widget>, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
@@ -67,10 +68,10 @@ pub trait CachingForm: ToForm + HtmlControls {
         if let Some(form_cache) = form_store.get(key.as_str()) {
             Ok(form_cache.map_widgets.clone())
         } else {
-            Err(format!(
-                "Form: `{}` -> Method: `form_wig()` : Failed to get data from cache.",
-                Self::form_name()
-            ))?
+            Err(Error::Cache {
+                model: Self::form_name(),
+                method: "form_wig()".to_string(),
+            })?
         }
     }
 
@@ -84,7 +85,7 @@ pub trait CachingForm: ToForm + HtmlControls {
     /// println!("{}", json_line);
     /// ```
     ///
-    fn form_json() -> Result<String, Box<dyn std::error::Error>> {
+    fn form_json() -> Result<String, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
@@ -112,10 +113,10 @@ pub trait CachingForm: ToForm + HtmlControls {
             }
             Ok(form_cache.form_json.clone())
         } else {
-            Err(format!(
-                "Form: `{}` -> Method: `form_json()` : Failed to get data from cache.",
-                Self::form_name()
-            ))?
+            Err(Error::Cache {
+                model: Self::form_name(),
+                method: "form_json()".to_string(),
+            })?
         }
     }
 
@@ -129,7 +130,7 @@ pub trait CachingForm: ToForm + HtmlControls {
     /// println!("{}", html);
     /// ```
     ///
-    fn form_html() -> Result<String, Box<dyn std::error::Error>> {
+    fn form_html() -> Result<String, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
@@ -158,10 +159,10 @@ pub trait CachingForm: ToForm + HtmlControls {
             }
             Ok(form_cache.form_html.clone())
         } else {
-            Err(format!(
-                "Form: `{}` -> Method: `form_html()` : Failed to get data from cache.",
-                Self::form_name()
-            ))?
+            Err(Error::Cache {
+                model: Self::form_name(),
+                method: "form_html()".to_string(),
+            })?
         }
     }
 
@@ -175,7 +176,7 @@ pub trait CachingForm: ToForm + HtmlControls {
     /// println!("{:?}", form_cache);
     /// ```
     ///
-    fn get_cache_data() -> Result<FormCache, Box<dyn std::error::Error>> {
+    fn get_cache_data() -> Result<FormCache, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
@@ -193,10 +194,10 @@ pub trait CachingForm: ToForm + HtmlControls {
         if let Some(form_cache) = form_store.get(key.as_str()) {
             Ok(form_cache.clone())
         } else {
-            Err(format!(
-                "Form: `{}` -> Method: `get_cache_data()` : Failed to get data from cache.",
-                Self::form_name()
-            ))?
+            Err(Error::Cache {
+                model: Self::form_name(),
+                method: "get_cache_data()".to_string(),
+            })?
         }
     }
 }
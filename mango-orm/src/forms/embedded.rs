@@ -0,0 +1,544 @@
+//! # Embedded documents.
+//! Forms nested in Models ( widgets `embeddedDoc` and `embeddedDocMult` ).
+//!
+//! `EmbeddedDoc` - Structure of an embedded document (fields, widgets, nested documents).
+//!
+//! Methods:
+//! `check` - Validation of an embedded document.
+//! `check_field` - Validation of the value of a field with an embedded document.
+//! `default_doc` - Get a document with default values.
+//! `structure` - Get the structure of an embedded document.
+//! `merge_value` - Bring the value of a field in line with the current structure.
+//!
+
+use std::convert::TryFrom;
+
+use crate::{
+    error::Error,
+    forms::{ListItems, TimeRange, Widget},
+    models::validation::ValidationModel,
+};
+use mongodb::bson::{document::Document, Bson};
+
+/// Structure of an embedded document.
+/// ( The fields of the nested Form, their widgets and nested embedded documents )
+// *************************************************************************************************
+#[derive(serde::Serialize, serde::Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct EmbeddedDoc {
+    pub form_name: String,
+    pub fields_name: Vec<String>,
+    pub map_widgets: std::collections::HashMap<String, Widget>,
+    // <field_name, EmbeddedDoc> - For nested embedded documents.
+    pub map_embedded: std::collections::HashMap<String, EmbeddedDoc>,
+}
+
+impl ValidationModel for EmbeddedDoc {}
+
+impl EmbeddedDoc {
+    /// Validation of an embedded document.
+    /// ( The state of the fields is saved in the widgets )
+    // *********************************************************************************************
+    ///
+    /// Returns: (is_err_symptom, document)
+    ///
+    pub fn check(&mut self, value: &serde_json::value::Value) -> Result<(bool, Document), Error> {
+        let form_name: String = self.form_name.clone();
+        if !value.is_object() {
+            Err(Error::InvalidValue(format!(
+                "Form: `{}` > Method: `check()` : Expected an embedded document.",
+                form_name
+            )))?
+        }
+        // User input error detection symptom.
+        let mut is_err_symptom = false;
+        // Document for the final result.
+        let mut final_doc = Document::new();
+        let json_null = serde_json::value::Value::Null;
+
+        // Loop over fields for validation.
+        for field_name in self.fields_name.iter() {
+            // Get field value for validation.
+            let pre_json_value: &serde_json::value::Value =
+                value.get(field_name).unwrap_or(&json_null);
+            let final_widget: &mut Widget = self.map_widgets.get_mut(field_name).unwrap();
+            let widget_type: &str = &final_widget.widget.clone()[..];
+
+            // Field validation.
+            let bson_field_value: Bson = match widget_type {
+                // Validation of text and date type fields.
+                // *********************************************************************************
+                "radioText" | "inputColor" | "inputEmail" | "inputPhone" | "inputText"
+                | "inputUrl" | "inputIP" | "inputIPv4" | "inputIPv6" | "textArea"
+                | "hiddenText" | "inputDate" | "inputDateTime" | "inputTime" | "inputTimeRange" => {
+                    let field_value: String = pre_json_value
+                        .as_str()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string();
+                    final_widget.value = field_value.clone();
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_doc.insert(field_name.clone(), Bson::Null);
+                        continue;
+                    }
+                    let mut errors: Vec<String> = Vec::new();
+                    if let Err(err) = Self::check_minlength(final_widget.minlength, &field_value) {
+                        errors.push(err.to_string());
+                    }
+                    if let Err(err) = Self::check_maxlength(final_widget.maxlength, &field_value) {
+                        errors.push(err.to_string());
+                    }
+                    if let Err(err) = Self::regex_validation(widget_type, &field_value) {
+                        errors.push(err.to_string());
+                    }
+                    // Dates are stored in ISO format, so they are compared as strings.
+                    if errors.is_empty()
+                        && (widget_type == "inputDate" || widget_type == "inputDateTime")
+                        && ((!final_widget.min.is_empty() && field_value < final_widget.min)
+                            || (!final_widget.max.is_empty() && field_value > final_widget.max))
+                    {
+                        errors.push("Date out of range between `min` and` max`.".to_string());
+                    }
+                    // Time is stored in "HH:MM" format, the range - in "HH:MM-HH:MM" format.
+                    if errors.is_empty()
+                        && (widget_type == "inputTime" || widget_type == "inputTimeRange")
+                    {
+                        if let Err(err) = TimeRange::check_value(final_widget, &field_value) {
+                            errors.push(err.to_string());
+                        }
+                    }
+                    for err in errors {
+                        is_err_symptom = true;
+                        final_widget.error = Self::accumula_err(final_widget, &err)?;
+                    }
+                    Bson::String(field_value)
+                }
+                // Validation of `select` type fields.
+                // *********************************************************************************
+                "selectText" | "selectI32" | "selectU32" | "selectI64" | "selectF64" => {
+                    if pre_json_value.is_null() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_widget.value = String::new();
+                        Bson::Null
+                    } else {
+                        match widget_type {
+                            "selectText" => {
+                                let val = pre_json_value.as_str().unwrap_or_default().to_string();
+                                final_widget.value = val.clone();
+                                if val.is_empty() && final_widget.required {
+                                    is_err_symptom = true;
+                                    final_widget.error = Self::accumula_err(
+                                        final_widget,
+                                        &"Required field.".to_owned(),
+                                    )?;
+                                }
+                                Bson::String(val)
+                            }
+                            "selectI32" => {
+                                let val = i32::try_from(pre_json_value.as_i64().unwrap())?;
+                                final_widget.value = val.to_string();
+                                Bson::Int32(val)
+                            }
+                            "selectU32" | "selectI64" => {
+                                let val = pre_json_value.as_i64().unwrap();
+                                final_widget.value = val.to_string();
+                                Bson::Int64(val)
+                            }
+                            _ => {
+                                let val = pre_json_value.as_f64().unwrap();
+                                final_widget.value = val.to_string();
+                                Bson::Double(val)
+                            }
+                        }
+                    }
+                }
+                "selectTextMult" | "selectI32Mult" | "selectU32Mult" | "selectI64Mult"
+                | "selectF64Mult" => {
+                    if pre_json_value.is_null() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_widget.value = String::new();
+                        Bson::Null
+                    } else {
+                        final_widget.value = serde_json::to_string(&pre_json_value)?;
+                        let items = pre_json_value.as_array().unwrap().iter();
+                        Bson::Array(match widget_type {
+                            "selectTextMult" => items
+                                .map(|item| Bson::String(item.as_str().unwrap().into()))
+                                .collect(),
+                            "selectI32Mult" => items
+                                .map(|item| Bson::Int32(item.as_i64().unwrap() as i32))
+                                .collect(),
+                            "selectU32Mult" | "selectI64Mult" => items
+                                .map(|item| Bson::Int64(item.as_i64().unwrap()))
+                                .collect(),
+                            _ => items
+                                .map(|item| Bson::Double(item.as_f64().unwrap()))
+                                .collect(),
+                        })
+                    }
+                }
+                // Validation of list type fields.
+                // *********************************************************************************
+                "listText" | "listI32" | "listU32" | "listI64" | "listF64" => {
+                    if pre_json_value
+                        .as_array()
+                        .is_none_or(|items| items.is_empty())
+                    {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_widget.value = String::new();
+                        Bson::Null
+                    } else {
+                        final_widget.value = serde_json::to_string(&pre_json_value)?;
+                        match ListItems::check_value(final_widget, pre_json_value) {
+                            Ok(items) => {
+                                final_widget.value = items.to_value();
+                                items.to_bson(widget_type)
+                            }
+                            Err(err) => {
+                                is_err_symptom = true;
+                                final_widget.error =
+                                    Self::accumula_err(final_widget, &err.to_string())?;
+                                Bson::Null
+                            }
+                        }
+                    }
+                }
+                // Validation of number type fields.
+                // *********************************************************************************
+                "radioI32" | "numberI32" | "rangeI32" | "hiddenI32" | "radioU32" | "numberU32"
+                | "rangeU32" | "hiddenU32" | "radioI64" | "numberI64" | "rangeI64"
+                | "hiddenI64" => {
+                    if pre_json_value.is_null() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_widget.value = String::new();
+                        final_doc.insert(field_name.clone(), Bson::Null);
+                        continue;
+                    }
+                    let field_value: i64 = pre_json_value.as_i64().unwrap();
+                    final_widget.value = field_value.to_string();
+                    // Validation of range (`min` <> `max`).
+                    let min: i64 = final_widget.min.parse().unwrap_or_default();
+                    let max: i64 = final_widget.max.parse().unwrap_or_default();
+                    if (min != 0_i64 || max != 0_i64) && (field_value < min || field_value > max) {
+                        is_err_symptom = true;
+                        let msg = format!(
+                            "Number {} is out of range (min={} <> max={}).",
+                            field_value, min, max
+                        );
+                        final_widget.error = Self::accumula_err(final_widget, &msg)?;
+                    }
+                    if widget_type.contains("I32") {
+                        Bson::Int32(i32::try_from(field_value)?)
+                    } else {
+                        Bson::Int64(field_value)
+                    }
+                }
+                "radioF64" | "numberF64" | "rangeF64" | "hiddenF64" => {
+                    if pre_json_value.is_null() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(final_widget, &"Required field.".to_owned())?;
+                        }
+                        final_widget.value = String::new();
+                        final_doc.insert(field_name.clone(), Bson::Null);
+                        continue;
+                    }
+                    let field_value: f64 = pre_json_value.as_f64().unwrap();
+                    final_widget.value = field_value.to_string();
+                    // Validation of range (`min` <> `max`).
+                    let min: f64 = final_widget.min.parse().unwrap_or_default();
+                    let max: f64 = final_widget.max.parse().unwrap_or_default();
+                    if (min != 0_f64 || max != 0_f64) && (field_value < min || field_value > max) {
+                        is_err_symptom = true;
+                        let msg = format!(
+                            "Number {} is out of range (min={} <> max={}).",
+                            field_value, min, max
+                        );
+                        final_widget.error = Self::accumula_err(final_widget, &msg)?;
+                    }
+                    Bson::Double(field_value)
+                }
+                // Validation of boolean type fields.
+                // *********************************************************************************
+                "checkBox" => {
+                    let field_value: bool = if !pre_json_value.is_null() {
+                        pre_json_value.as_bool().unwrap()
+                    } else if final_widget.required {
+                        is_err_symptom = true;
+                        final_widget.error = Self::accumula_err(
+                            final_widget,
+                            &"You must definitely choose.".to_owned(),
+                        )?;
+                        false
+                    } else {
+                        // Apply the value default.
+                        final_widget.checked
+                    };
+                    final_widget.checked = field_value;
+                    Bson::Boolean(field_value)
+                }
+                // Validation of nested embedded documents.
+                // *********************************************************************************
+                "embeddedDoc" | "embeddedDocMult" => {
+                    let embedded: &EmbeddedDoc =
+                        self.map_embedded
+                            .get(field_name)
+                            .ok_or_else(|| Error::MissingField {
+                                model: form_name.clone(),
+                                field: field_name.clone(),
+                                method: "embedded_docs()".to_string(),
+                            })?;
+                    let (is_err, bson_value) =
+                        embedded.check_field(final_widget, pre_json_value)?;
+                    if is_err {
+                        is_err_symptom = true;
+                    }
+                    bson_value
+                }
+                _ => Err(Error::UnsupportedWidget {
+                    model: form_name.clone(),
+                    field: field_name.clone(),
+                    widget: widget_type.to_string(),
+                })?,
+            };
+            final_doc.insert(field_name.clone(), bson_field_value);
+        }
+
+        Ok((is_err_symptom, final_doc))
+    }
+
+    /// Validation of the value of a field with the `embeddedDoc` or `embeddedDocMult` widget.
+    /// ( The state of embedded documents is saved in `widget.value` as a Json-line )
+    // *********************************************************************************************
+    ///
+    /// Returns: (is_err_symptom, value in BSON format)
+    ///
+    pub fn check_field(
+        &self,
+        widget: &mut Widget,
+        value: &serde_json::value::Value,
+    ) -> Result<(bool, Bson), Error> {
+        let is_mult: bool = widget.widget == "embeddedDocMult";
+        // Get the list of documents for validation.
+        let items: Vec<serde_json::value::Value> = if value.is_null() {
+            Vec::new()
+        } else if is_mult {
+            value
+                .as_array()
+                .ok_or_else(|| {
+                    Error::InvalidValue(format!(
+                        "Form: `{}` > Field: `{}` : Expected an array of embedded documents.",
+                        self.form_name, widget.name
+                    ))
+                })?
+                .clone()
+        } else {
+            vec![value.clone()]
+        };
+        // Validation, if the field is required and empty, accumulate the error.
+        if items.is_empty() {
+            widget.value = serde_json::to_string(&if is_mult {
+                Vec::new()
+            } else {
+                vec![self.clone()]
+            })?;
+            if widget.required {
+                widget.error = Self::accumula_err(widget, &"Required field.".to_owned())?;
+                return Ok((true, Bson::Null));
+            }
+            return Ok((
+                false,
+                if value.is_null() {
+                    Bson::Null
+                } else {
+                    Bson::Array(Vec::new())
+                },
+            ));
+        }
+        // Validation of documents.
+        let mut is_err_symptom = false;
+        let mut checked_docs: Vec<EmbeddedDoc> = Vec::new();
+        let mut bson_docs: Vec<Bson> = Vec::new();
+        for item in items.iter() {
+            let mut embedded: EmbeddedDoc = self.clone();
+            let (is_err, doc) = embedded.check(item)?;
+            if is_err {
+                is_err_symptom = true;
+            }
+            checked_docs.push(embedded);
+            bson_docs.push(Bson::Document(doc));
+        }
+        if is_err_symptom {
+            widget.error = Self::accumula_err(widget, &"Invalid embedded document.".to_owned())?;
+        }
+        // In case of an error, return the current
+        // state of the field to the user (client).
+        widget.value = serde_json::to_string(&checked_docs)?;
+
+        Ok((
+            is_err_symptom,
+            if is_mult {
+                Bson::Array(bson_docs)
+            } else {
+                bson_docs.remove(0)
+            },
+        ))
+    }
+
+    /// Get a document with default values.
+    /// ( Nested `embeddedDoc` - document with default values, `embeddedDocMult` - empty array )
+    // *********************************************************************************************
+    pub fn default_doc(&self) -> Result<Document, Error> {
+        let mut doc = Document::new();
+        for field_name in self.fields_name.iter() {
+            let widget: &Widget = self.map_widgets.get(field_name).unwrap();
+            let val: String = widget.value.clone();
+            let bson_value: Bson = match widget.widget.as_str() {
+                "embeddedDoc" => Bson::Document(
+                    self.map_embedded
+                        .get(field_name)
+                        .ok_or_else(|| Error::MissingField {
+                            model: self.form_name.clone(),
+                            field: field_name.clone(),
+                            method: "embedded_docs()".to_string(),
+                        })?
+                        .default_doc()?,
+                ),
+                "embeddedDocMult" => Bson::Array(Vec::new()),
+                "checkBox" => Bson::Boolean(widget.checked),
+                _ if val.is_empty() => Bson::Null,
+                "radioI32" | "numberI32" | "rangeI32" | "hiddenI32" | "selectI32" => {
+                    Bson::Int32(val.parse::<i32>()?)
+                }
+                "radioU32" | "numberU32" | "rangeU32" | "hiddenU32" | "selectU32" | "radioI64"
+                | "numberI64" | "rangeI64" | "hiddenI64" | "selectI64" => {
+                    Bson::Int64(val.parse::<i64>()?)
+                }
+                "radioF64" | "numberF64" | "rangeF64" | "hiddenF64" | "selectF64" => {
+                    Bson::Double(val.parse::<f64>()?)
+                }
+                "selectTextMult" | "listText" => Bson::Array(
+                    serde_json::from_str::<Vec<String>>(val.as_str())?
+                        .into_iter()
+                        .map(Bson::String)
+                        .collect(),
+                ),
+                "selectI32Mult" | "listI32" => Bson::Array(
+                    serde_json::from_str::<Vec<i32>>(val.as_str())?
+                        .into_iter()
+                        .map(Bson::Int32)
+                        .collect(),
+                ),
+                "selectU32Mult" | "selectI64Mult" | "listU32" | "listI64" => Bson::Array(
+                    serde_json::from_str::<Vec<i64>>(val.as_str())?
+                        .into_iter()
+                        .map(Bson::Int64)
+                        .collect(),
+                ),
+                "selectF64Mult" | "listF64" => Bson::Array(
+                    serde_json::from_str::<Vec<f64>>(val.as_str())?
+                        .into_iter()
+                        .map(Bson::Double)
+                        .collect(),
+                ),
+                _ => Bson::String(val),
+            };
+            doc.insert(field_name.clone(), bson_value);
+        }
+        Ok(doc)
+    }
+
+    /// Get the structure of an embedded document - the widget types of the fields.
+    /// ( Used in migration to detect changes in embedded documents )
+    // *********************************************************************************************
+    ///
+    /// Returns: { widget: widget_type, fields: { field_name: widget_type or nested structure } }
+    ///
+    pub fn structure(&self, widget_type: &str) -> Document {
+        let mut fields = Document::new();
+        for field_name in self.fields_name.iter() {
+            let nested_widget_type: String =
+                self.map_widgets.get(field_name).unwrap().widget.clone();
+            match self.map_embedded.get(field_name) {
+                Some(embedded) => {
+                    fields.insert(field_name.clone(), embedded.structure(&nested_widget_type));
+                }
+                None => {
+                    fields.insert(field_name.clone(), nested_widget_type);
+                }
+            }
+        }
+        mongodb::bson::doc! {
+            "widget": widget_type,
+            "fields": fields
+        }
+    }
+
+    /// Bring the value of a field with an embedded document in line with the current structure.
+    /// ( New nested fields and fields whose widget type has changed get the default value )
+    // *********************************************************************************************
+    ///
+    /// `old_fields` - Structure of the fields at the time of the previous migration.
+    ///
+    pub fn merge_value(&self, old_fields: &Document, value: &Bson) -> Result<Bson, Error> {
+        Ok(match value {
+            Bson::Document(doc_from_db) => {
+                let default_doc: Document = self.default_doc()?;
+                let mut doc = Document::new();
+                for field_name in self.fields_name.iter() {
+                    let widget_type: &str =
+                        self.map_widgets.get(field_name).unwrap().widget.as_str();
+                    let old_structure: Option<&Bson> = old_fields.get(field_name);
+                    let value_from_db: Option<&Bson> = doc_from_db.get(field_name);
+                    let new_value: Bson = match (
+                        self.map_embedded.get(field_name),
+                        old_structure,
+                        value_from_db,
+                    ) {
+                        // Nested embedded document with the same widget type - merge recursively.
+                        (Some(embedded), Some(Bson::Document(old)), Some(value))
+                            if old.get_str("widget").ok() == Some(widget_type) =>
+                        {
+                            embedded.merge_value(old.get_document("fields")?, value)?
+                        }
+                        // The widget type has not changed.
+                        (None, Some(Bson::String(old)), Some(value)) if old == widget_type => {
+                            value.clone()
+                        }
+                        _ => default_doc.get(field_name).unwrap().clone(),
+                    };
+                    doc.insert(field_name.clone(), new_value);
+                }
+                Bson::Document(doc)
+            }
+            Bson::Array(items) => Bson::Array(
+                items
+                    .iter()
+                    .map(|item| self.merge_value(old_fields, item))
+                    .collect::<Result<Vec<Bson>, Error>>()?,
+            ),
+            _ => value.clone(),
+        })
+    }
+}
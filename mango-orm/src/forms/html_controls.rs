@@ -4,7 +4,7 @@
 //! ( If necessary, customize the code generation yourself using html and css from Bootstrap, Material Design, etc. )
 //!
 
-use crate::forms::Widget;
+use crate::forms::{embedded::EmbeddedDoc, Widget};
 
 pub trait HtmlControls {
     /// Rendering HTML-controls code for Form.
@@ -17,6 +17,19 @@ pub trait HtmlControls {
     ) -> String {
         // Controls of Form.
         // -----------------------------------------------------------------------------------------
+        let controls = Self::to_html_controls(fields_name, map_widgets);
+        // Add buttons and Return.
+        // -----------------------------------------------------------------------------------------
+        format!("{}<p><input type=\"submit\" value=\"Save\"></p>", controls)
+    }
+
+    /// Rendering HTML-controls code for Form fields, without buttons.
+    /// ( Also used for nested rendering of embedded documents )
+    ///
+    fn to_html_controls(
+        fields_name: &Vec<String>,
+        map_widgets: std::collections::HashMap<String, Widget>,
+    ) -> String {
         let mut controls = String::new();
         for field_name in fields_name {
             let attrs = map_widgets.get(field_name).unwrap();
@@ -235,6 +248,64 @@ pub trait HtmlControls {
                         }
                     );
                 }
+                "time" => {
+                    controls = format!(
+                        "{}<p>{}<input{}{}{}{}{}{}{}{}{}{}{}{}>{}{}{}</p>",
+                        controls,
+                        if !attrs.label.is_empty() {
+                            format!("<label for=\"{}\">{}:</label><br>", attrs.id, attrs.label)
+                        } else {
+                            String::new()
+                        },
+                        format!(" id=\"{}\"", attrs.id),
+                        format!(" type=\"{}\"", attrs.input_type),
+                        format!(" name=\"{}\"", attrs.name),
+                        format!(" value=\"{}\"", attrs.value),
+                        if attrs.required { " required" } else { "" },
+                        if attrs.disabled { " disabled" } else { "" },
+                        if attrs.readonly { " readonly" } else { "" },
+                        if !attrs.step.is_empty() && attrs.step != "1" {
+                            format!(" step=\"{}\"", attrs.step)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.min.is_empty() {
+                            format!(" min=\"{}\"", attrs.min)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.max.is_empty() {
+                            format!(" max=\"{}\"", attrs.max)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.css_classes.is_empty() {
+                            format!(" class=\"{}\"", attrs.css_classes)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.other_attrs.is_empty() {
+                            format!(" {}", attrs.other_attrs)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.hint.is_empty() {
+                            format!("<br><small class=\"hint\">{}</small>", attrs.hint)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.warning.is_empty() {
+                            format!("<br><small class=\"warning\">{}</small>", attrs.warning)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.error.is_empty() {
+                            format!("<br><small class=\"error\">{}</small>", attrs.error)
+                        } else {
+                            String::new()
+                        }
+                    );
+                }
                 "file" => {
                     controls = format!(
                         "{}<p>{}<input{}{}{}{}{}{}{}{}>{}{}{}</p>",
@@ -303,13 +374,13 @@ pub trait HtmlControls {
                         } else {
                             String::new()
                         },
-                        if attrs.min != "0" {
-                            format!(" min=\"{}\"", attrs.step)
+                        if !attrs.min.is_empty() {
+                            format!(" min=\"{}\"", attrs.min)
                         } else {
                             String::new()
                         },
-                        if attrs.max != "0" {
-                            format!(" max=\"{}\"", attrs.step)
+                        if !attrs.max.is_empty() {
+                            format!(" max=\"{}\"", attrs.max)
                         } else {
                             String::new()
                         },
@@ -361,13 +432,13 @@ pub trait HtmlControls {
                         } else {
                             String::new()
                         },
-                        if attrs.min != "0" {
-                            format!(" min=\"{}\"", attrs.step)
+                        if !attrs.min.is_empty() {
+                            format!(" min=\"{}\"", attrs.min)
                         } else {
                             String::new()
                         },
-                        if attrs.max != "0" {
-                            format!(" max=\"{}\"", attrs.step)
+                        if !attrs.max.is_empty() {
+                            format!(" max=\"{}\"", attrs.max)
                         } else {
                             String::new()
                         },
@@ -514,6 +585,125 @@ pub trait HtmlControls {
                         }
                     );
                 }
+                "list" => {
+                    // One input per item and one empty input for a new item.
+                    // ( Hint: <field name>[] )
+                    let items: Vec<serde_json::Value> =
+                        serde_json::from_str(attrs.value.as_str()).unwrap_or_default();
+                    let mut values: Vec<String> = items
+                        .iter()
+                        .map(|item| match item.as_str() {
+                            Some(text) => text.to_string(),
+                            None => item.to_string(),
+                        })
+                        .collect();
+                    if attrs.max_items == 0 || (values.len() as u32) < attrs.max_items {
+                        values.push(String::new());
+                    }
+                    let is_text: bool = attrs.widget == "listText";
+                    let mut inputs = String::new();
+                    for (idx, value) in values.iter().enumerate() {
+                        inputs = format!(
+                            "{}<input{}{}{}{}{}{}{}{}{}>",
+                            inputs,
+                            format!(" id=\"{}--{}\"", attrs.id, idx),
+                            if is_text {
+                                " type=\"text\""
+                            } else {
+                                " type=\"number\""
+                            },
+                            format!(" name=\"{}[]\"", attrs.name),
+                            format!(" value=\"{}\"", value),
+                            if attrs.required && idx == 0 {
+                                " required"
+                            } else {
+                                ""
+                            },
+                            if attrs.disabled { " disabled" } else { "" },
+                            if attrs.readonly { " readonly" } else { "" },
+                            if !attrs.placeholder.is_empty() {
+                                format!(" placeholder=\"{}\"", attrs.placeholder)
+                            } else {
+                                String::new()
+                            },
+                            if is_text {
+                                format!(
+                                    "{}{}{}",
+                                    if !attrs.pattern.is_empty() {
+                                        format!(" pattern=\"{}\"", attrs.pattern)
+                                    } else {
+                                        String::new()
+                                    },
+                                    if attrs.minlength > 0 {
+                                        format!(" minlength=\"{}\"", attrs.minlength)
+                                    } else {
+                                        String::new()
+                                    },
+                                    if attrs.maxlength > 0 {
+                                        format!(" maxlength=\"{}\"", attrs.maxlength)
+                                    } else {
+                                        String::new()
+                                    }
+                                )
+                            } else {
+                                format!(
+                                    "{}{}{}",
+                                    if attrs.step != "0" {
+                                        format!(" step=\"{}\"", attrs.step)
+                                    } else {
+                                        String::new()
+                                    },
+                                    if !attrs.min.is_empty() {
+                                        format!(" min=\"{}\"", attrs.min)
+                                    } else {
+                                        String::new()
+                                    },
+                                    if !attrs.max.is_empty() {
+                                        format!(" max=\"{}\"", attrs.max)
+                                    } else {
+                                        String::new()
+                                    }
+                                )
+                            }
+                        );
+                    }
+                    controls = format!(
+                        "{}<fieldset{}{}{}>{}{}{}{}{}</fieldset>",
+                        controls,
+                        format!(" id=\"{}\"", attrs.id),
+                        if !attrs.css_classes.is_empty() {
+                            format!(" class=\"{}\"", attrs.css_classes)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.other_attrs.is_empty() {
+                            format!(" {}", attrs.other_attrs)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.label.is_empty() {
+                            format!("<legend>{}</legend>", attrs.label)
+                        } else {
+                            String::new()
+                        },
+                        inputs,
+                        if !attrs.hint.is_empty() {
+                            format!("<br><small class=\"hint\">{}</small>", attrs.hint)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.warning.is_empty() {
+                            format!("<br><small class=\"warning\">{}</small>", attrs.warning)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.error.is_empty() {
+                            format!("<br><small class=\"error\">{}</small>", attrs.error)
+                        } else {
+                            String::new()
+                        }
+                    );
+                }
                 "hidden" => {
                     controls = format!(
                         "{}<input{}{}{}{}{}{}{}>",
@@ -535,12 +725,74 @@ pub trait HtmlControls {
                         }
                     );
                 }
+                "embedded" => {
+                    // Controls of embedded documents.
+                    // ( Hint: <field name>[<nested field name>] or
+                    // <field name>[<index>][<nested field name>] for `embeddedDocMult` )
+                    let docs: Vec<EmbeddedDoc> =
+                        serde_json::from_str(attrs.value.as_str()).unwrap_or_default();
+                    let mut inputs = String::new();
+                    for (idx, doc) in docs.into_iter().enumerate() {
+                        let (prefix_id, prefix_name) = if attrs.widget == "embeddedDocMult" {
+                            (
+                                format!("{}--{}", attrs.id, idx),
+                                format!("{}[{}]", attrs.name, idx),
+                            )
+                        } else {
+                            (attrs.id.clone(), attrs.name.clone())
+                        };
+                        let mut nested_map_widgets = doc.map_widgets;
+                        for widget in nested_map_widgets.values_mut() {
+                            widget.id = format!("{}--{}", prefix_id, widget.name.replace('_', "-"));
+                            widget.name = format!("{}[{}]", prefix_name, widget.name);
+                        }
+                        inputs = format!(
+                            "{}{}",
+                            inputs,
+                            Self::to_html_controls(&doc.fields_name, nested_map_widgets)
+                        );
+                    }
+                    controls = format!(
+                        "{}<fieldset{}{}{}>{}{}{}{}{}</fieldset>",
+                        controls,
+                        format!(" id=\"{}\"", attrs.id),
+                        if !attrs.css_classes.is_empty() {
+                            format!(" class=\"{}\"", attrs.css_classes)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.other_attrs.is_empty() {
+                            format!(" {}", attrs.other_attrs)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.label.is_empty() {
+                            format!("<legend>{}</legend>", attrs.label)
+                        } else {
+                            String::new()
+                        },
+                        inputs,
+                        if !attrs.hint.is_empty() {
+                            format!("<small class=\"hint\">{}</small>", attrs.hint)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.warning.is_empty() {
+                            format!("<br><small class=\"warning\">{}</small>", attrs.warning)
+                        } else {
+                            String::new()
+                        },
+                        if !attrs.error.is_empty() {
+                            format!("<br><small class=\"error\">{}</small>", attrs.error)
+                        } else {
+                            String::new()
+                        }
+                    );
+                }
                 _ => panic!("Invalid input type."),
             }
         }
-        // Add buttons and Return.
-        // -----------------------------------------------------------------------------------------
-        format!("{}<p><input type=\"submit\" value=\"Save\"></p>", controls)
+        controls
     }
 
     // Get Html-line for `OutputDataForm`.
@@ -3,7 +3,10 @@
 //! `OutputDataForm` - To return results after processing Forms.
 //!
 
-use crate::forms::{html_controls::HtmlControls, Widget};
+use crate::{
+    error::Error,
+    forms::{html_controls::HtmlControls, Widget},
+};
 
 /// Output data type
 #[derive(Debug)]
@@ -32,6 +35,15 @@ pub enum OutputDataForm {
             String,
         ),
     ),
+    // The document has been changed by another query ( version mismatch ).
+    // Hint: The message for the client is in the `common_msg` of the `hash` widget.
+    Conflict(
+        (
+            Vec<String>,
+            std::collections::HashMap<String, Widget>,
+            String,
+        ),
+    ),
     Delete((bool, String)),
 }
 
@@ -51,6 +63,7 @@ impl HtmlControls for OutputDataForm {
             Self::CheckForm(data) => Self::to_html(&data.1, data.2.clone()),
             Self::CheckModel(data) => Self::to_html(&data.1, data.2.clone()),
             Self::Save(data) => Self::to_html(&data.1, data.2.clone()),
+            Self::Conflict(data) => Self::to_html(&data.0, data.1.clone()),
             _ => panic!("Invalid output type."),
         }
     }
@@ -59,9 +72,7 @@ impl HtmlControls for OutputDataForm {
 impl OutputDataForm {
     /// Get Hash-line
     // ---------------------------------------------------------------------------------------------
-    fn to_hash(
-        map_widgets: &std::collections::HashMap<String, Widget>,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    fn to_hash(map_widgets: &std::collections::HashMap<String, Widget>) -> Result<String, Error> {
         let mut errors = String::new();
         for (field_name, widget) in map_widgets {
             let tmp = errors.clone();
@@ -85,10 +96,11 @@ impl OutputDataForm {
     /// println!("{}", output_data.hash());
     /// ```
     ///
-    pub fn hash(&self) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn hash(&self) -> Result<String, Error> {
         match self {
             Self::CheckModel(data) => Ok(Self::to_hash(&data.2)?),
             Self::Save(data) => Ok(Self::to_hash(&data.2)?),
+            Self::Conflict(data) => Ok(Self::to_hash(&data.1)?),
             _ => panic!("Invalid output type."),
         }
     }
@@ -103,7 +115,7 @@ impl OutputDataForm {
     /// println!("{:?}", output_data.id()?);
     /// ```
     ///
-    pub fn id(&self) -> Result<mongodb::bson::oid::ObjectId, Box<dyn std::error::Error>> {
+    pub fn id(&self) -> Result<mongodb::bson::oid::ObjectId, Error> {
         match self {
             Self::CheckModel(data) => Ok(mongodb::bson::oid::ObjectId::with_string(
                 Self::to_hash(&data.2)?.as_str(),
@@ -111,6 +123,9 @@ impl OutputDataForm {
             Self::Save(data) => Ok(mongodb::bson::oid::ObjectId::with_string(
                 Self::to_hash(&data.2)?.as_str(),
             )?),
+            Self::Conflict(data) => Ok(mongodb::bson::oid::ObjectId::with_string(
+                Self::to_hash(&data.1)?.as_str(),
+            )?),
             _ => panic!("Invalid output type."),
         }
     }
@@ -131,6 +146,7 @@ impl OutputDataForm {
             Self::CheckForm(data) => data.2.clone(),
             Self::CheckModel(data) => data.2.clone(),
             Self::Save(data) => data.2.clone(),
+            Self::Conflict(data) => data.1.clone(),
             _ => panic!("Invalid output type."),
         }
     }
@@ -145,11 +161,12 @@ impl OutputDataForm {
     /// println!("{}", output_data.json()?);
     /// ```
     ///
-    pub fn json(&self) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn json(&self) -> Result<String, Error> {
         match self {
             Self::CheckForm(data) => Ok(serde_json::to_string(&data.2)?),
             Self::CheckModel(data) => Ok(serde_json::to_string(&data.2)?),
             Self::Save(data) => Ok(serde_json::to_string(&data.2)?),
+            Self::Conflict(data) => Ok(serde_json::to_string(&data.1)?),
             _ => panic!("Invalid output type."),
         }
     }
@@ -165,16 +182,16 @@ impl OutputDataForm {
     /// println!("{}", output_data.json_for_admin()?);
     /// ```
     ///
-    pub fn json_for_admin(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let data = match self {
-            Self::Save(data) => data,
+    pub fn json_for_admin(&self) -> Result<String, Error> {
+        let (fields_name, map_widgets) = match self {
+            Self::Save(data) => (&data.1, data.2.clone()),
+            Self::Conflict(data) => (&data.0, data.1.clone()),
             _ => panic!("Invalid output type."),
         };
-        let map_widgets = data.2.clone();
         let mut widget_list: Vec<Widget> = Vec::new();
         let hash = map_widgets.get("hash").unwrap().clone().value;
         // Get a list of widgets in the order of the model fields.
-        for field_name in data.1.iter() {
+        for field_name in fields_name.iter() {
             let mut widget = map_widgets.get(field_name).unwrap().clone();
             if field_name.contains("password") && !hash.is_empty() {
                 widget.widget = "hiddenText".to_string();
@@ -202,10 +219,28 @@ impl OutputDataForm {
             Self::CheckForm(data) => data.0,
             Self::CheckModel(data) => data.0,
             Self::Save(data) => data.0,
+            Self::Conflict(_) => false,
             Self::Delete(data) => data.0,
         }
     }
 
+    /// Get the status of the version conflict (boolean)
+    /// Hint: The document has been changed by another query - reload the document.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data = UserProfile.save()?;
+    /// if output_data.is_conflict() {
+    ///     println!("{}", output_data.html());
+    /// }
+    /// ```
+    ///
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Conflict(_))
+    }
+
     /// Get Document
     // ---------------------------------------------------------------------------------------------
     ///
@@ -13,9 +13,16 @@
 use std::convert::TryFrom;
 
 use crate::{
-    forms::{caching::CachingForm, output_data::OutputDataForm, ToForm, Widget},
+    error::Error,
+    forms::{
+        caching::CachingForm, embedded::EmbeddedDoc, output_data::OutputDataForm, DateTimeZone,
+        Decimal, GeoPoint, ListItems, TimeRange, ToForm, Widget,
+    },
     models::validation::AdditionalValidation,
-    store::{REGEX_IS_COLOR_CODE, REGEX_IS_DATE, REGEX_IS_DATETIME, REGEX_IS_PASSWORD},
+    store::{
+        REGEX_IS_COLOR_CODE, REGEX_IS_DATE, REGEX_IS_DATETIME, REGEX_IS_PASSWORD, REGEX_IS_TIME,
+        REGEX_IS_TIME_RANGE,
+    },
 };
 
 /// Validating Form fields for save and update.
@@ -23,25 +30,31 @@ use crate::{
 pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
     /// Validation of `minlength`.
     // ---------------------------------------------------------------------------------------------
-    fn check_minlength(minlength: usize, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn check_minlength(minlength: usize, value: &str) -> Result<(), Error> {
         if minlength > 0 && value.encode_utf16().count() < minlength {
-            Err(format!("Exceeds limit, minlength={}.", minlength))?
+            Err(Error::InvalidValue(format!(
+                "Exceeds limit, minlength={}.",
+                minlength
+            )))?
         }
         Ok(())
     }
 
     /// Validation of `maxlength`.
     // ---------------------------------------------------------------------------------------------
-    fn check_maxlength(maxlength: usize, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn check_maxlength(maxlength: usize, value: &str) -> Result<(), Error> {
         if maxlength > 0 && value.encode_utf16().count() > maxlength {
-            Err(format!("Exceeds limit, maxlength={}.", maxlength))?
+            Err(Error::InvalidValue(format!(
+                "Exceeds limit, maxlength={}.",
+                maxlength
+            )))?
         }
         Ok(())
     }
 
     /// Accumulation of errors.
     // ---------------------------------------------------------------------------------------------
-    fn accumula_err(widget: &Widget, err: &String) -> Result<String, Box<dyn std::error::Error>> {
+    fn accumula_err(widget: &Widget, err: &String) -> Result<String, Error> {
         let mut tmp = widget.error.clone();
         tmp = if !tmp.is_empty() {
             format!("{}<br>", tmp)
@@ -53,51 +66,72 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
 
     /// Validation in regular expression (email, password, etc...).
     // ---------------------------------------------------------------------------------------------
-    fn regex_validation(field_type: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn regex_validation(field_type: &str, value: &str) -> Result<(), Error> {
         match field_type {
             "inputEmail" => {
                 if !validator::validate_email(value) {
-                    Err("Invalid email address.")?
+                    Err(Error::InvalidValue("Invalid email address.".to_string()))?
                 }
             }
             "inputColor" => {
                 if !REGEX_IS_COLOR_CODE.is_match(value) {
-                    Err("Invalid Color code.")?
+                    Err(Error::InvalidValue("Invalid Color code.".to_string()))?
                 }
             }
             "inputUrl" => {
                 if !validator::validate_url(value) {
-                    Err("Invalid Url.")?
+                    Err(Error::InvalidValue("Invalid Url.".to_string()))?
                 }
             }
             "inputIP" => {
                 if !validator::validate_ip(value) {
-                    Err("Invalid IP address.")?
+                    Err(Error::InvalidValue("Invalid IP address.".to_string()))?
                 }
             }
             "inputIPv4" => {
                 if !validator::validate_ip_v4(value) {
-                    Err("Invalid IPv4 address.")?
+                    Err(Error::InvalidValue("Invalid IPv4 address.".to_string()))?
                 }
             }
             "inputIPv6" => {
                 if !validator::validate_ip_v6(value) {
-                    Err("Invalid IPv6 address.")?
+                    Err(Error::InvalidValue("Invalid IPv6 address.".to_string()))?
                 }
             }
             "inputPassword" => {
                 if !REGEX_IS_PASSWORD.is_match(value) {
-                    Err("Size 8-256 chars ; Allowed chars: a-z A-Z 0-9 @ # $ % ^ & + = * ! ~ ) (")?
+                    Err(Error::InvalidValue(
+                        "Size 8-256 chars ; Allowed chars: a-z A-Z 0-9 @ # $ % ^ & + = * ! ~ ) ("
+                            .to_string(),
+                    ))?
                 }
             }
             "inputDate" => {
                 if !REGEX_IS_DATE.is_match(value) {
-                    Err("Incorrect date format.<br>Example: 1970-02-28")?
+                    Err(Error::InvalidValue(
+                        "Incorrect date format.<br>Example: 1970-02-28".to_string(),
+                    ))?
                 }
             }
             "inputDateTime" => {
                 if !REGEX_IS_DATETIME.is_match(value) {
-                    Err("Incorrect date and time format.<br>Example: 1970-02-28T00:00")?
+                    Err(Error::InvalidValue(
+                        "Incorrect date and time format.<br>Example: 1970-02-28T00:00".to_string(),
+                    ))?
+                }
+            }
+            "inputTime" => {
+                if !REGEX_IS_TIME.is_match(value) {
+                    Err(Error::InvalidValue(
+                        "Incorrect time format.<br>Example: 09:00".to_string(),
+                    ))?
+                }
+            }
+            "inputTimeRange" => {
+                if !REGEX_IS_TIME_RANGE.is_match(value) {
+                    Err(Error::InvalidValue(
+                        "Incorrect time range format.<br>Example: 09:00-18:00".to_string(),
+                    ))?
                 }
             }
             _ => return Ok(()),
@@ -116,7 +150,7 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
     /// assert!(result.is_valid());
     /// ```
     ///
-    fn check(&self) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    fn check(&self) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let form_cache = Self::get_cache_data()?;
         // Get model name.
@@ -132,6 +166,8 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
         let fields_name: Vec<&str> = fields_name.iter().map(|item| item.as_str()).collect();
         let mut final_map_widgets: std::collections::HashMap<String, Widget> =
             form_cache.map_widgets.clone();
+        // Get the structure of embedded documents.
+        let map_embedded: std::collections::HashMap<String, EmbeddedDoc> = Self::embedded_docs()?;
         // Apply additional validation.
         {
             let error_map = self.add_validation()?;
@@ -139,11 +175,11 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                 is_err_symptom = true;
                 for (field_name, err_msg) in error_map {
                     if !fields_name.contains(&field_name) {
-                        Err(format!(
-                            "Form: `{}` >  Method: `add_validation()` : \
-                                            The `{}` field is missing from the form.",
-                            form_name, field_name
-                        ))?
+                        Err(Error::MissingField {
+                            model: form_name.to_string(),
+                            field: field_name.to_string(),
+                            method: "add_validation()".to_string(),
+                        })?
                     }
                     if let Some(widget) = final_map_widgets.get_mut(&field_name.to_owned()) {
                         widget.error = Self::accumula_err(&widget, &err_msg.to_string())?;
@@ -157,10 +193,11 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
             let pre_json_value: Option<&serde_json::value::Value> = pre_json.get(field_name);
             // Check field value.
             if pre_json_value.is_none() {
-                Err(format!(
-                    "Form: `{}` > Field: `{}` > Method: `check()` : This field is missing.",
-                    form_name, field_name
-                ))?
+                Err(Error::MissingField {
+                    model: form_name.to_string(),
+                    field: field_name.to_string(),
+                    method: "check()".to_string(),
+                })?
             }
             //
             let pre_json_value: &serde_json::value::Value = pre_json_value.unwrap();
@@ -283,19 +320,19 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                         continue;
                     }
                     // Create Date and Time Object.
+                    // ( Forms have no time zone settings - UTC is used )
                     // -----------------------------------------------------------------------------
-                    // Date to DateTime.
-                    let dt_value: chrono::DateTime<chrono::Utc> = {
-                        let field_value: String = if widget_type == "inputDate" {
-                            format!("{}T00:00", field_value.to_string())
-                        } else {
-                            field_value.to_string()
+                    let timezone = DateTimeZone::default();
+                    let dt_value: chrono::DateTime<chrono::Utc> =
+                        match timezone.parse(widget_type, field_value) {
+                            Ok(dt_value) => dt_value,
+                            Err(err) => {
+                                is_err_symptom = true;
+                                final_widget.error =
+                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                                continue;
+                            }
                         };
-                        chrono::DateTime::<chrono::Utc>::from_utc(
-                            chrono::NaiveDateTime::parse_from_str(&field_value, "%Y-%m-%dT%H:%M")?,
-                            chrono::Utc,
-                        )
-                    };
                     // Create dates for `min` and `max` attributes values to
                     // check, if the value of user falls within the range
                     // between these dates.
@@ -318,35 +355,11 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                             continue;
                         }
                         // Date to DateTime (min).
-                        let dt_min: chrono::DateTime<chrono::Utc> = {
-                            let min_value: String = if widget_type == "inputDate" {
-                                format!("{}T00:00", final_widget.min.clone())
-                            } else {
-                                final_widget.min.clone()
-                            };
-                            chrono::DateTime::<chrono::Utc>::from_utc(
-                                chrono::NaiveDateTime::parse_from_str(
-                                    &min_value,
-                                    "%Y-%m-%dT%H:%M",
-                                )?,
-                                chrono::Utc,
-                            )
-                        };
+                        let dt_min: chrono::DateTime<chrono::Utc> =
+                            timezone.parse(widget_type, final_widget.min.as_str())?;
                         // Date to DateTime (max).
-                        let dt_max: chrono::DateTime<chrono::Utc> = {
-                            let max_value: String = if widget_type == "inputDate" {
-                                format!("{}T00:00", final_widget.max.clone())
-                            } else {
-                                final_widget.max.clone()
-                            };
-                            chrono::DateTime::<chrono::Utc>::from_utc(
-                                chrono::NaiveDateTime::parse_from_str(
-                                    &max_value,
-                                    "%Y-%m-%dT%H:%M",
-                                )?,
-                                chrono::Utc,
-                            )
-                        };
+                        let dt_max: chrono::DateTime<chrono::Utc> =
+                            timezone.parse(widget_type, final_widget.max.as_str())?;
                         // Check hit in range (min <> max).
                         if dt_value < dt_min || dt_value > dt_max {
                             is_err_symptom = true;
@@ -358,6 +371,110 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                         }
                     }
                 }
+                // Validation of time type fields.
+                // *********************************************************************************
+                "inputTime" | "inputTimeRange" => {
+                    // Get field value for validation.
+                    let field_value: String = if !pre_json_value.is_null() {
+                        let clean_data: String =
+                            pre_json_value.as_str().unwrap().trim().to_string();
+                        // In case of an error, return the current
+                        // state of the field to the user (client).
+                        final_widget.value = clean_data.clone();
+                        clean_data
+                    } else {
+                        String::new()
+                    };
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+                    // Validation of format, range (`min` <> `max`) and `step`.
+                    // -----------------------------------------------------------------------------
+                    if let Err(err) = TimeRange::check_value(&final_widget, field_value.as_str()) {
+                        is_err_symptom = true;
+                        final_widget.error =
+                            Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                    }
+                }
+                // Validation of geospatial point fields.
+                // *********************************************************************************
+                "inputGeoPoint" => {
+                    // Get field value for validation.
+                    let field_value: String = if !pre_json_value.is_null() {
+                        let clean_data: String =
+                            pre_json_value.as_str().unwrap().trim().to_string();
+                        // In case of an error, return the current
+                        // state of the field to the user (client).
+                        final_widget.value = clean_data.clone();
+                        clean_data
+                    } else {
+                        String::new()
+                    };
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+                    // Validation of format and range of coordinates.
+                    // -----------------------------------------------------------------------------
+                    match GeoPoint::check_value(field_value.as_str()) {
+                        Ok(point) => final_widget.value = point.to_value(),
+                        Err(err) => {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                        }
+                    }
+                }
+                // Validation of list type fields.
+                // *********************************************************************************
+                "listText" | "listI32" | "listU32" | "listI64" | "listF64" => {
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( An empty list is an empty value )
+                    // -----------------------------------------------------------------------------
+                    if pre_json_value
+                        .as_array()
+                        .map_or(true, |items| items.is_empty())
+                    {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+                    // In case of an error, return the current
+                    // state of the field to the user (client).
+                    final_widget.value = serde_json::to_string(&pre_json_value)?;
+                    // Validation of items and of the number of items.
+                    // -----------------------------------------------------------------------------
+                    match ListItems::check_value(&final_widget, pre_json_value) {
+                        Ok(items) => final_widget.value = items.to_value(),
+                        Err(err) => {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                        }
+                    }
+                }
                 // Validation of `select` type fields.
                 // *********************************************************************************
                 "selectText" | "selectI32" | "selectU32" | "selectI64" | "selectF64" => {
@@ -388,11 +505,11 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                                 let val = pre_json_value.as_f64().unwrap();
                                 final_widget.value = val.to_string();
                             }
-                            _ => Err(format!(
-                                "Model: `{}` > Field: `{}` > Method: `check()` : \
-                                        Unsupported widget type - `{}`.",
-                                form_name, field_name, widget_type
-                            ))?,
+                            _ => Err(Error::UnsupportedWidget {
+                                model: form_name.to_string(),
+                                field: field_name.to_string(),
+                                widget: widget_type.to_string(),
+                            })?,
                         }
                     } else {
                         if final_widget.required {
@@ -532,6 +649,41 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                         final_widget.error = Self::accumula_err(&final_widget, &msg).unwrap();
                     }
                 }
+                "numberDecimal" | "rangeDecimal" | "hiddenDecimal" => {
+                    // Get field value for validation.
+                    // ( The number is transmitted as a string, so as not to lose precision )
+                    let field_value: String = match pre_json_value.as_str() {
+                        Some(value) => value.trim().to_string(),
+                        None if pre_json_value.is_number() => pre_json_value.to_string(),
+                        None => String::new(),
+                    };
+                    // In case of an error, return the current
+                    // state of the field to the user (client).
+                    final_widget.value = field_value.clone();
+
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        }
+                        continue;
+                    }
+                    // Validation of format, `precision`, `scale`, range (`min` <> `max`)
+                    // and `step`.
+                    // -----------------------------------------------------------------------------
+                    match Decimal::check_value(&final_widget, field_value.as_str()) {
+                        Ok(number) => final_widget.value = number.to_value(),
+                        Err(err) => {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                        }
+                    }
+                }
 
                 // Validation of boolean type fields.
                 // *********************************************************************************
@@ -559,10 +711,27 @@ pub trait ValidationForm: ToForm + CachingForm + AdditionalValidation {
                     // state of the field to the user (client).
                     final_widget.checked = field_value;
                 }
-                _ => Err(format!(
-                    "Form: `{}` > Field: `{}` > Method: `check()` : Unsupported widget type.",
-                    form_name, field_name
-                ))?,
+                // Validation of embedded documents.
+                // *********************************************************************************
+                "embeddedDoc" | "embeddedDocMult" => {
+                    let embedded: &EmbeddedDoc =
+                        map_embedded
+                            .get(field_name)
+                            .ok_or_else(|| Error::MissingField {
+                                model: form_name.to_string(),
+                                field: field_name.to_string(),
+                                method: "embedded_docs()".to_string(),
+                            })?;
+                    let (is_err, _) = embedded.check_field(final_widget, pre_json_value)?;
+                    if is_err {
+                        is_err_symptom = true;
+                    }
+                }
+                _ => Err(Error::UnsupportedWidget {
+                    model: form_name.to_string(),
+                    field: field_name.to_string(),
+                    widget: widget_type.to_string(),
+                })?,
             }
         }
 
@@ -2,28 +2,68 @@
 //!
 //! ORM-like API MongoDB for Rust.
 //!
+//! Features:
+//! `sync` ( default ) - Synchronous API, the MongoDB driver uses the `async-std` runtime.
+//! `async` - Asynchronous API, the MongoDB driver uses the `tokio` runtime.
+//! ( The runtimes of the driver are mutually exclusive - enable only one of these features )
+//!
+
+#[cfg(all(feature = "sync", feature = "async"))]
+compile_error!(
+    "The features `sync` and `async` are mutually exclusive. \
+     For the asynchronous API use: default-features = false, features = [\"async\"]"
+);
+#[cfg(not(any(feature = "sync", feature = "async")))]
+compile_error!("One of the features `sync` ( default ) or `async` must be enabled.");
 
 pub use crate::{
+    error::Error,
     forms::{
-        caching::CachingForm, html_controls::HtmlControls, output_data::OutputDataForm,
-        validation::ValidationForm, ToForm, TransMapWidgets, Widget,
+        caching::CachingForm, embedded::EmbeddedDoc, html_controls::HtmlControls,
+        output_data::OutputDataForm, validation::ValidationForm, DateTimeZone, GeoPoint, ToForm,
+        TransMapWidgets, Widget,
+    },
+    migration::{
+        archive::{ArchivedCollection, NapalmPolicy},
+        plan::MigrationPlan,
+        steps::MigrationStep,
+        ConfiguredMonitor, Monitor,
     },
-    migration::Monitor,
     models::{
+        audit::AuditRecord,
         caching::CachingModel,
-        db_query_api::{common::QCommon, paladins::QPaladins},
+        db_query_api::{audit::QAudit, common::QCommon, paladins::QPaladins},
+        hooks::Hooks,
+        output_data::ModelsIter,
         output_data::OutputDataMany,
         output_data::OutputDataOne,
+        output_data::OutputDataPage,
+        query::{Query, QueryBuilder, QueryField},
+        upload::Upload,
         validation::{AdditionalValidation, ValidationModel},
-        Meta, ToModel,
+        Index, Meta, RefModel, ToModel,
     },
-    store::{FormCache, FORM_STORE, MONGODB_CLIENT_STORE},
+    storage::{field_storage, LocalStorage, Storage},
+    store::{FormCache, FORM_STORE, STORAGE_STORE},
 };
 
+#[cfg(feature = "sync")]
+pub use crate::store::MONGODB_CLIENT_STORE;
+
+#[cfg(feature = "async")]
+pub use crate::store::MONGODB_ASYNC_CLIENT_STORE;
+
+#[cfg(feature = "s3")]
+pub use crate::storage::S3Storage;
+
+#[cfg(feature = "sync")]
 pub use crate::test_tool::del_test_db;
 
+pub mod error;
 pub mod forms;
 pub mod migration;
 pub mod models;
+pub mod storage;
 pub mod store;
+#[cfg(feature = "sync")]
 pub mod test_tool;
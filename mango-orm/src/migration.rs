@@ -2,23 +2,67 @@
 //!
 //! Structs:
 //! `ModelState` - Creation and updating of a technical database for monitoring the state of models.
+//! `ModelRelation` - Reference of a Model field to another Model.
 //! `Monitor` - Creation and updating of a technical database for monitoring the state of models.
+//! `ConfiguredMonitor` - Monitor with optional settings ( migration steps, napalm policy ).
+//! `MigrationStep` - Versioned migration step of the Model ( see `migration::steps` ).
+//! `MigrationPlan` - What `migrat` would change, without touching the database ( see `migration::plan` ).
+//! `NapalmPolicy` - What `napalm` does with orphaned collections ( see `migration::archive` ).
 //!
 //! Methods:
+//! `with_migrations` - Add versioned migration steps to the Monitor.
+//! `with_napalm_policy` - Set what `napalm` does with orphaned collections.
 //! `mango_tech_name` - Get the name of the technical database for a project.
 //! `refresh` - Refresh models state.
 //! `napalm` - Reorganize databases state.
+//! `orphaned_collections` - Get the collections whose Model is not registered in the Monitor.
+//! `archive_collection` - Archive the orphaned collection.
+//! `archives` - Get the records about the archived collections.
+//! `restore` - Restore an archived collection.
+//! `model_relations` - Get the references of the Model fields to other Models.
+//! `register_relations` - Register the references of the Model in the technical database.
+//! `check_migrations` - Check the migration steps registered in the Monitor.
+//! `applied_versions` - Get the versions of the applied migration steps of the Model.
+//! `existing_indexes` - Get the indexes of the Model collection.
+//! `index_changes` - Get the changes of the indexes of the Model collection.
+//! `sync_indexes` - Create, recreate and drop the indexes of the Model collection.
+//! `pending_migrations` - Get the migration steps of the Model that have not yet been applied.
+//! `apply_migrations` - Apply the pending migration steps of the Model.
+//! `embedded_structures` - Get the structure of the embedded documents of the Model.
+//! `embedded_with_defaults` - Get the value of a field with embedded documents after migration.
+//! `changed_fields` - Get the fields of the Model whose values will be reset to default.
+//! `model_plan` - Get the changes of the collection of the Model.
+//! `plan` - Get the migration plan ( dry-run of `migrat` ).
+//! `plan_async` - Asynchronous variant of `plan` ( feature `async` ).
+//! `archives_async`, `restore_async` - Asynchronous variants of `archives` and `restore`.
 //! `migrat` - Check the changes in the models and (if necessary) apply to the database.
+//! `migrat_async` - Asynchronous variant of `migrat` ( feature `async` ).
 //!
 
+pub mod archive;
+pub mod plan;
+pub mod steps;
+
+#[cfg(feature = "async")]
+use crate::store::MONGODB_ASYNC_CLIENT_STORE;
+#[cfg(feature = "sync")]
+use crate::store::MONGODB_CLIENT_STORE;
 use crate::{
+    error::Error,
     forms::{FileData, ImageData},
-    store::MONGODB_CLIENT_STORE,
-};
-use mongodb::{
-    bson, bson::document::Document, options::UpdateModifications, sync::Client, sync::Collection,
-    sync::Cursor, sync::Database,
+    migration::{
+        archive::{ArchivedCollection, NapalmPolicy},
+        plan::{ChangedField, DroppedCollection, MigrationPlan, ModelPlan},
+        steps::MigrationStep,
+    },
+    storage::field_storage,
 };
+#[cfg(feature = "async")]
+use futures::stream::StreamExt;
+use image::GenericImageView;
+use mongodb::{bson, bson::document::Document, options::UpdateModifications};
+#[cfg(feature = "sync")]
+use mongodb::{sync::Client, sync::Collection, sync::Cursor, sync::Database};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -32,27 +76,152 @@ pub struct ModelState {
     pub collection: String,
     pub fields: Vec<String>,
     pub map_widgets: HashMap<String, String>,
+    // <field_name, structure of embedded document> - For widgets `embeddedDoc` and `embeddedDocMult`.
+    #[serde(default)]
+    pub map_embedded: Document,
     pub status: bool,
 }
 
+/// Reference of a Model field to another Model.
+/// ( Stored in the technical database of the referenced Model, in the `relations` collection )
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelRelation {
+    pub model_name: String,
+    pub db_client_name: String,
+    pub database: String,
+    pub collection: String,
+    pub field: String,
+    pub widget: String,
+    pub on_delete: String,
+    pub ref_database: String,
+    pub ref_collection: String,
+}
+
 pub struct Monitor<'a> {
     pub project_name: &'a str,
     pub unique_project_key: &'a str,
     pub models: Vec<crate::models::Meta>,
 }
 
+/// Optional settings of the Monitor.
+#[derive(Default)]
+struct MonitorSettings {
+    // Versioned migration steps ( rename field, convert field, transform documents ).
+    migrations: Vec<MigrationStep>,
+    // What `napalm` does with orphaned collections ( drop, archive, keep, refuse ).
+    napalm_policy: NapalmPolicy,
+}
+
+/// Monitor with optional settings.
+/// ( See `Monitor::with_migrations` and `Monitor::with_napalm_policy` )
+pub struct ConfiguredMonitor<'a> {
+    monitor: Monitor<'a>,
+    settings: MonitorSettings,
+}
+
+impl<'a> std::ops::Deref for ConfiguredMonitor<'a> {
+    type Target = Monitor<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.monitor
+    }
+}
+
+impl<'a> ConfiguredMonitor<'a> {
+    /// Add versioned migration steps ( rename field, convert field, transform documents ).
+    // *********************************************************************************************
+    pub fn with_migrations(mut self, migrations: Vec<MigrationStep>) -> Self {
+        self.settings.migrations.extend(migrations);
+        self
+    }
+
+    /// Set what `napalm` does with orphaned collections ( drop, archive, keep, refuse ).
+    // *********************************************************************************************
+    pub fn with_napalm_policy(mut self, napalm_policy: NapalmPolicy) -> Self {
+        self.settings.napalm_policy = napalm_policy;
+        self
+    }
+
+    /// Get the migration plan ( see `Monitor::plan` ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    pub fn plan(&self) -> Result<MigrationPlan, Error> {
+        self.monitor.plan_with(&self.settings)
+    }
+
+    /// Migrating Models ( see `Monitor::migrat` ).
+    // *********************************************************************************************
+    ///
+    /// Returns: warnings about the collections left without a model ( `NapalmPolicy::Keep` ).
+    ///
+    #[cfg(feature = "sync")]
+    pub fn migrat(&self) -> Result<Vec<String>, Error> {
+        self.monitor.migrat_with(&self.settings)
+    }
+
+    /// Get the migration plan (asynchronous).
+    // *********************************************************************************************
+    #[cfg(feature = "async")]
+    pub async fn plan_async(&self) -> Result<MigrationPlan, Error> {
+        self.monitor.plan_with_async(&self.settings).await
+    }
+
+    /// Migrating Models (asynchronous).
+    // *********************************************************************************************
+    #[cfg(feature = "async")]
+    pub async fn migrat_async(&self) -> Result<Vec<String>, Error> {
+        self.monitor.migrat_with_async(&self.settings).await
+    }
+}
+
 impl<'a> Monitor<'a> {
+    /// Add versioned migration steps ( rename field, convert field, transform documents ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let monitor = Monitor {
+    ///     project_name: PROJECT_NAME,
+    ///     unique_project_key: UNIQUE_PROJECT_KEY,
+    ///     models: model_list()?,
+    /// }
+    /// .with_migrations(vec![MigrationStep::rename_field("User", 1, "name", "username")]);
+    /// monitor.migrat()?;
+    /// ```
+    ///
+    pub fn with_migrations(self, migrations: Vec<MigrationStep>) -> ConfiguredMonitor<'a> {
+        ConfiguredMonitor {
+            monitor: self,
+            settings: MonitorSettings::default(),
+        }
+        .with_migrations(migrations)
+    }
+
+    /// Set what `napalm` does with orphaned collections ( drop, archive, keep, refuse ).
+    // *********************************************************************************************
+    pub fn with_napalm_policy(self, napalm_policy: NapalmPolicy) -> ConfiguredMonitor<'a> {
+        ConfiguredMonitor {
+            monitor: self,
+            settings: MonitorSettings::default(),
+        }
+        .with_napalm_policy(napalm_policy)
+    }
+
     /// Get the name of the technical database for a project.
     // *********************************************************************************************
-    pub fn mango_tech_name(&self) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn mango_tech_name(&self) -> Result<String, Error> {
         // PROJECT_NAME Validation.
         // Valid characters: _ a-z A-Z 0-9
         // Max size: 21
         let re = Regex::new(r"^[a-zA-Z][_a-zA-Z\d]{1,21}$")?;
         if !re.is_match(self.project_name) {
-            Err(format!("PROJECT_NAME - Valid characters: _ a-z A-Z 0-9 ; \
-                         Max size: 21 ; \
-                         First character: a-z A-Z"))?
+            Err(Error::InvalidConfig {
+                context: "Monitor > PROJECT_NAME".to_string(),
+                message: "Valid characters: _ a-z A-Z 0-9 ; Max size: 21 ; \
+                          First character: a-z A-Z"
+                    .to_string(),
+            })?
         }
         // UNIQUE_PROJECT_KEY Validation.
         // UNIQUE_PROJECT_KEY - It is recommended not to change.
@@ -61,8 +230,10 @@ impl<'a> Monitor<'a> {
         // Example: "7rzgacfqQB3B7q7T"
         let re = Regex::new(r"^[a-zA-Z\d]{8,16}$")?;
         if !re.is_match(self.unique_project_key) {
-            Err(format!("UNIQUE_PROJECT_KEY - Valid characters: a-z A-Z 0-9 ; \
-                         Size: 8-16."))?
+            Err(Error::InvalidConfig {
+                context: "Monitor > UNIQUE_PROJECT_KEY".to_string(),
+                message: "Valid characters: a-z A-Z 0-9 ; Size: 8-16.".to_string(),
+            })?
         }
         //
         Ok(format!("mango_tech__{}__{}", self.project_name, self.unique_project_key))
@@ -79,13 +250,19 @@ impl<'a> Monitor<'a> {
     /// }
     /// ```
     ///
-    fn refresh(&self) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn refresh(&self) -> Result<(), Error> {
         // Get cache MongoDB clients.
         let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
             MONGODB_CLIENT_STORE.read()?;
         //
         for meta in self.models.iter() {
-            let client: &Client = client_store.get(&meta.db_client_name).unwrap();
+            let client: &Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
             // Get the name of the technical database for a project.
             let db_mango_tech: String = self.mango_tech_name()?;
             // Collection for monitoring the state of Models.
@@ -130,15 +307,31 @@ impl<'a> Monitor<'a> {
     }
 
     /// Reorganize databases state.
-    /// (full delete of orphaned collections and databases)
+    /// ( Orphaned collections are dropped, archived or kept - see `Monitor::with_napalm_policy` )
     // *********************************************************************************************
-    fn napalm(&self) -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// Returns: warnings about the collections left without a model.
+    ///
+    #[cfg(feature = "sync")]
+    fn napalm(&self, napalm_policy: NapalmPolicy) -> Result<Vec<String>, Error> {
         // Get cache MongoDB clients.
         let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
             MONGODB_CLIENT_STORE.read()?;
         //
+        let mut warnings: Vec<String> = Vec::new();
+        let mut client_names: Vec<&String> = Vec::new();
         for meta in self.models.iter() {
-            let client: &Client = client_store.get(&meta.db_client_name).unwrap();
+            // Each client is processed once.
+            if client_names.contains(&&meta.db_client_name) {
+                continue;
+            }
+            client_names.push(&meta.db_client_name);
+            let client: &Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
             // Get the name of the technical database for a project.
             let db_mango_tech: String = self.mango_tech_name()?;
             let collection_models_name: &str = "monitor_models";
@@ -153,457 +346,1450 @@ impl<'a> Monitor<'a> {
                 let document = result?;
                 let model_state: ModelState = bson::de::from_document(document)?;
                 if !model_state.status {
-                    // Delete Collection (left without a model).
-                    client
-                        .database(&model_state.database)
-                        .collection(&model_state.collection)
-                        .drop(None)?;
-                    // Delete a document with a record about the state of
-                    // the model from the technical base.
                     let query: Document = bson::doc! {
                         "database": &model_state.database,
                         "collection": &model_state.collection
                     };
+                    match napalm_policy {
+                        NapalmPolicy::Keep | NapalmPolicy::Refuse => {
+                            // Keep Collection (left without a model).
+                            warnings.push(format!(
+                                "Collection `{}.{}` is left without a model.",
+                                model_state.database, model_state.collection
+                            ));
+                            continue;
+                        }
+                        NapalmPolicy::Archive => {
+                            // Rename Collection (left without a model).
+                            self.archive_collection(client, &model_state)?;
+                        }
+                        NapalmPolicy::Drop => {
+                            // Delete Collection (left without a model).
+                            client
+                                .database(&model_state.database)
+                                .collection(&model_state.collection)
+                                .drop(None)?;
+                            // Delete the records about the applied migration steps.
+                            mango_tech_db
+                                .collection("migrations")
+                                .delete_many(query.clone(), None)?;
+                        }
+                    }
+                    // Delete a document with a record about the state of
+                    // the model from the technical base.
                     collection_models.delete_one(query.clone(), None)?;
-                    collection_dyn_widgets.delete_one(query, None)?;
+                    collection_dyn_widgets.delete_one(query.clone(), None)?;
+                    // Delete the references of the orphaned Model to other Models.
+                    mango_tech_db
+                        .collection("relations")
+                        .delete_many(query, None)?;
+                }
+            }
+        }
+        //
+        Ok(warnings)
+    }
+
+    /// Get the collections whose Model is not registered in the Monitor.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn orphaned_collections(
+        &self,
+        client_store: &HashMap<String, Client>,
+    ) -> Result<Vec<DroppedCollection>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut orphaned_collections: Vec<DroppedCollection> = Vec::new();
+        let mut client_names: Vec<&String> = self
+            .models
+            .iter()
+            .map(|item| &item.db_client_name)
+            .collect();
+        client_names.sort();
+        client_names.dedup();
+        for client_name in client_names {
+            let client: &Client =
+                client_store
+                    .get(client_name)
+                    .ok_or_else(|| Error::InvalidConfig {
+                        context: format!("Client: `{}`", client_name),
+                        message: "Not found in MONGODB_CLIENT_STORE.".to_string(),
+                    })?;
+            let mango_tech_db: Database = client.database(&db_mango_tech);
+            let cursor: Cursor = mango_tech_db
+                .collection("monitor_models")
+                .find(None, None)?;
+            for result in cursor {
+                let model_state: ModelState = bson::de::from_document(result?)?;
+                if self.models.iter().any(|meta| {
+                    meta.database_name == model_state.database
+                        && meta.collection_name == model_state.collection
+                }) {
+                    continue;
                 }
+                let filter: Document = mongodb::bson::doc! {
+                    "database": &model_state.database,
+                    "collection": &model_state.collection
+                };
+                let dyn_fields: Vec<String> = mango_tech_db
+                    .collection("dynamic_widgets")
+                    .find_one(filter, None)?
+                    .and_then(|doc| doc.get_document("fields").ok().cloned())
+                    .map(|doc| doc.keys().cloned().collect())
+                    .unwrap_or_default();
+                orphaned_collections.push(DroppedCollection {
+                    docs_count: client
+                        .database(&model_state.database)
+                        .collection(&model_state.collection)
+                        .count_documents(None, None)?,
+                    database: model_state.database,
+                    collection: model_state.collection,
+                    dyn_fields,
+                });
             }
         }
         //
+        Ok(orphaned_collections)
+    }
+
+    /// Return an error if there are orphaned collections ( for the `Refuse` policy ).
+    // *********************************************************************************************
+    fn refuse_orphaned(orphaned_collections: &[DroppedCollection]) -> Result<(), Error> {
+        if !orphaned_collections.is_empty() {
+            Err(Error::OrphanedCollections {
+                collections: orphaned_collections
+                    .iter()
+                    .map(|item| format!("{}.{}", item.database, item.collection))
+                    .collect(),
+            })?
+        }
         Ok(())
     }
 
-    /// Migrating Models.
+    /// Archive the orphaned collection.
+    /// ( Rename the collection and save the records about its state in the `archives` collection )
     // *********************************************************************************************
-    /// Check the changes in the models and (if necessary) apply to the database.
-    pub fn migrat(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // Run refresh models state.
-        self.refresh()?;
-        // Get cache MongoDB clients.
+    #[cfg(feature = "sync")]
+    fn archive_collection(&self, client: &Client, model_state: &ModelState) -> Result<(), Error> {
+        let mango_tech_db: Database = client.database(&self.mango_tech_name()?);
+        let filter: Document = mongodb::bson::doc! {
+            "database": &model_state.database,
+            "collection": &model_state.collection
+        };
+        let archived_at: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+        let archive: String =
+            ArchivedCollection::archive_name(&model_state.collection, &archived_at);
+        // Rename the collection ( if it exists ).
+        if client
+            .database(&model_state.database)
+            .list_collection_names(None)?
+            .contains(&model_state.collection)
+        {
+            let command: Document = mongodb::bson::doc! {
+                "renameCollection": format!("{}.{}", model_state.database, model_state.collection),
+                "to": format!("{}.{}", model_state.database, archive)
+            };
+            client.database("admin").run_command(command, None)?;
+        }
+        // Save the records about the state of the collection.
+        let record = ArchivedCollection {
+            database: model_state.database.clone(),
+            collection: model_state.collection.clone(),
+            archive,
+            archived_at: bson::DateTime(archived_at),
+            model_state: mango_tech_db
+                .collection("monitor_models")
+                .find_one(filter.clone(), None)?
+                .unwrap_or_default(),
+            dynamic_widgets: mango_tech_db
+                .collection("dynamic_widgets")
+                .find_one(filter, None)?,
+        };
+        mango_tech_db
+            .collection("archives")
+            .insert_one(bson::ser::to_document(&record)?, None)?;
+        //
+        Ok(())
+    }
+
+    /// Get the records about the archived collections.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// for record in monitor.archives()? {
+    ///     println!("{} -> {}", record.collection, record.archive);
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn archives(&self) -> Result<Vec<ArchivedCollection>, Error> {
         let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
             MONGODB_CLIENT_STORE.read()?;
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut archives: Vec<ArchivedCollection> = Vec::new();
+        let mut client_names: Vec<&String> = self
+            .models
+            .iter()
+            .map(|item| &item.db_client_name)
+            .collect();
+        client_names.sort();
+        client_names.dedup();
+        for client_name in client_names {
+            let client: &Client =
+                client_store
+                    .get(client_name)
+                    .ok_or_else(|| Error::InvalidConfig {
+                        context: format!("Client: `{}`", client_name),
+                        message: "Not found in MONGODB_CLIENT_STORE.".to_string(),
+                    })?;
+            let cursor: Cursor = client
+                .database(&db_mango_tech)
+                .collection("archives")
+                .find(None, None)?;
+            for result in cursor {
+                archives.push(bson::de::from_document(result?)?);
+            }
+        }
+        //
+        Ok(archives)
+    }
 
-        // Get model metadata
+    /// Restore an archived collection.
+    /// ( The Model of the collection must be registered in the Monitor before running `migrat`,
+    /// otherwise the collection will be archived again )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// monitor.restore("service_name_user__archive__20210101120000")?;
+    /// monitor.migrat()?;
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn restore(&self, archive: &str) -> Result<(), Error> {
+        let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
+            MONGODB_CLIENT_STORE.read()?;
+        let db_mango_tech: String = self.mango_tech_name()?;
         for meta in self.models.iter() {
-            // Service_name validation.
-            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{1,31}$").unwrap().is_match(meta.service_name.as_str()) {
-                Err(format!("Model: `{}` : Service_name - Valid characters: _ a-z A-Z 0-9 \
-                             ; Max size: 31 ; First character: _ a-z A-Z", meta.model_name))?;
+            let client: &Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
+            let mango_tech_db: Database = client.database(&db_mango_tech);
+            let collection_archives: Collection = mango_tech_db.collection("archives");
+            let record: Option<Document> =
+                collection_archives.find_one(bson::doc! {"archive": archive}, None)?;
+            if record.is_none() {
+                continue;
             }
-            // Database name validation.
-            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{14,62}$").unwrap().is_match(meta.database_name.as_str()) {
-                Err(format!("Model: `{}` : Database name - Valid characters: _ a-z A-Z 0-9 \
-                             ; Max size: 21 ; First character: _ a-z A-Z", meta.model_name))?;
+            let record: ArchivedCollection = bson::de::from_document(record.unwrap())?;
+            // The collection could have been created again by `migrat`.
+            let db: Database = client.database(&record.database);
+            if db.list_collection_names(None)?.contains(&record.collection) {
+                let collection: Collection = db.collection(&record.collection);
+                if collection.count_documents(None, None)? > 0 {
+                    Err(Error::Archive {
+                        archive: archive.to_string(),
+                        message: format!(
+                            "Collection `{}.{}` already contains documents.",
+                            record.database, record.collection
+                        ),
+                    })?
+                }
+                collection.drop(None)?;
             }
-            //
-            let client: &Client = client_store.get(&meta.db_client_name).unwrap();
-            let fields_name: Vec<&str> =
-                meta.fields_name.iter().map(|item| item.as_str()).collect();
-            let ignore_fields: Vec<&str> = meta
-                .ignore_fields
-                .iter()
-                .map(|item| item.as_str())
-                .collect();
-            // List field names without `hash` and ignored fields.
-            let trunc_list_fields_name: Vec<&str> = fields_name
-                .iter()
-                .filter(|item| **item != "hash" && !ignore_fields.contains(item))
-                .map(|item| *item)
-                .collect();
-            // Get the name of the technical database for a project.
-            let db_mango_tech: String = self.mango_tech_name()?;
-            let database_names: Vec<String> = client.list_database_names(None, None)?;
-            // Map of default values and value types from `value (default)` attribute -
-            // <field_name, (widget_type, value)>
-            let map_default_values: HashMap<String, (String, String)> =
-                meta.map_default_values.clone();
-            // Get map of widgets types.
-            let map_widget_type = meta.map_widget_type.clone();
-            // Get truncated map of widgets types.
-            let trunc_map_widget_type: HashMap<String, String> = map_widget_type.clone();
-            trunc_map_widget_type.clone().retain(|k, _| k != "hash" && !ignore_fields.contains(&k.as_str()));
-            // Get a map of widgets from the technical database,
-            // from the `monitor_models` collection for current Model.
-            let monitor_map_widget_type: HashMap<String, String>;
-
-            // Check the field changes in the Model and (if required)
-            // update documents in the current Collection.
-            // -------------------------------------------------------------------------------------
-            // Get a list of current model field names from the technical database
-            // `mango_orm_keyword`.
+            // Rename the archive back.
+            let command: Document = mongodb::bson::doc! {
+                "renameCollection": format!("{}.{}", record.database, record.archive),
+                "to": format!("{}.{}", record.database, record.collection)
+            };
+            client.database("admin").run_command(command, None)?;
+            // Restore the records about the state of the collection.
             let filter: Document = mongodb::bson::doc! {
-                "database": &meta.database_name,
-                "collection": &meta.collection_name
+                "database": &record.database,
+                "collection": &record.collection
             };
-            let model: Option<Document> = client
-                .database(&db_mango_tech)
-                .collection("monitor_models")
-                .find_one(filter, None)?;
-            if model.is_some() {
-                let model: Document = model.unwrap();
-                // Get a list of fields from the technical database,
-                // from the `monitor_models` collection for current Model.
-                let monitor_models_fields_name: Vec<String> = {
-                    let fields: Vec<mongodb::bson::Bson> =
-                        model.get_array("fields")?.to_vec();
-                    fields
-                        .into_iter()
-                        .map(|item| item.as_str().unwrap().to_string())
-                        .collect()
-                };
-                // Get a map of widgets from the technical database,
-                // from the `monitor_models` collection for current Model.
-                monitor_map_widget_type = {
-                    model.get_document("map_widgets")
-                        .unwrap().iter()
-                        .map(|item| (item.0.clone(), item.1.as_str().unwrap().to_string()))
-                        .collect()
-                };
-                // Check if the set of fields in the collection of
-                // the current Model needs to be updated.
-                let mut changed_fields: Vec<&str> = Vec::new();
-                for field in trunc_list_fields_name.iter() {
-                    if !monitor_models_fields_name.contains(&field.to_string()) || 
-                        (trunc_map_widget_type.get(*field).unwrap() != 
-                            monitor_map_widget_type.get(*field).unwrap_or(&String::new())) {
-                        changed_fields.push(field);
-                    }
+            let collection_models: Collection = mango_tech_db.collection("monitor_models");
+            collection_models.delete_one(filter.clone(), None)?;
+            if !record.model_state.is_empty() {
+                collection_models.insert_one(record.model_state, None)?;
+            }
+            let collection_dyn_widgets: Collection = mango_tech_db.collection("dynamic_widgets");
+            collection_dyn_widgets.delete_one(filter, None)?;
+            if let Some(dynamic_widgets) = record.dynamic_widgets {
+                collection_dyn_widgets.insert_one(dynamic_widgets, None)?;
+            }
+            collection_archives.delete_one(bson::doc! {"archive": archive}, None)?;
+            return Ok(());
+        }
+        //
+        Err(Error::Archive {
+            archive: archive.to_string(),
+            message: "Not found.".to_string(),
+        })?
+    }
+
+    /// Get the references of the Model fields to other Models.
+    /// ( The referenced Models must be registered in the Monitor )
+    // *********************************************************************************************
+    ///
+    /// Returns: <(metadata of the referenced Model, relation)>
+    ///
+    fn model_relations(
+        &self,
+        meta: &crate::models::Meta,
+    ) -> Result<Vec<(&crate::models::Meta, ModelRelation)>, Error> {
+        let mut relations: Vec<(&crate::models::Meta, ModelRelation)> = Vec::new();
+        for (field, ref_model) in meta.map_ref_models.iter() {
+            let ref_meta: &crate::models::Meta = self
+                .models
+                .iter()
+                .find(|item| {
+                    item.database_name == ref_model.database_name
+                        && item.collection_name == ref_model.collection_name
+                })
+                .ok_or_else(|| Error::InvalidConfig {
+                    context: format!("Model: `{}` > Field: `{}`", meta.model_name, field),
+                    message: format!(
+                        "The referenced Model `{}` is not registered in the Monitor.",
+                        ref_model.model_name
+                    ),
+                })?;
+            relations.push((
+                ref_meta,
+                ModelRelation {
+                    model_name: meta.model_name.clone(),
+                    db_client_name: meta.db_client_name.clone(),
+                    database: meta.database_name.clone(),
+                    collection: meta.collection_name.clone(),
+                    field: field.clone(),
+                    widget: meta.map_widget_type.get(field).cloned().unwrap_or_default(),
+                    on_delete: ref_model.on_delete.clone(),
+                    ref_database: ref_model.database_name.clone(),
+                    ref_collection: ref_model.collection_name.clone(),
+                },
+            ));
+        }
+        //
+        Ok(relations)
+    }
+
+    /// Register the references of the Model in the technical database.
+    /// ( Used to apply `on_delete` when deleting referenced documents )
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn register_relations(
+        &self,
+        meta: &crate::models::Meta,
+        client_store: &HashMap<String, Client>,
+    ) -> Result<(), Error> {
+        // The documents of the Model are deleted by its own function ( `cascade` policy ).
+        meta.register_delete_fn()?;
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let filter: Document = mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name
+        };
+        // Remove outdated records from all clients of the project.
+        let mut client_names: Vec<&String> = self
+            .models
+            .iter()
+            .map(|item| &item.db_client_name)
+            .collect();
+        client_names.sort();
+        client_names.dedup();
+        for client_name in client_names {
+            if let Some(client) = client_store.get(client_name) {
+                client
+                    .database(&db_mango_tech)
+                    .collection("relations")
+                    .delete_many(filter.clone(), None)?;
+            }
+        }
+        // Add the current records to the technical database of the referenced Models.
+        for (ref_meta, relation) in self.model_relations(meta)? {
+            let client: &Client = client_store.get(&ref_meta.db_client_name).ok_or_else(|| {
+                Error::ClientNotFound {
+                    model: ref_meta.model_name.clone(),
+                    client: ref_meta.db_client_name.clone(),
                 }
-                // Start (if necessary) updating the set of fields in the current collection.
-                if !changed_fields.is_empty() {
-                    // Get the database and collection of the current Model.
-                    let db: Database = client.database(&meta.database_name);
-                    let collection: mongodb::sync::Collection =
-                        db.collection(&meta.collection_name);
-                    // Get cursor to all documents of the current Model.
-                    let mut cursor: mongodb::sync::Cursor = collection.find(None, None)?;
-                    // Iterate through all documents in a current (model) collection.
-                    while let Some(result) = cursor.next() {
-                        let doc_from_db: mongodb::bson::document::Document = result.unwrap();
-                        // Create temporary blank document.
-                        let mut tmp_doc = mongodb::bson::document::Document::new();
-                        // Loop over all fields of the model.
-                        for field in fields_name.iter() {
-                            if *field == "hash" || ignore_fields.contains(&field) {
-                                continue;
-                            }
-                            // If the field exists, get its value.
-                            if !changed_fields.contains(field) {
-                                let value_from_db: Option<&mongodb::bson::Bson> =
-                                    doc_from_db.get(field);
-                                if value_from_db.is_some() {
-                                    tmp_doc.insert(field.to_string(), value_from_db.unwrap());
-                                } else {
-                                    Err(format!("Service: `{}` > Model: `{}` > Field: `{}` > \
-                                                 Method: `migrat()` : \
-                                                 Can't get field value from database.",
-                                        meta.service_name, meta.model_name, field))?;
-                                }
-                            } else {
-                                // If no field exists, get default value.
-                                let value = map_default_values.get(*field).unwrap();
-                                tmp_doc.insert(
-                                    field.to_string(),
-                                    match value.0.as_str() {
-                                        "checkBoxText" | "radioText" | "inputColor"
-                                        | "inputEmail" | "inputPassword" | "inputPhone"
-                                        | "inputText" | "inputUrl" | "inputIP" | "inputIPv4"
-                                        | "inputIPv6" | "textArea" | "selectText" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                mongodb::bson::Bson::String(val)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "inputDate" => {
-                                            // Example: "1970-02-28".
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                if !crate::store::REGEX_IS_DATE.is_match(&val) {
-                                                    Err(format!("Service: `{}` > Model: `{}` > \
-                                                                 Method: `widgets()` : Incorrect date \
-                                                                 format. Example: 1970-02-28",
-                                                        meta.service_name, meta.model_name))?
-                                                }
-                                                let val = format!("{}T00:00", val);
-                                                let dt: chrono::DateTime<chrono::Utc> =
-                                                    chrono::DateTime::<chrono::Utc>::from_utc(
-                                                        chrono::NaiveDateTime::parse_from_str(
-                                                            &val,
-                                                            "%Y-%m-%dT%H:%M",
-                                                        )?, chrono::Utc,
-                                                    );
-                                                mongodb::bson::Bson::DateTime(dt)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "inputDateTime" => {
-                                            // Example: "1970-02-28T00:00".
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                if !crate::store::REGEX_IS_DATETIME.is_match(&val) {
-                                                    Err(format!("Service: `{}` > Model: `{}` > \
-                                                                 Method: `widgets()` : \
-                                                                 Incorrect date and time format. \
-                                                                 Example: 1970-02-28T00:00",
-                                                        meta.service_name, meta.model_name
-                                                    ))?
-                                                }
-                                                let dt: chrono::DateTime<chrono::Utc> =
-                                                    chrono::DateTime::<chrono::Utc>::from_utc(
-                                                        chrono::NaiveDateTime::parse_from_str(
-                                                            &val,
-                                                            "%Y-%m-%dT%H:%M",
-                                                        )?, chrono::Utc,
-                                                    );
-                                                mongodb::bson::Bson::DateTime(dt)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "radioI32" | "numberI32"
-                                        | "rangeI32" | "selectI32" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                mongodb::bson::Bson::Int32(
-                                                    val.parse::<i32>()?
-                                                )
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "radioU32" | "numberU32" | "rangeU32"
-                                        | "selectU32" | "checkBoxI64" | "radioI64"
-                                        | "numberI64" | "rangeI64" | "selectI64" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                mongodb::bson::Bson::Int64(
-                                                    val.parse::<i64>()?
-                                                )
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "radioF64" | "numberF64" | "rangeF64" | "selectF64" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                mongodb::bson::Bson::Double(
-                                                    val.parse::<f64>()?
-                                                )
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "checkBox" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                mongodb::bson::Bson::Boolean(
-                                                    val.parse::<bool>()?
-                                                )
-                                            } else {
-                                                mongodb::bson::Bson::Boolean(false)
-                                            }
-                                        }
-                                        "inputFile" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let mut file_data = 
-                                                    serde_json::from_str::<FileData>(val.as_str())?;
-                                                // Define flags to check.
-                                                let is_emty_path = file_data.path.is_empty();
-                                                let is_emty_url = file_data.url.is_empty();
-                                                if (!is_emty_path && is_emty_url)
-                                                    || (is_emty_path && !is_emty_url) {
-                                                    Err(format!("Model: `{}` > Field: `{}` > Method: \
-                                                                 `migrat()` : Check the `path` and `url` \
-                                                                 attributes in the `default` field parameter.",
-                                                        meta.model_name, field)
-                                                    )?
-                                                }
-                                                // Create path for validation of file.
-                                                let path: String = file_data.path.clone();
-                                                let f_path = std::path::Path::new(path.as_str());
-                                                if !f_path.exists() || !f_path.is_file() {
-                                                    Err(format!("Model: `{}` > Field: `{}` > Method: \
-                                                                 `migrat()` : File is missing - {}",
-                                                        meta.model_name, field, path)
-                                                    )?
-                                                }
-                                                // Get file metadata.
-                                                let metadata: std::fs::Metadata = f_path.metadata()?;
-                                                // Get file size in bytes.
-                                                file_data.size = metadata.len() as u32;
-                                                // Get file name.
-                                                file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
-                                                // Create doc.
-                                                let result = mongodb::bson::ser::to_document(&file_data)?;
-                                                mongodb::bson::Bson::Document(result)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "inputImage" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let mut file_data = 
-                                                    serde_json::from_str::<ImageData>(val.as_str())?;
-                                                // Define flags to check.
-                                                let is_emty_path = file_data.path.is_empty();
-                                                let is_emty_url = file_data.url.is_empty();
-                                                if (!is_emty_path && is_emty_url)
-                                                    || (is_emty_path && !is_emty_url) {
-                                                    Err(format!("Model: `{}` > Field: `{}` > Method: \
-                                                                 `migrat()` : Check the `path` and `url` \
-                                                                 attributes in the `default` field parameter.",
-                                                        meta.model_name, field
-                                                    ))?
-                                                }
-                                                // Create path for validation of file.
-                                                let path: String = file_data.path.clone();
-                                                let f_path = std::path::Path::new(path.as_str());
-                                                if !f_path.exists() || !f_path.is_file() {
-                                                    Err(format!("Model: `{}` > Field: `{}` > Method: \
-                                                                 `migrat()` : File is missing - {}",
-                                                        meta.model_name, field, path
-                                                    ))?
-                                                }
-                                                // Get file metadata.
-                                                let metadata: std::fs::Metadata = f_path.metadata()?;
-                                                // Get file size in bytes.
-                                                file_data.size = metadata.len() as u32;
-                                                // Get file name.
-                                                file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
-                                                // Get image width and height.
-                                                let dimensions: (u32, u32) = image::image_dimensions(path)?;
-                                                file_data.width = dimensions.0;
-                                                file_data.height = dimensions.1;
-                                                // Create doc.
-                                                let result = mongodb::bson::ser::to_document(&file_data)?;
-                                                mongodb::bson::Bson::Document(result)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "selectTextMult" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let val = serde_json::from_str::<Vec<String>>(val.as_str())?
-                                                    .iter().map(|item| mongodb::bson::Bson::String(item.clone()))
-                                                    .collect::<Vec<mongodb::bson::Bson>>();
-                                                mongodb::bson::Bson::Array(val)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "selectI32Mult" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let val = serde_json::from_str::<Vec<i32>>(val.as_str())?
-                                                    .iter().map(|item| mongodb::bson::Bson::Int32(item.clone()))
-                                                    .collect::<Vec<mongodb::bson::Bson>>();
-                                                mongodb::bson::Bson::Array(val)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                         "selectU32Mult" | "selectI64Mult"  => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let val = serde_json::from_str::<Vec<i64>>(val.as_str())?
-                                                    .iter().map(|item| mongodb::bson::Bson::Int64(item.clone()))
-                                                    .collect::<Vec<mongodb::bson::Bson>>();
-                                                mongodb::bson::Bson::Array(val)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "selectF64Mult" => {
-                                            let val: String = value.1.clone();
-                                            if !val.is_empty() {
-                                                let val = serde_json::from_str::<Vec<f64>>(val.as_str())?
-                                                    .iter().map(|item| mongodb::bson::Bson::Double(item.clone()))
-                                                    .collect::<Vec<mongodb::bson::Bson>>();
-                                                mongodb::bson::Bson::Array(val)
-                                            } else {
-                                                mongodb::bson::Bson::Null
-                                            }
-                                        }
-                                        "selectTextDyn" | "selectTextMultDyn" | "selectI32Dyn"
-                                        | "selectI32MultDyn" | "selectU32Dyn" | "selectU32MultDyn"
-                                        | "selectI64Dyn" | "selectI64MultDyn" | "selectF64Dyn"
-                                        | "selectF64MultDyn" => {
-                                            mongodb::bson::Bson::Null
-                                        }
-                                        _ => {
-                                            Err(format!("Service: `{}` > Model: `{}` > Method: \
-                                                         `migrat()` : Invalid Widget type.",
-                                                meta.service_name, meta.model_name
-                                            ))?
-                                        }
-                                    },
-                                );
-                            }
-                        }
-                        // Insert the reserved fields.
-                        for field in vec!["created_at", "updated_at"] {
-                            if doc_from_db.contains_key(field) {
-                                let value_from_db: Option<&mongodb::bson::Bson> =
-                                    doc_from_db.get(field);
-                                if value_from_db.is_some() {
-                                    tmp_doc.insert(field.to_string(), value_from_db.unwrap());
-                                } else {
-                                    Err(format!("Service: `{}` > Model: `{}` > \
-                                                 Method: `migrat()` : \
-                                                 Cannot get field value from database for \
-                                                 field `{}`.",
-                                        meta.service_name, meta.model_name, field
-                                    ))?
-                                }
-                            } else {
-                                Err(format!("Service: `{}` > Model: `{}` > Method: `migrat()` : \
-                                             Key `{}` was not found in the document from \
-                                             the database.",
-                                    meta.service_name, meta.model_name, field
-                                ))?
-                            }
-                        }
-                        // Save updated document.
-                        let query = mongodb::bson::doc! {"_id": doc_from_db.get_object_id("_id")?};
-                        collection.update_one(query, tmp_doc, None)?;
-                    }
+            })?;
+            client
+                .database(&db_mango_tech)
+                .collection("relations")
+                .insert_one(bson::ser::to_document(&relation)?, None)?;
+        }
+        //
+        Ok(())
+    }
+
+    /// Check the migration steps registered in the Monitor.
+    // *********************************************************************************************
+    fn check_migrations(&self, migrations: &[MigrationStep]) -> Result<(), Error> {
+        for (idx, step) in migrations.iter().enumerate() {
+            let meta: &crate::models::Meta = self
+                .models
+                .iter()
+                .find(|meta| meta.model_name == step.model_name)
+                .ok_or_else(|| Error::InvalidConfig {
+                    context: format!(
+                        "Migration: `{}` > Version: `{}`",
+                        step.model_name, step.version
+                    ),
+                    message: "The Model is not registered in the Monitor.".to_string(),
+                })?;
+            if migrations[..idx]
+                .iter()
+                .any(|item| item.model_name == step.model_name && item.version == step.version)
+            {
+                Err(Error::InvalidConfig {
+                    context: format!(
+                        "Migration: `{}` > Version: `{}`",
+                        step.model_name, step.version
+                    ),
+                    message: "The version is not unique.".to_string(),
+                })?
+            }
+            for field in step.migrated_fields() {
+                if field == "hash"
+                    || !meta.fields_name.contains(&field)
+                    || meta.ignore_fields.contains(&field)
+                {
+                    Err(Error::InvalidConfig {
+                        context: format!(
+                            "Migration: `{}` > Version: `{}`",
+                            step.model_name, step.version
+                        ),
+                        message: format!("The Model does not have a field named `{}`.", field),
+                    })?
                 }
-            } else {
-                monitor_map_widget_type = HashMap::new();
             }
+        }
+        //
+        Ok(())
+    }
 
-            // Create a new database (if doesn't exist) and add new collection.
-            // -------------------------------------------------------------------------------------
-            // Get the database for the current collection of Model.
-            let db: Database = client.database(&meta.database_name);
-            // If there is no collection for the current Model, create it.
-            if !database_names.contains(&meta.database_name)
-                || !db.list_collection_names(None)?.contains(&meta.collection_name)
-            {
-                db.create_collection(&meta.collection_name, None)?;
+    /// Get the migration steps of the Model that have not yet been applied.
+    /// ( In order of versions, the steps are not allowed to be older than the applied ones )
+    // *********************************************************************************************
+    fn pending_migrations<'s>(
+        migrations: &'s [MigrationStep],
+        meta: &crate::models::Meta,
+        applied_versions: &[u32],
+    ) -> Result<Vec<&'s MigrationStep>, Error> {
+        let last_version: Option<&u32> = applied_versions.iter().max();
+        let mut steps: Vec<&MigrationStep> = migrations
+            .iter()
+            .filter(|step| {
+                step.model_name == meta.model_name && !applied_versions.contains(&step.version)
+            })
+            .collect();
+        steps.sort_by_key(|step| step.version);
+        if let (Some(step), Some(last_version)) = (steps.first(), last_version) {
+            if step.version < *last_version {
+                Err(Error::InvalidConfig {
+                    context: format!(
+                        "Migration: `{}` > Version: `{}`",
+                        step.model_name, step.version
+                    ),
+                    message: format!(
+                        "The version is older than the applied version `{}`.",
+                        last_version
+                    ),
+                })?
             }
+        }
+        Ok(steps)
+    }
 
-            // Get the technical database `db_mango_tech` for the current model.
-            // -------------------------------------------------------------------------------------
-            let db: Database = client.database(&db_mango_tech);
+    /// Get the versions of the applied migration steps of the Model.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn applied_versions(
+        &self,
+        meta: &crate::models::Meta,
+        client: &Client,
+    ) -> Result<Vec<u32>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let filter: Document = mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name
+        };
+        let mut applied_versions: Vec<u32> = Vec::new();
+        for result in client
+            .database(&db_mango_tech)
+            .collection("migrations")
+            .find(filter, None)?
+        {
+            applied_versions.push(result?.get_i64("version")? as u32);
+        }
+        Ok(applied_versions)
+    }
 
-            // Update the state of models for `models::Monitor`.
-            // -------------------------------------------------------------------------------------
-            // Check if there is a technical database of the project, if not, causes panic.
-            if !database_names.contains(&db_mango_tech)
-                || !db.list_collection_names(None)?.contains(&"monitor_models".to_owned())
-            {
-                Err(format!("In the `refresh()` method, \
-                             no technical database has been created for the project."))?
-            } else {
-                let collection: Collection = db.collection("monitor_models");
-                let filter: Document = mongodb::bson::doc! {
+    /// Get the indexes of the Model collection.
+    /// ( Result of the `listIndexes` command, empty if there is no collection )
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn existing_indexes(
+        meta: &crate::models::Meta,
+        client: &Client,
+    ) -> Result<Vec<Document>, Error> {
+        let db: Database = client.database(&meta.database_name);
+        if !db
+            .list_collection_names(None)?
+            .contains(&meta.collection_name)
+        {
+            return Ok(Vec::new());
+        }
+        let result: Document =
+            db.run_command(bson::doc! {"listIndexes": &meta.collection_name}, None)?;
+        Self::indexes_from_cursor(&result)
+    }
+
+    /// Get the indexes from the result of the `listIndexes` command.
+    // *********************************************************************************************
+    fn indexes_from_cursor(result: &Document) -> Result<Vec<Document>, Error> {
+        Ok(result
+            .get_document("cursor")?
+            .get_array("firstBatch")?
+            .iter()
+            .filter_map(|item| item.as_document().cloned())
+            .collect())
+    }
+
+    /// Get the changes of the indexes of the Model collection.
+    /// ( Specifications of indexes to create, names of indexes to drop )
+    /// Hint: Only indexes with the `mango__` prefix are managed, other indexes are not touched.
+    // *********************************************************************************************
+    fn index_changes(
+        meta: &crate::models::Meta,
+        existing_indexes: &[Document],
+    ) -> (Vec<Document>, Vec<String>) {
+        let mut dropped_indexes: Vec<String> = existing_indexes
+            .iter()
+            .filter_map(|item| item.get_str("name").ok())
+            .filter(|name| name.starts_with("mango__"))
+            .filter(|name| !meta.indexes.iter().any(|index| index.name == *name))
+            .map(|name| name.to_string())
+            .collect();
+        let mut created_indexes: Vec<Document> = Vec::new();
+        for index in meta.indexes.iter() {
+            match existing_indexes
+                .iter()
+                .find(|item| item.get_str("name").ok() == Some(index.name.as_str()))
+            {
+                Some(index_from_db) if index.is_synced(meta, index_from_db) => {}
+                Some(_) => {
+                    // The index has changed - recreate it.
+                    dropped_indexes.push(index.name.clone());
+                    created_indexes.push(index.spec(meta));
+                }
+                None => created_indexes.push(index.spec(meta)),
+            }
+        }
+        (created_indexes, dropped_indexes)
+    }
+
+    /// Create, recreate and drop the indexes of the Model collection.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn sync_indexes(meta: &crate::models::Meta, client: &Client) -> Result<(), Error> {
+        let (created_indexes, dropped_indexes) =
+            Self::index_changes(meta, &Self::existing_indexes(meta, client)?);
+        let db: Database = client.database(&meta.database_name);
+        for name in dropped_indexes {
+            let command: Document = bson::doc! {
+                "dropIndexes": &meta.collection_name,
+                "index": name
+            };
+            db.run_command(command, None)?;
+        }
+        if !created_indexes.is_empty() {
+            let command: Document = bson::doc! {
+                "createIndexes": &meta.collection_name,
+                "indexes": created_indexes
+            };
+            db.run_command(command, None)
+                .map_err(|err| Error::InvalidConfig {
+                    context: format!("Model: `{}`", meta.model_name),
+                    message: format!(
+                        "Failed to create indexes \
+                         ( check the documents for duplicate values of unique fields ) - {}",
+                        err
+                    ),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Get a record about the applied migration step.
+    // *********************************************************************************************
+    fn migration_record(meta: &crate::models::Meta, step: &MigrationStep) -> Document {
+        mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name,
+            "model_name": &meta.model_name,
+            "version": step.version as i64,
+            "action": step.action_name(),
+            "applied_at": mongodb::bson::Bson::DateTime(chrono::Utc::now())
+        }
+    }
+
+    /// Apply the pending migration steps of the Model and record them in the technical database.
+    /// ( For a new Model, the steps are only recorded - there is no data to migrate )
+    // *********************************************************************************************
+    ///
+    /// Returns: list of the Model fields whose values are produced by the applied steps.
+    ///
+    #[cfg(feature = "sync")]
+    fn apply_migrations(
+        &self,
+        migrations: &[MigrationStep],
+        meta: &crate::models::Meta,
+        client: &Client,
+        is_new_model: bool,
+    ) -> Result<Vec<String>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let collection_migrations: Collection =
+            client.database(&db_mango_tech).collection("migrations");
+        let applied_versions: Vec<u32> = self.applied_versions(meta, client)?;
+        // Apply the pending steps.
+        let mut migrated_fields: Vec<String> = Vec::new();
+        let collection: Collection = client
+            .database(&meta.database_name)
+            .collection(&meta.collection_name);
+        for step in Self::pending_migrations(migrations, meta, &applied_versions)? {
+            if !is_new_model {
+                step.apply(&collection)?;
+                migrated_fields.append(&mut step.migrated_fields());
+            }
+            collection_migrations.insert_one(Self::migration_record(meta, step), None)?;
+        }
+        //
+        Ok(migrated_fields)
+    }
+
+    /// Check the time zones of the `inputDate` and `inputDateTime` fields.
+    // *********************************************************************************************
+    fn check_timezones(meta: &crate::models::Meta) -> Result<(), Error> {
+        for (field_name, timezone) in meta.map_timezones.iter() {
+            if crate::forms::DateTimeZone::new(timezone).is_none() {
+                Err(Error::InvalidConfig {
+                    context: format!("Model: `{}` > Field: `{}`", meta.model_name, field_name),
+                    message: Error::UnknownTimezone {
+                        timezone: timezone.to_string(),
+                    }
+                    .to_string(),
+                })?
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the default value of field in BSON format.
+    /// ( `value` - (widget_type, value) from `Meta.map_default_values` )
+    // *********************************************************************************************
+    fn default_value_to_bson(
+        meta: &crate::models::Meta,
+        field: &str,
+        value: &(String, String),
+    ) -> Result<mongodb::bson::Bson, Error> {
+        Ok(match value.0.as_str() {
+            "checkBoxText" | "radioText" | "inputColor" | "inputEmail" | "inputPassword"
+            | "inputPhone" | "inputText" | "inputUrl" | "inputIP" | "inputIPv4" | "inputIPv6"
+            | "textArea" | "selectText" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    mongodb::bson::Bson::String(val)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "inputDate" => {
+                // Example: "1970-02-28".
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    if !crate::store::REGEX_IS_DATE.is_match(&val) {
+                        Err(Error::InvalidConfig {
+                            context: format!(
+                                "Service: `{}` > Model: `{}` > Field: `{}` > Method: `widgets()`",
+                                meta.service_name, meta.model_name, field
+                            ),
+                            message: "Incorrect date format. Example: 1970-02-28".to_string(),
+                        })?
+                    }
+                    // The value is in the time zone of the field.
+                    let dt: chrono::DateTime<chrono::Utc> =
+                        crate::forms::DateTimeZone::of_field(&meta.map_timezones, field)
+                            .parse(value.0.as_str(), &val)?;
+                    mongodb::bson::Bson::DateTime(dt)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "inputDateTime" => {
+                // Example: "1970-02-28T00:00".
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    if !crate::store::REGEX_IS_DATETIME.is_match(&val) {
+                        Err(Error::InvalidConfig {
+                            context: format!(
+                                "Service: `{}` > Model: `{}` > Field: `{}` > Method: `widgets()`",
+                                meta.service_name, meta.model_name, field
+                            ),
+                            message: "Incorrect date and time format. Example: 1970-02-28T00:00"
+                                .to_string(),
+                        })?
+                    }
+                    // The value is in the time zone of the field.
+                    let dt: chrono::DateTime<chrono::Utc> =
+                        crate::forms::DateTimeZone::of_field(&meta.map_timezones, field)
+                            .parse(value.0.as_str(), &val)?;
+                    mongodb::bson::Bson::DateTime(dt)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "inputTime" | "inputTimeRange" => {
+                // Example: "09:00" or "09:00-18:00".
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let widget = crate::forms::Widget {
+                        widget: value.0.clone(),
+                        ..Default::default()
+                    };
+                    crate::forms::TimeRange::check_value(&widget, &val).map_err(|err| {
+                        Error::InvalidConfig {
+                            context: format!(
+                                "Service: `{}` > Model: `{}` > Field: `{}` > Method: `widgets()`",
+                                meta.service_name, meta.model_name, field
+                            ),
+                            message: err.to_string().replace("<br>", " "),
+                        }
+                    })?
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "numberDecimal" | "rangeDecimal" | "hiddenDecimal" => {
+                // Example: "1234.50" ( the macro brings the value to the `scale` ).
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    crate::forms::Decimal::parse(&val)
+                        .ok_or_else(|| Error::InvalidConfig {
+                            context: format!(
+                                "Service: `{}` > Model: `{}` > Field: `{}` > Method: `widgets()`",
+                                meta.service_name, meta.model_name, field
+                            ),
+                            message: "Incorrect number format. Example: 1234.56".to_string(),
+                        })?
+                        .to_bson()
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "inputGeoPoint" => {
+                // Example: "52.520008,13.404954" ( latitude,longitude ).
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    crate::forms::GeoPoint::check_value(&val)
+                        .map_err(|err| Error::InvalidConfig {
+                            context: format!(
+                                "Service: `{}` > Model: `{}` > Field: `{}` > Method: `widgets()`",
+                                meta.service_name, meta.model_name, field
+                            ),
+                            message: err.to_string().replace("<br>", " "),
+                        })?
+                        .to_bson()
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "radioI32" | "numberI32" | "rangeI32" | "selectI32" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    mongodb::bson::Bson::Int32(val.parse::<i32>()?)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "radioU32" | "numberU32" | "rangeU32" | "selectU32" | "checkBoxI64" | "radioI64"
+            | "numberI64" | "rangeI64" | "selectI64" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    mongodb::bson::Bson::Int64(val.parse::<i64>()?)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "radioF64" | "numberF64" | "rangeF64" | "selectF64" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    mongodb::bson::Bson::Double(val.parse::<f64>()?)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "checkBox" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    mongodb::bson::Bson::Boolean(val.parse::<bool>()?)
+                } else {
+                    mongodb::bson::Bson::Boolean(false)
+                }
+            }
+            "inputFile" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let mut file_data = serde_json::from_str::<FileData>(val.as_str())?;
+                    // Define flags to check.
+                    let is_emty_path = file_data.path.is_empty();
+                    let is_emty_url = file_data.url.is_empty();
+                    if (!is_emty_path && is_emty_url) || (is_emty_path && !is_emty_url) {
+                        Err(Error::InvalidConfig {
+                            context: format!(
+                                "Model: `{}` > Field: `{}` > Method: `migrat()`",
+                                meta.model_name, field
+                            ),
+                            message: "Check the `path` and `url` attributes \
+                                      in the `default` field parameter."
+                                .to_string(),
+                        })?
+                    }
+                    // Validation of file in the storage.
+                    let storage = field_storage(meta, field)?;
+                    let path: String = file_data.path.clone();
+                    if !storage.exists(path.as_str())? {
+                        Err(Error::FileNotFound {
+                            model: meta.model_name.clone(),
+                            field: field.to_string(),
+                            path: path.to_string(),
+                        })?
+                    }
+                    // Get file size in bytes.
+                    file_data.size = storage.size(path.as_str())? as u32;
+                    // Get file name.
+                    file_data.name = std::path::Path::new(path.as_str())
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .to_string();
+                    // Create doc.
+                    let result = mongodb::bson::ser::to_document(&file_data)?;
+                    mongodb::bson::Bson::Document(result)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "inputImage" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let mut file_data = serde_json::from_str::<ImageData>(val.as_str())?;
+                    // Define flags to check.
+                    let is_emty_path = file_data.path.is_empty();
+                    let is_emty_url = file_data.url.is_empty();
+                    if (!is_emty_path && is_emty_url) || (is_emty_path && !is_emty_url) {
+                        Err(Error::InvalidConfig {
+                            context: format!(
+                                "Model: `{}` > Field: `{}` > Method: `migrat()`",
+                                meta.model_name, field
+                            ),
+                            message: "Check the `path` and `url` attributes \
+                                      in the `default` field parameter."
+                                .to_string(),
+                        })?
+                    }
+                    // Validation of file in the storage.
+                    let storage = field_storage(meta, field)?;
+                    let path: String = file_data.path.clone();
+                    if !storage.exists(path.as_str())? {
+                        Err(Error::FileNotFound {
+                            model: meta.model_name.clone(),
+                            field: field.to_string(),
+                            path: path.to_string(),
+                        })?
+                    }
+                    // Get file size in bytes.
+                    file_data.size = storage.size(path.as_str())? as u32;
+                    // Get file name.
+                    file_data.name = std::path::Path::new(path.as_str())
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .to_string();
+                    // Get image width and height.
+                    let dimensions: (u32, u32) =
+                        image::load_from_memory(storage.get(path.as_str())?.as_slice())?
+                            .dimensions();
+                    file_data.width = dimensions.0;
+                    file_data.height = dimensions.1;
+                    // Create doc.
+                    let result = mongodb::bson::ser::to_document(&file_data)?;
+                    mongodb::bson::Bson::Document(result)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "selectTextMult" | "listText" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let val = serde_json::from_str::<Vec<String>>(val.as_str())?
+                        .iter()
+                        .map(|item| mongodb::bson::Bson::String(item.clone()))
+                        .collect::<Vec<mongodb::bson::Bson>>();
+                    mongodb::bson::Bson::Array(val)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "selectI32Mult" | "listI32" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let val = serde_json::from_str::<Vec<i32>>(val.as_str())?
+                        .iter()
+                        .map(|item| mongodb::bson::Bson::Int32(item.clone()))
+                        .collect::<Vec<mongodb::bson::Bson>>();
+                    mongodb::bson::Bson::Array(val)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "selectU32Mult" | "selectI64Mult" | "listU32" | "listI64" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let val = serde_json::from_str::<Vec<i64>>(val.as_str())?
+                        .iter()
+                        .map(|item| mongodb::bson::Bson::Int64(item.clone()))
+                        .collect::<Vec<mongodb::bson::Bson>>();
+                    mongodb::bson::Bson::Array(val)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "selectF64Mult" | "listF64" => {
+                let val: String = value.1.clone();
+                if !val.is_empty() {
+                    let val = serde_json::from_str::<Vec<f64>>(val.as_str())?
+                        .iter()
+                        .map(|item| mongodb::bson::Bson::Double(item.clone()))
+                        .collect::<Vec<mongodb::bson::Bson>>();
+                    mongodb::bson::Bson::Array(val)
+                } else {
+                    mongodb::bson::Bson::Null
+                }
+            }
+            "selectTextDyn" | "selectTextMultDyn" | "selectI32Dyn" | "selectI32MultDyn"
+            | "selectU32Dyn" | "selectU32MultDyn" | "selectI64Dyn" | "selectI64MultDyn"
+            | "selectF64Dyn" | "selectF64MultDyn" => mongodb::bson::Bson::Null,
+            "selectRef" | "selectRefMult" => mongodb::bson::Bson::Null,
+            _ => Err(Error::UnsupportedWidget {
+                model: meta.model_name.clone(),
+                field: field.to_string(),
+                widget: value.0.clone(),
+            })?,
+        })
+    }
+
+    /// Get the structure of the embedded documents of the Model.
+    /// Hint: <field name, { widget, fields }> - ignored fields are skipped.
+    // *********************************************************************************************
+    fn embedded_structures(meta: &crate::models::Meta) -> Document {
+        let mut structures = Document::new();
+        for field in meta.fields_name.iter() {
+            if meta.ignore_fields.contains(field) {
+                continue;
+            }
+            if let Some(embedded) = meta.map_embedded.get(field) {
+                let widget_type: &str = meta.map_widget_type.get(field).unwrap().as_str();
+                structures.insert(field.clone(), embedded.structure(widget_type));
+            }
+        }
+        structures
+    }
+
+    /// Get the value of a field with embedded documents after migration.
+    /// ( Nested fields that are new or whose widget type has changed get the default value )
+    // *********************************************************************************************
+    fn embedded_with_defaults(
+        meta: &crate::models::Meta,
+        field: &str,
+        monitor_map_embedded: &Document,
+        doc_from_db: &Document,
+    ) -> Result<mongodb::bson::Bson, Error> {
+        let embedded: &crate::forms::embedded::EmbeddedDoc = meta.map_embedded.get(field).unwrap();
+        let widget_type: &str = meta.map_widget_type.get(field).unwrap().as_str();
+        // If the widget type has not changed, bring the existing value in line with the structure.
+        if let (Ok(old_structure), Some(value_from_db)) = (
+            monitor_map_embedded.get_document(field),
+            doc_from_db.get(field),
+        ) {
+            if old_structure.get_str("widget").ok() == Some(widget_type) {
+                return embedded.merge_value(old_structure.get_document("fields")?, value_from_db);
+            }
+        }
+        // Otherwise, use the default value.
+        Ok(if widget_type == "embeddedDoc" {
+            mongodb::bson::Bson::Document(embedded.default_doc()?)
+        } else {
+            mongodb::bson::Bson::Array(Vec::new())
+        })
+    }
+
+    /// Get the fields of the Model whose values will be reset to default.
+    /// ( New fields and fields whose widget type or embedded structure has changed )
+    // *********************************************************************************************
+    ///
+    /// `model_state` - State of the Model from the `monitor_models` collection.
+    /// `migrated_fields` - Fields whose values are produced by the migration steps.
+    ///
+    fn changed_fields(
+        meta: &crate::models::Meta,
+        model_state: &Document,
+        migrated_fields: &[String],
+    ) -> Result<Vec<ChangedField>, Error> {
+        let monitor_fields_name: Vec<&str> = model_state
+            .get_array("fields")?
+            .iter()
+            .filter_map(|item| item.as_str())
+            .collect();
+        let monitor_map_widget_type: &Document = model_state.get_document("map_widgets")?;
+        let monitor_map_embedded: Option<&Document> = model_state.get_document("map_embedded").ok();
+        let map_embedded: Document = Self::embedded_structures(meta);
+        let mut changed_fields: Vec<ChangedField> = Vec::new();
+        for field in meta.fields_name.iter() {
+            // The values of migrated fields are already up to date.
+            if field == "hash"
+                || meta.ignore_fields.contains(field)
+                || migrated_fields.contains(field)
+            {
+                continue;
+            }
+            let old_widget: &str = monitor_map_widget_type.get_str(field).unwrap_or_default();
+            let new_widget: &str = meta.map_widget_type.get(field).unwrap().as_str();
+            if !monitor_fields_name.contains(&field.as_str())
+                || old_widget != new_widget
+                || map_embedded.get(field) != monitor_map_embedded.and_then(|doc| doc.get(field))
+            {
+                changed_fields.push(ChangedField {
+                    field: field.clone(),
+                    old_widget: old_widget.to_string(),
+                    new_widget: new_widget.to_string(),
+                });
+            }
+        }
+        //
+        Ok(changed_fields)
+    }
+
+    /// Get the changes of the collection of the Model.
+    // *********************************************************************************************
+    ///
+    /// `model_state` - State of the Model from the `monitor_models` collection.
+    /// `dyn_fields` - Fields of dynamic widgets from the `dynamic_widgets` collection.
+    /// `docs_count` - Number of documents in the collection of the Model.
+    ///
+    fn model_plan(
+        migrations: &[MigrationStep],
+        meta: &crate::models::Meta,
+        model_state: Option<&Document>,
+        applied_versions: &[u32],
+        dyn_fields: Option<&Document>,
+        docs_count: i64,
+    ) -> Result<ModelPlan, Error> {
+        let mut model_plan = ModelPlan {
+            model_name: meta.model_name.clone(),
+            database: meta.database_name.clone(),
+            collection: meta.collection_name.clone(),
+            is_new: model_state.is_none(),
+            pending_migrations: Self::pending_migrations(migrations, meta, applied_versions)?
+                .iter()
+                .map(|step| step.version)
+                .collect(),
+            ..Default::default()
+        };
+        if let Some(model_state) = model_state {
+            let migrated_fields: Vec<String> = migrations
+                .iter()
+                .filter(|step| model_plan.pending_migrations.contains(&step.version))
+                .filter(|step| step.model_name == meta.model_name)
+                .flat_map(|step| step.migrated_fields())
+                .collect();
+            model_plan.changed_fields = Self::changed_fields(meta, model_state, &migrated_fields)?;
+            model_plan.removed_fields = model_state
+                .get_array("fields")?
+                .iter()
+                .filter_map(|item| item.as_str())
+                .filter(|field| {
+                    !meta.fields_name.contains(&field.to_string())
+                        || meta.ignore_fields.contains(&field.to_string())
+                })
+                .map(|field| field.to_string())
+                .collect();
+            if !model_plan.changed_fields.is_empty() || !model_plan.pending_migrations.is_empty() {
+                model_plan.docs_to_rewrite = docs_count;
+            }
+            // Fields of dynamic widgets that are no longer in the Model or whose widget has changed.
+            if let Some(dyn_fields) = dyn_fields {
+                let monitor_map_widget_type: &Document = model_state.get_document("map_widgets")?;
+                for field in dyn_fields.keys() {
+                    let new_widget: Option<&String> = meta
+                        .map_widget_type
+                        .get(field)
+                        .filter(|_| !meta.ignore_fields.contains(field));
+                    if new_widget.map(|widget| widget.contains("Dyn")) != Some(true)
+                        || new_widget.map(|widget| widget.as_str())
+                            != monitor_map_widget_type.get_str(field).ok()
+                    {
+                        model_plan.dropped_dyn_fields.push(field.clone());
+                    }
+                }
+            }
+        }
+        //
+        Ok(model_plan)
+    }
+
+    /// Get the migration plan - what `migrat` would change, without touching the database.
+    /// ( New collections, changed fields, collections and dynamic widgets that would be dropped,
+    /// number of documents to be rewritten )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let monitor = Monitor {
+    ///     project_name: PROJECT_NAME,
+    ///     unique_project_key: UNIQUE_PROJECT_KEY,
+    ///     models: model_list()?,
+    /// };
+    /// let plan = monitor.plan()?;
+    /// println!("{}", plan.to_json()?);
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn plan(&self) -> Result<MigrationPlan, Error> {
+        self.plan_with(&MonitorSettings::default())
+    }
+
+    /// Get the migration plan with the settings of the Monitor.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn plan_with(&self, settings: &MonitorSettings) -> Result<MigrationPlan, Error> {
+        // Check the migration steps.
+        self.check_migrations(&settings.migrations)?;
+        // Get cache MongoDB clients.
+        let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
+            MONGODB_CLIENT_STORE.read()?;
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut plan = MigrationPlan::default();
+        // Changes of the registered Models.
+        for meta in self.models.iter() {
+            let client: &Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
+            let filter: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name
+            };
+            let mango_tech_db: Database = client.database(&db_mango_tech);
+            let model_state: Option<Document> = mango_tech_db
+                .collection("monitor_models")
+                .find_one(filter.clone(), None)?;
+            let dyn_fields: Option<Document> = mango_tech_db
+                .collection("dynamic_widgets")
+                .find_one(filter, None)?
+                .and_then(|doc| doc.get_document("fields").ok().cloned());
+            let docs_count: i64 = if model_state.is_some() {
+                client
+                    .database(&meta.database_name)
+                    .collection(&meta.collection_name)
+                    .count_documents(None, None)?
+            } else {
+                0_i64
+            };
+            let mut model_plan: ModelPlan = Self::model_plan(
+                &settings.migrations,
+                meta,
+                model_state.as_ref(),
+                &self.applied_versions(meta, client)?,
+                dyn_fields.as_ref(),
+                docs_count,
+            )?;
+            let (created_indexes, dropped_indexes) =
+                Self::index_changes(meta, &Self::existing_indexes(meta, client)?);
+            model_plan.set_index_changes(&created_indexes, dropped_indexes);
+            plan.models.push(model_plan);
+        }
+        // Orphaned collections.
+        plan.dropped_collections = self.orphaned_collections(&client_store)?;
+        plan.napalm_policy = settings.napalm_policy;
+        //
+        Ok(plan)
+    }
+
+    /// Create an updated document, for fields whose data type has changed, the default value is used.
+    /// ( `monitor_map_embedded` - structure of embedded documents at the previous migration )
+    // *********************************************************************************************
+    fn doc_with_defaults(
+        meta: &crate::models::Meta,
+        changed_fields: &[&str],
+        monitor_map_embedded: &Document,
+        doc_from_db: &Document,
+    ) -> Result<Document, Error> {
+        let ignore_fields: Vec<&str> = meta
+            .ignore_fields
+            .iter()
+            .map(|item| item.as_str())
+            .collect();
+        // Create temporary blank document.
+        let mut tmp_doc = mongodb::bson::document::Document::new();
+        // Loop over all fields of the model.
+        for field in meta.fields_name.iter() {
+            let field: &str = field.as_str();
+            if field == "hash" || ignore_fields.contains(&field) {
+                continue;
+            }
+            // If the field exists, get its value.
+            if !changed_fields.contains(&field) {
+                let value_from_db: Option<&mongodb::bson::Bson> = doc_from_db.get(field);
+                if value_from_db.is_some() {
+                    tmp_doc.insert(field.to_string(), value_from_db.unwrap());
+                } else {
+                    Err(Error::MissingField {
+                        model: meta.model_name.clone(),
+                        field: field.to_string(),
+                        method: "migrat()".to_string(),
+                    })?;
+                }
+            } else if meta.map_embedded.contains_key(field) {
+                // Embedded documents - default values are applied recursively.
+                tmp_doc.insert(
+                    field.to_string(),
+                    Self::embedded_with_defaults(meta, field, monitor_map_embedded, doc_from_db)?,
+                );
+            } else {
+                // If no field exists, get default value.
+                let value = meta.map_default_values.get(field).unwrap();
+                tmp_doc.insert(
+                    field.to_string(),
+                    Self::default_value_to_bson(meta, field, value)?,
+                );
+            }
+        }
+        // Insert the reserved fields.
+        // ( `deleted_at` is only in soft-deleted documents )
+        if let Some(value_from_db) = doc_from_db.get("deleted_at") {
+            tmp_doc.insert("deleted_at", value_from_db);
+        }
+        for field in vec!["created_at", "updated_at"] {
+            if doc_from_db.contains_key(field) {
+                let value_from_db: Option<&mongodb::bson::Bson> = doc_from_db.get(field);
+                if value_from_db.is_some() {
+                    tmp_doc.insert(field.to_string(), value_from_db.unwrap());
+                } else {
+                    Err(Error::MissingField {
+                        model: meta.model_name.clone(),
+                        field: field.to_string(),
+                        method: "migrat()".to_string(),
+                    })?
+                }
+            } else {
+                Err(Error::MissingField {
+                    model: meta.model_name.clone(),
+                    field: field.to_string(),
+                    method: "migrat()".to_string(),
+                })?
+            }
+        }
+        //
+        Ok(tmp_doc)
+    }
+
+    /// Migrating Models.
+    // *********************************************************************************************
+    /// Check the changes in the models and (if necessary) apply to the database.
+    #[cfg(feature = "sync")]
+    pub fn migrat(&self) -> Result<(), Error> {
+        self.migrat_with(&MonitorSettings::default())?;
+        Ok(())
+    }
+
+    /// Migrating Models with the settings of the Monitor.
+    // *********************************************************************************************
+    ///
+    /// Returns: warnings about the collections left without a model ( `NapalmPolicy::Keep` ).
+    ///
+    #[cfg(feature = "sync")]
+    fn migrat_with(&self, settings: &MonitorSettings) -> Result<Vec<String>, Error> {
+        // Check the migration steps.
+        self.check_migrations(&settings.migrations)?;
+        // Refuse to migrate if there are orphaned collections.
+        if settings.napalm_policy == NapalmPolicy::Refuse {
+            let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
+                MONGODB_CLIENT_STORE.read()?;
+            Self::refuse_orphaned(&self.orphaned_collections(&client_store)?)?;
+        }
+        // Run refresh models state.
+        self.refresh()?;
+        // Get cache MongoDB clients.
+        let client_store: std::sync::RwLockReadGuard<HashMap<String, Client>> =
+            MONGODB_CLIENT_STORE.read()?;
+
+        // Get model metadata
+        for meta in self.models.iter() {
+            // Service_name validation.
+            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{1,31}$").unwrap().is_match(meta.service_name.as_str()) {
+                Err(Error::InvalidConfig {
+                    context: format!("Model: `{}` > Service_name", meta.model_name),
+                    message: "Valid characters: _ a-z A-Z 0-9 ; Max size: 31 ; \
+                              First character: _ a-z A-Z"
+                        .to_string(),
+                })?;
+            }
+            // Database name validation.
+            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{14,62}$").unwrap().is_match(meta.database_name.as_str()) {
+                Err(Error::InvalidConfig {
+                    context: format!("Model: `{}` > Database name", meta.model_name),
+                    message: "Valid characters: _ a-z A-Z 0-9 ; Max size: 21 ; \
+                              First character: _ a-z A-Z"
+                        .to_string(),
+                })?;
+            }
+            // Time zones validation.
+            Self::check_timezones(meta)?;
+            //
+            let client: &Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
+            let fields_name: Vec<&str> =
+                meta.fields_name.iter().map(|item| item.as_str()).collect();
+            let ignore_fields: Vec<&str> = meta
+                .ignore_fields
+                .iter()
+                .map(|item| item.as_str())
+                .collect();
+            // List field names without `hash` and ignored fields.
+            let trunc_list_fields_name: Vec<&str> = fields_name
+                .iter()
+                .filter(|item| **item != "hash" && !ignore_fields.contains(item))
+                .map(|item| *item)
+                .collect();
+            // Get the name of the technical database for a project.
+            let db_mango_tech: String = self.mango_tech_name()?;
+            let database_names: Vec<String> = client.list_database_names(None, None)?;
+            // Get map of widgets types.
+            let map_widget_type = meta.map_widget_type.clone();
+            // Get truncated map of widgets types.
+            let trunc_map_widget_type: HashMap<String, String> = map_widget_type.clone();
+            trunc_map_widget_type.clone().retain(|k, _| k != "hash" && !ignore_fields.contains(&k.as_str()));
+            // Get the structure of embedded documents.
+            let map_embedded: Document = Self::embedded_structures(meta);
+            // Get a map of widgets from the technical database,
+            // from the `monitor_models` collection for current Model.
+            let monitor_map_widget_type: HashMap<String, String>;
+
+            // Check the field changes in the Model and (if required)
+            // update documents in the current Collection.
+            // -------------------------------------------------------------------------------------
+            // Get a list of current model field names from the technical database
+            // `mango_orm_keyword`.
+            let filter: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name
+            };
+            let model: Option<Document> = client
+                .database(&db_mango_tech)
+                .collection("monitor_models")
+                .find_one(filter, None)?;
+            // Apply the versioned migration steps.
+            let migrated_fields: Vec<String> =
+                self.apply_migrations(&settings.migrations, meta, client, model.is_none())?;
+            if model.is_some() {
+                let model: Document = model.unwrap();
+                // Get a map of widgets from the technical database,
+                // from the `monitor_models` collection for current Model.
+                monitor_map_widget_type = {
+                    model.get_document("map_widgets")
+                        .unwrap().iter()
+                        .map(|item| (item.0.clone(), item.1.as_str().unwrap().to_string()))
+                        .collect()
+                };
+                // Get the structure of embedded documents from the technical database.
+                let monitor_map_embedded: Document = model
+                    .get_document("map_embedded")
+                    .cloned()
+                    .unwrap_or_default();
+                // Check if the set of fields in the collection of
+                // the current Model needs to be updated.
+                let changed_fields: Vec<ChangedField> =
+                    Self::changed_fields(meta, &model, &migrated_fields)?;
+                let changed_fields: Vec<&str> = changed_fields
+                    .iter()
+                    .map(|item| item.field.as_str())
+                    .collect();
+                // Start (if necessary) updating the set of fields in the current collection.
+                if !changed_fields.is_empty() {
+                    // Get the database and collection of the current Model.
+                    let db: Database = client.database(&meta.database_name);
+                    let collection: mongodb::sync::Collection =
+                        db.collection(&meta.collection_name);
+                    // Get cursor to all documents of the current Model.
+                    let mut cursor: mongodb::sync::Cursor = collection.find(None, None)?;
+                    // Iterate through all documents in a current (model) collection.
+                    while let Some(result) = cursor.next() {
+                        let doc_from_db: mongodb::bson::document::Document = result.unwrap();
+                        // Create an updated document with default values for changed fields.
+                        let tmp_doc: Document = Self::doc_with_defaults(meta, &changed_fields, &monitor_map_embedded, &doc_from_db)?;
+                        // Save updated document.
+                        let query = mongodb::bson::doc! {"_id": doc_from_db.get_object_id("_id")?};
+                        collection.update_one(query, tmp_doc, None)?;
+                    }
+                }
+            } else {
+                monitor_map_widget_type = HashMap::new();
+            }
+
+            // Create a new database (if doesn't exist) and add new collection.
+            // -------------------------------------------------------------------------------------
+            // Get the database for the current collection of Model.
+            let db: Database = client.database(&meta.database_name);
+            // If there is no collection for the current Model, create it.
+            if !database_names.contains(&meta.database_name)
+                || !db.list_collection_names(None)?.contains(&meta.collection_name)
+            {
+                db.create_collection(&meta.collection_name, None)?;
+            }
+            // Create, recreate and drop the indexes of the collection.
+            Self::sync_indexes(meta, client)?;
+
+            // Get the technical database `db_mango_tech` for the current model.
+            // -------------------------------------------------------------------------------------
+            let db: Database = client.database(&db_mango_tech);
+
+            // Update the state of models for `models::Monitor`.
+            // -------------------------------------------------------------------------------------
+            // Check if there is a technical database of the project, if not, causes panic.
+            if !database_names.contains(&db_mango_tech)
+                || !db.list_collection_names(None)?.contains(&"monitor_models".to_owned())
+            {
+                Err(Error::MissingTechDatabase {
+                    database: db_mango_tech.clone(),
+                })?
+            } else {
+                let collection: Collection = db.collection("monitor_models");
+                let filter: Document = mongodb::bson::doc! {
                     "database": &meta.database_name,
                     "collection": &meta.collection_name
                 };
@@ -613,91 +1799,901 @@ impl<'a> Monitor<'a> {
                     "fields": trunc_list_fields_name.iter().map(|item| item.to_string())
                         .collect::<Vec<String>>(),
                     "map_widgets": bson::ser::to_bson(&trunc_map_widget_type.clone())?,
+                    "map_embedded": map_embedded.clone(),
                     "status": true
                 };
-                // Check if there is model state in the database.
-                if collection.count_documents(filter.clone(), None)?  == 0_i64 {
-                    // Add model state information.
-                    collection.insert_one(doc, None)?;
-                } else {
-                    // Full update model state information.
-                    let update = UpdateModifications::Document(doc);
-                    collection.update_one(filter, update, None)?;
+                // Check if there is model state in the database.
+                if collection.count_documents(filter.clone(), None)?  == 0_i64 {
+                    // Add model state information.
+                    collection.insert_one(doc, None)?;
+                } else {
+                    // Full update model state information.
+                    let update = UpdateModifications::Document(doc);
+                    collection.update_one(filter, update, None)?;
+                }
+            }
+
+            // Document management to support model fields with dynamic widgets.
+            // -------------------------------------------------------------------------------------
+            // Check if there is a technical database of the project, if not, causes panic.
+            if !database_names.contains(&db_mango_tech)
+                || !db.list_collection_names(None)?.contains(&"dynamic_widgets".to_owned())
+            {
+                Err(Error::MissingTechDatabase {
+                    database: db_mango_tech.clone(),
+                })?
+            } else {
+                let collection: Collection = db.collection("dynamic_widgets");
+                let filter: Document = mongodb::bson::doc! {
+                    "database": &meta.database_name,
+                    "collection": &meta.collection_name
+                };
+                // Check if there is a document in the database for 
+                // storing the values of dynamic widgets of model.
+                if collection.count_documents(filter.clone(), None)? == 0_i64 {
+                    // Init new document.
+                    let mut new_doc: Document = mongodb::bson::doc! {
+                        "database": &meta.database_name,
+                        "collection": &meta.collection_name,
+                        "fields": {}
+                    };
+                    // Add empty arrays to the new document.
+                    let mut fields_doc:  Document = mongodb::bson::document::Document::new();
+                    for (field, widget) in map_widget_type.clone() {
+                        if widget.contains("Dyn") {
+                            fields_doc.insert(field, mongodb::bson::Bson::Array(Vec::new()));
+                        }
+                    }
+                    // Insert new document.
+                    new_doc.insert("fields".to_string(), fields_doc);
+                    collection.insert_one(new_doc, None)?;
+                } else {
+                    // Get an existing document.
+                    let mut exist_doc = collection.find_one(filter.clone(), None)?.unwrap();
+                    // Get a document with `dynamic_widgets` fields.
+                    let fields_doc = exist_doc.get_document_mut("fields")?;
+                    // Get a list of fields from the technical database,
+                    // from the `dynamic_widgets` collection for current Model.
+                    let dyn_fields_from_db: Vec<String> = fields_doc.keys().map(|item| item.into()).collect();
+                    // Create an empty list for fields with dynamic widget types.
+                    let mut dyn_fields_from_model: Vec<String> = Vec::new();
+                    // Add new (if any) fields in `fields_doc`.
+                    for (field, widget) in trunc_map_widget_type.clone() {
+                        if widget.contains("Dyn") {
+                            dyn_fields_from_model.push(field.clone());
+                            // If the new field or widgets do not match,
+                            // initialize with an empty array.
+                            if !dyn_fields_from_db.contains(&field) || 
+                                (widget != *monitor_map_widget_type.get(field.as_str()).unwrap_or(&String::new())) {
+                                fields_doc.insert(field, mongodb::bson::Bson::Array(Vec::new()));
+                            }
+                        }
+                    }
+                    // Remove orphaned fields.
+                    for field in dyn_fields_from_db {
+                        if !dyn_fields_from_model.contains(&field) {
+                            fields_doc.remove(&field).unwrap();
+                        }
+                    }
+                    // Full update existing document.
+                    collection.update_one(filter, exist_doc, None)?;
+                }
+            }
+
+            // Register the references of the Model to other Models.
+            // -------------------------------------------------------------------------------------
+            self.register_relations(meta, &client_store)?;
+        }
+
+        // Unlock.
+        drop(client_store);
+        // Run reorganize databases state.
+        self.napalm(settings.napalm_policy)
+    }
+}
+
+// ASYNCHRONOUS MIGRATION
+// #################################################################################################
+#[cfg(feature = "async")]
+impl<'a> Monitor<'a> {
+    /// Get asynchronous MongoDB client for the Model.
+    // *********************************************************************************************
+    fn async_client(meta: &crate::models::Meta) -> Result<mongodb::Client, Error> {
+        let client_store = MONGODB_ASYNC_CLIENT_STORE.read()?;
+        let client: &mongodb::Client =
+            client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
+        Ok(client.clone())
+    }
+
+    /// Register the references of the Model in the technical database (asynchronous).
+    /// ( Used to apply `on_delete` when deleting referenced documents )
+    // *********************************************************************************************
+    async fn register_relations_async(&self, meta: &crate::models::Meta) -> Result<(), Error> {
+        // The documents of the Model are deleted by its own function ( `cascade` policy ).
+        meta.register_delete_fn()?;
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let filter: Document = mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name
+        };
+        // Remove outdated records from all clients of the project.
+        let mut client_names: Vec<&String> = Vec::new();
+        for item in self.models.iter() {
+            if !client_names.contains(&&item.db_client_name) {
+                client_names.push(&item.db_client_name);
+                Self::async_client(item)?
+                    .database(&db_mango_tech)
+                    .collection("relations")
+                    .delete_many(filter.clone(), None)
+                    .await?;
+            }
+        }
+        // Add the current records to the technical database of the referenced Models.
+        for (ref_meta, relation) in self.model_relations(meta)? {
+            Self::async_client(ref_meta)?
+                .database(&db_mango_tech)
+                .collection("relations")
+                .insert_one(bson::ser::to_document(&relation)?, None)
+                .await?;
+        }
+        //
+        Ok(())
+    }
+
+    /// Get the versions of the applied migration steps of the Model (asynchronous).
+    // *********************************************************************************************
+    async fn applied_versions_async(
+        &self,
+        meta: &crate::models::Meta,
+        client: &mongodb::Client,
+    ) -> Result<Vec<u32>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let filter: Document = mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name
+        };
+        let mut applied_versions: Vec<u32> = Vec::new();
+        let mut cursor: mongodb::Cursor = client
+            .database(&db_mango_tech)
+            .collection("migrations")
+            .find(filter, None)
+            .await?;
+        while let Some(result) = cursor.next().await {
+            applied_versions.push(result?.get_i64("version")? as u32);
+        }
+        Ok(applied_versions)
+    }
+
+    /// Get the indexes of the Model collection (asynchronous).
+    // *********************************************************************************************
+    async fn existing_indexes_async(
+        meta: &crate::models::Meta,
+        client: &mongodb::Client,
+    ) -> Result<Vec<Document>, Error> {
+        let db: mongodb::Database = client.database(&meta.database_name);
+        if !db
+            .list_collection_names(None)
+            .await?
+            .contains(&meta.collection_name)
+        {
+            return Ok(Vec::new());
+        }
+        let result: Document = db
+            .run_command(bson::doc! {"listIndexes": &meta.collection_name}, None)
+            .await?;
+        Self::indexes_from_cursor(&result)
+    }
+
+    /// Create, recreate and drop the indexes of the Model collection (asynchronous).
+    // *********************************************************************************************
+    async fn sync_indexes_async(
+        meta: &crate::models::Meta,
+        client: &mongodb::Client,
+    ) -> Result<(), Error> {
+        let (created_indexes, dropped_indexes) =
+            Self::index_changes(meta, &Self::existing_indexes_async(meta, client).await?);
+        let db: mongodb::Database = client.database(&meta.database_name);
+        for name in dropped_indexes {
+            let command: Document = bson::doc! {
+                "dropIndexes": &meta.collection_name,
+                "index": name
+            };
+            db.run_command(command, None).await?;
+        }
+        if !created_indexes.is_empty() {
+            let command: Document = bson::doc! {
+                "createIndexes": &meta.collection_name,
+                "indexes": created_indexes
+            };
+            db.run_command(command, None)
+                .await
+                .map_err(|err| Error::InvalidConfig {
+                    context: format!("Model: `{}`", meta.model_name),
+                    message: format!(
+                        "Failed to create indexes \
+                         ( check the documents for duplicate values of unique fields ) - {}",
+                        err
+                    ),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Apply the pending migration steps of the Model (asynchronous).
+    /// ( For a new Model, the steps are only recorded - there is no data to migrate )
+    // *********************************************************************************************
+    async fn apply_migrations_async(
+        &self,
+        migrations: &[MigrationStep],
+        meta: &crate::models::Meta,
+        client: &mongodb::Client,
+        is_new_model: bool,
+    ) -> Result<Vec<String>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let collection_migrations: mongodb::Collection =
+            client.database(&db_mango_tech).collection("migrations");
+        let applied_versions: Vec<u32> = self.applied_versions_async(meta, client).await?;
+        // Apply the pending steps.
+        let mut migrated_fields: Vec<String> = Vec::new();
+        let collection: mongodb::Collection = client
+            .database(&meta.database_name)
+            .collection(&meta.collection_name);
+        for step in Self::pending_migrations(migrations, meta, &applied_versions)? {
+            if !is_new_model {
+                step.apply_async(&collection).await?;
+                migrated_fields.append(&mut step.migrated_fields());
+            }
+            collection_migrations
+                .insert_one(Self::migration_record(meta, step), None)
+                .await?;
+        }
+        //
+        Ok(migrated_fields)
+    }
+
+    /// Refresh models state (asynchronous).
+    // *********************************************************************************************
+    async fn refresh_async(&self) -> Result<(), Error> {
+        for meta in self.models.iter() {
+            let client: mongodb::Client = Self::async_client(meta)?;
+            // Get the name of the technical database for a project.
+            let db_mango_tech: String = self.mango_tech_name()?;
+            //Get a list of databases.
+            let database_names: Vec<String> = client.list_database_names(None, None).await?;
+            // Create a technical database for the project if it doesn't exist.
+            if !database_names.contains(&db_mango_tech) {
+                // Create a collection for models.
+                client
+                    .database(&db_mango_tech)
+                    .create_collection("monitor_models", None)
+                    .await?;
+                // Create a collection for widget types of `select`.
+                // (selectTextDyn, selectTextMultDyn, etc.)
+                client
+                    .database(&db_mango_tech)
+                    .create_collection("dynamic_widgets", None)
+                    .await?;
+            } else {
+                // Reset models state information.
+                let collection_models: mongodb::Collection =
+                    client.database(&db_mango_tech).collection("monitor_models");
+                let mut cursor: mongodb::Cursor = collection_models.find(None, None).await?;
+                while let Some(result) = cursor.next().await {
+                    let document = result?;
+                    let mut model_state: ModelState = bson::de::from_document(document)?;
+                    model_state.status = false;
+                    let query: Document = bson::doc! {
+                        "database": &model_state.database,
+                        "collection": &model_state.collection
+                    };
+                    let update = bson::ser::to_document(&model_state)?;
+                    collection_models.update_one(query, update, None).await?;
+                }
+            }
+        }
+        //
+        Ok(())
+    }
+
+    /// Reorganize databases state (asynchronous).
+    /// ( Orphaned collections are dropped, archived or kept - see `Monitor::with_napalm_policy` )
+    // *********************************************************************************************
+    async fn napalm_async(&self, napalm_policy: NapalmPolicy) -> Result<Vec<String>, Error> {
+        let mut warnings: Vec<String> = Vec::new();
+        let mut client_names: Vec<&String> = Vec::new();
+        for meta in self.models.iter() {
+            // Each client is processed once.
+            if client_names.contains(&&meta.db_client_name) {
+                continue;
+            }
+            client_names.push(&meta.db_client_name);
+            let client: mongodb::Client = Self::async_client(meta)?;
+            // Get the name of the technical database for a project.
+            let db_mango_tech: String = self.mango_tech_name()?;
+            let mango_tech_db: mongodb::Database = client.database(&db_mango_tech);
+            let collection_models: mongodb::Collection = mango_tech_db.collection("monitor_models");
+            let collection_dyn_widgets: mongodb::Collection =
+                mango_tech_db.collection("dynamic_widgets");
+            // Delete orphaned Collections.
+            let cursor: mongodb::Cursor = collection_models.find(None, None).await?;
+            let results: Vec<Result<Document, mongodb::error::Error>> = cursor.collect().await;
+            for result in results {
+                let document = result?;
+                let model_state: ModelState = bson::de::from_document(document)?;
+                if !model_state.status {
+                    let query: Document = bson::doc! {
+                        "database": &model_state.database,
+                        "collection": &model_state.collection
+                    };
+                    match napalm_policy {
+                        NapalmPolicy::Keep | NapalmPolicy::Refuse => {
+                            // Keep Collection (left without a model).
+                            warnings.push(format!(
+                                "Collection `{}.{}` is left without a model.",
+                                model_state.database, model_state.collection
+                            ));
+                            continue;
+                        }
+                        NapalmPolicy::Archive => {
+                            // Rename Collection (left without a model).
+                            self.archive_collection_async(&client, &model_state).await?;
+                        }
+                        NapalmPolicy::Drop => {
+                            // Delete Collection (left without a model).
+                            client
+                                .database(&model_state.database)
+                                .collection(&model_state.collection)
+                                .drop(None)
+                                .await?;
+                            // Delete the records about the applied migration steps.
+                            mango_tech_db
+                                .collection("migrations")
+                                .delete_many(query.clone(), None)
+                                .await?;
+                        }
+                    }
+                    // Delete a document with a record about the state of
+                    // the model from the technical base.
+                    collection_models.delete_one(query.clone(), None).await?;
+                    collection_dyn_widgets
+                        .delete_one(query.clone(), None)
+                        .await?;
+                    // Delete the references of the orphaned Model to other Models.
+                    mango_tech_db
+                        .collection("relations")
+                        .delete_many(query, None)
+                        .await?;
+                }
+            }
+        }
+        //
+        Ok(warnings)
+    }
+
+    /// Get the collections whose Model is not registered in the Monitor (asynchronous).
+    // *********************************************************************************************
+    async fn orphaned_collections_async(&self) -> Result<Vec<DroppedCollection>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut orphaned_collections: Vec<DroppedCollection> = Vec::new();
+        let mut client_names: Vec<&String> = Vec::new();
+        for item in self.models.iter() {
+            if client_names.contains(&&item.db_client_name) {
+                continue;
+            }
+            client_names.push(&item.db_client_name);
+            let client: mongodb::Client = Self::async_client(item)?;
+            let mango_tech_db: mongodb::Database = client.database(&db_mango_tech);
+            let mut cursor: mongodb::Cursor = mango_tech_db
+                .collection("monitor_models")
+                .find(None, None)
+                .await?;
+            while let Some(result) = cursor.next().await {
+                let model_state: ModelState = bson::de::from_document(result?)?;
+                if self.models.iter().any(|meta| {
+                    meta.database_name == model_state.database
+                        && meta.collection_name == model_state.collection
+                }) {
+                    continue;
+                }
+                let filter: Document = mongodb::bson::doc! {
+                    "database": &model_state.database,
+                    "collection": &model_state.collection
+                };
+                let dyn_fields: Vec<String> = mango_tech_db
+                    .collection("dynamic_widgets")
+                    .find_one(filter, None)
+                    .await?
+                    .and_then(|doc| doc.get_document("fields").ok().cloned())
+                    .map(|doc| doc.keys().cloned().collect())
+                    .unwrap_or_default();
+                orphaned_collections.push(DroppedCollection {
+                    docs_count: client
+                        .database(&model_state.database)
+                        .collection(&model_state.collection)
+                        .count_documents(None, None)
+                        .await?,
+                    database: model_state.database,
+                    collection: model_state.collection,
+                    dyn_fields,
+                });
+            }
+        }
+        //
+        Ok(orphaned_collections)
+    }
+
+    /// Archive the orphaned collection (asynchronous).
+    // *********************************************************************************************
+    async fn archive_collection_async(
+        &self,
+        client: &mongodb::Client,
+        model_state: &ModelState,
+    ) -> Result<(), Error> {
+        let mango_tech_db: mongodb::Database = client.database(&self.mango_tech_name()?);
+        let filter: Document = mongodb::bson::doc! {
+            "database": &model_state.database,
+            "collection": &model_state.collection
+        };
+        let archived_at: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+        let archive: String =
+            ArchivedCollection::archive_name(&model_state.collection, &archived_at);
+        // Rename the collection ( if it exists ).
+        if client
+            .database(&model_state.database)
+            .list_collection_names(None)
+            .await?
+            .contains(&model_state.collection)
+        {
+            let command: Document = mongodb::bson::doc! {
+                "renameCollection": format!("{}.{}", model_state.database, model_state.collection),
+                "to": format!("{}.{}", model_state.database, archive)
+            };
+            client.database("admin").run_command(command, None).await?;
+        }
+        // Save the records about the state of the collection.
+        let record = ArchivedCollection {
+            database: model_state.database.clone(),
+            collection: model_state.collection.clone(),
+            archive,
+            archived_at: bson::DateTime(archived_at),
+            model_state: mango_tech_db
+                .collection("monitor_models")
+                .find_one(filter.clone(), None)
+                .await?
+                .unwrap_or_default(),
+            dynamic_widgets: mango_tech_db
+                .collection("dynamic_widgets")
+                .find_one(filter, None)
+                .await?,
+        };
+        mango_tech_db
+            .collection("archives")
+            .insert_one(bson::ser::to_document(&record)?, None)
+            .await?;
+        //
+        Ok(())
+    }
+
+    /// Get the records about the archived collections (asynchronous).
+    // *********************************************************************************************
+    pub async fn archives_async(&self) -> Result<Vec<ArchivedCollection>, Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut archives: Vec<ArchivedCollection> = Vec::new();
+        let mut client_names: Vec<&String> = Vec::new();
+        for item in self.models.iter() {
+            if client_names.contains(&&item.db_client_name) {
+                continue;
+            }
+            client_names.push(&item.db_client_name);
+            let mut cursor: mongodb::Cursor = Self::async_client(item)?
+                .database(&db_mango_tech)
+                .collection("archives")
+                .find(None, None)
+                .await?;
+            while let Some(result) = cursor.next().await {
+                archives.push(bson::de::from_document(result?)?);
+            }
+        }
+        //
+        Ok(archives)
+    }
+
+    /// Restore an archived collection (asynchronous).
+    // *********************************************************************************************
+    pub async fn restore_async(&self, archive: &str) -> Result<(), Error> {
+        let db_mango_tech: String = self.mango_tech_name()?;
+        for meta in self.models.iter() {
+            let client: mongodb::Client = Self::async_client(meta)?;
+            let mango_tech_db: mongodb::Database = client.database(&db_mango_tech);
+            let collection_archives: mongodb::Collection = mango_tech_db.collection("archives");
+            let record: Option<Document> = collection_archives
+                .find_one(bson::doc! {"archive": archive}, None)
+                .await?;
+            if record.is_none() {
+                continue;
+            }
+            let record: ArchivedCollection = bson::de::from_document(record.unwrap())?;
+            // The collection could have been created again by `migrat_async`.
+            let db: mongodb::Database = client.database(&record.database);
+            if db
+                .list_collection_names(None)
+                .await?
+                .contains(&record.collection)
+            {
+                let collection: mongodb::Collection = db.collection(&record.collection);
+                if collection.count_documents(None, None).await? > 0 {
+                    Err(Error::Archive {
+                        archive: archive.to_string(),
+                        message: format!(
+                            "Collection `{}.{}` already contains documents.",
+                            record.database, record.collection
+                        ),
+                    })?
                 }
+                collection.drop(None).await?;
+            }
+            // Rename the archive back.
+            let command: Document = mongodb::bson::doc! {
+                "renameCollection": format!("{}.{}", record.database, record.archive),
+                "to": format!("{}.{}", record.database, record.collection)
+            };
+            client.database("admin").run_command(command, None).await?;
+            // Restore the records about the state of the collection.
+            let filter: Document = mongodb::bson::doc! {
+                "database": &record.database,
+                "collection": &record.collection
+            };
+            let collection_models: mongodb::Collection = mango_tech_db.collection("monitor_models");
+            collection_models.delete_one(filter.clone(), None).await?;
+            if !record.model_state.is_empty() {
+                collection_models
+                    .insert_one(record.model_state, None)
+                    .await?;
             }
+            let collection_dyn_widgets: mongodb::Collection =
+                mango_tech_db.collection("dynamic_widgets");
+            collection_dyn_widgets.delete_one(filter, None).await?;
+            if let Some(dynamic_widgets) = record.dynamic_widgets {
+                collection_dyn_widgets
+                    .insert_one(dynamic_widgets, None)
+                    .await?;
+            }
+            collection_archives
+                .delete_one(bson::doc! {"archive": archive}, None)
+                .await?;
+            return Ok(());
+        }
+        //
+        Err(Error::Archive {
+            archive: archive.to_string(),
+            message: "Not found.".to_string(),
+        })?
+    }
 
-            // Document management to support model fields with dynamic widgets.
+    /// Get the migration plan (asynchronous).
+    /// ( What `migrat_async` would change, without touching the database )
+    // *********************************************************************************************
+    pub async fn plan_async(&self) -> Result<MigrationPlan, Error> {
+        self.plan_with_async(&MonitorSettings::default()).await
+    }
+
+    /// Get the migration plan with the settings of the Monitor (asynchronous).
+    // *********************************************************************************************
+    async fn plan_with_async(&self, settings: &MonitorSettings) -> Result<MigrationPlan, Error> {
+        // Check the migration steps.
+        self.check_migrations(&settings.migrations)?;
+        let db_mango_tech: String = self.mango_tech_name()?;
+        let mut plan = MigrationPlan::default();
+        // Changes of the registered Models.
+        for meta in self.models.iter() {
+            let client: mongodb::Client = Self::async_client(meta)?;
+            let filter: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name
+            };
+            let mango_tech_db: mongodb::Database = client.database(&db_mango_tech);
+            let model_state: Option<Document> = mango_tech_db
+                .collection("monitor_models")
+                .find_one(filter.clone(), None)
+                .await?;
+            let dyn_fields: Option<Document> = mango_tech_db
+                .collection("dynamic_widgets")
+                .find_one(filter, None)
+                .await?
+                .and_then(|doc| doc.get_document("fields").ok().cloned());
+            let docs_count: i64 = if model_state.is_some() {
+                client
+                    .database(&meta.database_name)
+                    .collection(&meta.collection_name)
+                    .count_documents(None, None)
+                    .await?
+            } else {
+                0_i64
+            };
+            let applied_versions: Vec<u32> = self.applied_versions_async(meta, &client).await?;
+            let mut model_plan: ModelPlan = Self::model_plan(
+                &settings.migrations,
+                meta,
+                model_state.as_ref(),
+                &applied_versions,
+                dyn_fields.as_ref(),
+                docs_count,
+            )?;
+            let (created_indexes, dropped_indexes) =
+                Self::index_changes(meta, &Self::existing_indexes_async(meta, &client).await?);
+            model_plan.set_index_changes(&created_indexes, dropped_indexes);
+            plan.models.push(model_plan);
+        }
+        // Orphaned collections.
+        plan.dropped_collections = self.orphaned_collections_async().await?;
+        plan.napalm_policy = settings.napalm_policy;
+        //
+        Ok(plan)
+    }
+
+    /// Migrating Models (asynchronous).
+    // *********************************************************************************************
+    /// Check the changes in the models and (if necessary) apply to the database.
+    /// Hint: Clients must be added to `MONGODB_ASYNC_CLIENT_STORE`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let monitor = Monitor {
+    ///     project_name: PROJECT_NAME,
+    ///     unique_project_key: UNIQUE_PROJECT_KEY,
+    ///     models: model_list()?,
+    /// };
+    /// monitor.migrat_async().await?;
+    /// ```
+    ///
+    pub async fn migrat_async(&self) -> Result<(), Error> {
+        self.migrat_with_async(&MonitorSettings::default()).await?;
+        Ok(())
+    }
+
+    /// Migrating Models with the settings of the Monitor (asynchronous).
+    // *********************************************************************************************
+    async fn migrat_with_async(&self, settings: &MonitorSettings) -> Result<Vec<String>, Error> {
+        // Check the migration steps.
+        self.check_migrations(&settings.migrations)?;
+        // Refuse to migrate if there are orphaned collections.
+        if settings.napalm_policy == NapalmPolicy::Refuse {
+            Self::refuse_orphaned(&self.orphaned_collections_async().await?)?;
+        }
+        // Run refresh models state.
+        self.refresh_async().await?;
+
+        // Get model metadata
+        for meta in self.models.iter() {
+            // Service_name validation.
+            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{1,31}$")
+                .unwrap()
+                .is_match(meta.service_name.as_str())
+            {
+                Err(Error::InvalidConfig {
+                    context: format!("Model: `{}` > Service_name", meta.model_name),
+                    message: "Valid characters: _ a-z A-Z 0-9 ; Max size: 31 ; \
+                              First character: _ a-z A-Z"
+                        .to_string(),
+                })?;
+            }
+            // Database name validation.
+            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{14,62}$")
+                .unwrap()
+                .is_match(meta.database_name.as_str())
+            {
+                Err(Error::InvalidConfig {
+                    context: format!("Model: `{}` > Database name", meta.model_name),
+                    message: "Valid characters: _ a-z A-Z 0-9 ; Max size: 21 ; \
+                              First character: _ a-z A-Z"
+                        .to_string(),
+                })?;
+            }
+            // Time zones validation.
+            Self::check_timezones(meta)?;
+            //
+            let client: mongodb::Client = Self::async_client(meta)?;
+            let ignore_fields: Vec<&str> = meta
+                .ignore_fields
+                .iter()
+                .map(|item| item.as_str())
+                .collect();
+            // List field names without `hash` and ignored fields.
+            let trunc_list_fields_name: Vec<&str> = meta
+                .fields_name
+                .iter()
+                .map(|item| item.as_str())
+                .filter(|item| *item != "hash" && !ignore_fields.contains(item))
+                .collect();
+            // Get the name of the technical database for a project.
+            let db_mango_tech: String = self.mango_tech_name()?;
+            let database_names: Vec<String> = client.list_database_names(None, None).await?;
+            // Get map of widgets types.
+            let map_widget_type: HashMap<String, String> = meta.map_widget_type.clone();
+            // Get the structure of embedded documents.
+            let map_embedded: Document = Self::embedded_structures(meta);
+            // Get a map of widgets from the technical database,
+            // from the `monitor_models` collection for current Model.
+            let mut monitor_map_widget_type: HashMap<String, String> = HashMap::new();
+
+            // Check the field changes in the Model and (if required)
+            // update documents in the current Collection.
             // -------------------------------------------------------------------------------------
-            // Check if there is a technical database of the project, if not, causes panic.
-            if !database_names.contains(&db_mango_tech)
-                || !db.list_collection_names(None)?.contains(&"dynamic_widgets".to_owned())
+            let filter: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name
+            };
+            let model: Option<Document> = client
+                .database(&db_mango_tech)
+                .collection("monitor_models")
+                .find_one(filter, None)
+                .await?;
+            // Apply the versioned migration steps.
+            let migrated_fields: Vec<String> = self
+                .apply_migrations_async(&settings.migrations, meta, &client, model.is_none())
+                .await?;
+            if let Some(model) = model {
+                // Get a map of widgets from the technical database,
+                // from the `monitor_models` collection for current Model.
+                monitor_map_widget_type = model
+                    .get_document("map_widgets")?
+                    .iter()
+                    .map(|item| (item.0.clone(), item.1.as_str().unwrap().to_string()))
+                    .collect();
+                // Get the structure of embedded documents from the technical database.
+                let monitor_map_embedded: Document = model
+                    .get_document("map_embedded")
+                    .cloned()
+                    .unwrap_or_default();
+                // Check if the set of fields in the collection of
+                // the current Model needs to be updated.
+                let changed_fields: Vec<ChangedField> =
+                    Self::changed_fields(meta, &model, &migrated_fields)?;
+                let changed_fields: Vec<&str> = changed_fields
+                    .iter()
+                    .map(|item| item.field.as_str())
+                    .collect();
+                // Start (if necessary) updating the set of fields in the current collection.
+                if !changed_fields.is_empty() {
+                    let collection: mongodb::Collection = client
+                        .database(&meta.database_name)
+                        .collection(&meta.collection_name);
+                    // Get cursor to all documents of the current Model.
+                    let mut cursor: mongodb::Cursor = collection.find(None, None).await?;
+                    // Iterate through all documents in a current (model) collection.
+                    while let Some(result) = cursor.next().await {
+                        let doc_from_db: Document = result?;
+                        // Create an updated document with default values for changed fields.
+                        let tmp_doc: Document = Self::doc_with_defaults(
+                            meta,
+                            &changed_fields,
+                            &monitor_map_embedded,
+                            &doc_from_db,
+                        )?;
+                        // Save updated document.
+                        let query = mongodb::bson::doc! {"_id": doc_from_db.get_object_id("_id")?};
+                        collection.update_one(query, tmp_doc, None).await?;
+                    }
+                }
+            }
+
+            // Create a new database (if doesn't exist) and add new collection.
+            // -------------------------------------------------------------------------------------
+            let db: mongodb::Database = client.database(&meta.database_name);
+            if !database_names.contains(&meta.database_name)
+                || !db
+                    .list_collection_names(None)
+                    .await?
+                    .contains(&meta.collection_name)
+            {
+                db.create_collection(&meta.collection_name, None).await?;
+            }
+            // Create, recreate and drop the indexes of the collection.
+            Self::sync_indexes_async(meta, &client).await?;
+
+            // Update the state of models for `models::Monitor`.
+            // -------------------------------------------------------------------------------------
+            let db: mongodb::Database = client.database(&db_mango_tech);
+            let collection_names: Vec<String> = db.list_collection_names(None).await?;
+            if !collection_names.contains(&"monitor_models".to_owned())
+                || !collection_names.contains(&"dynamic_widgets".to_owned())
             {
-                Err(format!("In the `refresh()` method, \
-                             no technical database has been created for the project."))?
+                Err(Error::MissingTechDatabase {
+                    database: db_mango_tech.clone(),
+                })?
+            }
+            let trunc_map_widget_type: HashMap<String, String> = map_widget_type
+                .iter()
+                .filter(|(field, _)| trunc_list_fields_name.contains(&field.as_str()))
+                .map(|(field, widget)| (field.clone(), widget.clone()))
+                .collect();
+            let collection: mongodb::Collection = db.collection("monitor_models");
+            let filter: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name
+            };
+            let doc: Document = mongodb::bson::doc! {
+                "database": &meta.database_name,
+                "collection": &meta.collection_name,
+                "fields": trunc_list_fields_name.iter().map(|item| item.to_string())
+                    .collect::<Vec<String>>(),
+                "map_widgets": bson::ser::to_bson(&trunc_map_widget_type)?,
+                "map_embedded": map_embedded.clone(),
+                "status": true
+            };
+            // Check if there is model state in the database.
+            if collection.count_documents(filter.clone(), None).await? == 0_i64 {
+                // Add model state information.
+                collection.insert_one(doc, None).await?;
             } else {
-                let collection: Collection = db.collection("dynamic_widgets");
-                let filter: Document = mongodb::bson::doc! {
-                    "database": &meta.database_name,
-                    "collection": &meta.collection_name
-                };
-                // Check if there is a document in the database for 
-                // storing the values of dynamic widgets of model.
-                if collection.count_documents(filter.clone(), None)? == 0_i64 {
-                    // Init new document.
-                    let mut new_doc: Document = mongodb::bson::doc! {
-                        "database": &meta.database_name,
-                        "collection": &meta.collection_name,
-                        "fields": {}
-                    };
-                    // Add empty arrays to the new document.
-                    let mut fields_doc:  Document = mongodb::bson::document::Document::new();
-                    for (field, widget) in map_widget_type.clone() {
-                        if widget.contains("Dyn") {
-                            fields_doc.insert(field, mongodb::bson::Bson::Array(Vec::new()));
+                // Full update model state information.
+                let update = UpdateModifications::Document(doc);
+                collection.update_one(filter.clone(), update, None).await?;
+            }
+
+            // Document management to support model fields with dynamic widgets.
+            // -------------------------------------------------------------------------------------
+            let collection: mongodb::Collection = db.collection("dynamic_widgets");
+            let exist_doc: Option<Document> = collection.find_one(filter.clone(), None).await?;
+            if let Some(mut exist_doc) = exist_doc {
+                // Get a document with `dynamic_widgets` fields.
+                let fields_doc = exist_doc.get_document_mut("fields")?;
+                // Get a list of fields from the technical database,
+                // from the `dynamic_widgets` collection for current Model.
+                let dyn_fields_from_db: Vec<String> =
+                    fields_doc.keys().map(|item| item.into()).collect();
+                // Create an empty list for fields with dynamic widget types.
+                let mut dyn_fields_from_model: Vec<String> = Vec::new();
+                // Add new (if any) fields in `fields_doc`.
+                for (field, widget) in trunc_map_widget_type.iter() {
+                    if widget.contains("Dyn") {
+                        dyn_fields_from_model.push(field.clone());
+                        // If the new field or widgets do not match,
+                        // initialize with an empty array.
+                        if !dyn_fields_from_db.contains(field)
+                            || Some(widget) != monitor_map_widget_type.get(field)
+                        {
+                            fields_doc
+                                .insert(field.clone(), mongodb::bson::Bson::Array(Vec::new()));
                         }
                     }
-                    // Insert new document.
-                    new_doc.insert("fields".to_string(), fields_doc);
-                    collection.insert_one(new_doc, None)?;
-                } else {
-                    // Get an existing document.
-                    let mut exist_doc = collection.find_one(filter.clone(), None)?.unwrap();
-                    // Get a document with `dynamic_widgets` fields.
-                    let fields_doc = exist_doc.get_document_mut("fields")?;
-                    // Get a list of fields from the technical database,
-                    // from the `dynamic_widgets` collection for current Model.
-                    let dyn_fields_from_db: Vec<String> = fields_doc.keys().map(|item| item.into()).collect();
-                    // Create an empty list for fields with dynamic widget types.
-                    let mut dyn_fields_from_model: Vec<String> = Vec::new();
-                    // Add new (if any) fields in `fields_doc`.
-                    for (field, widget) in trunc_map_widget_type.clone() {
-                        if widget.contains("Dyn") {
-                            dyn_fields_from_model.push(field.clone());
-                            // If the new field or widgets do not match,
-                            // initialize with an empty array.
-                            if !dyn_fields_from_db.contains(&field) || 
-                                (widget != *monitor_map_widget_type.get(field.as_str()).unwrap_or(&String::new())) {
-                                fields_doc.insert(field, mongodb::bson::Bson::Array(Vec::new()));
-                            }
-                        }
+                }
+                // Remove orphaned fields.
+                for field in dyn_fields_from_db {
+                    if !dyn_fields_from_model.contains(&field) {
+                        fields_doc.remove(&field).unwrap();
                     }
-                    // Remove orphaned fields.
-                    for field in dyn_fields_from_db {
-                        if !dyn_fields_from_model.contains(&field) {
-                            fields_doc.remove(&field).unwrap();
-                        }
+                }
+                // Full update existing document.
+                collection.update_one(filter, exist_doc, None).await?;
+            } else {
+                // Init new document with empty arrays.
+                let mut fields_doc: Document = mongodb::bson::document::Document::new();
+                for (field, widget) in map_widget_type.iter() {
+                    if widget.contains("Dyn") {
+                        fields_doc.insert(field.clone(), mongodb::bson::Bson::Array(Vec::new()));
                     }
-                    // Full update existing document.
-                    collection.update_one(filter, exist_doc, None)?;
                 }
+                let new_doc: Document = mongodb::bson::doc! {
+                    "database": &meta.database_name,
+                    "collection": &meta.collection_name,
+                    "fields": fields_doc
+                };
+                collection.insert_one(new_doc, None).await?;
             }
+
+            // Register the references of the Model to other Models.
+            // -------------------------------------------------------------------------------------
+            self.register_relations_async(meta).await?;
         }
 
-        // Unlock.
-        drop(client_store);
         // Run reorganize databases state.
-        self.napalm()?;
-        //
-        Ok(())
+        self.napalm_async(settings.napalm_policy).await
     }
 }
@@ -0,0 +1,69 @@
+//! # Orphaned collections.
+//! Collections whose Model is no longer registered in the Monitor.
+//!
+//! `NapalmPolicy` - What `napalm` does with orphaned collections.
+//! `ArchivedCollection` - Record about an archived collection.
+//!
+//! Methods:
+//! `archive_name` - Get the name of the archive collection.
+//!
+
+use mongodb::bson::{document::Document, oid::ObjectId, DateTime};
+use serde::{Deserialize, Serialize};
+
+/// What `napalm` does with orphaned collections.
+/// ( `Monitor::with_napalm_policy`, default - `Archive` )
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum NapalmPolicy {
+    /// Drop the collection and its records in the technical database.
+    Drop,
+    /// Rename the collection to `<collection>__archive__<timestamp>_<unique suffix>`,
+    /// it can be restored with `Monitor::restore()`.
+    Archive,
+    /// Keep the collection and its records, `migrat()` returns a warning.
+    Keep,
+    /// Return an error before the migration starts.
+    Refuse,
+}
+
+impl Default for NapalmPolicy {
+    fn default() -> Self {
+        NapalmPolicy::Archive
+    }
+}
+
+/// Record about an archived collection.
+/// ( Stored in the technical database, in the `archives` collection )
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArchivedCollection {
+    pub database: String,
+    pub collection: String,
+    pub archive: String,
+    pub archived_at: DateTime,
+    // State of the Model from the `monitor_models` collection.
+    pub model_state: Document,
+    // Fields of dynamic widgets from the `dynamic_widgets` collection.
+    pub dynamic_widgets: Option<Document>,
+}
+
+impl ArchivedCollection {
+    /// Get the name of the archive collection.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let archive = ArchivedCollection::archive_name("service_name_user", &chrono::Utc::now());
+    /// // service_name_user__archive__20210101120000_5ff18e4c4d2e8e3a7c1f2a9b
+    /// ```
+    ///
+    pub fn archive_name(collection: &str, archived_at: &chrono::DateTime<chrono::Utc>) -> String {
+        // The suffix keeps the names unique within one second.
+        format!(
+            "{}__archive__{}_{}",
+            collection,
+            archived_at.format("%Y%m%d%H%M%S"),
+            ObjectId::new().to_hex()
+        )
+    }
+}
@@ -0,0 +1,122 @@
+//! # Migration plan.
+//! The result of `Monitor::plan()` - what `Monitor::migrat()` would change, without touching the database.
+//!
+//! `MigrationPlan` - Structured diff between the registered Models and the state of the database.
+//! `ModelPlan` - Changes of the collection of a registered Model.
+//! `ChangedField` - Field whose value will be reset to default.
+//! `DroppedCollection` - Orphaned collection that will be processed by `napalm`.
+//!
+//! Methods:
+//! `is_empty` - Check if the plan does not contain any changes.
+//! `set_index_changes` - Add the changes of indexes to the plan.
+//! `to_json` - Get the plan in json-line format ( for review in CI or deployment ).
+//!
+
+use crate::{error::Error, migration::archive::NapalmPolicy};
+use mongodb::bson::document::Document;
+use serde::{Deserialize, Serialize};
+
+/// Field whose value will be reset to default.
+/// ( `old_widget` is empty for a new field )
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct ChangedField {
+    pub field: String,
+    pub old_widget: String,
+    pub new_widget: String,
+}
+
+/// Changes of the collection of a registered Model.
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct ModelPlan {
+    pub model_name: String,
+    pub database: String,
+    pub collection: String,
+    // The collection will be created.
+    pub is_new: bool,
+    // Versions of the migration steps that have not yet been applied.
+    pub pending_migrations: Vec<u32>,
+    pub changed_fields: Vec<ChangedField>,
+    // Fields that are no longer in the Model ( removed when documents are rewritten ).
+    pub removed_fields: Vec<String>,
+    // Fields of dynamic widgets whose list of options will be dropped or reset.
+    pub dropped_dyn_fields: Vec<String>,
+    // Number of documents to be rewritten.
+    pub docs_to_rewrite: i64,
+    // Names of indexes that will be created ( or recreated, if the index has changed ).
+    pub created_indexes: Vec<String>,
+    // Names of indexes that will be dropped.
+    pub dropped_indexes: Vec<String>,
+}
+
+impl ModelPlan {
+    /// Check if the plan does not contain any changes.
+    // *********************************************************************************************
+    pub fn is_empty(&self) -> bool {
+        !self.is_new
+            && self.pending_migrations.is_empty()
+            && self.changed_fields.is_empty()
+            && self.removed_fields.is_empty()
+            && self.dropped_dyn_fields.is_empty()
+            && self.created_indexes.is_empty()
+            && self.dropped_indexes.is_empty()
+    }
+
+    /// Add the changes of indexes to the plan.
+    /// ( `created_indexes` - Specifications of indexes for the `createIndexes` command )
+    // *********************************************************************************************
+    pub fn set_index_changes(
+        &mut self,
+        created_indexes: &[Document],
+        dropped_indexes: Vec<String>,
+    ) {
+        self.created_indexes = created_indexes
+            .iter()
+            .filter_map(|item| item.get_str("name").ok())
+            .map(|name| name.to_string())
+            .collect();
+        self.dropped_indexes = dropped_indexes;
+    }
+}
+
+/// Orphaned collection that will be processed by `napalm`.
+/// ( The Model of the collection is not registered in the Monitor,
+/// what happens to it depends on `MigrationPlan.napalm_policy` )
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct DroppedCollection {
+    pub database: String,
+    pub collection: String,
+    pub docs_count: i64,
+    // Fields of dynamic widgets whose list of options will be dropped.
+    pub dyn_fields: Vec<String>,
+}
+
+/// Structured diff between the registered Models and the state of the database.
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct MigrationPlan {
+    pub models: Vec<ModelPlan>,
+    pub dropped_collections: Vec<DroppedCollection>,
+    // What `napalm` does with `dropped_collections`.
+    pub napalm_policy: NapalmPolicy,
+}
+
+impl MigrationPlan {
+    /// Check if the plan does not contain any changes.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let plan = monitor.plan()?;
+    /// assert!(plan.is_empty(), "{}", plan.to_json()?);
+    /// ```
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.models.iter().all(|item| item.is_empty()) && self.dropped_collections.is_empty()
+    }
+
+    /// Get the plan in json-line format.
+    // *********************************************************************************************
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
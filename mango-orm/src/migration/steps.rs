@@ -0,0 +1,222 @@
+//! # Versioned migration steps.
+//!
+//! `MigrationAction` - Action of the migration step ( rename field, convert field, transform ).
+//! `MigrationStep` - Migration step of the Model, applied once and in order of versions.
+//!
+//! Methods:
+//! `rename_field` - Create a step that renames the field in all documents of the Model.
+//! `convert_field` - Create a step that converts the value of the field with a user closure.
+//! `transform` - Create a step with arbitrary transformation of each document of the Model.
+//! `action_name` - Get the name of the step action.
+//! `migrated_fields` - Get the Model fields whose values are produced by the step.
+//! `apply` - Apply the step to the collection of the Model.
+//! `apply_async` - Asynchronous variant of `apply` ( feature `async` ).
+//!
+
+use crate::error::Error;
+#[cfg(feature = "async")]
+use futures::stream::StreamExt;
+use mongodb::bson::{doc, document::Document, Bson};
+
+/// Closure for converting the value of field.
+/// Hint: If the field is missing in the document, `Bson::Null` is passed.
+pub type ConvertFn = Box<dyn Fn(&Bson) -> Result<Bson, Box<dyn std::error::Error>> + Send + Sync>;
+
+/// Closure for transforming a document.
+/// Hint: The `_id` field is restored after the transformation.
+pub type TransformFn =
+    Box<dyn Fn(Document) -> Result<Document, Box<dyn std::error::Error>> + Send + Sync>;
+
+/// Action of the migration step.
+pub enum MigrationAction {
+    /// Rename the field in all documents of the Model.
+    RenameField { from: String, to: String },
+    /// Convert the value of field in all documents of the Model.
+    ConvertField { field: String, convert: ConvertFn },
+    /// Arbitrary transformation of each document of the Model.
+    /// `fields` - Model fields whose values are produced by the transformation.
+    Transform {
+        fields: Vec<String>,
+        transform: TransformFn,
+    },
+}
+
+/// Migration step of the Model.
+/// ( Applied once and in order of versions, the applied steps are recorded
+/// in the technical database, in the `migrations` collection )
+pub struct MigrationStep {
+    pub model_name: String,
+    pub version: u32,
+    pub action: MigrationAction,
+}
+
+impl MigrationStep {
+    /// Create a step that renames the field in all documents of the Model.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let step = MigrationStep::rename_field("UserProfile", 1, "name", "username");
+    /// ```
+    ///
+    pub fn rename_field(model_name: &str, version: u32, from: &str, to: &str) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            version,
+            action: MigrationAction::RenameField {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        }
+    }
+
+    /// Create a step that converts the value of the field with a user closure.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let step = MigrationStep::convert_field("UserProfile", 2, "age", |value| {
+    ///     Ok(match value {
+    ///         Bson::String(val) => Bson::Int32(val.parse()?),
+    ///         _ => Bson::Null,
+    ///     })
+    /// });
+    /// ```
+    ///
+    pub fn convert_field<F>(model_name: &str, version: u32, field: &str, convert: F) -> Self
+    where
+        F: Fn(&Bson) -> Result<Bson, Box<dyn std::error::Error>> + Send + Sync + 'static,
+    {
+        Self {
+            model_name: model_name.to_string(),
+            version,
+            action: MigrationAction::ConvertField {
+                field: field.to_string(),
+                convert: Box::new(convert),
+            },
+        }
+    }
+
+    /// Create a step with arbitrary transformation of each document of the Model.
+    /// `fields` - Model fields whose values are produced by the transformation.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let step = MigrationStep::transform("UserProfile", 3, &["full_name"], |mut doc| {
+    ///     let full_name = format!(
+    ///         "{} {}",
+    ///         doc.get_str("first_name").unwrap_or_default(),
+    ///         doc.get_str("last_name").unwrap_or_default()
+    ///     );
+    ///     doc.insert("full_name", full_name.trim());
+    ///     Ok(doc)
+    /// });
+    /// ```
+    ///
+    pub fn transform<F>(model_name: &str, version: u32, fields: &[&str], transform: F) -> Self
+    where
+        F: Fn(Document) -> Result<Document, Box<dyn std::error::Error>> + Send + Sync + 'static,
+    {
+        Self {
+            model_name: model_name.to_string(),
+            version,
+            action: MigrationAction::Transform {
+                fields: fields.iter().map(|field| field.to_string()).collect(),
+                transform: Box::new(transform),
+            },
+        }
+    }
+
+    /// Get the name of the step action.
+    // *********************************************************************************************
+    pub fn action_name(&self) -> &str {
+        match self.action {
+            MigrationAction::RenameField { .. } => "rename_field",
+            MigrationAction::ConvertField { .. } => "convert_field",
+            MigrationAction::Transform { .. } => "transform",
+        }
+    }
+
+    /// Get the Model fields whose values are produced by the step.
+    /// ( `migrat` keeps the values of these fields instead of resetting them to default )
+    // *********************************************************************************************
+    pub fn migrated_fields(&self) -> Vec<String> {
+        match &self.action {
+            MigrationAction::RenameField { to, .. } => vec![to.clone()],
+            MigrationAction::ConvertField { field, .. } => vec![field.clone()],
+            MigrationAction::Transform { fields, .. } => fields.clone(),
+        }
+    }
+
+    /// Apply the step to the collection of the Model.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    pub fn apply(&self, collection: &mongodb::sync::Collection) -> Result<(), Error> {
+        match &self.action {
+            MigrationAction::RenameField { from, to } => {
+                let filter: Document = doc! {from.as_str(): {"$exists": true}};
+                let update: Document = doc! {"$rename": {from.as_str(): to.as_str()}};
+                collection.update_many(filter, update, None)?;
+            }
+            MigrationAction::ConvertField { field, convert } => {
+                for result in collection.find(None, None)? {
+                    let doc_from_db: Document = result?;
+                    let value: Bson = convert(doc_from_db.get(field).unwrap_or(&Bson::Null))?;
+                    let query: Document = doc! {"_id": doc_from_db.get_object_id("_id")?};
+                    let update: Document = doc! {"$set": {field.as_str(): value}};
+                    collection.update_one(query, update, None)?;
+                }
+            }
+            MigrationAction::Transform { transform, .. } => {
+                for result in collection.find(None, None)? {
+                    let doc_from_db: Document = result?;
+                    let object_id = doc_from_db.get_object_id("_id")?.clone();
+                    let mut new_doc: Document = transform(doc_from_db)?;
+                    new_doc.insert("_id", object_id.clone());
+                    collection.replace_one(doc! {"_id": object_id}, new_doc, None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the step to the collection of the Model (asynchronous).
+    // *********************************************************************************************
+    #[cfg(feature = "async")]
+    pub async fn apply_async(&self, collection: &mongodb::Collection) -> Result<(), Error> {
+        match &self.action {
+            MigrationAction::RenameField { from, to } => {
+                let filter: Document = doc! {from.as_str(): {"$exists": true}};
+                let update: Document = doc! {"$rename": {from.as_str(): to.as_str()}};
+                collection.update_many(filter, update, None).await?;
+            }
+            MigrationAction::ConvertField { field, convert } => {
+                let mut cursor: mongodb::Cursor = collection.find(None, None).await?;
+                while let Some(result) = cursor.next().await {
+                    let doc_from_db: Document = result?;
+                    let value: Bson = convert(doc_from_db.get(field).unwrap_or(&Bson::Null))?;
+                    let query: Document = doc! {"_id": doc_from_db.get_object_id("_id")?};
+                    let update: Document = doc! {"$set": {field.as_str(): value}};
+                    collection.update_one(query, update, None).await?;
+                }
+            }
+            MigrationAction::Transform { transform, .. } => {
+                let mut cursor: mongodb::Cursor = collection.find(None, None).await?;
+                while let Some(result) = cursor.next().await {
+                    let doc_from_db: Document = result?;
+                    let object_id = doc_from_db.get_object_id("_id")?.clone();
+                    let mut new_doc: Document = transform(doc_from_db)?;
+                    new_doc.insert("_id", object_id.clone());
+                    collection
+                        .replace_one(doc! {"_id": object_id}, new_doc, None)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
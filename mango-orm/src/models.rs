@@ -2,21 +2,58 @@
 //! Adapts the Structure for database queries using a programmatic or web interface.
 //!
 //! `Meta` - Metadata of model (database name, collection name, etc).
+//! `RefModel` - Information about the Model referenced by the field.
+//! `Index` - Index of the collection ( created and synced by `Monitor::migrat` ).
 //! `ToModel` - Transforms the Structure into a Model.
+//! ( Typed query builder - `query` module )
+//! ( Audit trail - `audit` module )
+//! ( Lifecycle hooks - `hooks` module )
+//! ( Upload ingestion - `upload` module )
 //!
 
 use crate::{
-    forms::{html_controls::HtmlControls, Widget},
-    models::validation::{AdditionalValidation, ValidationModel},
+    error::Error,
+    forms::{
+        embedded::EmbeddedDoc, html_controls::HtmlControls, output_data::OutputDataForm, Widget,
+    },
+    models::{
+        hooks::Hooks,
+        validation::{AdditionalValidation, ValidationModel},
+    },
+    store::DELETE_FN_STORE,
 };
 
+pub mod audit;
 pub mod caching;
+#[cfg(feature = "async")]
+pub mod caching_async;
 pub mod db_query_api;
+pub mod hooks;
 pub mod output_data;
+pub mod query;
+pub mod upload;
 pub mod validation;
 
+#[cfg(feature = "sync")]
+pub use crate::models::db_query_api::paladins::delete_doc;
+#[cfg(feature = "async")]
+pub use crate::models::db_query_api::paladins_async::delete_doc;
+
 // MODEL
 // #################################################################################################
+/// Deletes a document of the Model with the `delete()` method of the Model.
+/// ( For the `cascade` policy - hooks, files, soft delete, audit trail and nested relations )
+#[cfg(feature = "sync")]
+pub type DeleteFn =
+    fn(mongodb::bson::document::Document, Option<&str>) -> Result<OutputDataForm, Error>;
+#[cfg(feature = "async")]
+pub type DeleteFn = fn(
+    mongodb::bson::document::Document,
+    Option<String>,
+) -> std::pin::Pin<
+    Box<dyn std::future::Future<Output = Result<OutputDataForm, Error>> + Send>,
+>;
+
 /// Metadata.
 /// ( Model parameters )
 // *************************************************************************************************
@@ -35,12 +72,31 @@ pub struct Meta {
     pub is_add_docs: bool,
     pub is_up_docs: bool,
     pub is_del_docs: bool,
+    // `delete()` marks the document with `deleted_at` instead of removing it.
+    pub is_soft_delete: bool,
+    // Save and delete are recorded in the audit trail ( `audit_log` collection ).
+    pub is_audit_log: bool,
+    // Optimistic concurrency - `save()` checks and increments the `version` field.
+    pub is_versioned: bool,
     pub map_field_type: std::collections::HashMap<String, String>,
     pub map_widget_type: std::collections::HashMap<String, String>,
     // <field_name, (widget_type, value)>.
     pub map_default_values: std::collections::HashMap<String, (String, String)>,
     // List of field names that will not be saved to the database.
     pub ignore_fields: Vec<String>,
+    // <field_name, RefModel> - For widgets `selectRef` and `selectRefMult`.
+    pub map_ref_models: std::collections::HashMap<String, RefModel>,
+    // <field_name, EmbeddedDoc> - For widgets `embeddedDoc` and `embeddedDocMult`.
+    pub map_embedded: std::collections::HashMap<String, EmbeddedDoc>,
+    // <field_name, storage name> - For widgets `inputFile` and `inputImage`.
+    pub map_storages: std::collections::HashMap<String, String>,
+    // <field_name, time zone> - For widgets `inputDate` and `inputDateTime`.
+    pub map_timezones: std::collections::HashMap<String, String>,
+    // Indexes of the collection.
+    pub indexes: Vec<Index>,
+    // Deletes a document of the Model ( set by the `Model` macro, see `DeleteFn` ).
+    #[serde(skip)]
+    pub delete_fn: Option<DeleteFn>,
 }
 
 impl Default for Meta {
@@ -59,18 +115,248 @@ impl Default for Meta {
             is_add_docs: true,
             is_up_docs: true,
             is_del_docs: true,
+            is_soft_delete: false,
+            is_audit_log: false,
+            is_versioned: false,
             map_field_type: std::collections::HashMap::new(),
             map_widget_type: std::collections::HashMap::new(),
             map_default_values: std::collections::HashMap::new(),
             // List of field names that will not be saved to the database.
             ignore_fields: Vec::new(),
+            map_ref_models: std::collections::HashMap::new(),
+            map_embedded: std::collections::HashMap::new(),
+            map_storages: std::collections::HashMap::new(),
+            map_timezones: std::collections::HashMap::new(),
+            indexes: Vec::new(),
+            delete_fn: None,
+        }
+    }
+}
+
+/// Information about the Model referenced by the field.
+/// ( For widgets `selectRef` and `selectRefMult` )
+// *************************************************************************************************
+///
+/// `on_delete` - What to do with the documents of the current Model
+/// when the referenced document is deleted:
+/// `restrict` - Forbid deleting the referenced document (default).
+/// `cascade` - Delete the documents by `delete()` of their Model
+/// ( for `selectRefMult`, remove the ID from the array ).
+/// `set_null` - Set the field to `null` ( for `selectRefMult`, remove the ID from the array ).
+///
+#[derive(serde::Deserialize, serde::Serialize, Default, Clone, Debug)]
+pub struct RefModel {
+    pub model_name: String,
+    pub db_client_name: String,
+    pub database_name: String,
+    pub collection_name: String,
+    pub on_delete: String,
+}
+
+impl Meta {
+    /// Register the function for deleting documents of the Model.
+    /// ( Referring documents with the `cascade` policy are deleted by the function of their Model )
+    // ---------------------------------------------------------------------------------------------
+    pub fn register_delete_fn(&self) -> Result<(), Error> {
+        if let Some(delete_fn) = self.delete_fn {
+            DELETE_FN_STORE.write()?.insert(
+                format!("{}.{}", self.database_name, self.collection_name),
+                delete_fn,
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the function for deleting documents of the collection.
+    /// ( The Model must be registered in the Monitor or used in the current process )
+    // ---------------------------------------------------------------------------------------------
+    pub fn delete_fn_of(
+        model_name: &str,
+        database_name: &str,
+        collection_name: &str,
+    ) -> Result<DeleteFn, Error> {
+        DELETE_FN_STORE
+            .read()?
+            .get(&format!("{}.{}", database_name, collection_name))
+            .copied()
+            .ok_or_else(|| Error::InvalidConfig {
+                context: format!("Model: `{}`", model_name),
+                message: "The Model is not registered - the `cascade` policy can not be applied."
+                    .to_string(),
+            })
+    }
+
+    /// Exclude soft-deleted documents from the filter.
+    /// ( For Models with the `is_soft_delete = true` parameter )
+    /// Hint: If the filter contains the `deleted_at` field, it is used as is.
+    // ---------------------------------------------------------------------------------------------
+    pub fn not_deleted_filter(
+        &self,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Option<mongodb::bson::document::Document> {
+        if !self.is_soft_delete {
+            return filter;
+        }
+        let mut filter = filter.unwrap_or_default();
+        if !filter.contains_key("deleted_at") {
+            filter.insert("deleted_at", mongodb::bson::Bson::Null);
+        }
+        Some(filter)
+    }
+
+    /// Get the update that marks documents as deleted.
+    /// ( For Models with the `is_soft_delete = true` parameter )
+    // ---------------------------------------------------------------------------------------------
+    pub fn soft_delete_update() -> mongodb::bson::document::Document {
+        let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+        mongodb::bson::doc! {"$set": {"deleted_at": mongodb::bson::Bson::DateTime(dt)}}
+    }
+
+    /// Exclude soft-deleted documents from the aggregation pipeline.
+    /// ( For Models with the `is_soft_delete = true` parameter )
+    /// Hint: For `$geoNear`, the filter is added to its `query`. The stages that must be first
+    /// ( `$collStats`, `$indexStats`, `$search` ) are followed by `$match`.
+    // ---------------------------------------------------------------------------------------------
+    pub fn not_deleted_pipeline(
+        &self,
+        mut pipeline: Vec<mongodb::bson::document::Document>,
+    ) -> Vec<mongodb::bson::document::Document> {
+        if !self.is_soft_delete {
+            return pipeline;
+        }
+        let first_stage: Option<String> = pipeline
+            .first()
+            .and_then(|stage| stage.keys().next().cloned());
+        match first_stage.as_deref() {
+            Some("$geoNear") => {
+                if let Ok(geo_near) = pipeline[0].get_document_mut("$geoNear") {
+                    let query = geo_near.get_document("query").ok().cloned();
+                    if let Some(query) = self.not_deleted_filter(query) {
+                        geo_near.insert("query", query);
+                    }
+                }
+            }
+            Some("$collStats") | Some("$indexStats") | Some("$search") => {
+                pipeline.insert(1, mongodb::bson::doc! {"$match": {"deleted_at": null}});
+            }
+            _ => {
+                pipeline.insert(0, mongodb::bson::doc! {"$match": {"deleted_at": null}});
+            }
+        }
+        pipeline
+    }
+}
+
+/// Index of the collection.
+/// ( Field parameters `unique`, `index`, `sparse`, `expire_after` and the Model parameter `indexes`,
+/// fields of the `inputGeoPoint` widget - `2dsphere` index )
+// *************************************************************************************************
+///
+/// `name` - `mango__<field>_<order>__<field>_<order>` ( The value is determined automatically ).
+/// `keys` - <field_name, `asc` | `desc` | `text` | `2dsphere`>.
+/// `expire_after` - TTL in seconds ( 0 - without TTL ).
+/// Hint: Unique indexes ignore documents where the field is empty ( `null` ),
+/// therefore the `sparse` parameter is not applied to them.
+///
+#[derive(serde::Deserialize, serde::Serialize, Default, Clone, Debug)]
+pub struct Index {
+    pub name: String,
+    pub keys: Vec<(String, String)>,
+    pub unique: bool,
+    pub sparse: bool,
+    pub expire_after: u32,
+}
+
+impl Index {
+    /// Get the index specification for the `createIndexes` command.
+    // ---------------------------------------------------------------------------------------------
+    pub fn spec(&self, meta: &Meta) -> mongodb::bson::document::Document {
+        let mut keys = mongodb::bson::document::Document::new();
+        let mut partial_filter = mongodb::bson::document::Document::new();
+        for (field_name, order) in self.keys.iter() {
+            match order.as_str() {
+                "text" => keys.insert(field_name, "text"),
+                "2dsphere" => keys.insert(field_name, "2dsphere"),
+                "desc" => keys.insert(field_name, -1_i32),
+                _ => keys.insert(field_name, 1_i32),
+            };
+            partial_filter.insert(
+                field_name,
+                mongodb::bson::doc! {"$type": Self::bson_type(meta, field_name)},
+            );
+        }
+        let mut spec = mongodb::bson::doc! {
+            "key": keys,
+            "name": &self.name
+        };
+        if self.unique {
+            spec.insert("unique", true);
+            spec.insert("partialFilterExpression", partial_filter);
+        } else if self.sparse {
+            spec.insert("sparse", true);
+        }
+        if self.expire_after > 0 {
+            spec.insert("expireAfterSeconds", self.expire_after as i64);
+        }
+        spec
+    }
+
+    /// Check if the index in the collection matches the specification.
+    /// Hint: `index_from_db` - Result of the `listIndexes` command.
+    // ---------------------------------------------------------------------------------------------
+    pub fn is_synced(
+        &self,
+        meta: &Meta,
+        index_from_db: &mongodb::bson::document::Document,
+    ) -> bool {
+        let spec = self.spec(meta);
+        let expire_after = |doc: &mongodb::bson::document::Document| -> Option<i64> {
+            match doc.get("expireAfterSeconds") {
+                Some(mongodb::bson::Bson::Int32(val)) => Some(*val as i64),
+                Some(mongodb::bson::Bson::Int64(val)) => Some(*val),
+                Some(mongodb::bson::Bson::Double(val)) => Some(*val as i64),
+                _ => None,
+            }
+        };
+        index_from_db.get_str("name").ok() == Some(self.name.as_str())
+            && index_from_db.get_bool("unique").unwrap_or_default() == self.unique
+            && index_from_db.get_bool("sparse").unwrap_or_default()
+                == spec.get_bool("sparse").unwrap_or_default()
+            && expire_after(index_from_db) == expire_after(&spec)
+            && index_from_db.get_document("partialFilterExpression").ok()
+                == spec.get_document("partialFilterExpression").ok()
+    }
+
+    /// Get the type of field value in the database ( for `partialFilterExpression` ).
+    // ---------------------------------------------------------------------------------------------
+    fn bson_type(meta: &Meta, field_name: &str) -> &'static str {
+        let widget: &str = meta
+            .map_widget_type
+            .get(field_name)
+            .map(|item| item.as_str())
+            .unwrap_or_default();
+        let field_type: &str = meta
+            .map_field_type
+            .get(field_name)
+            .map(|item| item.as_str())
+            .unwrap_or_default();
+        match widget {
+            "" | "inputDate" | "inputDateTime" => "date", // Including `created_at`, `updated_at`.
+            "inputFile" | "inputImage" | "inputTimeRange" | "inputGeoPoint" | "embeddedDoc" => {
+                "object"
+            }
+            "inputTime" | "numberDecimal" | "rangeDecimal" | "hiddenDecimal" => "number",
+            "checkBox" => "bool",
+            _ if field_type.starts_with("Vec") => "array",
+            _ if field_type == "String" => "string",
+            _ => "number",
         }
     }
 }
 
 // Model options and widget map for Form.
 // *************************************************************************************************
-pub trait ToModel: HtmlControls + AdditionalValidation + ValidationModel {
+pub trait ToModel: HtmlControls + AdditionalValidation + ValidationModel + Hooks {
     /// Get model key.
     /// (To access data in the cache)
     // ---------------------------------------------------------------------------------------------
@@ -78,25 +364,38 @@ pub trait ToModel: HtmlControls + AdditionalValidation + ValidationModel {
 
     /// Get metadata of Model.
     // ---------------------------------------------------------------------------------------------
-    fn meta() -> Result<Meta, Box<dyn std::error::Error>>;
+    fn meta() -> Result<Meta, Error>;
+
+    /// Get metadata of Model without information about references to other Models.
+    /// ( Used to resolve `ref_model` - mutual references do not lead to recursion )
+    // ---------------------------------------------------------------------------------------------
+    fn meta_base() -> Result<Meta, Error>;
 
     /// Get map of widgets for model fields.
     /// Hint: <field name, Widget>
     // ---------------------------------------------------------------------------------------------
-    fn widgets() -> Result<std::collections::HashMap<String, Widget>, Box<dyn std::error::Error>>;
+    fn widgets() -> Result<std::collections::HashMap<String, Widget>, Error>;
 
     // Getter and Setter for field `hash`.
     // ---------------------------------------------------------------------------------------------
     fn get_hash(&self) -> Option<String>;
     fn set_hash(&mut self, value: String);
 
+    // Getter and Setter for field `version`.
+    // ( For Models with the `is_versioned = true` parameter )
+    // ---------------------------------------------------------------------------------------------
+    fn get_version(&self) -> Option<i64> {
+        None
+    }
+    fn set_version(&mut self, _value: i64) {}
+
     /// Serialize an instance of the Model to a hash-line.
     // ---------------------------------------------------------------------------------------------
-    fn self_to_json(&self) -> Result<serde_json::value::Value, Box<dyn std::error::Error>>;
+    fn self_to_json(&self) -> Result<serde_json::value::Value, Error>;
 
     /// Convert hash-line to MongoDB ID.
     // ---------------------------------------------------------------------------------------------
-    fn hash_to_id(hash: &str) -> Result<mongodb::bson::oid::ObjectId, Box<dyn std::error::Error>> {
+    fn hash_to_id(hash: &str) -> Result<mongodb::bson::oid::ObjectId, Error> {
         Ok(mongodb::bson::oid::ObjectId::with_string(hash)?)
     }
 
@@ -108,13 +407,14 @@ pub trait ToModel: HtmlControls + AdditionalValidation + ValidationModel {
 
     /// Enrich the widget map with values for dynamic widgets.
     // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
     fn vitaminize(
         project_name: &str,
         unique_project_key: &str,
         collection_name: &str,
         client: &mongodb::sync::Client,
         map_widgets: &mut std::collections::HashMap<String, Widget>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         // Init the name of the project's technical database.
         let db_mango_tech: String = format!("mango_tech__{}__{}", project_name, unique_project_key);
         // Access to the collection with values for dynamic widgets.
@@ -127,33 +427,43 @@ pub trait ToModel: HtmlControls + AdditionalValidation + ValidationModel {
         };
         // Get a document with values for dynamic widgets.
         if let Some(doc) = collection.find_one(filter, None)? {
-            let doc_dyn_values = doc.get_document("fields")?;
-            // Updating the `options` parameter for fields with a dynamic widget.
-            for (field_name, widget) in map_widgets {
-                let widget_type = widget.widget.clone();
-                if widget_type.contains("Dyn") {
-                    let arr = doc_dyn_values.get_array(field_name)?;
-                    let options: Vec<(String, String)> = arr
-                        .iter()
-                        .map(|item| {
-                            let arr = item.as_array().unwrap();
-                            (
-                                arr[0].as_str().unwrap().to_string(),
-                                arr[1].as_str().unwrap().to_string(),
-                            )
-                        })
-                        .collect();
-                    widget.options = options;
-                }
-            }
+            Self::apply_dyn_values(&doc, map_widgets)?;
         } else {
-            Err(format!(
-                "Model: {} > Method: `vitaminize()` : \
-                Document with values for dynamic widgets not found.",
-                Self::meta()?.model_name
-            ))?
+            Err(Error::DocumentNotFound {
+                model: Self::meta()?.model_name,
+                method: "vitaminize()".to_string(),
+            })?
         }
 
         Ok(())
     }
+
+    /// Updating the `options` parameter for fields with a dynamic widget.
+    /// ( `doc` - Document from the `dynamic_widgets` collection of the technical database )
+    // ---------------------------------------------------------------------------------------------
+    fn apply_dyn_values(
+        doc: &mongodb::bson::document::Document,
+        map_widgets: &mut std::collections::HashMap<String, Widget>,
+    ) -> Result<(), Error> {
+        let doc_dyn_values = doc.get_document("fields")?;
+        for (field_name, widget) in map_widgets {
+            let widget_type = widget.widget.clone();
+            if widget_type.contains("Dyn") {
+                let arr = doc_dyn_values.get_array(field_name)?;
+                let options: Vec<(String, String)> = arr
+                    .iter()
+                    .map(|item| {
+                        let arr = item.as_array().unwrap();
+                        (
+                            arr[0].as_str().unwrap().to_string(),
+                            arr[1].as_str().unwrap().to_string(),
+                        )
+                    })
+                    .collect();
+                widget.options = options;
+            }
+        }
+        //
+        Ok(())
+    }
 }
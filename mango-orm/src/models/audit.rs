@@ -0,0 +1,205 @@
+//! # Audit trail.
+//! ( For Models with the `is_audit_log = true` parameter )
+//!
+//! `AuditRecord` - Record of the document history ( one save or delete ).
+//! Records are stored in the `audit_log` collection of the technical database of the project.
+//!
+
+use crate::{error::Error, models::Meta};
+
+/// Record of the document history ( one save or delete ).
+// *************************************************************************************************
+///
+/// `id` - Hash-line of the record ( for the `revert` method ).
+/// `action` - `create` | `update` | `delete` | `restore` | `revert`.
+/// `changes` - <field_name, {"old": value, "new": value}> ( `inputPassword` fields are excluded ).
+/// `snapshot` - State of the document after the change ( for `delete` - before the change ).
+///
+#[derive(Clone, Debug)]
+pub struct AuditRecord {
+    pub id: String,
+    pub model_name: String,
+    pub hash: String,
+    pub action: String,
+    pub changes: mongodb::bson::document::Document,
+    pub snapshot: mongodb::bson::document::Document,
+    pub actor: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditRecord {
+    /// Name of the collection of audit records.
+    pub const COLLECTION: &'static str = "audit_log";
+
+    /// Get the name of the technical database of the project.
+    // ---------------------------------------------------------------------------------------------
+    pub fn db_name(meta: &Meta) -> String {
+        format!(
+            "mango_tech__{}__{}",
+            meta.project_name, meta.unique_project_key
+        )
+    }
+
+    /// Get a filter for the records of the document.
+    // ---------------------------------------------------------------------------------------------
+    pub fn history_filter(meta: &Meta, hash: &str) -> mongodb::bson::document::Document {
+        mongodb::bson::doc! {
+            "database": &meta.database_name,
+            "collection": &meta.collection_name,
+            "hash": hash
+        }
+    }
+
+    /// Get the fields of the document tracked by the audit trail.
+    /// ( Without `hash`, `version`, ignored fields and `inputPassword` fields )
+    // ---------------------------------------------------------------------------------------------
+    pub fn tracked_doc(
+        meta: &Meta,
+        doc: &mongodb::bson::document::Document,
+    ) -> mongodb::bson::document::Document {
+        let mut tracked_doc = mongodb::bson::document::Document::new();
+        for field_name in meta.fields_name.iter() {
+            let widget_type: &str = meta
+                .map_widget_type
+                .get(field_name)
+                .map_or("", |item| item.as_str());
+            if field_name == "hash"
+                || (field_name == "version" && meta.is_versioned)
+                || widget_type == "inputPassword"
+                || meta.ignore_fields.contains(field_name)
+            {
+                continue;
+            }
+            if let Some(value) = doc.get(field_name) {
+                tracked_doc.insert(field_name.clone(), value.clone());
+            }
+        }
+        tracked_doc
+    }
+
+    /// Create a record for the database.
+    /// `old_doc` - State of the document before the change ( None - a new document ).
+    /// `changed_doc` - New values of the fields ( None - the document has been deleted ).
+    /// `actor` - Id of the actor ( for example, the hash of the user ).
+    /// Hint: Returns None if the update did not change the tracked fields.
+    // ---------------------------------------------------------------------------------------------
+    pub fn new_doc(
+        meta: &Meta,
+        hash: &str,
+        action: &str,
+        old_doc: Option<&mongodb::bson::document::Document>,
+        changed_doc: Option<&mongodb::bson::document::Document>,
+        actor: Option<&str>,
+    ) -> Option<mongodb::bson::document::Document> {
+        let old_doc = old_doc.map(|doc| Self::tracked_doc(meta, doc));
+        let new_doc = changed_doc.map(|doc| {
+            let mut new_doc = old_doc.clone().unwrap_or_default();
+            for (field_name, value) in Self::tracked_doc(meta, doc) {
+                new_doc.insert(field_name, value);
+            }
+            new_doc
+        });
+        // Get the changed fields.
+        let bson_null = &mongodb::bson::Bson::Null;
+        let mut changes = mongodb::bson::document::Document::new();
+        for field_name in meta.fields_name.iter() {
+            let old_value = old_doc.as_ref().and_then(|doc| doc.get(field_name));
+            let new_value = new_doc.as_ref().and_then(|doc| doc.get(field_name));
+            if old_value.is_none() && new_value.is_none() {
+                continue;
+            }
+            let old_value = old_value.unwrap_or(bson_null);
+            let new_value = new_value.unwrap_or(bson_null);
+            if old_value != new_value {
+                changes.insert(
+                    field_name.clone(),
+                    mongodb::bson::doc! {"old": old_value.clone(), "new": new_value.clone()},
+                );
+            }
+        }
+        if action == "update" && changes.is_empty() {
+            return None;
+        }
+        let actor = match actor {
+            Some(actor) => mongodb::bson::Bson::String(actor.to_string()),
+            None => mongodb::bson::Bson::Null,
+        };
+        Some(mongodb::bson::doc! {
+            "model": &meta.model_name,
+            "database": &meta.database_name,
+            "collection": &meta.collection_name,
+            "hash": hash,
+            "action": action,
+            "changes": changes,
+            "snapshot": new_doc.or(old_doc).unwrap_or_default(),
+            "actor": actor,
+            "created_at": mongodb::bson::Bson::DateTime(chrono::Utc::now())
+        })
+    }
+
+    /// Convert a document of the database to a record.
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_doc(doc: mongodb::bson::document::Document) -> Result<Self, Error> {
+        Ok(Self {
+            id: doc.get_object_id("_id")?.to_hex(),
+            model_name: doc.get_str("model")?.to_string(),
+            hash: doc.get_str("hash")?.to_string(),
+            action: doc.get_str("action")?.to_string(),
+            changes: doc.get_document("changes")?.clone(),
+            snapshot: doc.get_document("snapshot")?.clone(),
+            actor: doc.get_str("actor").ok().map(|actor| actor.to_string()),
+            created_at: *doc.get_datetime("created_at")?,
+        })
+    }
+
+    /// Write a record to the database.
+    /// ( If the Model does not use the audit trail, nothing is written )
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    pub fn write(
+        meta: &Meta,
+        client: &mongodb::sync::Client,
+        hash: &str,
+        action: &str,
+        old_doc: Option<&mongodb::bson::document::Document>,
+        changed_doc: Option<&mongodb::bson::document::Document>,
+        actor: Option<&str>,
+    ) -> Result<(), Error> {
+        if !meta.is_audit_log {
+            return Ok(());
+        }
+        if let Some(record) = Self::new_doc(meta, hash, action, old_doc, changed_doc, actor) {
+            client
+                .database(Self::db_name(meta).as_str())
+                .collection(Self::COLLECTION)
+                .insert_one(record, None)?;
+        }
+        Ok(())
+    }
+
+    /// Write a record to the database ( asynchronous ).
+    /// ( If the Model does not use the audit trail, nothing is written )
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "async")]
+    pub async fn write_async(
+        meta: &Meta,
+        client: &mongodb::Client,
+        hash: &str,
+        action: &str,
+        old_doc: Option<&mongodb::bson::document::Document>,
+        changed_doc: Option<&mongodb::bson::document::Document>,
+        actor: Option<&str>,
+    ) -> Result<(), Error> {
+        if !meta.is_audit_log {
+            return Ok(());
+        }
+        if let Some(record) = Self::new_doc(meta, hash, action, old_doc, changed_doc, actor) {
+            client
+                .database(Self::db_name(meta).as_str())
+                .collection(Self::COLLECTION)
+                .insert_one(record, None)
+                .await?;
+        }
+        Ok(())
+    }
+}
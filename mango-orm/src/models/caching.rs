@@ -12,12 +12,14 @@
 //! `form_html` - Get Html Form of Model for page templates.
 //! `get_cache_data_for_query` - Get cached Model data.
 //! `db_update_dyn_widgets` - Accepts json-line to update data, for dynamic widgets.
+//! ( Without the `sync` feature, the cache is filled by `CachingModelAsync::to_cache` )
 //!
 
+use crate::{error::Error, forms::Widget, models::ToModel, store::FORM_STORE};
+#[cfg(feature = "sync")]
 use crate::{
-    forms::Widget,
-    models::{Meta, ToModel},
-    store::{FormCache, FORM_STORE, MONGODB_CLIENT_STORE},
+    models::Meta,
+    store::{FormCache, MONGODB_CLIENT_STORE},
 };
 
 /// Caching information about Models for speed up work.
@@ -25,7 +27,8 @@ use crate::{
 pub trait CachingModel: ToModel {
     /// Add metadata and widgects map to cache.
     // *********************************************************************************************
-    fn to_cache() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn to_cache() -> Result<(), Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get write access in cache.
@@ -34,7 +37,12 @@ pub trait CachingModel: ToModel {
         let meta: Meta = Self::meta()?;
         // Get MongoDB client for current model.
         let client_store = MONGODB_CLIENT_STORE.read()?;
-        let client_cache: &mongodb::sync::Client = client_store.get(&meta.db_client_name).unwrap();
+        let client_cache: &mongodb::sync::Client = client_store
+            .get(&meta.db_client_name)
+            .ok_or_else(|| Error::ClientNotFound {
+                model: meta.model_name.clone(),
+                client: meta.db_client_name.clone(),
+            })?;
         // Get a widget map.
         let mut map_widgets: std::collections::HashMap<String, Widget> = Self::widgets()?;
         // Enrich the widget map with values for dynamic widgets.
@@ -45,6 +53,8 @@ pub trait CachingModel: ToModel {
             &client_cache,
             &mut map_widgets,
         )?;
+        // Register the function for deleting documents ( `cascade` policy ).
+        meta.register_delete_fn()?;
         // Init new FormCache.
         let new_form_cache = FormCache {
             meta,
@@ -67,12 +77,14 @@ pub trait CachingModel: ToModel {
     /// println!("{:?}", widgets_map);
     /// ```
     ///
-    fn form_wig() -> Result<std::collections::HashMap<String, Widget>, Box<dyn std::error::Error>> {
+    fn form_wig() -> Result<std::collections::HashMap<String, Widget>, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
+        #[cfg_attr(not(feature = "sync"), allow(unused_mut))]
         let mut form_store = FORM_STORE.read()?;
         // Check if there is metadata for the Model in the cache.
+        #[cfg(feature = "sync")]
         if !form_store.contains_key(key.as_str()) {
             // Unlock.
             drop(form_store);
@@ -86,10 +98,10 @@ pub trait CachingModel: ToModel {
             Ok(form_cache.map_widgets.clone())
         } else {
             let meta = Self::meta()?;
-            Err(format!(
-                "Model: `{}` -> Method: `form_wig()` : Failed to get data from cache.",
-                meta.model_name
-            ))?
+            Err(Error::Cache {
+                model: meta.model_name,
+                method: "form_wig()".to_string(),
+            })?
         }
     }
 
@@ -103,12 +115,14 @@ pub trait CachingModel: ToModel {
     /// println!("{}", json_line);
     /// ```
     ///
-    fn form_json() -> Result<String, Box<dyn std::error::Error>> {
+    fn form_json() -> Result<String, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
+        #[cfg_attr(not(feature = "sync"), allow(unused_mut))]
         let mut form_store = FORM_STORE.read()?;
         // Check if there is metadata for the Model in the cache.
+        #[cfg(feature = "sync")]
         if !form_store.contains_key(key.as_str()) {
             // Unlock.
             drop(form_store);
@@ -132,10 +146,10 @@ pub trait CachingModel: ToModel {
             Ok(form_cache.form_json.clone())
         } else {
             let meta = Self::meta()?;
-            Err(format!(
-                "Model: `{}` -> Method: `form_json()` : Failed to get data from cache.",
-                meta.model_name
-            ))?
+            Err(Error::Cache {
+                model: meta.model_name,
+                method: "form_json()".to_string(),
+            })?
         }
     }
 
@@ -150,7 +164,8 @@ pub trait CachingModel: ToModel {
     /// println!("{}", json_line);
     /// ```
     ///
-    fn form_json_for_admin() -> Result<String, Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn form_json_for_admin() -> Result<String, Error> {
         // Get cached Model data.
         let (form_cache, _client_cache) = Self::get_cache_data_for_query()?;
         // Get Model metadata.
@@ -176,12 +191,14 @@ pub trait CachingModel: ToModel {
     /// println!("{}", html);
     /// ```
     ///
-    fn form_html() -> Result<String, Box<dyn std::error::Error>> {
+    fn form_html() -> Result<String, Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
+        #[cfg_attr(not(feature = "sync"), allow(unused_mut))]
         let mut form_store = FORM_STORE.read()?;
         // Check if there is metadata for the Model in the cache.
+        #[cfg(feature = "sync")]
         if !form_store.contains_key(key.as_str()) {
             // Unlock.
             drop(form_store);
@@ -206,10 +223,10 @@ pub trait CachingModel: ToModel {
             Ok(form_cache.form_html.clone())
         } else {
             let meta = Self::meta()?;
-            Err(format!(
-                "Model: `{}` -> Method: `form_html()` : Failed to get data from cache.",
-                meta.model_name
-            ))?
+            Err(Error::Cache {
+                model: meta.model_name,
+                method: "form_html()".to_string(),
+            })?
         }
     }
 
@@ -223,11 +240,12 @@ pub trait CachingModel: ToModel {
     /// println!("{:?}", form_cache);
     /// ```
     ///
-    fn get_cache_data_for_query(
-    ) -> Result<(FormCache, mongodb::sync::Client), Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn get_cache_data_for_query() -> Result<(FormCache, mongodb::sync::Client), Error> {
         // Get a key to access Model data in the cache.
         let key: String = Self::key();
         // Get read access from cache.
+        #[cfg_attr(not(feature = "sync"), allow(unused_mut))]
         let mut form_store = FORM_STORE.read()?;
         // Check if there is metadata for the Model in the cache.
         if !form_store.contains_key(key.as_str()) {
@@ -244,15 +262,20 @@ pub trait CachingModel: ToModel {
             let meta: &Meta = &form_cache.meta;
             // Get MongoDB client for current model.
             let client_store = MONGODB_CLIENT_STORE.read()?;
-            let client: &mongodb::sync::Client = client_store.get(&meta.db_client_name).unwrap();
+            let client: &mongodb::sync::Client = client_store
+                .get(&meta.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: meta.model_name.clone(),
+                    client: meta.db_client_name.clone(),
+                })?;
             //
             Ok((form_cache.clone(), client.clone()))
         } else {
             let meta = Self::meta()?;
-            Err(format!(
-                "Model: `{}` -> Method: `get_cache_data_for_query()` : Failed to get data from cache.",
-                meta.model_name
-            ))?
+            Err(Error::Cache {
+                model: meta.model_name,
+                method: "get_cache_data_for_query()".to_string(),
+            })?
         }
     }
 
@@ -274,7 +297,8 @@ pub trait CachingModel: ToModel {
     /// ```
     ///
     // *********************************************************************************************
-    fn db_update_dyn_widgets(json_line: &str) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn db_update_dyn_widgets(json_line: &str) -> Result<(), Error> {
         // Refresh the state in the technical database.
         // -----------------------------------------------------------------------------------------
         // Validation json-line.
@@ -283,12 +307,12 @@ pub trait CachingModel: ToModel {
             .build()
             .unwrap();
         if !re.is_match(json_line) {
-            Err(format!(
-                r#"Model: {} > Method: `db_update_dyn_widgets()` : \
-                   The `json_line` parameter was not validation. \
-                   Example: {{"field_name":[["value","Title"]]}}"#,
-                Self::meta()?.model_name
-            ))?
+            Err(Error::InvalidArgument {
+                model: Self::meta()?.model_name,
+                method: "db_update_dyn_widgets()".to_string(),
+                message: r#"The `json_line` parameter was not validation. Example: {"field_name":[["value","Title"]]}"#
+                    .to_string(),
+            })?
         }
 
         // Get cached Model data.
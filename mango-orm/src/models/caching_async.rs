@@ -0,0 +1,142 @@
+//! # Caching (asynchronous).
+//! Caching information about Models for speed up work, using asynchronous MongoDB clients.
+//! ( Available with the `async` feature )
+//!
+//! Trait:
+//! `CachingModelAsync` - Methods caching information about Models for asynchronous queries.
+//!
+//! Methods:
+//! `to_cache` - Add metadata and widgects map to cache.
+//! `get_cache_data_for_query` - Get cached Model data.
+//! `vitaminize_async` - Enrich the widget map with values for dynamic widgets.
+//!
+
+use crate::{
+    error::Error,
+    forms::Widget,
+    models::{Meta, ToModel},
+    store::{FormCache, FORM_STORE, MONGODB_ASYNC_CLIENT_STORE},
+};
+
+/// Caching information about Models for asynchronous queries.
+// #################################################################################################
+#[async_trait::async_trait]
+pub trait CachingModelAsync: ToModel + Send + Sync {
+    /// Add metadata and widgects map to cache.
+    // *********************************************************************************************
+    async fn to_cache() -> Result<(), Error> {
+        // Get a key to access Model data in the cache.
+        let key: String = Self::key();
+        // Get metadata of model.
+        let meta: Meta = Self::meta()?;
+        // Get MongoDB client for current model.
+        let client: Option<mongodb::Client> = MONGODB_ASYNC_CLIENT_STORE
+            .read()?
+            .get(&meta.db_client_name)
+            .cloned();
+        if client.is_none() {
+            Err(Error::ClientNotFound {
+                model: meta.model_name.clone(),
+                client: meta.db_client_name.clone(),
+            })?
+        }
+        let client: mongodb::Client = client.unwrap();
+        // Get a widget map.
+        let mut map_widgets: std::collections::HashMap<String, Widget> = Self::widgets()?;
+        // Enrich the widget map with values for dynamic widgets.
+        Self::vitaminize_async(
+            meta.project_name.as_str(),
+            meta.unique_project_key.as_str(),
+            meta.collection_name.as_str(),
+            &client,
+            &mut map_widgets,
+        )
+        .await?;
+        // Register the function for deleting documents ( `cascade` policy ).
+        meta.register_delete_fn()?;
+        // Init new FormCache.
+        let new_form_cache = FormCache {
+            meta,
+            map_widgets,
+            ..Default::default()
+        };
+        // Save structure `FormCache` to store.
+        FORM_STORE.write()?.insert(key, new_form_cache);
+        //
+        Ok(())
+    }
+
+    /// Get cached Model data.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let (form_cache, client_cache) = UserProfile::get_cache_data_for_query().await?;
+    /// println!("{:?}", form_cache);
+    /// ```
+    ///
+    async fn get_cache_data_for_query() -> Result<(FormCache, mongodb::Client), Error> {
+        // Get a key to access Model data in the cache.
+        let key: String = Self::key();
+        // Check if there is metadata for the Model in the cache.
+        let is_cached: bool = FORM_STORE.read()?.contains_key(key.as_str());
+        if !is_cached {
+            // Add metadata and widgects map to cache.
+            Self::to_cache().await?;
+        }
+        // Get data and return the result.
+        let form_cache: Option<FormCache> = FORM_STORE.read()?.get(key.as_str()).cloned();
+        if let Some(form_cache) = form_cache {
+            // Get MongoDB client for current model.
+            let client: Option<mongodb::Client> = MONGODB_ASYNC_CLIENT_STORE
+                .read()?
+                .get(&form_cache.meta.db_client_name)
+                .cloned();
+            if let Some(client) = client {
+                return Ok((form_cache, client));
+            }
+        }
+        let meta = Self::meta()?;
+        Err(Error::Cache {
+            model: meta.model_name,
+            method: "get_cache_data_for_query()".to_string(),
+        })?
+    }
+
+    /// Enrich the widget map with values for dynamic widgets.
+    // *********************************************************************************************
+    async fn vitaminize_async(
+        project_name: &str,
+        unique_project_key: &str,
+        collection_name: &str,
+        client: &mongodb::Client,
+        map_widgets: &mut std::collections::HashMap<String, Widget>,
+    ) -> Result<(), Error> {
+        // Init the name of the project's technical database.
+        let db_mango_tech: String = format!("mango_tech__{}__{}", project_name, unique_project_key);
+        // Access to the collection with values for dynamic widgets.
+        let collection = client
+            .database(&db_mango_tech)
+            .collection("dynamic_widgets");
+        // Filter for searching a document.
+        let filter = mongodb::bson::doc! {
+            "collection": collection_name
+        };
+        // Get a document with values for dynamic widgets.
+        let doc: Option<mongodb::bson::document::Document> =
+            collection.find_one(filter, None).await?;
+        if let Some(doc) = doc {
+            Self::apply_dyn_values(&doc, map_widgets)?;
+        } else {
+            Err(Error::DocumentNotFound {
+                model: Self::meta()?.model_name,
+                method: "vitaminize_async()".to_string(),
+            })?
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: ToModel + Send + Sync> CachingModelAsync for T {}
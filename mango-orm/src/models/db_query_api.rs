@@ -1,5 +1,12 @@
 //! # Database Query API
 //!
 
+pub mod audit;
+#[cfg(feature = "async")]
+pub mod audit_async;
 pub mod common;
+#[cfg(feature = "async")]
+pub mod common_async;
 pub mod paladins;
+#[cfg(feature = "async")]
+pub mod paladins_async;
@@ -0,0 +1,150 @@
+//! # Audit trail query methods (asynchronous).
+//! ( For Models with the `is_audit_log = true` parameter )
+//! ( Available with the `async` feature )
+//!
+//! Trait:
+//! `QAuditAsync` - Query methods for the history of documents.
+//! Methods:
+//! `history` - Get the history of the document ( audit records in chronological order ).
+//! `revert` - Revert the document to the state of the audit record.
+//! `revert_by` - The same, with the actor id for the audit trail.
+//!
+
+use crate::{
+    error::Error,
+    models::{audit::AuditRecord, caching_async::CachingModelAsync, Meta, ToModel},
+};
+use futures::stream::TryStreamExt;
+
+#[async_trait::async_trait]
+pub trait QAuditAsync: ToModel + CachingModelAsync + Send + Sync {
+    /// Get the history of the document ( audit records in chronological order ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// for record in UserProfile::history(hash).await? {
+    ///     println!("{} {} {:?} {:?}", record.created_at, record.action, record.actor, record.changes);
+    /// }
+    /// ```
+    ///
+    async fn history(hash: &str) -> Result<Vec<AuditRecord>, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(AuditRecord::db_name(&meta).as_str())
+            .collection(AuditRecord::COLLECTION);
+        // Execute query.
+        let options = mongodb::options::FindOptions::builder()
+            .sort(Some(mongodb::bson::doc! {"created_at": 1, "_id": 1}))
+            .build();
+        let cursor: mongodb::Cursor = coll
+            .find(AuditRecord::history_filter(&meta, hash), Some(options))
+            .await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        docs.into_iter().map(AuditRecord::from_doc).collect()
+    }
+
+    /// Revert the document to the state of the audit record.
+    /// ( The values are not checked, the `updated_at` field is updated )
+    /// Hint: Fields of the `inputFile` and `inputImage` widgets are not reverted ( the files
+    /// may have been deleted ). If the document has been removed, it is inserted again
+    /// with the same hash and these fields are empty.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let history = UserProfile::history(hash).await?;
+    /// UserProfile::revert(hash, history[0].id.as_str()).await?;
+    /// ```
+    ///
+    async fn revert(hash: &str, record_id: &str) -> Result<(), Error> {
+        Self::revert_by(None, hash, record_id).await
+    }
+
+    /// The same as `revert()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn revert_by(actor: Option<&str>, hash: &str, record_id: &str) -> Result<(), Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Get the audit record.
+        let mut filter = AuditRecord::history_filter(&meta, hash);
+        filter.insert("_id", mongodb::bson::oid::ObjectId::with_string(record_id)?);
+        let record: AuditRecord = AuditRecord::from_doc(
+            client_cache
+                .database(AuditRecord::db_name(&meta).as_str())
+                .collection(AuditRecord::COLLECTION)
+                .find_one(filter, None)
+                .await?
+                .ok_or_else(|| Error::DocumentNotFound {
+                    model: meta.model_name.clone(),
+                    method: "revert()".to_string(),
+                })?,
+        )?;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let object_id = mongodb::bson::oid::ObjectId::with_string(hash)?;
+        let query: mongodb::bson::document::Document =
+            mongodb::bson::doc! {"_id": object_id.clone()};
+        let doc_from_db: Option<mongodb::bson::document::Document> =
+            coll.find_one(query.clone(), None).await?;
+        // Execute query.
+        let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+        let mut final_doc: mongodb::bson::document::Document = record.snapshot.clone();
+        // The files may have been removed or replaced - file fields are not reverted.
+        for (field_name, widget_type) in meta.map_widget_type.iter() {
+            if widget_type == "inputFile" || widget_type == "inputImage" {
+                final_doc.remove(field_name);
+            }
+        }
+        let reverted_doc: mongodb::bson::document::Document = final_doc.clone();
+        final_doc.insert("updated_at", mongodb::bson::Bson::DateTime(dt));
+        if doc_from_db.is_some() {
+            let mut update = mongodb::bson::doc! {"$set": final_doc};
+            // Optimistic concurrency - instances loaded before the revert become outdated.
+            if meta.is_versioned {
+                update.insert("$inc", mongodb::bson::doc! {"version": 1_i64});
+            }
+            coll.update_one(query, update, None).await?;
+        } else {
+            // The document has been removed - insert it again.
+            final_doc.insert("_id", object_id);
+            final_doc.insert("created_at", mongodb::bson::Bson::DateTime(dt));
+            if meta.is_versioned {
+                final_doc.insert("version", 1_i64);
+            }
+            for (field_name, widget_type) in meta.map_widget_type.iter() {
+                if (widget_type == "inputPassword"
+                    || widget_type == "inputFile"
+                    || widget_type == "inputImage")
+                    && !meta.ignore_fields.contains(field_name)
+                {
+                    final_doc.insert(field_name.clone(), mongodb::bson::Bson::Null);
+                }
+            }
+            coll.insert_one(final_doc, None).await?;
+        }
+        AuditRecord::write_async(
+            &meta,
+            &client_cache,
+            hash,
+            "revert",
+            doc_from_db.as_ref(),
+            Some(&reverted_doc),
+            actor,
+        )
+        .await?;
+        //
+        Ok(())
+    }
+}
+
+impl<T: ToModel + CachingModelAsync + Send + Sync> QAuditAsync for T {}
@@ -1,4 +1,5 @@
 //! # Common query methods.
+//! ( Available with the `sync` feature )
 //!
 //! Trait:
 //! `QCommon` - Common database query methods.
@@ -11,18 +12,25 @@
 //! `drop` - Drops the collection, deleting all data and indexes stored in it.
 //! `estimated_document_count` - Estimates the number of documents in the collection using collection metadata.
 //! `find` - Finds the documents in the collection matching filter.
+//! `find_with_deleted` - Finds the documents matching filter, including soft-deleted documents.
+//! `find_near` - Finds the documents near the point, ordered by distance.
+//! `find_within` - Finds the documents within the area, ordered by distance to the point.
+//! `paginate` - Gets one page of documents matching filter, with the data for page navigation.
+//! `paginate_keyset` - Gets one page of documents matching filter using keyset pagination.
 //! `find_one` - Finds a single document in the collection matching filter.
 //! `find_one_and_delete` - Atomically finds up to one document in the collection matching filter and deletes it.
 //! `name` - Gets the name of the Collection.
 //! `namespace` - Gets the namespace of the Collection.
 //!
 
+use crate::models::{caching::CachingModel, ToModel};
+#[cfg(feature = "sync")]
 use crate::{
-    forms::output_data::OutputDataForm,
+    error::Error,
+    forms::{output_data::OutputDataForm, GeoPoint},
     models::{
-        caching::CachingModel,
-        output_data::{OutputDataMany, OutputDataOne},
-        Meta, ToModel,
+        output_data::{OutputDataMany, OutputDataOne, OutputDataPage},
+        Meta,
     },
 };
 
@@ -40,10 +48,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{:?}", document);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn aggregate(
         pipeline: Vec<mongodb::bson::document::Document>,
         options: Option<mongodb::options::AggregateOptions>,
-    ) -> Result<Vec<mongodb::bson::document::Document>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<mongodb::bson::document::Document>, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -53,7 +62,7 @@ pub trait QCommon: ToModel + CachingModel {
             .collection(meta.collection_name.as_str());
         // Execute query.
         Ok(coll
-            .aggregate(pipeline, options)?
+            .aggregate(meta.not_deleted_pipeline(pipeline), options)?
             .map(|item| item.unwrap())
             .collect())
     }
@@ -70,10 +79,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{}", count);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn count_documents(
         filter: Option<mongodb::bson::document::Document>,
         options: Option<mongodb::options::CountOptions>,
-    ) -> Result<i64, Box<dyn std::error::Error>> {
+    ) -> Result<i64, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -82,10 +92,12 @@ pub trait QCommon: ToModel + CachingModel {
             .database(meta.database_name.as_str())
             .collection(meta.collection_name.as_str());
         // Execute query.
-        Ok(coll.count_documents(filter, options)?)
+        Ok(coll.count_documents(meta.not_deleted_filter(filter), options)?)
     }
 
     /// Deletes all documents stored in the collection matching query.
+    /// Hint: For Models with `is_soft_delete = true`, the documents are marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
     /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.delete_many
     // ---------------------------------------------------------------------------------------------
     ///
@@ -99,10 +111,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn delete_many(
         query: mongodb::bson::document::Document,
         options: Option<mongodb::options::DeleteOptions>,
-    ) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -122,7 +135,23 @@ pub trait QCommon: ToModel + CachingModel {
                 .database(meta.database_name.as_str())
                 .collection(meta.collection_name.as_str());
             // Execute query.
-            coll.delete_many(query, options).is_ok()
+            if meta.is_soft_delete {
+                // Mark the documents as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::UpdateOptions::builder()
+                        .collation(options.collation)
+                        .write_concern(options.write_concern)
+                        .build()
+                });
+                coll.update_many(
+                    meta.not_deleted_filter(Some(query)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )
+                .is_ok()
+            } else {
+                coll.delete_many(query, options).is_ok()
+            }
         } else {
             false
         };
@@ -130,6 +159,8 @@ pub trait QCommon: ToModel + CachingModel {
     }
 
     /// Deletes up to one document found matching query.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
     /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.delete_one
     // ---------------------------------------------------------------------------------------------
     ///
@@ -143,10 +174,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn delete_one(
         query: mongodb::bson::document::Document,
         options: Option<mongodb::options::DeleteOptions>,
-    ) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -166,7 +198,23 @@ pub trait QCommon: ToModel + CachingModel {
                 .database(meta.database_name.as_str())
                 .collection(meta.collection_name.as_str());
             // Execute query.
-            coll.delete_one(query, options).is_ok()
+            if meta.is_soft_delete {
+                // Mark the documents as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::UpdateOptions::builder()
+                        .collation(options.collation)
+                        .write_concern(options.write_concern)
+                        .build()
+                });
+                coll.update_one(
+                    meta.not_deleted_filter(Some(query)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )
+                .is_ok()
+            } else {
+                coll.delete_one(query, options).is_ok()
+            }
         } else {
             false
         };
@@ -186,11 +234,12 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{:?}", routput_data);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn distinct(
         field_name: &str,
         filter: Option<mongodb::bson::document::Document>,
         options: Option<mongodb::options::DistinctOptions>,
-    ) -> Result<Vec<mongodb::bson::Bson>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<mongodb::bson::Bson>, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -199,7 +248,7 @@ pub trait QCommon: ToModel + CachingModel {
             .database(meta.database_name.as_str())
             .collection(meta.collection_name.as_str());
         // Execute query.
-        Ok(coll.distinct(field_name, filter, options)?)
+        Ok(coll.distinct(field_name, meta.not_deleted_filter(filter), options)?)
     }
 
     /// Drops the collection, deleting all data and indexes stored in it.
@@ -215,9 +264,10 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn drop(
         options: Option<mongodb::options::DropCollectionOptions>,
-    ) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -255,9 +305,10 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{}", count);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn estimated_document_count(
         options: Option<mongodb::options::EstimatedDocumentCountOptions>,
-    ) -> Result<i64, Box<dyn std::error::Error>> {
+    ) -> Result<i64, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -266,10 +317,16 @@ pub trait QCommon: ToModel + CachingModel {
             .database(meta.database_name.as_str())
             .collection(meta.collection_name.as_str());
         // Execute query.
-        Ok(coll.estimated_document_count(options)?)
+        // ( The collection metadata also counts soft-deleted documents )
+        if meta.is_soft_delete {
+            Ok(coll.count_documents(meta.not_deleted_filter(None), None)?)
+        } else {
+            Ok(coll.estimated_document_count(options)?)
+        }
     }
 
     /// Finds the documents in the collection matching filter.
+    /// Hint: For Models with `is_soft_delete = true`, soft-deleted documents are excluded.
     /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find
     // ---------------------------------------------------------------------------------------------
     ///
@@ -290,10 +347,36 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn find(
         filter: Option<mongodb::bson::document::Document>,
         options: Option<mongodb::options::FindOptions>,
-    ) -> Result<OutputDataMany, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, _client_cache) = Self::get_cache_data_for_query()?;
+        // Exclude soft-deleted documents.
+        let filter = form_cache.meta.not_deleted_filter(filter);
+        // Execute query.
+        Self::find_with_deleted(filter, options)
+    }
+
+    /// Finds the documents in the collection matching filter, including soft-deleted documents.
+    /// Hint: Soft-deleted documents contain the `deleted_at` field.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{"deleted_at": {"$ne": null}};
+    /// let output_data  = UserProfile::find_with_deleted(Some(filter), None)?;
+    /// println!("{:?}", output_data.raw_docs()?);
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn find_with_deleted(
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::FindOptions>,
+    ) -> Result<OutputDataMany, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -322,9 +405,205 @@ pub trait QCommon: ToModel + CachingModel {
             meta.ignore_fields.clone(),
             meta.map_widget_type.clone(),
             meta.model_name.clone(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Finds the documents near the point, ordered by distance ( the nearest first ).
+    /// Hint: `field_name` - Field of the `inputGeoPoint` widget ( `2dsphere` index ).
+    /// `max_distance` - In meters. The distance is returned in the `_distance` field of raw documents.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let point = GeoPoint::new(52.520008, 13.404954).unwrap();
+    /// let output_data  = Shop::find_near("location", &point, Some(5000.0), None)?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn find_near(
+        field_name: &str,
+        point: &GeoPoint,
+        max_distance: Option<f64>,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let pipeline = OutputDataMany::geo_near_pipeline(
+            &meta,
+            field_name,
+            point,
+            max_distance,
+            None,
+            filter,
+        )?;
+        // Execute query.
+        let docs = coll
+            .aggregate(pipeline, None)?
+            .collect::<mongodb::error::Result<Vec<mongodb::bson::document::Document>>>()?;
+        Ok(OutputDataMany::Docs((
+            docs,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Finds the documents within the area, ordered by distance to the point.
+    /// Hint: `geometry` - GeoJSON Polygon or MultiPolygon ( see `GeoPoint::polygon` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let area = GeoPoint::polygon(&[
+    ///     GeoPoint::new(52.6, 13.2).unwrap(),
+    ///     GeoPoint::new(52.6, 13.6).unwrap(),
+    ///     GeoPoint::new(52.4, 13.6).unwrap(),
+    ///     GeoPoint::new(52.4, 13.2).unwrap(),
+    /// ]);
+    /// let point = GeoPoint::new(52.520008, 13.404954).unwrap();
+    /// let output_data  = Shop::find_within("location", area, &point, None)?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn find_within(
+        field_name: &str,
+        geometry: mongodb::bson::document::Document,
+        point: &GeoPoint,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let pipeline = OutputDataMany::geo_near_pipeline(
+            &meta,
+            field_name,
+            point,
+            None,
+            Some(geometry),
+            filter,
+        )?;
+        // Execute query.
+        let docs = coll
+            .aggregate(pipeline, None)?
+            .collect::<mongodb::error::Result<Vec<mongodb::bson::document::Document>>>()?;
+        Ok(OutputDataMany::Docs((
+            docs,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            meta.map_timezones.clone(),
         )))
     }
 
+    /// Gets one page of documents matching filter, with the data for page navigation.
+    /// Hint: `page` - Page number, starts from 1.
+    /// `per_page` - Is limited by the `db_query_docs_limit` parameter of Model.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{"age": {"$gt": 18}};
+    /// let sort = doc!{"username": 1};
+    /// let output_data  = UserProfile::paginate(Some(filter), Some(sort), 2, 20)?;
+    /// // Get prepared documents. (Hint: For page template.)
+    /// println!("{:?}", output_data.docs()?);
+    /// // Get the data for page navigation.
+    /// println!("{} / {}", output_data.page, output_data.page_count);
+    /// // Get json-line. (Hint: For Ajax and admin panel.)
+    /// println!("{}", output_data.json()?);
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn paginate(
+        filter: Option<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<OutputDataPage, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Exclude soft-deleted documents.
+        let filter = meta.not_deleted_filter(filter);
+        // Execute queries.
+        let per_page = OutputDataPage::per_page(&meta, per_page);
+        let total: i64 = coll.count_documents(filter.clone(), None)?;
+        let options = OutputDataPage::page_options(sort, page, per_page);
+        let docs = coll
+            .find(filter, Some(options))?
+            .collect::<mongodb::error::Result<Vec<mongodb::bson::document::Document>>>()?;
+        Ok(OutputDataPage::from_page(
+            &meta, docs, total, page, per_page,
+        ))
+    }
+
+    /// Gets one page of documents matching filter using keyset ( cursor-based ) pagination.
+    /// Hint: For large collections - does not skip documents and does not count them.
+    /// `sort` - By one field, `{field: 1}` or `{field: -1}` ( `_id` by default and as tie-breaker ).
+    /// Hint: Documents with a null or missing sort field go first for `1` and last for `-1`.
+    /// `after` - `next_cursor` of the previous page ( None for the first page ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let sort = doc!{"created_at": -1};
+    /// let output_data  = UserProfile::paginate_keyset(None, Some(sort.clone()), None, 20)?;
+    /// println!("{:?}", output_data.docs()?);
+    /// if output_data.has_next {
+    ///     let after = output_data.next_cursor.clone();
+    ///     let output_data  =
+    ///         UserProfile::paginate_keyset(None, Some(sort), after.as_deref(), 20)?;
+    ///     println!("{:?}", output_data.docs()?);
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn paginate_keyset(
+        filter: Option<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        after: Option<&str>,
+        per_page: u32,
+    ) -> Result<OutputDataPage, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Exclude soft-deleted documents.
+        let filter = meta.not_deleted_filter(filter);
+        // Execute query.
+        let per_page = OutputDataPage::per_page(&meta, per_page);
+        let (keyset_filter, options) =
+            OutputDataPage::keyset_query(filter, sort.clone(), after, per_page)?;
+        let docs = coll
+            .find(keyset_filter, Some(options))?
+            .collect::<mongodb::error::Result<Vec<mongodb::bson::document::Document>>>()?;
+        OutputDataPage::from_keyset(&meta, docs, sort, after, per_page)
+    }
+
     /// Finds a single document in the collection matching filter.
     /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find_one
     // ---------------------------------------------------------------------------------------------
@@ -346,10 +625,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn find_one(
         filter: Option<mongodb::bson::document::Document>,
         options: Option<mongodb::options::FindOneOptions>,
-    ) -> Result<OutputDataOne, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataOne, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -359,15 +639,18 @@ pub trait QCommon: ToModel + CachingModel {
             .collection(meta.collection_name.as_str());
         // Execute query.
         Ok(OutputDataOne::Doc((
-            coll.find_one(filter, options)?,
+            coll.find_one(meta.not_deleted_filter(filter), options)?,
             meta.ignore_fields.clone(),
             meta.map_widget_type.clone(),
             meta.model_name.clone(),
             String::new(),
+            meta.map_timezones.clone(),
         )))
     }
 
     /// Atomically finds up to one document in the collection matching filter and deletes it.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
     /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find_one_and_delete
     // ---------------------------------------------------------------------------------------------
     ///
@@ -381,10 +664,11 @@ pub trait QCommon: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn find_one_and_delete(
         filter: mongodb::bson::document::Document,
         options: Option<mongodb::options::FindOneAndDeleteOptions>,
-    ) -> Result<OutputDataOne, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataOne, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -404,12 +688,32 @@ pub trait QCommon: ToModel + CachingModel {
                 .database(meta.database_name.as_str())
                 .collection(meta.collection_name.as_str());
             // Execute query.
+            let doc: Option<mongodb::bson::document::Document> = if meta.is_soft_delete {
+                // Mark the document as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::FindOneAndUpdateOptions::builder()
+                        .max_time(options.max_time)
+                        .projection(options.projection)
+                        .sort(options.sort)
+                        .write_concern(options.write_concern)
+                        .collation(options.collation)
+                        .build()
+                });
+                coll.find_one_and_update(
+                    meta.not_deleted_filter(Some(filter)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )?
+            } else {
+                coll.find_one_and_delete(filter, options)?
+            };
             Ok(OutputDataOne::Doc((
-                coll.find_one_and_delete(filter, options)?,
+                doc,
                 meta.ignore_fields.clone(),
                 meta.map_widget_type.clone(),
                 meta.model_name.clone(),
                 String::new(),
+                meta.map_timezones.clone(),
             )))
         } else {
             // Execute query.
@@ -419,6 +723,7 @@ pub trait QCommon: ToModel + CachingModel {
                 std::collections::HashMap::new(),
                 String::new(),
                 err_msg.clone(),
+                std::collections::HashMap::new(),
             )))
         }
     }
@@ -434,7 +739,8 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{}", name);
     /// ```
     ///
-    fn name() -> Result<String, Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn name() -> Result<String, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -457,7 +763,8 @@ pub trait QCommon: ToModel + CachingModel {
     /// println!("{:?}", name);
     /// ```
     ///
-    fn namespace() -> Result<mongodb::Namespace, Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn namespace() -> Result<mongodb::Namespace, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         let meta: Meta = form_cache.meta;
@@ -0,0 +1,770 @@
+//! # Common query methods (asynchronous).
+//! ( Available with the `async` feature )
+//!
+//! Trait:
+//! `QCommonAsync` - Common database query methods for asynchronous MongoDB clients.
+//! Methods:
+//! 'aggregate' - Runs an aggregation operation.
+//! `count_documents` - Gets the number of documents matching filter.
+//! `delete_many` - Deletes all documents stored in the collection matching query.
+//! `delete_one` - Finds a single document in the collection matching filter.
+//! `distinct` - Finds the distinct values of the field specified by field_name across the collection.
+//! `drop` - Drops the collection, deleting all data and indexes stored in it.
+//! `estimated_document_count` - Estimates the number of documents in the collection using collection metadata.
+//! `find` - Finds the documents in the collection matching filter.
+//! `find_with_deleted` - Finds the documents matching filter, including soft-deleted documents.
+//! `find_near` - Finds the documents near the point, ordered by distance.
+//! `find_within` - Finds the documents within the area, ordered by distance to the point.
+//! `paginate` - Gets one page of documents matching filter, with the data for page navigation.
+//! `paginate_keyset` - Gets one page of documents matching filter using keyset pagination.
+//! `find_one` - Finds a single document in the collection matching filter.
+//! `find_one_and_delete` - Atomically finds up to one document in the collection matching filter and deletes it.
+//! `name` - Gets the name of the Collection.
+//! `namespace` - Gets the namespace of the Collection.
+//!
+
+use crate::{
+    error::Error,
+    forms::{output_data::OutputDataForm, GeoPoint},
+    models::{
+        caching_async::CachingModelAsync,
+        output_data::{OutputDataMany, OutputDataOne, OutputDataPage},
+        Meta, ToModel,
+    },
+};
+use futures::stream::TryStreamExt;
+
+#[async_trait::async_trait]
+pub trait QCommonAsync: ToModel + CachingModelAsync + Send + Sync {
+    /// Runs an aggregation operation.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.aggregate
+    /// See the documentation https://docs.mongodb.com/manual/aggregation/ for more information on aggregations.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let pipeline = doc!{};
+    /// let document  = UserProfile::aggregate(pipeline, None).await?;
+    /// println!("{:?}", document);
+    /// ```
+    ///
+    async fn aggregate(
+        pipeline: Vec<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::AggregateOptions>,
+    ) -> Result<Vec<mongodb::bson::document::Document>, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        let cursor: mongodb::Cursor = coll
+            .aggregate(meta.not_deleted_pipeline(pipeline), options)
+            .await?;
+        Ok(cursor.try_collect().await?)
+    }
+
+    /// Gets the number of documents matching filter.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.count_documents
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let count  = UserProfile::count_documents(filter, None).await?;
+    /// println!("{}", count);
+    /// ```
+    ///
+    async fn count_documents(
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::CountOptions>,
+    ) -> Result<i64, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        Ok(coll
+            .count_documents(meta.not_deleted_filter(filter), options)
+            .await?)
+    }
+
+    /// Deletes all documents stored in the collection matching query.
+    /// Hint: For Models with `is_soft_delete = true`, the documents are marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.delete_many
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let query = doc!{};
+    /// let output_data  = UserProfile::delete_many(query, None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn delete_many(
+        query: mongodb::bson::document::Document,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Execute query.
+            if meta.is_soft_delete {
+                // Mark the documents as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::UpdateOptions::builder()
+                        .collation(options.collation)
+                        .write_concern(options.write_concern)
+                        .build()
+                });
+                coll.update_many(
+                    meta.not_deleted_filter(Some(query)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )
+                .await
+                .is_ok()
+            } else {
+                coll.delete_many(query, options).await.is_ok()
+            }
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Deletes up to one document found matching query.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.delete_one
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let query = doc!{};
+    /// let output_data  = UserProfile::delete_one(query, None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn delete_one(
+        query: mongodb::bson::document::Document,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Execute query.
+            if meta.is_soft_delete {
+                // Mark the documents as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::UpdateOptions::builder()
+                        .collation(options.collation)
+                        .write_concern(options.write_concern)
+                        .build()
+                });
+                coll.update_one(
+                    meta.not_deleted_filter(Some(query)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )
+                .await
+                .is_ok()
+            } else {
+                coll.delete_one(query, options).await.is_ok()
+            }
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Finds the distinct values of the field specified by field_name across the collection.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.distinct
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let ield_name = "";
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::distinct(field_name, filter, None).await?;
+    /// println!("{:?}", routput_data);
+    /// ```
+    ///
+    async fn distinct(
+        field_name: &str,
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::DistinctOptions>,
+    ) -> Result<Vec<mongodb::bson::Bson>, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        Ok(coll
+            .distinct(field_name, meta.not_deleted_filter(filter), options)
+            .await?)
+    }
+
+    /// Drops the collection, deleting all data and indexes stored in it.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.drop
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::drop(None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn drop(
+        options: Option<mongodb::options::DropCollectionOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Execute query.
+            coll.drop(options).await.is_ok()
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Estimates the number of documents in the collection using collection metadata.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.estimated_document_count
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let count  = UserProfile::estimated_document_count(None).await?;
+    /// println!("{}", count);
+    /// ```
+    ///
+    async fn estimated_document_count(
+        options: Option<mongodb::options::EstimatedDocumentCountOptions>,
+    ) -> Result<i64, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        // ( The collection metadata also counts soft-deleted documents )
+        if meta.is_soft_delete {
+            Ok(coll
+                .count_documents(meta.not_deleted_filter(None), None)
+                .await?)
+        } else {
+            Ok(coll.estimated_document_count(options).await?)
+        }
+    }
+
+    /// Finds the documents in the collection matching filter.
+    /// Hint: For Models with `is_soft_delete = true`, soft-deleted documents are excluded.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find(filter, None).await?;
+    /// if output_data.is_valid()? {
+    ///     // Get raw documents. (Hint: For non-standard operations.)
+    ///     println!("{:?}", routput_data.raw_docs()?);
+    ///     // Get prepared documents. (Hint: For page template.)
+    ///     println!("{:?}", routput_data.docs()?);
+    ///     // Get json-line. (Hint: For Ajax.)
+    ///     println!("{:?}", routput_data.json()?);
+    ///     // Get the number of documents.
+    ///     println!("{}", routput_data.count()?);
+    /// }
+    /// ```
+    ///
+    async fn find(
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::FindOptions>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, _client_cache) = Self::get_cache_data_for_query().await?;
+        // Exclude soft-deleted documents.
+        let filter = form_cache.meta.not_deleted_filter(filter);
+        // Execute query.
+        Self::find_with_deleted(filter, options).await
+    }
+
+    /// Finds the documents in the collection matching filter, including soft-deleted documents.
+    /// Hint: Soft-deleted documents contain the `deleted_at` field.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{"deleted_at": {"$ne": null}};
+    /// let output_data  = UserProfile::find_with_deleted(Some(filter), None).await?;
+    /// println!("{:?}", output_data.raw_docs()?);
+    /// ```
+    ///
+    async fn find_with_deleted(
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::FindOptions>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Apply parameter `db_query_docs_limit`.
+        // (if necessary)
+        let options = if options.is_some() {
+            let mut options = options.unwrap();
+            if options.limit == Some(0_i64) {
+                options.limit = Some(meta.db_query_docs_limit as i64);
+            }
+            options
+        } else {
+            mongodb::options::FindOptions::builder()
+                .limit(Some(meta.db_query_docs_limit as i64))
+                .build()
+        };
+        // Execute query.
+        let cursor: mongodb::Cursor = coll.find(filter, Some(options)).await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        Ok(OutputDataMany::Docs((
+            docs,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Finds the documents near the point, ordered by distance ( the nearest first ).
+    /// Hint: `field_name` - Field of the `inputGeoPoint` widget ( `2dsphere` index ).
+    /// `max_distance` - In meters. The distance is returned in the `_distance` field of raw documents.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let point = GeoPoint::new(52.520008, 13.404954).unwrap();
+    /// let output_data  = Shop::find_near("location", &point, Some(5000.0), None).await?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    async fn find_near(
+        field_name: &str,
+        point: &GeoPoint,
+        max_distance: Option<f64>,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let pipeline = OutputDataMany::geo_near_pipeline(
+            &meta,
+            field_name,
+            point,
+            max_distance,
+            None,
+            filter,
+        )?;
+        // Execute query.
+        let cursor: mongodb::Cursor = coll.aggregate(pipeline, None).await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        Ok(OutputDataMany::Docs((
+            docs,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Finds the documents within the area, ordered by distance to the point.
+    /// Hint: `geometry` - GeoJSON Polygon or MultiPolygon ( see `GeoPoint::polygon` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let area = GeoPoint::polygon(&[
+    ///     GeoPoint::new(52.6, 13.2).unwrap(),
+    ///     GeoPoint::new(52.6, 13.6).unwrap(),
+    ///     GeoPoint::new(52.4, 13.6).unwrap(),
+    ///     GeoPoint::new(52.4, 13.2).unwrap(),
+    /// ]);
+    /// let point = GeoPoint::new(52.520008, 13.404954).unwrap();
+    /// let output_data  = Shop::find_within("location", area, &point, None).await?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    async fn find_within(
+        field_name: &str,
+        geometry: mongodb::bson::document::Document,
+        point: &GeoPoint,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Result<OutputDataMany, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let pipeline = OutputDataMany::geo_near_pipeline(
+            &meta,
+            field_name,
+            point,
+            None,
+            Some(geometry),
+            filter,
+        )?;
+        // Execute query.
+        let cursor: mongodb::Cursor = coll.aggregate(pipeline, None).await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        Ok(OutputDataMany::Docs((
+            docs,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Gets one page of documents matching filter, with the data for page navigation.
+    /// Hint: `page` - Page number, starts from 1.
+    /// `per_page` - Is limited by the `db_query_docs_limit` parameter of Model.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{"age": {"$gt": 18}};
+    /// let sort = doc!{"username": 1};
+    /// let output_data  = UserProfile::paginate(Some(filter), Some(sort), 2, 20).await?;
+    /// // Get prepared documents. (Hint: For page template.)
+    /// println!("{:?}", output_data.docs()?);
+    /// // Get the data for page navigation.
+    /// println!("{} / {}", output_data.page, output_data.page_count);
+    /// // Get json-line. (Hint: For Ajax and admin panel.)
+    /// println!("{}", output_data.json()?);
+    /// ```
+    ///
+    async fn paginate(
+        filter: Option<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<OutputDataPage, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Exclude soft-deleted documents.
+        let filter = meta.not_deleted_filter(filter);
+        // Execute queries.
+        let per_page = OutputDataPage::per_page(&meta, per_page);
+        let total: i64 = coll.count_documents(filter.clone(), None).await?;
+        let options = OutputDataPage::page_options(sort, page, per_page);
+        let cursor: mongodb::Cursor = coll.find(filter, Some(options)).await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        Ok(OutputDataPage::from_page(
+            &meta, docs, total, page, per_page,
+        ))
+    }
+
+    /// Gets one page of documents matching filter using keyset ( cursor-based ) pagination.
+    /// Hint: For large collections - does not skip documents and does not count them.
+    /// `sort` - By one field, `{field: 1}` or `{field: -1}` ( `_id` by default and as tie-breaker ).
+    /// Hint: Documents with a null or missing sort field go first for `1` and last for `-1`.
+    /// `after` - `next_cursor` of the previous page ( None for the first page ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let sort = doc!{"created_at": -1};
+    /// let output_data  = UserProfile::paginate_keyset(None, Some(sort.clone()), None, 20).await?;
+    /// println!("{:?}", output_data.docs()?);
+    /// if output_data.has_next {
+    ///     let after = output_data.next_cursor.clone();
+    ///     let output_data  =
+    ///         UserProfile::paginate_keyset(None, Some(sort), after.as_deref(), 20).await?;
+    ///     println!("{:?}", output_data.docs()?);
+    /// }
+    /// ```
+    ///
+    async fn paginate_keyset(
+        filter: Option<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        after: Option<&str>,
+        per_page: u32,
+    ) -> Result<OutputDataPage, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Exclude soft-deleted documents.
+        let filter = meta.not_deleted_filter(filter);
+        // Execute query.
+        let per_page = OutputDataPage::per_page(&meta, per_page);
+        let (keyset_filter, options) =
+            OutputDataPage::keyset_query(filter, sort.clone(), after, per_page)?;
+        let cursor: mongodb::Cursor = coll.find(keyset_filter, Some(options)).await?;
+        let docs: Vec<mongodb::bson::document::Document> = cursor.try_collect().await?;
+        OutputDataPage::from_keyset(&meta, docs, sort, after, per_page)
+    }
+
+    /// Finds a single document in the collection matching filter.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find_one
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find_one(filter, None).await?;
+    /// if output_data.is_valid()? {
+    ///     // Get raw document. (Hint: For non-standard operations.)
+    ///     println!("{:?}", output_data.raw_doc()?);
+    ///     // Get prepared document. (Hint: For page template.)
+    ///     println!("{:?}", output_data.doc()?);
+    ///     //Get json-line. (Hint: For Ajax.)
+    ///     println!("{}", output_data.json()?);
+    ///     // Get model instance. (Hint: For the `save`, `update`, `delete` operations.)
+    ///     println!("{:?}", output_data.model::<UserProfile>()?);
+    /// }
+    /// ```
+    ///
+    async fn find_one(
+        filter: Option<mongodb::bson::document::Document>,
+        options: Option<mongodb::options::FindOneOptions>,
+    ) -> Result<OutputDataOne, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        Ok(OutputDataOne::Doc((
+            coll.find_one(meta.not_deleted_filter(filter), options)
+                .await?,
+            meta.ignore_fields.clone(),
+            meta.map_widget_type.clone(),
+            meta.model_name.clone(),
+            String::new(),
+            meta.map_timezones.clone(),
+        )))
+    }
+
+    /// Atomically finds up to one document in the collection matching filter and deletes it.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`.
+    /// Hooks, files, the audit trail and `on_delete` of references are not applied.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.find_one_and_delete
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find_one_and_delete(filter, None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn find_one_and_delete(
+        filter: mongodb::bson::document::Document,
+        options: Option<mongodb::options::FindOneAndDeleteOptions>,
+    ) -> Result<OutputDataOne, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        //
+        if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Execute query.
+            let doc: Option<mongodb::bson::document::Document> = if meta.is_soft_delete {
+                // Mark the document as deleted.
+                let options = options.map(|options| {
+                    mongodb::options::FindOneAndUpdateOptions::builder()
+                        .max_time(options.max_time)
+                        .projection(options.projection)
+                        .sort(options.sort)
+                        .write_concern(options.write_concern)
+                        .collation(options.collation)
+                        .build()
+                });
+                coll.find_one_and_update(
+                    meta.not_deleted_filter(Some(filter)).unwrap_or_default(),
+                    Meta::soft_delete_update(),
+                    options,
+                )
+                .await?
+            } else {
+                coll.find_one_and_delete(filter, options).await?
+            };
+            Ok(OutputDataOne::Doc((
+                doc,
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                String::new(),
+                meta.map_timezones.clone(),
+            )))
+        } else {
+            // Execute query.
+            Ok(OutputDataOne::Doc((
+                Some(mongodb::bson::document::Document::new()),
+                Vec::new(),
+                std::collections::HashMap::new(),
+                String::new(),
+                err_msg.clone(),
+                std::collections::HashMap::new(),
+            )))
+        }
+    }
+
+    /// Gets the name of the Collection.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.name
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let name  = UserProfile::name().await?;
+    /// println!("{}", name);
+    /// ```
+    ///
+    async fn name() -> Result<String, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        Ok(coll.name().to_string())
+    }
+
+    /// Gets the namespace of the Collection.
+    /// https://docs.rs/mongodb/1.1.1/mongodb/struct.Collection.html#method.namespace
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let name  = UserProfile::namespace().await?;
+    /// println!("{:?}", name);
+    /// ```
+    ///
+    async fn namespace() -> Result<mongodb::Namespace, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Execute query.
+        Ok(coll.namespace())
+    }
+}
+
+impl<T: ToModel + CachingModelAsync + Send + Sync> QCommonAsync for T {}
@@ -6,19 +6,45 @@
 //! Methods:
 //! `check` - Checking the Model before queries the database.
 //! `save` - Save to database as a new document or update an existing document.
-//! `delete` - Remove document from collection.
+//! `save_many` - Save several Models to database, with the check of each Model.
+//! `patch` - Partial update of a document - only the passed fields are checked and updated.
+//! `delete` - Remove document from collection ( or mark it as deleted, for soft delete ).
+//! `restore` - Restore the soft-deleted document.
+//! `purge` - Remove document from collection and delete its files.
+//! `save_by`, `save_many_by`, `patch_by`, `delete_by`, `restore_by`, `purge_by` - The same,
+//! with the actor id for the audit trail.
+//! `db_get_relations` - Get the relations of Models that refer to the current Model.
+//! `check_restrict` - Check whether deletion is forbidden by referring documents.
+//! `apply_on_delete` - Apply the `cascade` and `set_null` policies to referring documents.
 //! `create_password_hash` - Generate password hash and add to result document.
 //! `verify_password` - Match the password from the user to the password in the database.
 //! `update_password` - For replace or recover password.
 //!
+//! Function:
+//! `delete_doc` - Delete the document with the `delete()` method of the Model ( `DeleteFn` ).
+//!
 
 use crate::{
-    forms::{output_data::OutputDataForm, FileData, ImageData, Widget},
+    error::Error,
+    forms::{
+        embedded::EmbeddedDoc, DateTimeZone, Decimal, FileData, GeoPoint, ImageData, ListItems,
+        TimeRange, Widget,
+    },
     models::{caching::CachingModel, Meta, ToModel},
+    storage::{field_storage, Storage},
+    store::FormCache,
+};
+#[cfg(feature = "sync")]
+use crate::{
+    forms::output_data::OutputDataForm,
+    migration::ModelRelation,
+    models::{audit::AuditRecord, output_data::OutputDataOne, DeleteFn},
+    store::MONGODB_CLIENT_STORE,
 };
+use image::GenericImageView;
 use rand::Rng;
 use std::convert::TryFrom;
-use std::{fs, path::Path};
+use std::path::Path;
 
 pub trait QPaladins: ToModel + CachingModel {
     /// Json-line for admin panel.
@@ -32,7 +58,8 @@ pub trait QPaladins: ToModel + CachingModel {
     /// println!("{}", user_profile.json_for_admin()?);
     /// ```
     ///
-    fn json_for_admin(&self) -> Result<String, Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn json_for_admin(&self) -> Result<String, Error> {
         // Get cached Model data.
         let (form_cache, _client_cache) = Self::get_cache_data_for_query()?;
         // Get Model metadata.
@@ -74,8 +101,9 @@ pub trait QPaladins: ToModel + CachingModel {
         Ok(serde_json::to_string(&widget_list)?)
     }
 
-    /// Deleting a file in the database and in the file system.
+    /// Deleting a file in the database and in the storage.
     // *********************************************************************************************
+    #[cfg(feature = "sync")]
     fn delete_file(
         &self,
         coll: &mongodb::sync::Collection,
@@ -83,7 +111,8 @@ pub trait QPaladins: ToModel + CachingModel {
         field_name: &str,
         widget_default_value: &str,
         is_image: bool,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        storage: &dyn Storage,
+    ) -> Result<(), Error> {
         let hash = self.get_hash().unwrap_or_default();
         if !hash.is_empty() {
             let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
@@ -99,56 +128,19 @@ pub trait QPaladins: ToModel + CachingModel {
                 coll.update_one(filter, update, None)?;
                 // Delete the orphaned file.
                 if let Some(info_file) = document.get(field_name).unwrap().as_document() {
-                    if !is_image {
-                        let default_path = if !widget_default_value.is_empty() {
-                            serde_json::from_str::<FileData>(widget_default_value)?.path
-                        } else {
-                            String::new()
-                        };
-                        let path = info_file.get_str("path")?;
-                        if path != default_path {
-                            let path = Path::new(path);
-                            if path.exists() {
-                                fs::remove_file(path)?;
-                            }
-                        }
-                    } else {
-                        let default_path = if !widget_default_value.is_empty() {
-                            serde_json::from_str::<ImageData>(widget_default_value)?.path
-                        } else {
-                            String::new()
-                        };
-                        let path = info_file.get_str("path")?;
-                        if path != default_path {
-                            let path = Path::new(path);
-                            if path.exists() {
-                                fs::remove_file(path)?;
-                            }
-                            // Remove thumbnails.
-                            let size_names: [&str; 4] = ["lg", "md", "sm", "xs"];
-                            for size_name in size_names.iter() {
-                                let key_name = format!("path_{}", size_name);
-                                let path = info_file.get_str(key_name.as_str())?;
-                                if !path.is_empty() {
-                                    let path = Path::new(path);
-                                    if path.exists() {
-                                        fs::remove_file(path)?;
-                                    }
-                                }
-                            }
-                        }
-                    }
+                    Self::remove_files(info_file, widget_default_value, is_image, storage)?;
                 } else {
-                    Err(format!(
-                        "Model: `{}` > Field: `{}` > Method: `delete_file()` : Document (info file) not found.",
-                        model_name, field_name
-                    ))?
+                    Err(Error::MissingField {
+                        model: model_name.to_string(),
+                        field: field_name.to_string(),
+                        method: "delete_file()".to_string(),
+                    })?
                 }
             } else {
-                Err(format!(
-                    "Model: `{}` > Field: `{}` > Method: `delete_file()` : Document not found.",
-                    model_name, field_name
-                ))?
+                Err(Error::DocumentNotFound {
+                    model: model_name.to_string(),
+                    method: "delete_file()".to_string(),
+                })?
             }
         }
         //
@@ -157,26 +149,88 @@ pub trait QPaladins: ToModel + CachingModel {
 
     /// Get file info from database.
     // *********************************************************************************************
+    #[cfg(feature = "sync")]
     fn db_get_file_info(
         &self,
         coll: &mongodb::sync::Collection,
         field_name: &str,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, Error> {
         let hash = self.get_hash().unwrap_or_default();
         let mut result = String::new();
         if !hash.is_empty() {
             let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
             let filter = mongodb::bson::doc! {"_id": object_id};
-            if let Some(document) = coll.find_one(filter, None)? {
-                if let Some(file_doc) = document.get(field_name).unwrap().as_document() {
-                    result = serde_json::to_string(file_doc)?;
-                }
+            let document = coll.find_one(filter, None)?;
+            result = Self::file_info_from_doc(document.as_ref(), field_name)?;
+        }
+        //
+        Ok(result)
+    }
+
+    /// Get file info from the document of database.
+    // *********************************************************************************************
+    fn file_info_from_doc(
+        document: Option<&mongodb::bson::document::Document>,
+        field_name: &str,
+    ) -> Result<String, Error> {
+        let mut result = String::new();
+        if let Some(document) = document {
+            if let Some(file_doc) = document.get(field_name).and_then(|item| item.as_document()) {
+                result = serde_json::to_string(file_doc)?;
             }
         }
         //
         Ok(result)
     }
 
+    /// Remove the file (and thumbnails for images) from the storage.
+    /// ( Files by default are not deleted )
+    // *********************************************************************************************
+    fn remove_files(
+        info_file: &mongodb::bson::document::Document,
+        widget_default_value: &str,
+        is_image: bool,
+        storage: &dyn Storage,
+    ) -> Result<(), Error> {
+        let default_path = if widget_default_value.is_empty() {
+            String::new()
+        } else if !is_image {
+            serde_json::from_str::<FileData>(widget_default_value)?.path
+        } else {
+            serde_json::from_str::<ImageData>(widget_default_value)?.path
+        };
+        let path = info_file.get_str("path")?;
+        if path != default_path {
+            storage.delete(path)?;
+            // Remove thumbnails.
+            if is_image {
+                let size_names: [&str; 4] = ["lg", "md", "sm", "xs"];
+                for size_name in size_names.iter() {
+                    let key_name = format!("path_{}", size_name);
+                    let path = info_file.get_str(key_name.as_str())?;
+                    if !path.is_empty() {
+                        storage.delete(path)?;
+                    }
+                }
+            }
+        }
+        //
+        Ok(())
+    }
+
+    /// Encode the image and write it to the storage.
+    // *********************************************************************************************
+    fn put_image(
+        img: &image::DynamicImage,
+        image_format: image::ImageFormat,
+        path: &str,
+        storage: &dyn Storage,
+    ) -> Result<(), Error> {
+        let mut buffer: Vec<u8> = Vec::new();
+        img.write_to(&mut buffer, image_format)?;
+        storage.put(path, buffer.as_slice())
+    }
+
     /// Calculate the maximum size for a thumbnail.
     // *********************************************************************************************
     fn calculate_thumbnail_size(width: u32, height: u32, max_size: u32) -> (u32, u32) {
@@ -209,11 +263,175 @@ pub trait QPaladins: ToModel + CachingModel {
     /// assert!(result.is_valid());
     /// ```
     ///
-    fn check(&self) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    #[cfg(feature = "sync")]
+    fn check(&self) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        self.check_with_cache(&form_cache, &client_cache)
+    }
+
+    /// Checking the Model with the cached data of the Model.
+    /// ( For partial updates, the cached data contains only the fields being updated )
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn check_with_cache(
+        &self,
+        form_cache: &FormCache,
+        client_cache: &mongodb::sync::Client,
+    ) -> Result<OutputDataForm, Error> {
         // Get Model metadata.
-        let meta: Meta = form_cache.meta;
+        let meta: &Meta = &form_cache.meta;
+        // Determines the mode of accessing the database (insert or update).
+        let hash = self.get_hash().unwrap_or_default();
+        let hash = hash.as_str();
+        let is_update: bool = !hash.is_empty();
+        // Access the collection.
+        let coll: mongodb::sync::Collection = client_cache
+            .database(&meta.database_name)
+            .collection(&meta.collection_name);
+        // Get the current state of the document from database.
+        let doc_from_db: Option<mongodb::bson::document::Document> = if is_update {
+            let object_id = mongodb::bson::oid::ObjectId::with_string(hash)?;
+            coll.find_one(mongodb::bson::doc! {"_id": object_id}, None)?
+        } else {
+            None
+        };
+        // Validation of fields.
+        let (
+            mut is_err_symptom,
+            mut final_map_widgets,
+            mut final_doc,
+            unique_queue,
+            ref_queue,
+            delete_file_queue,
+        ) = self.check_fields(form_cache, doc_from_db)?;
+
+        // Delete files, if the client asked about it.
+        // -----------------------------------------------------------------------------------------
+        for (field_name, is_image) in delete_file_queue {
+            let default_value = form_cache
+                .map_widgets
+                .get(&field_name)
+                .unwrap()
+                .value
+                .clone();
+            self.delete_file(
+                &coll,
+                meta.model_name.as_str(),
+                field_name.as_str(),
+                default_value.as_str(),
+                is_image,
+                field_storage(meta, field_name.as_str())?.as_ref(),
+            )?;
+        }
+
+        // Validation of `unique`.
+        // -----------------------------------------------------------------------------------------
+        for (field_name, bson_field_value) in unique_queue {
+            let final_widget: &mut Widget = final_map_widgets.get_mut(&field_name).unwrap();
+            Self::check_unique(hash, field_name.as_str(), &bson_field_value, &coll).unwrap_or_else(
+                |err| {
+                    is_err_symptom = true;
+                    final_widget.error =
+                        Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                },
+            );
+        }
+
+        // Validation of references to other Models.
+        // -----------------------------------------------------------------------------------------
+        if !ref_queue.is_empty() {
+            let client_store = MONGODB_CLIENT_STORE.read()?;
+            for (field_name, object_ids) in ref_queue {
+                let ref_model =
+                    meta.map_ref_models
+                        .get(&field_name)
+                        .ok_or_else(|| Error::MissingField {
+                            model: meta.model_name.clone(),
+                            field: field_name.clone(),
+                            method: "check()".to_string(),
+                        })?;
+                let ref_client: &mongodb::sync::Client = client_store
+                    .get(&ref_model.db_client_name)
+                    .ok_or_else(|| Error::ClientNotFound {
+                        model: ref_model.model_name.clone(),
+                        client: ref_model.db_client_name.clone(),
+                    })?;
+                let ref_coll: mongodb::sync::Collection = ref_client
+                    .database(&ref_model.database_name)
+                    .collection(&ref_model.collection_name);
+                let final_widget: &mut Widget = final_map_widgets.get_mut(&field_name).unwrap();
+                Self::check_ref(&object_ids, &ref_coll).unwrap_or_else(|err| {
+                    is_err_symptom = true;
+                    final_widget.error =
+                        Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                });
+            }
+        }
+
+        // Timestamps and removal of orphaned files.
+        Self::check_epilogue(
+            is_err_symptom,
+            is_update,
+            meta,
+            &mut final_map_widgets,
+            &mut final_doc,
+        )?;
+
+        // Lifecycle hook - the final document can be changed.
+        if !is_err_symptom {
+            self.post_check(&mut final_doc, is_update)?;
+        }
+
+        // Enrich the widget map with values for dynamic widgets.
+        Self::vitaminize(
+            meta.project_name.as_str(),
+            meta.unique_project_key.as_str(),
+            meta.collection_name.as_str(),
+            client_cache,
+            &mut final_map_widgets,
+        )?;
+
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        Ok(OutputDataForm::CheckModel((
+            !is_err_symptom,
+            meta.fields_name.clone(),
+            final_map_widgets,
+            final_doc,
+        )))
+    }
+
+    /// Validation of Model fields.
+    /// ( Without database queries - the current state of the document is passed as an argument )
+    // *********************************************************************************************
+    ///
+    /// Returns: (
+    ///     is_err_symptom,
+    ///     final_map_widgets,
+    ///     final_doc,
+    ///     <field name, value> - values for validation of `unique`,
+    ///     <field name, document identifiers> - references for validation of existence,
+    ///     <field name, is image> - files that the client asked to delete,
+    /// )
+    ///
+    fn check_fields(
+        &self,
+        form_cache: &FormCache,
+        doc_from_db: Option<mongodb::bson::document::Document>,
+    ) -> Result<
+        (
+            bool,
+            std::collections::HashMap<String, Widget>,
+            mongodb::bson::document::Document,
+            Vec<(String, mongodb::bson::Bson)>,
+            Vec<(String, Vec<mongodb::bson::oid::ObjectId>)>,
+            Vec<(String, bool)>,
+        ),
+        Error,
+    > {
+        // Get Model metadata.
+        let meta: &Meta = &form_cache.meta;
         // Get model name.
         let model_name: &str = meta.model_name.as_str();
         // User input error detection symptom.
@@ -232,14 +450,19 @@ pub trait QPaladins: ToModel + CachingModel {
             .iter()
             .map(|item| item.as_str())
             .collect();
-        // Access the collection.
-        let coll: mongodb::sync::Collection = client_cache
-            .database(&meta.database_name)
-            .collection(&meta.collection_name);
         // Get preliminary data from the model.
         let pre_json: serde_json::value::Value = self.self_to_json()?;
         // Document for the final result.
         let mut final_doc = mongodb::bson::document::Document::new();
+        // Values for validation of `unique`.
+        // Hint: <field name, value>
+        let mut unique_queue: Vec<(String, mongodb::bson::Bson)> = Vec::new();
+        // References for validation of existence.
+        // Hint: <field name, document identifiers>
+        let mut ref_queue: Vec<(String, Vec<mongodb::bson::oid::ObjectId>)> = Vec::new();
+        // Files that the client asked to delete.
+        // Hint: <field name, is image>
+        let mut delete_file_queue: Vec<(String, bool)> = Vec::new();
 
         // Validation of field by attributes (maxlength, unique, min, max, etc...).
         // -----------------------------------------------------------------------------------------
@@ -249,19 +472,21 @@ pub trait QPaladins: ToModel + CachingModel {
         // Apply additional validation.
         {
             let error_map = self.add_validation()?;
-            if !error_map.is_empty() {
+            for (field_name, err_msg) in error_map {
+                if !form_cache.map_widgets.contains_key(field_name) {
+                    Err(Error::MissingField {
+                        model: model_name.to_string(),
+                        field: field_name.to_string(),
+                        method: "add_validation()".to_string(),
+                    })?
+                }
+                // For partial updates, only the fields being updated are checked.
+                if !fields_name.contains(&field_name) {
+                    continue;
+                }
                 is_err_symptom = true;
-                for (field_name, err_msg) in error_map {
-                    if !fields_name.contains(&field_name) {
-                        Err(format!(
-                            "Model: `{}` >  Method: `add_validation()` : \
-                            The `{}` field is missing from the model.",
-                            model_name, field_name
-                        ))?
-                    }
-                    if let Some(widget) = final_map_widgets.get_mut(&field_name.to_owned()) {
-                        widget.error = Self::accumula_err(&widget, &err_msg.to_string())?;
-                    }
+                if let Some(widget) = final_map_widgets.get_mut(&field_name.to_owned()) {
+                    widget.error = Self::accumula_err(&widget, &err_msg.to_string())?;
                 }
             }
         }
@@ -283,10 +508,11 @@ pub trait QPaladins: ToModel + CachingModel {
             let pre_json_value: Option<&serde_json::value::Value> = pre_json.get(field_name);
             // Check field value.
             if pre_json_value.is_none() {
-                Err(format!(
-                    "Model: `{}` > Field: `{}` > Method: `check()` : This field is missing.",
-                    model_name, field_name
-                ))?
+                Err(Error::MissingField {
+                    model: model_name.to_string(),
+                    field: field_name.to_string(),
+                    method: "check()".to_string(),
+                })?
             }
             //
             let pre_json_value: &serde_json::value::Value = pre_json_value.unwrap();
@@ -383,12 +609,7 @@ pub trait QPaladins: ToModel + CachingModel {
                     // Validation of `unique`.
                     // -----------------------------------------------------------------------------
                     if widget_type != "inputPassword" && final_widget.unique {
-                        Self::check_unique(hash, field_name, &bson_field_value, &coll)
-                            .unwrap_or_else(|err| {
-                                is_err_symptom = true;
-                                final_widget.error =
-                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
-                            });
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
                     }
 
                     // Validation in regular expression (email, password, etc...).
@@ -469,19 +690,19 @@ pub trait QPaladins: ToModel + CachingModel {
                     }
 
                     // Create Date and Time Object.
+                    // ( The value is parsed in the time zone of the field )
                     // -----------------------------------------------------------------------------
-                    // Date to DateTime.
-                    let dt_value: chrono::DateTime<chrono::Utc> = {
-                        let field_value: String = if widget_type == "inputDate" {
-                            format!("{}T00:00", field_value.to_string())
-                        } else {
-                            field_value.to_string()
+                    let timezone = DateTimeZone::of_field(&meta.map_timezones, field_name);
+                    let dt_value: chrono::DateTime<chrono::Utc> =
+                        match timezone.parse(widget_type, field_value) {
+                            Ok(dt_value) => dt_value,
+                            Err(err) => {
+                                is_err_symptom = true;
+                                final_widget.error =
+                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                                continue;
+                            }
                         };
-                        chrono::DateTime::<chrono::Utc>::from_utc(
-                            chrono::NaiveDateTime::parse_from_str(&field_value, "%Y-%m-%dT%H:%M")?,
-                            chrono::Utc,
-                        )
-                    };
                     // Create dates for `min` and `max` attributes values to
                     // check, if the value of user falls within the range
                     // between these dates.
@@ -504,35 +725,11 @@ pub trait QPaladins: ToModel + CachingModel {
                             continue;
                         }
                         // Date to DateTime (min).
-                        let dt_min: chrono::DateTime<chrono::Utc> = {
-                            let min_value: String = if widget_type == "inputDate" {
-                                format!("{}T00:00", final_widget.min.clone())
-                            } else {
-                                final_widget.min.clone()
-                            };
-                            chrono::DateTime::<chrono::Utc>::from_utc(
-                                chrono::NaiveDateTime::parse_from_str(
-                                    &min_value,
-                                    "%Y-%m-%dT%H:%M",
-                                )?,
-                                chrono::Utc,
-                            )
-                        };
+                        let dt_min: chrono::DateTime<chrono::Utc> =
+                            timezone.parse(widget_type, final_widget.min.as_str())?;
                         // Date to DateTime (max).
-                        let dt_max: chrono::DateTime<chrono::Utc> = {
-                            let max_value: String = if widget_type == "inputDate" {
-                                format!("{}T00:00", final_widget.max.clone())
-                            } else {
-                                final_widget.max.clone()
-                            };
-                            chrono::DateTime::<chrono::Utc>::from_utc(
-                                chrono::NaiveDateTime::parse_from_str(
-                                    &max_value,
-                                    "%Y-%m-%dT%H:%M",
-                                )?,
-                                chrono::Utc,
-                            )
-                        };
+                        let dt_max: chrono::DateTime<chrono::Utc> =
+                            timezone.parse(widget_type, final_widget.max.as_str())?;
                         // Check hit in range (min <> max).
                         if dt_value < dt_min || dt_value > dt_max {
                             is_err_symptom = true;
@@ -545,25 +742,174 @@ pub trait QPaladins: ToModel + CachingModel {
                         }
                     }
 
+                    // Return the value to the user in the time zone of the field.
+                    final_widget.value = timezone.render(widget_type, &dt_value);
+
                     // Create datetime in bson type.
                     // -----------------------------------------------------------------------------
                     let dt_value_bson = mongodb::bson::Bson::DateTime(dt_value);
                     // Validation of `unique`
                     // -----------------------------------------------------------------------------
                     if final_widget.unique {
-                        Self::check_unique(hash, field_name, &dt_value_bson, &coll).unwrap_or_else(
-                            |err| {
+                        unique_queue.push((field_name.to_string(), dt_value_bson.clone()));
+                    }
+
+                    // Insert result.
+                    // -----------------------------------------------------------------------------
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        final_doc.insert(field_name, dt_value_bson);
+                    }
+                }
+                // Validation of time type fields.
+                // *********************************************************************************
+                "inputTime" | "inputTimeRange" => {
+                    // Get field value for validation.
+                    let field_value: String = if !pre_json_value.is_null() {
+                        let clean_data: String =
+                            pre_json_value.as_str().unwrap().trim().to_string();
+                        // In case of an error, return the current
+                        // state of the field to the user (client).
+                        final_widget.value = clean_data.clone();
+                        clean_data
+                    } else {
+                        String::new()
+                    };
+
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        } else if !ignore_fields.contains(&field_name) {
+                            final_doc.insert(field_name, mongodb::bson::Bson::Null);
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+
+                    // Validation of format, range (`min` <> `max`) and `step`.
+                    // Time is stored in minutes since midnight.
+                    // -----------------------------------------------------------------------------
+                    let bson_field_value =
+                        match TimeRange::check_value(&final_widget, field_value.as_str()) {
+                            Ok(bson_field_value) => bson_field_value,
+                            Err(err) => {
                                 is_err_symptom = true;
                                 final_widget.error =
                                     Self::accumula_err(&final_widget, &err.to_string()).unwrap();
-                            },
-                        );
+                                continue;
+                            }
+                        };
+                    // Validation of `unique`
+                    // -----------------------------------------------------------------------------
+                    if final_widget.unique {
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
                     }
 
                     // Insert result.
                     // -----------------------------------------------------------------------------
                     if !is_err_symptom && !ignore_fields.contains(&field_name) {
-                        final_doc.insert(field_name, dt_value_bson);
+                        final_doc.insert(field_name, bson_field_value);
+                    }
+                }
+                // Validation of geospatial point fields.
+                // *********************************************************************************
+                "inputGeoPoint" => {
+                    // Get field value for validation.
+                    let field_value: String = if !pre_json_value.is_null() {
+                        let clean_data: String =
+                            pre_json_value.as_str().unwrap().trim().to_string();
+                        // In case of an error, return the current
+                        // state of the field to the user (client).
+                        final_widget.value = clean_data.clone();
+                        clean_data
+                    } else {
+                        String::new()
+                    };
+
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        } else if !ignore_fields.contains(&field_name) {
+                            final_doc.insert(field_name, mongodb::bson::Bson::Null);
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+
+                    // Validation of format and range of coordinates.
+                    // The point is stored in GeoJSON format.
+                    // -----------------------------------------------------------------------------
+                    let point: GeoPoint = match GeoPoint::check_value(field_value.as_str()) {
+                        Ok(point) => point,
+                        Err(err) => {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                            continue;
+                        }
+                    };
+                    final_widget.value = point.to_value();
+
+                    // Insert result.
+                    // -----------------------------------------------------------------------------
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        final_doc.insert(field_name, point.to_bson());
+                    }
+                }
+                // Validation of list type fields.
+                // *********************************************************************************
+                "listText" | "listI32" | "listU32" | "listI64" | "listF64" => {
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( An empty list is an empty value )
+                    // -----------------------------------------------------------------------------
+                    if pre_json_value
+                        .as_array()
+                        .map_or(true, |items| items.is_empty())
+                    {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        } else if !ignore_fields.contains(&field_name) {
+                            final_doc.insert(field_name, mongodb::bson::Bson::Null);
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+                    // In case of an error, return the current
+                    // state of the field to the user (client).
+                    final_widget.value = serde_json::to_string(&pre_json_value)?;
+
+                    // Validation of items and of the number of items.
+                    // -----------------------------------------------------------------------------
+                    let items: ListItems =
+                        match ListItems::check_value(&final_widget, pre_json_value) {
+                            Ok(items) => items,
+                            Err(err) => {
+                                is_err_symptom = true;
+                                final_widget.error =
+                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                                continue;
+                            }
+                        };
+                    final_widget.value = items.to_value();
+
+                    // Insert result.
+                    // -----------------------------------------------------------------------------
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        final_doc.insert(field_name, items.to_bson(widget_type));
                     }
                 }
                 // Validation of `select` type fields.
@@ -604,11 +950,11 @@ pub trait QPaladins: ToModel + CachingModel {
                                     final_widget.value = val.to_string();
                                     mongodb::bson::Bson::Double(val)
                                 }
-                                _ => Err(format!(
-                                    "Model: `{}` > Field: `{}` > Method: `check()` : \
-                                        Unsupported widget type - `{}`.",
-                                    model_name, field_name, widget_type
-                                ))?,
+                                _ => Err(Error::UnsupportedWidget {
+                                    model: model_name.to_string(),
+                                    field: field_name.to_string(),
+                                    widget: widget_type.to_string(),
+                                })?,
                             },
                         );
                     } else {
@@ -677,11 +1023,11 @@ pub trait QPaladins: ToModel + CachingModel {
                                         })
                                         .collect::<Vec<mongodb::bson::Bson>>(),
                                 ),
-                                _ => Err(format!(
-                                    "Model: `{}` > Field: `{}` > Method: `check()` : \
-                                        Unsupported widget type - `{}`.",
-                                    model_name, field_name, widget_type
-                                ))?,
+                                _ => Err(Error::UnsupportedWidget {
+                                    model: model_name.to_string(),
+                                    field: field_name.to_string(),
+                                    widget: widget_type.to_string(),
+                                })?,
                             },
                         );
                         final_widget.value = serde_json::to_string(&pre_json_value)?;
@@ -697,11 +1043,116 @@ pub trait QPaladins: ToModel + CachingModel {
                         final_widget.value = String::new();
                     }
                 }
-                // Validation of file type fields.
+                // Validation of embedded documents.
                 // *********************************************************************************
-                "inputFile" => {
-                    // Get field value for validation.
-                    let mut field_value: FileData = if !pre_json_value.is_null() {
+                "embeddedDoc" | "embeddedDocMult" => {
+                    let embedded: &EmbeddedDoc =
+                        meta.map_embedded
+                            .get(field_name)
+                            .ok_or_else(|| Error::MissingField {
+                                model: model_name.to_string(),
+                                field: field_name.to_string(),
+                                method: "check()".to_string(),
+                            })?;
+                    let (is_err, bson_field_value) =
+                        embedded.check_field(final_widget, pre_json_value)?;
+                    if is_err {
+                        is_err_symptom = true;
+                    }
+                    // Insert result.
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        final_doc.insert(field_name, bson_field_value);
+                    }
+                }
+                // Validation of reference type fields.
+                // *********************************************************************************
+                "selectRef" | "selectRefMult" => {
+                    // Get field value for validation.
+                    let hashes: Vec<String> = if !pre_json_value.is_null() {
+                        if widget_type == "selectRef" {
+                            let clean_data: String =
+                                pre_json_value.as_str().unwrap().trim().to_string();
+                            final_widget.value = clean_data.clone();
+                            if !clean_data.is_empty() {
+                                vec![clean_data]
+                            } else {
+                                Vec::new()
+                            }
+                        } else {
+                            final_widget.value = serde_json::to_string(&pre_json_value)?;
+                            pre_json_value
+                                .as_array()
+                                .unwrap()
+                                .iter()
+                                .map(|item| item.as_str().unwrap().trim().to_string())
+                                .filter(|item| !item.is_empty())
+                                .collect()
+                        }
+                    } else {
+                        Vec::new()
+                    };
+
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // -----------------------------------------------------------------------------
+                    if hashes.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        } else if !ignore_fields.contains(&field_name) {
+                            final_doc.insert(field_name, mongodb::bson::Bson::Null);
+                        }
+                        final_widget.value = String::new();
+                        continue;
+                    }
+
+                    // Convert hash-lines to document identifiers.
+                    // -----------------------------------------------------------------------------
+                    let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = Vec::new();
+                    for hash in hashes.iter() {
+                        if let Ok(object_id) = mongodb::bson::oid::ObjectId::with_string(hash) {
+                            object_ids.push(object_id);
+                        } else {
+                            is_err_symptom = true;
+                            final_widget.error = Self::accumula_err(
+                                &final_widget,
+                                &format!("Invalid document identifier - {}", hash),
+                            )
+                            .unwrap();
+                        }
+                    }
+                    if object_ids.len() != hashes.len() {
+                        continue;
+                    }
+
+                    // Validation of the existence of referenced documents.
+                    // -----------------------------------------------------------------------------
+                    ref_queue.push((field_name.to_string(), object_ids.clone()));
+
+                    // Insert result.
+                    // -----------------------------------------------------------------------------
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        let bson_field_value = if widget_type == "selectRef" {
+                            mongodb::bson::Bson::ObjectId(object_ids[0].clone())
+                        } else {
+                            mongodb::bson::Bson::Array(
+                                object_ids
+                                    .into_iter()
+                                    .map(mongodb::bson::Bson::ObjectId)
+                                    .collect(),
+                            )
+                        };
+                        final_doc.insert(field_name, bson_field_value);
+                    }
+                }
+                // Validation of file type fields.
+                // *********************************************************************************
+                "inputFile" => {
+                    // Get field value for validation.
+                    // The flag is set if the client asked to delete the file.
+                    let mut is_file_deleted = false;
+                    let mut field_value: FileData = if !pre_json_value.is_null() {
                         let obj_str = pre_json_value.as_str().unwrap();
                         if let Some(is_delete) = serde_json::from_str::<
                             serde_json::map::Map<String, serde_json::Value>,
@@ -710,22 +1161,27 @@ pub trait QPaladins: ToModel + CachingModel {
                         .get("is_delete")
                         {
                             if is_update && is_delete.as_bool().unwrap() {
-                                self.delete_file(
-                                    &coll,
-                                    model_name,
-                                    field_name,
-                                    final_widget.value.as_str(),
-                                    false,
-                                )?;
+                                delete_file_queue.push((field_name.to_string(), false));
+                                is_file_deleted = true;
                                 final_doc.insert(field_name, mongodb::bson::Bson::Null);
                             }
                         }
-                        serde_json::from_str::<FileData>(obj_str)?
+                        serde_json::from_str::<FileData>(obj_str).map_err(|err| {
+                            Error::InvalidWidgetJson {
+                                model: model_name.to_string(),
+                                field: field_name.to_string(),
+                                message: err.to_string(),
+                            }
+                        })?
                     } else {
                         FileData::default()
                     };
                     // Get the current information about file from database.
-                    let curr_info_file: String = self.db_get_file_info(&coll, field_name)?;
+                    let curr_info_file: String = if !is_file_deleted {
+                        Self::file_info_from_doc(doc_from_db.as_ref(), field_name)?
+                    } else {
+                        String::new()
+                    };
                     // Validation, if the field is required and empty, accumulate the error.
                     // ( The default value is used whenever possible )
                     if field_value.path.is_empty() && field_value.url.is_empty() {
@@ -740,13 +1196,18 @@ pub trait QPaladins: ToModel + CachingModel {
                                 continue;
                             } else if !final_widget.value.is_empty() {
                                 // Trying to apply the value default.
-                                field_value = serde_json::from_str(final_widget.value.trim())?;
+                                field_value = serde_json::from_str(final_widget.value.trim())
+                                    .map_err(|err| Error::InvalidWidgetJson {
+                                        model: model_name.to_string(),
+                                        field: field_name.to_string(),
+                                        message: err.to_string(),
+                                    })?;
                             } else {
                                 final_doc.insert(field_name, mongodb::bson::Bson::Null);
                                 continue;
                             }
                         } else {
-                            final_widget.value = self.db_get_file_info(&coll, field_name)?;
+                            final_widget.value = curr_info_file.clone();
                             continue;
                         }
                     }
@@ -757,30 +1218,44 @@ pub trait QPaladins: ToModel + CachingModel {
                     let is_emty_url = field_value.url.is_empty();
                     // Invalid if there is only one value.
                     if (!is_emty_path && is_emty_url) || (is_emty_path && !is_emty_url) {
-                        Err(format!(
-                            "Model: `{}` > Field: `{}` > Method: \
-                            `check()` : Incorrectly filled field. \
-                            Example: (for default): {{\"path\":\"./media/resume.docx\",\"url\":\"/media/resume.docx\"}} ;\
-                            Example: (from client side): {{\"path\":\"\",\"url\":\"\",\"is_delete\":true}}",
-                            model_name, field_name
-                        ))?
-                    }
-                    // Create path for validation of file.
+                        Err(Error::InvalidWidgetJson {
+                            model: model_name.to_string(),
+                            field: field_name.to_string(),
+                            message: "Incorrectly filled field. \
+                            Example: (for default): {\"path\":\"./media/resume.docx\",\"url\":\"/media/resume.docx\"} ;\
+                            Example: (from client side): {\"path\":\"\",\"url\":\"\",\"is_delete\":true}"
+                                .to_string(),
+                        })?
+                    }
+                    // Validation of file in the storage.
+                    let storage = field_storage(meta, field_name)?;
                     let path: String = field_value.path.clone();
-                    let f_path = std::path::Path::new(path.as_str());
-                    if !f_path.exists() || !f_path.is_file() {
-                        Err(format!(
-                            "Model: `{}` > Field: `{}` > Method: \
-                                `check()` : File is missing - {}",
-                            model_name, field_name, path
-                        ))?
-                    }
-                    // Get file metadata.
-                    let metadata: std::fs::Metadata = f_path.metadata()?;
+                    if !storage.exists(path.as_str())? {
+                        Err(Error::FileNotFound {
+                            model: model_name.to_string(),
+                            field: field_name.to_string(),
+                            path: path.clone(),
+                        })?
+                    }
                     // Get file size in bytes.
-                    field_value.size = metadata.len() as u32;
+                    field_value.size = storage.size(path.as_str())? as u32;
+                    // Validation of file size.
+                    if final_widget.maxsize > 0 && field_value.size > final_widget.maxsize {
+                        is_err_symptom = true;
+                        final_widget.error = Self::accumula_err(
+                            &final_widget,
+                            &format!("The file size exceeds {} bytes.", final_widget.maxsize),
+                        )
+                        .unwrap();
+                        continue;
+                    }
                     // Get file name.
-                    field_value.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
+                    field_value.name = Path::new(path.as_str())
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .to_string();
                     // Insert result.
                     if !is_err_symptom && !ignore_fields.contains(&field_name) {
                         // Add file data to widget.
@@ -792,6 +1267,8 @@ pub trait QPaladins: ToModel + CachingModel {
                 }
                 "inputImage" => {
                     // Get field value for validation.
+                    // The flag is set if the client asked to delete the file.
+                    let mut is_file_deleted = false;
                     let mut field_value: ImageData = if !pre_json_value.is_null() {
                         let obj_str = pre_json_value.as_str().unwrap();
                         if let Some(is_delete) = serde_json::from_str::<
@@ -801,22 +1278,27 @@ pub trait QPaladins: ToModel + CachingModel {
                         .get("is_delete")
                         {
                             if is_update && is_delete.as_bool().unwrap() {
-                                self.delete_file(
-                                    &coll,
-                                    model_name,
-                                    field_name,
-                                    final_widget.value.as_str(),
-                                    true,
-                                )?;
+                                delete_file_queue.push((field_name.to_string(), true));
+                                is_file_deleted = true;
                                 final_doc.insert(field_name, mongodb::bson::Bson::Null);
                             }
                         }
-                        serde_json::from_str::<ImageData>(obj_str)?
+                        serde_json::from_str::<ImageData>(obj_str).map_err(|err| {
+                            Error::InvalidWidgetJson {
+                                model: model_name.to_string(),
+                                field: field_name.to_string(),
+                                message: err.to_string(),
+                            }
+                        })?
                     } else {
                         ImageData::default()
                     };
                     // Get the current information about file from database.
-                    let curr_info_file: String = self.db_get_file_info(&coll, field_name)?;
+                    let curr_info_file: String = if !is_file_deleted {
+                        Self::file_info_from_doc(doc_from_db.as_ref(), field_name)?
+                    } else {
+                        String::new()
+                    };
                     // Validation, if the field is required and empty, accumulate the error.
                     // ( The default value is used whenever possible )
                     if field_value.path.is_empty() && field_value.url.is_empty() {
@@ -831,13 +1313,18 @@ pub trait QPaladins: ToModel + CachingModel {
                                 continue;
                             } else if !final_widget.value.is_empty() {
                                 // Trying to apply the value default.
-                                field_value = serde_json::from_str(final_widget.value.trim())?;
+                                field_value = serde_json::from_str(final_widget.value.trim())
+                                    .map_err(|err| Error::InvalidWidgetJson {
+                                        model: model_name.to_string(),
+                                        field: field_name.to_string(),
+                                        message: err.to_string(),
+                                    })?;
                             } else {
                                 final_doc.insert(field_name, mongodb::bson::Bson::Null);
                                 continue;
                             }
                         } else {
-                            final_widget.value = self.db_get_file_info(&coll, field_name)?;
+                            final_widget.value = curr_info_file.clone();
                             continue;
                         }
                     }
@@ -848,36 +1335,53 @@ pub trait QPaladins: ToModel + CachingModel {
                     let is_emty_url = field_value.url.is_empty();
                     // Invalid if there is only one value.
                     if (!is_emty_path && is_emty_url) || (is_emty_path && !is_emty_url) {
-                        Err(format!(
-                            "Model: `{}` > Field: `{}` > Method: \
-                            `check()` : Incorrectly filled field. \
-                            Example: (for default): {{\"path\":\"./media/no_photo.jpg\",\"url\":\"/media/no_photo.jpg\"}} ;\
-                            Example: (from client side): {{\"path\":\"\",\"url\":\"\",\"is_delete\":true}}",
-                            model_name, field_name
-                        ))?
-                    }
-                    // Create path for validation of file.
-                    let f_path = std::path::Path::new(field_value.path.as_str());
-                    if !f_path.exists() || !f_path.is_file() {
-                        Err(format!(
-                            "Model: `{}` > Field: `{}` > Method: \
-                                `check()` : File is missing - {}",
-                            model_name, field_name, field_value.path
-                        ))?
-                    }
-                    // Get file metadata.
-                    let metadata: std::fs::Metadata = f_path.metadata()?;
+                        Err(Error::InvalidWidgetJson {
+                            model: model_name.to_string(),
+                            field: field_name.to_string(),
+                            message: "Incorrectly filled field. \
+                            Example: (for default): {\"path\":\"./media/no_photo.jpg\",\"url\":\"/media/no_photo.jpg\"} ;\
+                            Example: (from client side): {\"path\":\"\",\"url\":\"\",\"is_delete\":true}"
+                                .to_string(),
+                        })?
+                    }
+                    // Validation of image in the storage.
+                    let storage = field_storage(meta, field_name)?;
+                    if !storage.exists(field_value.path.as_str())? {
+                        Err(Error::FileNotFound {
+                            model: model_name.to_string(),
+                            field: field_name.to_string(),
+                            path: field_value.path.clone(),
+                        })?
+                    }
+                    let image_bytes: Vec<u8> = storage.get(field_value.path.as_str())?;
                     // Get file size in bytes.
-                    field_value.size = metadata.len() as u32;
+                    field_value.size = image_bytes.len() as u32;
+                    // Validation of file size.
+                    if final_widget.maxsize > 0 && field_value.size > final_widget.maxsize {
+                        is_err_symptom = true;
+                        final_widget.error = Self::accumula_err(
+                            &final_widget,
+                            &format!("The file size exceeds {} bytes.", final_widget.maxsize),
+                        )
+                        .unwrap();
+                        continue;
+                    }
                     // Get file name
-                    field_value.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
+                    field_value.name = Path::new(field_value.path.as_str())
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .to_string();
                     // Get image width and height.
-                    let dimensions: (u32, u32) = image::image_dimensions(f_path)?;
+                    let mut img = image::load_from_memory(image_bytes.as_slice())?;
+                    let dimensions: (u32, u32) = img.dimensions();
                     field_value.width = dimensions.0;
                     field_value.height = dimensions.1;
                     // Generate sub-size images.
                     if !final_widget.thumbnails.is_empty() {
-                        let mut img = image::open(f_path)?;
+                        let image_format =
+                            image::ImageFormat::from_path(field_value.path.as_str())?;
                         for max_size in final_widget.thumbnails.iter() {
                             let thumbnail_size: (u32, u32) = Self::calculate_thumbnail_size(
                                 dimensions.0,
@@ -903,22 +1407,42 @@ pub trait QPaladins: ToModel + CachingModel {
                                 );
                                 match max_size.0.as_str() {
                                     "lg" => {
-                                        img.save(thumb_path.clone())?;
+                                        Self::put_image(
+                                            &img,
+                                            image_format,
+                                            &thumb_path,
+                                            storage.as_ref(),
+                                        )?;
                                         field_value.path_lg = thumb_path;
                                         field_value.url_lg = thumb_url;
                                     }
                                     "md" => {
-                                        img.save(thumb_path.clone())?;
+                                        Self::put_image(
+                                            &img,
+                                            image_format,
+                                            &thumb_path,
+                                            storage.as_ref(),
+                                        )?;
                                         field_value.path_md = thumb_path;
                                         field_value.url_md = thumb_url;
                                     }
                                     "sm" => {
-                                        img.save(thumb_path.clone())?;
+                                        Self::put_image(
+                                            &img,
+                                            image_format,
+                                            &thumb_path,
+                                            storage.as_ref(),
+                                        )?;
                                         field_value.path_sm = thumb_path;
                                         field_value.url_sm = thumb_url;
                                     }
                                     "xs" => {
-                                        img.save(thumb_path.clone())?;
+                                        Self::put_image(
+                                            &img,
+                                            image_format,
+                                            &thumb_path,
+                                            storage.as_ref(),
+                                        )?;
                                         field_value.path_xs = thumb_path;
                                         field_value.url_xs = thumb_url;
                                     }
@@ -968,12 +1492,7 @@ pub trait QPaladins: ToModel + CachingModel {
                     // Validation of `unique`
                     // -----------------------------------------------------------------------------
                     if final_widget.unique {
-                        Self::check_unique(hash, field_name, &bson_field_value, &coll)
-                            .unwrap_or_else(|err| {
-                                is_err_symptom = true;
-                                final_widget.error =
-                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
-                            });
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
                     }
 
                     // Validation of range (`min` <> `max`).
@@ -1026,12 +1545,7 @@ pub trait QPaladins: ToModel + CachingModel {
                     // Validation of `unique`.
                     // -----------------------------------------------------------------------------
                     if final_widget.unique {
-                        Self::check_unique(hash, field_name, &bson_field_value, &coll)
-                            .unwrap_or_else(|err| {
-                                is_err_symptom = true;
-                                final_widget.error =
-                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
-                            });
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
                     }
 
                     // Validation of range (`min` <> `max`).
@@ -1082,12 +1596,7 @@ pub trait QPaladins: ToModel + CachingModel {
                     // Validation of `unique`.
                     // -----------------------------------------------------------------------------
                     if final_widget.unique {
-                        Self::check_unique(hash, field_name, &bson_field_value, &coll)
-                            .unwrap_or_else(|err| {
-                                is_err_symptom = true;
-                                final_widget.error =
-                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
-                            });
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
                     }
                     // Validation of range (`min` <> `max`).
                     // -----------------------------------------------------------------------------
@@ -1107,6 +1616,60 @@ pub trait QPaladins: ToModel + CachingModel {
                         final_doc.insert(field_name, bson_field_value);
                     }
                 }
+                "numberDecimal" | "rangeDecimal" | "hiddenDecimal" => {
+                    // Get field value for validation.
+                    // ( The number is transmitted as a string, so as not to lose precision )
+                    let field_value: String = match pre_json_value.as_str() {
+                        Some(value) => value.trim().to_string(),
+                        None if pre_json_value.is_number() => pre_json_value.to_string(),
+                        None => String::new(),
+                    };
+                    // In case of an error, return the current
+                    // state of the field to the user (client).
+                    final_widget.value = field_value.clone();
+
+                    // Validation, if the field is required and empty, accumulate the error
+                    // ( The default value is used whenever possible ).
+                    // -----------------------------------------------------------------------------
+                    if field_value.is_empty() {
+                        if final_widget.required {
+                            is_err_symptom = true;
+                            final_widget.error =
+                                Self::accumula_err(&final_widget, &"Required field.".to_owned())
+                                    .unwrap();
+                        } else if !ignore_fields.contains(&field_name) {
+                            final_doc.insert(field_name, mongodb::bson::Bson::Null);
+                        }
+                        continue;
+                    }
+
+                    // Validation of format, `precision`, `scale`, range (`min` <> `max`)
+                    // and `step`. The number is stored in Decimal128.
+                    // -----------------------------------------------------------------------------
+                    let number: Decimal =
+                        match Decimal::check_value(&final_widget, field_value.as_str()) {
+                            Ok(number) => number,
+                            Err(err) => {
+                                is_err_symptom = true;
+                                final_widget.error =
+                                    Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+                                continue;
+                            }
+                        };
+                    final_widget.value = number.to_value();
+                    // Used to validation uniqueness and in the final result.
+                    let bson_field_value = number.to_bson();
+                    // Validation of `unique`.
+                    // -----------------------------------------------------------------------------
+                    if final_widget.unique {
+                        unique_queue.push((field_name.to_string(), bson_field_value.clone()));
+                    }
+                    // Insert result.
+                    // -----------------------------------------------------------------------------
+                    if !is_err_symptom && !ignore_fields.contains(&field_name) {
+                        final_doc.insert(field_name, bson_field_value);
+                    }
+                }
 
                 // Validation of boolean type fields.
                 // *********************************************************************************
@@ -1141,29 +1704,51 @@ pub trait QPaladins: ToModel + CachingModel {
                         final_doc.insert(field_name, bson_field_value);
                     }
                 }
-                _ => Err(format!(
-                    "Model: `{}` > Field: `{}` > Method: `check()` : \
-                     Unsupported widget type - `{}`.",
-                    model_name, field_name, widget_type
-                ))?,
+                _ => Err(Error::UnsupportedWidget {
+                    model: model_name.to_string(),
+                    field: field_name.to_string(),
+                    widget: widget_type.to_string(),
+                })?,
             }
+        }
 
-            // Insert or update fields for timestamps `created_at` and `updated_at`.
-            // -------------------------------------------------------------------------------------
-            if !is_err_symptom {
-                let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
-                if !is_update {
-                    final_doc.insert("created_at", mongodb::bson::Bson::DateTime(dt));
-                    final_doc.insert("updated_at", mongodb::bson::Bson::DateTime(dt));
-                } else {
-                    final_doc.insert("updated_at", mongodb::bson::Bson::DateTime(dt));
-                }
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        Ok((
+            is_err_symptom,
+            final_map_widgets,
+            final_doc,
+            unique_queue,
+            ref_queue,
+            delete_file_queue,
+        ))
+    }
+
+    /// Completion of the check - timestamps and removal of orphaned files.
+    // *********************************************************************************************
+    fn check_epilogue(
+        is_err_symptom: bool,
+        is_update: bool,
+        meta: &Meta,
+        final_map_widgets: &mut std::collections::HashMap<String, Widget>,
+        final_doc: &mut mongodb::bson::document::Document,
+    ) -> Result<(), Error> {
+        // Insert or update fields for timestamps `created_at` and `updated_at`.
+        // -----------------------------------------------------------------------------------------
+        if !is_err_symptom {
+            let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+            if !is_update {
+                final_doc.insert("created_at", mongodb::bson::Bson::DateTime(dt));
+                final_doc.insert("updated_at", mongodb::bson::Bson::DateTime(dt));
+            } else {
+                final_doc.insert("updated_at", mongodb::bson::Bson::DateTime(dt));
             }
         }
 
         // If the validation is negative, delete the orphaned files.
+        // -----------------------------------------------------------------------------------------
         if is_err_symptom && !is_update {
-            let map_default_values = meta.map_default_values;
+            let map_default_values = &meta.map_default_values;
             for (field, widget) in final_map_widgets.iter_mut() {
                 match widget.widget.as_str() {
                     "inputFile" if !widget.value.is_empty() => {
@@ -1176,10 +1761,7 @@ pub trait QPaladins: ToModel + CachingModel {
                         let current = serde_json::from_str::<FileData>(widget.value.as_str())?;
                         // Exclude files by default.
                         if current.path != default_path {
-                            let path = Path::new(&current.path);
-                            if path.exists() {
-                                fs::remove_file(path)?;
-                            }
+                            field_storage(meta, field)?.delete(current.path.as_str())?;
                             widget.value = String::new();
                         }
                     }
@@ -1193,10 +1775,8 @@ pub trait QPaladins: ToModel + CachingModel {
                         let current = serde_json::from_str::<ImageData>(widget.value.as_str())?;
                         // Exclude files by default.
                         if current.path != default_path {
-                            let path = Path::new(&current.path);
-                            if path.exists() {
-                                fs::remove_file(path)?;
-                            }
+                            let storage = field_storage(meta, field)?;
+                            storage.delete(current.path.as_str())?;
                             // Remove thumbnails.
                             let size_names: [&str; 4] = ["lg", "md", "sm", "xs"];
                             for size_name in size_names.iter() {
@@ -1208,10 +1788,7 @@ pub trait QPaladins: ToModel + CachingModel {
                                     _ => String::new(),
                                 };
                                 if !path.is_empty() {
-                                    let path = Path::new(path.as_str());
-                                    if path.exists() {
-                                        fs::remove_file(path)?;
-                                    }
+                                    storage.delete(path.as_str())?;
                                 }
                             }
                             widget.value = String::new();
@@ -1221,37 +1798,43 @@ pub trait QPaladins: ToModel + CachingModel {
                 }
             }
         }
-
-        // Enrich the widget map with values for dynamic widgets.
-        Self::vitaminize(
-            meta.project_name.as_str(),
-            meta.unique_project_key.as_str(),
-            meta.collection_name.as_str(),
-            &client_cache,
-            &mut final_map_widgets,
-        )?;
-
-        // Return result.
-        // -----------------------------------------------------------------------------------------
-        Ok(OutputDataForm::CheckModel((
-            !is_err_symptom,
-            meta.fields_name.clone(),
-            final_map_widgets,
-            final_doc,
-        )))
+        //
+        Ok(())
     }
 
     /// Save to database as a new document or update an existing document.
     /// ( Used in conjunction with the `check ()` method. )
+    /// Hint: For Models with the `is_versioned = true` parameter, the update is applied only if
+    /// the stored version matches the version of the instance, otherwise `OutputDataForm::Conflict`.
+    /// For Models with the `is_soft_delete = true` parameter, a soft-deleted document
+    /// is not updated ( `OutputDataForm::Conflict` ).
+    /// Not transactional ( driver 1.x has no sessions ) - the document and its files are written
+    /// by separate operations, writes to other Models are not rolled back.
     // *********************************************************************************************
+    #[cfg(feature = "sync")]
     fn save(
         &mut self,
         options_insert: Option<mongodb::options::InsertOneOptions>,
         options_update: Option<mongodb::options::UpdateOptions>,
-    ) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataForm, Error> {
+        self.save_by(None, options_insert, options_update)
+    }
+
+    /// The same as `save()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn save_by(
+        &mut self,
+        actor: Option<&str>,
+        options_insert: Option<mongodb::options::InsertOneOptions>,
+        options_update: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Lifecycle hook - normalization of values before the check.
+        self.pre_save(!self.get_hash().unwrap_or_default().is_empty())?;
         // Get checked data from the `check()` method.
         let verified_data: OutputDataForm = self.check()?;
-        let is_no_error: bool = verified_data.is_valid();
+        let mut is_no_error: bool = verified_data.is_valid();
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         // Get Model metadata.
@@ -1263,31 +1846,128 @@ pub trait QPaladins: ToModel + CachingModel {
             .database(meta.database_name.as_str())
             .collection(meta.collection_name.as_str());
 
+        // Optimistic concurrency - the document has been changed by another query.
+        let mut is_conflict: bool = false;
+
         // Save to database.
         // -----------------------------------------------------------------------------------------
         if is_no_error {
-            let final_doc = verified_data.doc();
-            if !is_update {
-                let result: mongodb::results::InsertOneResult =
-                    coll.insert_one(final_doc, options_insert)?;
-                self.set_hash(result.inserted_id.as_object_id().unwrap().to_hex());
+            let mut final_doc = verified_data.doc();
+            // Optimistic concurrency - the version of the loaded document and the new version.
+            let old_version: Option<i64> = self.get_version();
+            let new_version: i64 = if is_update {
+                old_version.unwrap_or_default() + 1
+            } else {
+                1_i64
+            };
+            if meta.is_versioned {
+                final_doc.insert("version", new_version);
+            }
+            // State of the document before saving ( for the audit trail ).
+            let audit_docs: Option<(
+                Option<mongodb::bson::document::Document>,
+                mongodb::bson::document::Document,
+            )> = if meta.is_audit_log {
+                let doc_from_db = if is_update {
+                    let hash: String = self.get_hash().unwrap_or_default();
+                    let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                    coll.find_one(
+                        meta.not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id})),
+                        None,
+                    )?
+                } else {
+                    None
+                };
+                Some((doc_from_db, final_doc.clone()))
+            } else {
+                None
+            };
+            let result: Result<(), mongodb::error::Error> = if !is_update {
+                coll.insert_one(final_doc.clone(), options_insert)
+                    .map(|result| {
+                        self.set_hash(result.inserted_id.as_object_id().unwrap().to_hex())
+                    })
             } else if !final_doc.is_empty() {
                 let hash: Option<String> = self.get_hash();
                 if hash.is_none() {
-                    Err(format!(
-                        "Model: `{}` > Field: `hash` : \
-                        An empty `hash` field is not allowed when updating.",
-                        meta.model_name
-                    ))?
+                    Err(Error::MissingHash {
+                        model: meta.model_name.clone(),
+                        method: "save()".to_string(),
+                    })?
                 }
                 let object_id: mongodb::bson::oid::ObjectId =
                     mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
-                let query: mongodb::bson::document::Document =
-                    mongodb::bson::doc! {"_id": object_id};
+                let mut query: mongodb::bson::document::Document = meta
+                    .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id}))
+                    .unwrap_or_default();
+                // The update is applied only if the stored version matches the version of the instance.
+                if meta.is_versioned {
+                    let old_version = match old_version {
+                        Some(old_version) => mongodb::bson::Bson::Int64(old_version),
+                        None => mongodb::bson::Bson::Null,
+                    };
+                    query.insert("version", old_version);
+                }
                 let update: mongodb::bson::document::Document = mongodb::bson::doc! {
-                    "$set": final_doc,
+                    "$set": final_doc.clone(),
                 };
-                coll.update_one(query, update, options_update)?;
+                coll.update_one(query, update, options_update)
+                    .map(|result| {
+                        is_conflict =
+                            (meta.is_versioned || meta.is_soft_delete) && result.matched_count == 0
+                    })
+            } else {
+                Ok(())
+            };
+            // Duplicate key - the unique value was saved by a concurrent query.
+            if let Err(err) = result {
+                let fields: Vec<String> = Self::duplicate_key_fields(&meta, &err);
+                if fields.is_empty() {
+                    Err(err)?
+                }
+                is_no_error = false;
+                for field_name in fields {
+                    if let Some(final_widget) = final_map_widgets.get_mut(&field_name) {
+                        final_widget.error =
+                            Self::accumula_err(&final_widget, &"Is not unique.".to_owned())?;
+                    }
+                }
+            } else if is_conflict {
+                is_no_error = false;
+                // The new files of the document will not be saved.
+                let hash: String = self.get_hash().unwrap_or_default();
+                let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                let stored_doc: Option<mongodb::bson::document::Document> =
+                    coll.find_one(mongodb::bson::doc! {"_id": object_id}, None)?;
+                Self::delete_unsaved_files(
+                    &final_doc,
+                    stored_doc.as_ref(),
+                    &meta,
+                    &mut final_map_widgets,
+                )?;
+                let final_widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else {
+                if meta.is_versioned {
+                    self.set_version(new_version);
+                    final_map_widgets
+                        .get_mut(&"version".to_owned())
+                        .unwrap()
+                        .value = new_version.to_string();
+                }
+                if let Some((doc_from_db, audit_doc)) = audit_docs {
+                    AuditRecord::write(
+                        &meta,
+                        &client_cache,
+                        self.get_hash().unwrap_or_default().as_str(),
+                        if is_update { "update" } else { "create" },
+                        doc_from_db.as_ref(),
+                        Some(&audit_doc),
+                        actor,
+                    )?;
+                }
+                self.post_save(&final_doc, is_update)?;
             }
         }
 
@@ -1300,6 +1980,13 @@ pub trait QPaladins: ToModel + CachingModel {
 
         // Return result.
         // -----------------------------------------------------------------------------------------
+        if is_conflict {
+            return Ok(OutputDataForm::Conflict((
+                meta.fields_name.clone(),
+                final_map_widgets,
+                hash,
+            )));
+        }
         Ok(OutputDataForm::Save((
             is_no_error,
             meta.fields_name.clone(),
@@ -1308,7 +1995,527 @@ pub trait QPaladins: ToModel + CachingModel {
         )))
     }
 
+    /// Partial update of a document - only the passed fields are checked and updated.
+    /// `values` - Json object <field name, value>, values in the same format as in the Model.
+    /// The `updated_at` field is updated. The widgets contain only the values of the passed fields.
+    /// Hint: To change the password, use the `update_password` method.
+    /// For Models with the `is_versioned = true` parameter, the update is applied only if
+    /// the version has not changed since the document was read, otherwise `OutputDataForm::Conflict`.
+    /// For Models with the `is_soft_delete = true` parameter, a soft-deleted document is not found
+    /// ( deleted after reading - `OutputDataForm::Conflict` ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let values = serde_json::json!({"username": "Rust", "age": 42});
+    /// let output_data = UserProfile::patch(hash, values, None)?;
+    /// if !output_data.is_valid() {
+    ///     println!("{}", output_data.html());
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn patch(
+        hash: &str,
+        values: serde_json::value::Value,
+        options: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error>
+    where
+        Self: Sized + serde::de::DeserializeOwned,
+    {
+        Self::patch_by(None, hash, values, options)
+    }
+
+    /// The same as `patch()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn patch_by(
+        actor: Option<&str>,
+        hash: &str,
+        values: serde_json::value::Value,
+        options: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error>
+    where
+        Self: Sized + serde::de::DeserializeOwned,
+    {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        // Get Model metadata.
+        let meta: &Meta = &form_cache.meta;
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get the fields being updated.
+        let values = match values {
+            serde_json::value::Value::Object(values) => values,
+            _ => Err(Error::InvalidArgument {
+                model: meta.model_name.clone(),
+                method: "patch()".to_string(),
+                message: "The values must be a json object.".to_string(),
+            })?,
+        };
+        let mut fields_name: Vec<String> = vec!["hash".to_string()];
+        for field_name in values.keys() {
+            if field_name == "hash"
+                || (field_name == "version" && meta.is_versioned)
+                || !meta.fields_name.contains(field_name)
+            {
+                Err(Error::MissingField {
+                    model: meta.model_name.clone(),
+                    field: field_name.clone(),
+                    method: "patch()".to_string(),
+                })?
+            }
+            let widget_type: &str = form_cache.map_widgets[field_name].widget.as_str();
+            if widget_type == "inputPassword" {
+                Err(Error::UnsupportedWidget {
+                    model: meta.model_name.clone(),
+                    field: field_name.clone(),
+                    widget: widget_type.to_string(),
+                })?
+            }
+            fields_name.push(field_name.clone());
+        }
+        // Get the current state of the document from database.
+        let object_id = mongodb::bson::oid::ObjectId::with_string(hash)?;
+        let doc_from_db: mongodb::bson::document::Document = coll
+            .find_one(
+                meta.not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()})),
+                None,
+            )?
+            .ok_or_else(|| Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "patch()".to_string(),
+            })?;
+        // Get an instance of the Model with the new values of the fields.
+        let mut instance: Self = {
+            let instance: Self = OutputDataOne::Doc((
+                Some(doc_from_db.clone()),
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                String::new(),
+                meta.map_timezones.clone(),
+            ))
+            .model::<Self>()?;
+            let mut model_json: serde_json::value::Value = instance.self_to_json()?;
+            for (field_name, value) in values {
+                model_json[field_name.as_str()] = value;
+            }
+            serde_json::from_value::<Self>(model_json)?
+        };
+        // Lifecycle hook - normalization of values before the check.
+        instance.pre_save(true)?;
+        // Check only the fields being updated.
+        let mut patch_cache: FormCache = form_cache.clone();
+        patch_cache.meta.fields_name = fields_name;
+        let verified_data: OutputDataForm =
+            instance.check_with_cache(&patch_cache, &client_cache)?;
+        let mut is_no_error: bool = verified_data.is_valid();
+        let mut final_map_widgets: std::collections::HashMap<String, Widget> = verified_data.wig();
+        // Optimistic concurrency - the document has been changed by another query.
+        let mut is_conflict: bool = false;
+
+        // Update the document.
+        // -----------------------------------------------------------------------------------------
+        if is_no_error {
+            let mut query: mongodb::bson::document::Document = meta
+                .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()}))
+                .unwrap_or_default();
+            let mut update: mongodb::bson::document::Document = mongodb::bson::doc! {
+                "$set": verified_data.doc(),
+            };
+            // The update is applied only if the stored version has not changed since it was read.
+            if meta.is_versioned {
+                let version = doc_from_db
+                    .get("version")
+                    .cloned()
+                    .unwrap_or(mongodb::bson::Bson::Null);
+                query.insert("version", version);
+                update.insert("$inc", mongodb::bson::doc! {"version": 1_i64});
+            }
+            let result = coll.update_one(query, update, options);
+            is_conflict = (meta.is_versioned || meta.is_soft_delete)
+                && matches!(result.as_ref(), Ok(result) if result.matched_count == 0);
+            if let Err(err) = result {
+                // Duplicate key - the unique value was saved by a concurrent query.
+                let fields: Vec<String> = Self::duplicate_key_fields(meta, &err);
+                if fields.is_empty() {
+                    Err(err)?
+                }
+                is_no_error = false;
+                Self::set_not_unique(&mut final_map_widgets, fields)?;
+            } else if is_conflict {
+                is_no_error = false;
+                // The new files of the document will not be saved.
+                let stored_doc: Option<mongodb::bson::document::Document> =
+                    coll.find_one(mongodb::bson::doc! {"_id": object_id}, None)?;
+                Self::delete_unsaved_files(
+                    &verified_data.doc(),
+                    stored_doc.as_ref(),
+                    meta,
+                    &mut final_map_widgets,
+                )?;
+                let final_widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else {
+                AuditRecord::write(
+                    meta,
+                    &client_cache,
+                    hash,
+                    "update",
+                    Some(&doc_from_db),
+                    Some(&verified_data.doc()),
+                    actor,
+                )?;
+                instance.post_save(&verified_data.doc(), true)?;
+            }
+        }
+        final_map_widgets.get_mut("hash").unwrap().value = hash.to_string();
+
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        if is_conflict {
+            return Ok(OutputDataForm::Conflict((
+                meta.fields_name.clone(),
+                final_map_widgets,
+                hash.to_string(),
+            )));
+        }
+        Ok(OutputDataForm::Save((
+            is_no_error,
+            meta.fields_name.clone(),
+            final_map_widgets,
+            hash.to_string(),
+        )))
+    }
+
+    /// Save several Models to database - as new documents or updates of existing documents.
+    /// Each Model is checked by the `check()` method, the result is returned for each Model.
+    /// New documents are written by one `insert_many` query.
+    /// Hint: `all_or_nothing` - If at least one Model contains errors, nothing is saved.
+    /// If the write fails, the new documents are removed and the updated documents are restored
+    /// ( the driver 1.x does not support transactions - the rollback is not atomic ).
+    /// For Models with the `is_versioned = true` parameter, a version mismatch returns
+    /// `OutputDataForm::Conflict` for the Model ( also for a soft-deleted document ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut user_profiles = vec![UserProfile {...}, UserProfile {...}];
+    /// let results = UserProfile::save_many(&mut user_profiles, true)?;
+    /// for output_data in results {
+    ///     if !output_data.is_valid() {
+    ///         println!("{}", output_data.html());
+    ///     }
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn save_many(models: &mut [Self], all_or_nothing: bool) -> Result<Vec<OutputDataForm>, Error>
+    where
+        Self: Sized,
+    {
+        Self::save_many_by(None, models, all_or_nothing)
+    }
+
+    /// The same as `save_many()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn save_many_by(
+        actor: Option<&str>,
+        models: &mut [Self],
+        all_or_nothing: bool,
+    ) -> Result<Vec<OutputDataForm>, Error>
+    where
+        Self: Sized,
+    {
+        // Get checked data from the `check()` method.
+        let mut verified_data_list: Vec<OutputDataForm> = Vec::with_capacity(models.len());
+        for model in models.iter_mut() {
+            // Lifecycle hook - normalization of values before the check.
+            model.pre_save(!model.get_hash().unwrap_or_default().is_empty())?;
+            verified_data_list.push(model.check()?);
+        }
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let mut statuses: Vec<bool> = verified_data_list
+            .iter()
+            .map(|verified_data| verified_data.is_valid())
+            .collect();
+        let mut map_widgets_list: Vec<std::collections::HashMap<String, Widget>> =
+            verified_data_list
+                .iter()
+                .map(|verified_data| verified_data.wig())
+                .collect();
+        // Hash-lines of new documents.
+        let mut new_hashes: Vec<Option<String>> = vec![None; models.len()];
+        let mut is_rollback: bool = all_or_nothing && statuses.contains(&false);
+        // Optimistic concurrency - the documents changed by another query.
+        let mut conflicts: Vec<bool> = vec![false; models.len()];
+
+        // Save to database.
+        // -----------------------------------------------------------------------------------------
+        if !is_rollback {
+            // Queues of new and updated documents.
+            let mut insert_queue: Vec<usize> = Vec::new();
+            let mut insert_docs: Vec<mongodb::bson::document::Document> = Vec::new();
+            let mut update_queue: Vec<(usize, mongodb::bson::oid::ObjectId)> = Vec::new();
+            for (idx, model) in models.iter().enumerate() {
+                if !statuses[idx] {
+                    continue;
+                }
+                let mut final_doc = verified_data_list[idx].doc();
+                let hash = model.get_hash().unwrap_or_default();
+                if hash.is_empty() {
+                    let object_id = mongodb::bson::oid::ObjectId::new();
+                    new_hashes[idx] = Some(object_id.to_hex());
+                    final_doc.insert("_id", object_id);
+                    if meta.is_versioned {
+                        final_doc.insert("version", 1_i64);
+                    }
+                    insert_queue.push(idx);
+                    insert_docs.push(final_doc);
+                } else if !final_doc.is_empty() {
+                    let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                    update_queue.push((idx, object_id));
+                }
+            }
+            // The current state of the updated documents ( for rollback and the audit trail ).
+            let originals: Vec<mongodb::bson::document::Document> =
+                if (all_or_nothing || meta.is_audit_log) && !update_queue.is_empty() {
+                    let object_ids: Vec<mongodb::bson::oid::ObjectId> = update_queue
+                        .iter()
+                        .map(|(_, object_id)| object_id.clone())
+                        .collect();
+                    let filter = meta
+                        .not_deleted_filter(Some(mongodb::bson::doc! {"_id": {"$in": object_ids}}));
+                    coll.find(filter, None)?
+                        .collect::<mongodb::error::Result<_>>()?
+                } else {
+                    Vec::new()
+                };
+            // New documents.
+            if !insert_docs.is_empty() {
+                let options = mongodb::options::InsertManyOptions::builder()
+                    .ordered(Some(all_or_nothing))
+                    .build();
+                if let Err(err) = coll.insert_many(insert_docs, Some(options)) {
+                    let failures = Self::bulk_duplicate_key_fields(&meta, &err);
+                    if failures.is_empty() {
+                        if all_or_nothing {
+                            Self::rollback_many(&coll, &new_hashes, &[])?;
+                        }
+                        Err(err)?
+                    }
+                    for (queue_idx, fields) in failures {
+                        let idx = insert_queue[queue_idx];
+                        statuses[idx] = false;
+                        new_hashes[idx] = None;
+                        Self::set_not_unique(&mut map_widgets_list[idx], fields)?;
+                    }
+                    is_rollback = all_or_nothing;
+                }
+            }
+            // Updated documents.
+            // ( the driver 1.x does not support bulk write - one query per document )
+            let mut updated_originals: Vec<mongodb::bson::document::Document> = Vec::new();
+            for (idx, object_id) in update_queue {
+                if is_rollback {
+                    break;
+                }
+                let mut query = meta
+                    .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()}))
+                    .unwrap_or_default();
+                let mut final_doc = verified_data_list[idx].doc();
+                if meta.is_versioned {
+                    let old_version: Option<i64> = models[idx].get_version();
+                    let old_version = match old_version {
+                        Some(old_version) => mongodb::bson::Bson::Int64(old_version),
+                        None => mongodb::bson::Bson::Null,
+                    };
+                    query.insert("version", old_version);
+                    final_doc.insert("version", models[idx].get_version().unwrap_or_default() + 1);
+                }
+                let update = mongodb::bson::doc! {"$set": final_doc};
+                match coll.update_one(query, update, None) {
+                    Ok(result)
+                        if (meta.is_versioned || meta.is_soft_delete)
+                            && result.matched_count == 0 =>
+                    {
+                        statuses[idx] = false;
+                        conflicts[idx] = true;
+                        is_rollback = all_or_nothing;
+                        // The new files of the document will not be saved.
+                        let stored_doc: Option<mongodb::bson::document::Document> =
+                            coll.find_one(mongodb::bson::doc! {"_id": object_id}, None)?;
+                        Self::delete_unsaved_files(
+                            &verified_data_list[idx].doc(),
+                            stored_doc.as_ref(),
+                            &meta,
+                            &mut map_widgets_list[idx],
+                        )?;
+                    }
+                    Ok(_) => updated_originals.extend(
+                        originals
+                            .iter()
+                            .find(|doc| doc.get_object_id("_id").ok() == Some(&object_id))
+                            .cloned(),
+                    ),
+                    Err(err) => {
+                        let fields: Vec<String> = Self::duplicate_key_fields(&meta, &err);
+                        if fields.is_empty() {
+                            if all_or_nothing {
+                                Self::rollback_many(&coll, &new_hashes, &updated_originals)?;
+                            }
+                            Err(err)?
+                        }
+                        statuses[idx] = false;
+                        Self::set_not_unique(&mut map_widgets_list[idx], fields)?;
+                        is_rollback = all_or_nothing;
+                    }
+                }
+            }
+            if is_rollback {
+                Self::rollback_many(&coll, &new_hashes, &updated_originals)?;
+            }
+            // Audit trail.
+            if !is_rollback && meta.is_audit_log {
+                for (idx, model) in models.iter().enumerate() {
+                    if !statuses[idx] {
+                        continue;
+                    }
+                    let (hash, action) = match new_hashes[idx].as_ref() {
+                        Some(hash) => (hash.clone(), "create"),
+                        None => (model.get_hash().unwrap_or_default(), "update"),
+                    };
+                    let doc_from_db = originals.iter().find(|doc| {
+                        doc.get_object_id("_id").map(|item| item.to_hex()).ok()
+                            == Some(hash.clone())
+                    });
+                    AuditRecord::write(
+                        &meta,
+                        &client_cache,
+                        hash.as_str(),
+                        action,
+                        doc_from_db,
+                        Some(&verified_data_list[idx].doc()),
+                        actor,
+                    )?;
+                }
+            }
+        }
+
+        // Return results.
+        // -----------------------------------------------------------------------------------------
+        let mut results: Vec<OutputDataForm> = Vec::with_capacity(models.len());
+        for (idx, model) in models.iter_mut().enumerate() {
+            let is_update: bool = new_hashes[idx].is_none();
+            let final_map_widgets = &mut map_widgets_list[idx];
+            let final_widget: &mut Widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+            if conflicts[idx] {
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else if is_rollback {
+                if statuses[idx] {
+                    statuses[idx] = false;
+                    final_widget.common_msg =
+                        "It was not saved - other models contain errors.".to_string();
+                }
+            } else if statuses[idx] && meta.is_versioned {
+                let new_version: i64 = if new_hashes[idx].is_some() {
+                    1_i64
+                } else {
+                    model.get_version().unwrap_or_default() + 1
+                };
+                model.set_version(new_version);
+                final_map_widgets
+                    .get_mut(&"version".to_owned())
+                    .unwrap()
+                    .value = new_version.to_string();
+            }
+            if !is_rollback {
+                if let Some(hash) = new_hashes[idx].take() {
+                    model.set_hash(hash);
+                }
+                if statuses[idx] {
+                    model.post_save(&verified_data_list[idx].doc(), is_update)?;
+                }
+            }
+            let hash = model.get_hash().unwrap_or_default();
+            if !hash.is_empty() {
+                final_map_widgets.get_mut(&"hash".to_owned()).unwrap().value = hash.clone();
+            }
+            if conflicts[idx] {
+                results.push(OutputDataForm::Conflict((
+                    meta.fields_name.clone(),
+                    map_widgets_list[idx].clone(),
+                    hash,
+                )));
+                continue;
+            }
+            results.push(OutputDataForm::Save((
+                statuses[idx],
+                meta.fields_name.clone(),
+                map_widgets_list[idx].clone(),
+                hash,
+            )));
+        }
+        Ok(results)
+    }
+
+    /// Rollback of `save_many` - remove new documents and restore updated documents.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn rollback_many(
+        coll: &mongodb::sync::Collection,
+        new_hashes: &[Option<String>],
+        originals: &[mongodb::bson::document::Document],
+    ) -> Result<(), Error> {
+        let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = Vec::new();
+        for hash in new_hashes.iter().flatten() {
+            object_ids.push(mongodb::bson::oid::ObjectId::with_string(hash.as_str())?);
+        }
+        if !object_ids.is_empty() {
+            coll.delete_many(mongodb::bson::doc! {"_id": {"$in": object_ids}}, None)?;
+        }
+        for original in originals {
+            let query = mongodb::bson::doc! {"_id": original.get_object_id("_id")?.clone()};
+            coll.replace_one(query, original.clone(), None)?;
+        }
+        Ok(())
+    }
+
+    /// Add the error `Is not unique.` to the widgets of the fields of a unique index.
+    // *********************************************************************************************
+    fn set_not_unique(
+        map_widgets: &mut std::collections::HashMap<String, Widget>,
+        fields: Vec<String>,
+    ) -> Result<(), Error> {
+        for field_name in fields {
+            if let Some(final_widget) = map_widgets.get_mut(&field_name) {
+                final_widget.error =
+                    Self::accumula_err(&final_widget, &"Is not unique.".to_owned())?;
+            }
+        }
+        Ok(())
+    }
+
     /// Remove document from collection.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`
+    /// ( files and referring documents do not change, see `restore()` and `purge()` ).
+    /// Not transactional ( driver 1.x has no sessions ) - files are removed only after
+    /// the document has been deleted, so a failure leaves orphaned files, never missing ones.
     // *********************************************************************************************
     ///
     /// # Example:
@@ -1320,14 +2527,31 @@ pub trait QPaladins: ToModel + CachingModel {
     /// }
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn delete(
         &self,
         options: Option<mongodb::options::DeleteOptions>,
-    ) -> Result<OutputDataForm, Box<dyn std::error::Error>> {
+    ) -> Result<OutputDataForm, Error> {
+        self.delete_by(None, options)
+    }
+
+    /// The same as `delete()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn delete_by(
+        &self,
+        actor: Option<&str>,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         // Get Model metadata.
         let meta: Meta = form_cache.meta;
+        // Remove the document from the collection.
+        if !meta.is_soft_delete {
+            return self.purge_by(actor, options);
+        }
         // Get permission to delete the document.
         let is_permission_delete: bool = meta.is_del_docs;
         // Error message for the client.
@@ -1346,103 +2570,457 @@ pub trait QPaladins: ToModel + CachingModel {
             // Get Model hash  for ObjectId.
             let hash: Option<String> = self.get_hash();
             if hash.is_none() {
-                Err(format!(
-                    "Model: `{}` > Field: `hash` : \
-                        An empty `hash` field is not allowed when deleting.",
-                    meta.model_name
-                ))?
+                Err(Error::MissingHash {
+                    model: meta.model_name.clone(),
+                    method: "delete()".to_string(),
+                })?
             }
             let object_id: mongodb::bson::oid::ObjectId =
                 mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
             // Create query.
             let query: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
-            // Removeve files
-            if let Some(document) = coll.find_one(query.clone(), None)? {
-                for (field_name, widget_name) in meta.map_widget_type.iter() {
-                    if !document.is_null(field_name) {
-                        match widget_name.as_str() {
-                            "inputFile" => {
-                                if let Some(info_file) =
-                                    document.get(field_name).unwrap().as_document()
-                                {
-                                    let path = info_file.get_str("path")?;
-                                    let default_value =
-                                        meta.map_default_values.get(field_name).unwrap().1.as_str();
-                                    let default_path = if !default_value.is_empty() {
-                                        serde_json::from_str::<FileData>(default_value)?.path
-                                    } else {
-                                        String::new()
-                                    };
-                                    if path != default_path {
-                                        let path = Path::new(path);
-                                        if path.exists() {
-                                            fs::remove_file(path)?;
-                                        }
-                                    }
-                                } else {
-                                    Err(format!(
-                                        "Model: `{}` > Field: `{}` > \
-                                         Method: `delete()` : Document (info file) not found.",
-                                        meta.model_name, field_name
-                                    ))?
-                                }
-                            }
-                            "inputImage" => {
-                                if let Some(info_file) =
-                                    document.get(field_name).unwrap().as_document()
-                                {
-                                    let path = info_file.get_str("path")?;
-                                    let default_value =
-                                        meta.map_default_values.get(field_name).unwrap().1.as_str();
-                                    let default_path = if !default_value.is_empty() {
-                                        serde_json::from_str::<ImageData>(default_value)?.path
-                                    } else {
-                                        String::new()
-                                    };
-                                    if path != default_path {
-                                        let path = Path::new(path);
-                                        if path.exists() {
-                                            fs::remove_file(path)?;
-                                        }
-                                        // Remove thumbnails.
-                                        let size_names: [&str; 4] = ["lg", "md", "sm", "xs"];
-                                        for size_name in size_names.iter() {
-                                            let key_name = format!("path_{}", size_name);
-                                            let path = info_file.get_str(key_name.as_str())?;
-                                            if !path.is_empty() {
-                                                let path = Path::new(path);
-                                                if path.exists() {
-                                                    fs::remove_file(path)?;
-                                                }
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    Err(format!(
-                                        "Model: `{}` > Field: `{}` > \
-                                         Method: `delete()` : Document (info file) not found.",
-                                        meta.model_name, field_name
-                                    ))?
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(query.clone(), None)?;
+            if document.is_none() {
+                Err(Error::DocumentNotFound {
+                    model: meta.model_name.clone(),
+                    method: "delete()".to_string(),
+                })?
+            }
+            // Mark the document as deleted.
+            // ( The time of the first deletion is kept )
+            let bson_null = &mongodb::bson::Bson::Null;
+            let document: mongodb::bson::document::Document = document.unwrap();
+            // Lifecycle hook - the deletion can be forbidden.
+            let err_msg: String = self.pre_delete(&document)?;
+            if !err_msg.is_empty() {
+                return Ok(OutputDataForm::Delete((false, err_msg)));
+            }
+            if document.get("deleted_at").unwrap_or(bson_null) == bson_null {
+                let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+                let update = mongodb::bson::doc! {
+                    "$set": {"deleted_at": mongodb::bson::Bson::DateTime(dt)}
+                };
+                coll.update_one(query, update, None)?;
+                AuditRecord::write(
+                    &meta,
+                    &client_cache,
+                    self.get_hash().unwrap_or_default().as_str(),
+                    "delete",
+                    Some(&document),
+                    Some(&document),
+                    actor,
+                )?;
+                self.post_delete(&document)?;
+            }
+            true
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Restore the soft-deleted document.
+    /// ( For Models with `is_soft_delete = true` )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let user_profile = UserProfile::find_with_deleted(Some(filter), None)?
+    ///     .models::<UserProfile>()?
+    ///     .remove(0);
+    /// user_profile.restore()?;
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn restore(&self) -> Result<(), Error> {
+        self.restore_by(None)
+    }
+
+    /// The same as `restore()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn restore_by(&self, actor: Option<&str>) -> Result<(), Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        if !meta.is_soft_delete {
+            Err(Error::PermissionDenied {
+                model: meta.model_name.clone(),
+                operation: "restore - the Model does not use soft delete".to_string(),
+            })?
+        }
+        // Access collection.
+        let coll: mongodb::sync::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get Model hash  for ObjectId.
+        let hash: Option<String> = self.get_hash();
+        if hash.is_none() {
+            Err(Error::MissingHash {
+                model: meta.model_name.clone(),
+                method: "restore()".to_string(),
+            })?
+        }
+        let object_id: mongodb::bson::oid::ObjectId =
+            mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+        // Create query.
+        let query: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
+        // Execute query.
+        let update = mongodb::bson::doc! {"$unset": {"deleted_at": ""}};
+        if coll.update_one(query.clone(), update, None)?.matched_count == 0 {
+            Err(Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "restore()".to_string(),
+            })?
+        }
+        if meta.is_audit_log {
+            let document: Option<mongodb::bson::document::Document> = coll.find_one(query, None)?;
+            AuditRecord::write(
+                &meta,
+                &client_cache,
+                self.get_hash().unwrap_or_default().as_str(),
+                "restore",
+                document.as_ref(),
+                document.as_ref(),
+                actor,
+            )?;
+        }
+        //
+        Ok(())
+    }
+
+    /// Remove document from collection and delete its files.
+    /// ( For Models with `is_soft_delete = true` - including soft-deleted documents )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile.purge(None)?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    fn purge(
+        &self,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        self.purge_by(None, options)
+    }
+
+    /// The same as `purge()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn purge_by(
+        &self,
+        actor: Option<&str>,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let mut err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::sync::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Get Model hash  for ObjectId.
+            let hash: Option<String> = self.get_hash();
+            if hash.is_none() {
+                Err(Error::MissingHash {
+                    model: meta.model_name.clone(),
+                    method: "purge()".to_string(),
+                })?
+            }
+            let object_id: mongodb::bson::oid::ObjectId =
+                mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+            // Create query.
+            let query: mongodb::bson::document::Document =
+                mongodb::bson::doc! {"_id": object_id.clone()};
+            // Get the document with information about files.
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(query.clone(), None)?;
+            if document.is_none() {
+                Err(Error::DocumentNotFound {
+                    model: meta.model_name.clone(),
+                    method: "purge()".to_string(),
+                })?
+            }
+            // Get the relations of Models that refer to the current Model.
+            let relations: Vec<ModelRelation> = Self::db_get_relations(&meta, &client_cache)?;
+            // Lifecycle hook - the deletion can be forbidden.
+            err_msg = self.pre_delete(document.as_ref().unwrap())?;
+            // Check whether deletion is forbidden by referring documents.
+            if err_msg.is_empty() {
+                err_msg = Self::check_restrict(&relations, &object_id)?;
+            }
+            if err_msg.is_empty() {
+                // Execute query.
+                let is_deleted: bool = coll.delete_one(query, options).is_ok();
+                // Update referring documents and remove files
+                // only after the document has been deleted.
+                // ( MongoDB 1.x driver does not support transactions )
+                if is_deleted {
+                    Self::apply_on_delete(&relations, &object_id, actor)?;
+                    AuditRecord::write(
+                        &meta,
+                        &client_cache,
+                        object_id.to_hex().as_str(),
+                        "delete",
+                        document.as_ref(),
+                        None,
+                        actor,
+                    )?;
+                    self.post_delete(document.as_ref().unwrap())?;
+                    Self::delete_files_from_doc(&document.unwrap(), &meta)?;
                 }
+                is_deleted
             } else {
-                Err(format!(
-                    "Model: `{}` > Method: `delete()` : Document not found.",
-                    meta.model_name
-                ))?
+                false
             }
-            // Execute query.
-            coll.delete_one(query, options).is_ok()
         } else {
             false
         };
         Ok(OutputDataForm::Delete((result_bool, err_msg)))
     }
 
+    /// Get the relations of Models that refer to the current Model.
+    /// ( Relations are registered in the technical database during migration )
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn db_get_relations(
+        meta: &Meta,
+        client: &mongodb::sync::Client,
+    ) -> Result<Vec<ModelRelation>, Error> {
+        // Init the name of the project's technical database.
+        let db_mango_tech: String = format!(
+            "mango_tech__{}__{}",
+            meta.project_name, meta.unique_project_key
+        );
+        let filter = mongodb::bson::doc! {
+            "ref_database": &meta.database_name,
+            "ref_collection": &meta.collection_name
+        };
+        let cursor: mongodb::sync::Cursor = client
+            .database(&db_mango_tech)
+            .collection("relations")
+            .find(filter, None)?;
+        let mut relations: Vec<ModelRelation> = Vec::new();
+        for result in cursor {
+            relations.push(mongodb::bson::de::from_document(result?)?);
+        }
+        //
+        Ok(relations)
+    }
+
+    /// Check whether deletion is forbidden by referring documents.
+    /// ( For relations with `on_delete = "restrict"` )
+    /// Hint: For the `cascade` policy, the Model of referring documents must be registered.
+    // *********************************************************************************************
+    ///
+    /// Returns an error message for the client, or an empty string if deletion is allowed.
+    ///
+    #[cfg(feature = "sync")]
+    fn check_restrict(
+        relations: &[ModelRelation],
+        object_id: &mongodb::bson::oid::ObjectId,
+    ) -> Result<String, Error> {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        for relation in relations.iter() {
+            if relation.on_delete != "restrict" {
+                if relation.widget == "selectRef" && relation.on_delete == "cascade" {
+                    Meta::delete_fn_of(
+                        &relation.model_name,
+                        &relation.database,
+                        &relation.collection,
+                    )?;
+                }
+                continue;
+            }
+            let client: &mongodb::sync::Client = client_store
+                .get(&relation.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: relation.model_name.clone(),
+                    client: relation.db_client_name.clone(),
+                })?;
+            let coll: mongodb::sync::Collection = client
+                .database(&relation.database)
+                .collection(&relation.collection);
+            let filter = mongodb::bson::doc! { relation.field.as_str(): object_id.clone() };
+            if coll.count_documents(filter, None)? > 0 {
+                return Ok(format!(
+                    "It is forbidden to perform delete - \
+                     the document is referenced by the Model `{}`.",
+                    relation.model_name
+                ));
+            }
+        }
+        //
+        Ok(String::new())
+    }
+
+    /// Apply the `cascade` and `set_null` policies to referring documents.
+    /// ( For `selectRefMult`, the identifier is removed from the array )
+    /// Hint: With `cascade`, each referring document is deleted by the `delete()` method
+    /// of its Model - hooks, files, soft delete, audit trail and nested relations are applied.
+    // *********************************************************************************************
+    #[cfg(feature = "sync")]
+    fn apply_on_delete(
+        relations: &[ModelRelation],
+        object_id: &mongodb::bson::oid::ObjectId,
+        actor: Option<&str>,
+    ) -> Result<(), Error> {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        for relation in relations.iter() {
+            if relation.on_delete == "restrict" {
+                continue;
+            }
+            let client: &mongodb::sync::Client = client_store
+                .get(&relation.db_client_name)
+                .ok_or_else(|| Error::ClientNotFound {
+                    model: relation.model_name.clone(),
+                    client: relation.db_client_name.clone(),
+                })?;
+            let coll: mongodb::sync::Collection = client
+                .database(&relation.database)
+                .collection(&relation.collection);
+            let field_name: &str = relation.field.as_str();
+            let filter = mongodb::bson::doc! { field_name: object_id.clone() };
+            match (relation.widget.as_str(), relation.on_delete.as_str()) {
+                ("selectRef", "cascade") => {
+                    let delete_fn: DeleteFn = Meta::delete_fn_of(
+                        &relation.model_name,
+                        &relation.database,
+                        &relation.collection,
+                    )?;
+                    let docs: Vec<mongodb::bson::document::Document> =
+                        coll.find(filter, None)?
+                            .collect::<mongodb::error::Result<_>>()?;
+                    for doc in docs {
+                        match delete_fn(doc, actor) {
+                            Ok(output_data) if !output_data.is_valid() => {
+                                Err(Error::PermissionDenied {
+                                    model: relation.model_name.clone(),
+                                    operation: format!(
+                                        "cascade delete - {}",
+                                        output_data.err_msg()
+                                    ),
+                                })?
+                            }
+                            // The document has already been deleted by a nested relation.
+                            Ok(_) | Err(Error::DocumentNotFound { .. }) => {}
+                            Err(err) => Err(err)?,
+                        }
+                    }
+                }
+                ("selectRef", "set_null") => {
+                    let update = mongodb::bson::doc! {
+                        "$set": { field_name: mongodb::bson::Bson::Null }
+                    };
+                    coll.update_many(filter, update, None)?;
+                }
+                ("selectRefMult", _) => {
+                    let update = mongodb::bson::doc! {
+                        "$pull": { field_name: object_id.clone() }
+                    };
+                    coll.update_many(filter, update, None)?;
+                }
+                _ => Err(Error::UnsupportedWidget {
+                    model: relation.model_name.clone(),
+                    field: relation.field.clone(),
+                    widget: relation.widget.clone(),
+                })?,
+            }
+        }
+        //
+        Ok(())
+    }
+
+    /// Remove files of the `inputFile` and `inputImage` widgets that were not saved
+    /// ( for example, on a version conflict ).
+    /// ( Files of the stored document and files by default are not deleted, the widgets get
+    /// the values of the stored document )
+    // *********************************************************************************************
+    fn delete_unsaved_files(
+        new_doc: &mongodb::bson::document::Document,
+        stored_doc: Option<&mongodb::bson::document::Document>,
+        meta: &Meta,
+        final_map_widgets: &mut std::collections::HashMap<String, Widget>,
+    ) -> Result<(), Error> {
+        for (field_name, widget_name) in meta.map_widget_type.iter() {
+            let is_image = match widget_name.as_str() {
+                "inputFile" => false,
+                "inputImage" => true,
+                _ => continue,
+            };
+            if let Some(info_file) = new_doc.get(field_name).and_then(|item| item.as_document()) {
+                let stored_path: Option<&str> = stored_doc
+                    .and_then(|doc| doc.get_document(field_name).ok())
+                    .and_then(|doc| doc.get_str("path").ok());
+                if stored_path == info_file.get_str("path").ok() {
+                    continue;
+                }
+                let default_value = meta.map_default_values.get(field_name).unwrap().1.as_str();
+                let storage = field_storage(meta, field_name)?;
+                Self::remove_files(info_file, default_value, is_image, storage.as_ref())?;
+                if let Some(final_widget) = final_map_widgets.get_mut(field_name) {
+                    final_widget.value = Self::file_info_from_doc(stored_doc, field_name)?;
+                }
+            }
+        }
+        //
+        Ok(())
+    }
+
+    /// Remove files of the `inputFile` and `inputImage` widgets for the document being deleted.
+    /// ( Files are removed from the storage of the field )
+    // *********************************************************************************************
+    fn delete_files_from_doc(
+        document: &mongodb::bson::document::Document,
+        meta: &Meta,
+    ) -> Result<(), Error> {
+        for (field_name, widget_name) in meta.map_widget_type.iter() {
+            if !document.is_null(field_name) {
+                let is_image = match widget_name.as_str() {
+                    "inputFile" => false,
+                    "inputImage" => true,
+                    _ => continue,
+                };
+                if let Some(info_file) = document.get(field_name).unwrap().as_document() {
+                    let default_value = meta.map_default_values.get(field_name).unwrap().1.as_str();
+                    let storage = field_storage(meta, field_name)?;
+                    Self::remove_files(info_file, default_value, is_image, storage.as_ref())?;
+                } else {
+                    Err(Error::MissingField {
+                        model: meta.model_name.clone(),
+                        field: field_name.clone(),
+                        method: "delete()".to_string(),
+                    })?
+                }
+            }
+        }
+        //
+        Ok(())
+    }
+
     // Operations with passwords.
     // *********************************************************************************************
     /// Generate password hash and add to result document.
@@ -1456,7 +3034,7 @@ pub trait QPaladins: ToModel + CachingModel {
     /// println!("{}", user_profile.create_password_hash(field_value)?);
     /// ```
     ///
-    fn create_password_hash(field_value: &str) -> Result<String, Box<dyn std::error::Error>> {
+    fn create_password_hash(field_value: &str) -> Result<String, Error> {
         const CHARSET: &[u8] =
             b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789@#$%^&+=*!~)(";
         const SALT_LEN: usize = 12;
@@ -1485,11 +3063,12 @@ pub trait QPaladins: ToModel + CachingModel {
     /// assert!(user_profile.create_password_hash(password, None)?);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn verify_password(
         &self,
         password: &str,
         options: Option<mongodb::options::FindOneOptions>,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, Error> {
         // Get cached Model data.
         let (form_cache, client_cache) = Self::get_cache_data_for_query()?;
         // Get Model metadata.
@@ -1501,11 +3080,10 @@ pub trait QPaladins: ToModel + CachingModel {
         // Get hash-line of Model.
         let hash: Option<String> = self.get_hash();
         if hash.is_none() {
-            Err(format!(
-                "Model: `{}` > Method: `verify_password` : \
-                An empty `hash` field is not allowed when updating.",
-                meta.model_name
-            ))?
+            Err(Error::MissingHash {
+                model: meta.model_name.clone(),
+                method: "verify_password()".to_string(),
+            })?
         }
         // Convert hash-line to ObjectId.
         let object_id: mongodb::bson::oid::ObjectId =
@@ -1516,22 +3094,21 @@ pub trait QPaladins: ToModel + CachingModel {
         let doc = coll.find_one(filter, options)?;
         // We check that for the given `hash` a document is found in the database.
         if doc.is_none() {
-            Err(format!(
-                "Model: `{}` > Method: `verify_password` : \
-                There is no document in the database for the current `hash` value.",
-                meta.model_name
-            ))?
+            Err(Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "verify_password()".to_string(),
+            })?
         }
         //
         let doc = doc.unwrap();
         // Check for the presence of the `password` field.
         let password_hash = doc.get("password");
         if password_hash.is_none() {
-            Err(format!(
-                "Model: `{}` > Method: `verify_password` : \
-                The password field is missing.",
-                meta.model_name
-            ))?
+            Err(Error::MissingField {
+                model: meta.model_name.clone(),
+                field: "password".to_string(),
+                method: "verify_password()".to_string(),
+            })?
         }
         // Get password hash or empty string.
         let password_hash = password_hash.unwrap();
@@ -1557,13 +3134,14 @@ pub trait QPaladins: ToModel + CachingModel {
     /// assert!(user_profile.create_password_hash(old_password, new_password, None)?);
     /// ```
     ///
+    #[cfg(feature = "sync")]
     fn update_password(
         &self,
         old_password: &str,
         new_password: &str,
         options_find_old: Option<mongodb::options::FindOneOptions>,
         options_update: Option<mongodb::options::UpdateOptions>,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, Error> {
         // Validation current password.
         if !self.verify_password(old_password, options_find_old)? {
             return Ok(false);
@@ -1596,3 +3174,27 @@ pub trait QPaladins: ToModel + CachingModel {
             == 1_i64)
     }
 }
+
+/// Delete the document with the `delete()` method of the Model.
+/// ( `DeleteFn` of the Model - for the `cascade` policy of referring Models )
+/// `actor` - Id of the actor for the audit trail ( the actor of the initial deletion ).
+// *************************************************************************************************
+#[cfg(feature = "sync")]
+pub fn delete_doc<T>(
+    doc: mongodb::bson::document::Document,
+    actor: Option<&str>,
+) -> Result<OutputDataForm, Error>
+where
+    T: QPaladins + serde::de::DeserializeOwned,
+{
+    let (form_cache, _) = T::get_cache_data_for_query()?;
+    let meta: Meta = form_cache.meta;
+    let model: T = OutputDataOne::to_model::<T>(
+        doc,
+        meta.ignore_fields,
+        meta.map_widget_type,
+        meta.model_name,
+        meta.map_timezones,
+    )?;
+    model.delete_by(actor, None)
+}
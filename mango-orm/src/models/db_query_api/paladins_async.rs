@@ -0,0 +1,1596 @@
+//! # Query methods for a Model instance (asynchronous).
+//! ( Available with the `async` feature )
+//!
+//! Trait:
+//! `QPaladinsAsync` - Database query methods directly related to the Model instance,
+//! for asynchronous MongoDB clients.
+//!
+//! Methods:
+//! `check` - Checking the Model before queries the database.
+//! `save` - Save to database as a new document or update an existing document.
+//! `save_many` - Save several Models to database, with the check of each Model.
+//! `patch` - Partial update of a document - only the passed fields are checked and updated.
+//! `delete` - Remove document from collection ( or mark it as deleted, for soft delete ).
+//! `restore` - Restore the soft-deleted document.
+//! `purge` - Remove document from collection and delete its files.
+//! `save_by`, `save_many_by`, `patch_by`, `delete_by`, `restore_by`, `purge_by` - The same,
+//! with the actor id for the audit trail.
+//! `db_get_relations_async` - Get the relations of Models that refer to the current Model.
+//! `check_restrict_async` - Check whether deletion is forbidden by referring documents.
+//! `apply_on_delete_async` - Apply the `cascade` and `set_null` policies to referring documents.
+//! `verify_password` - Match the password from the user to the password in the database.
+//! `update_password` - For replace or recover password.
+//!
+//! Function:
+//! `delete_doc` - Delete the document with the `delete()` method of the Model ( `DeleteFn` ).
+//!
+
+use crate::{
+    error::Error,
+    forms::{output_data::OutputDataForm, Widget},
+    migration::ModelRelation,
+    models::{
+        audit::AuditRecord, caching_async::CachingModelAsync, db_query_api::paladins::QPaladins,
+        output_data::OutputDataOne, DeleteFn, Meta,
+    },
+    storage::{field_storage, Storage},
+    store::{FormCache, MONGODB_ASYNC_CLIENT_STORE},
+};
+use futures::stream::TryStreamExt;
+
+#[async_trait::async_trait]
+pub trait QPaladinsAsync: QPaladins + CachingModelAsync + Send + Sync {
+    /// Deleting a file in the database and in the storage.
+    // *********************************************************************************************
+    async fn delete_file(
+        &self,
+        coll: &mongodb::Collection,
+        model_name: &str,
+        field_name: &str,
+        widget_default_value: &str,
+        is_image: bool,
+        storage: &dyn Storage,
+    ) -> Result<(), Error> {
+        let hash = self.get_hash().unwrap_or_default();
+        if !hash.is_empty() {
+            let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+            let filter = mongodb::bson::doc! {"_id": object_id};
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(filter.clone(), None).await?;
+            if let Some(document) = document {
+                // If `is_deleted=true` was passed incorrectly.
+                if document.is_null(field_name) {
+                    return Ok(());
+                }
+                // Delete the file information in the database.
+                let file_doc = mongodb::bson::doc! {field_name: mongodb::bson::Bson::Null};
+                let update = mongodb::bson::doc! { "$set": file_doc };
+                coll.update_one(filter, update, None).await?;
+                // Delete the orphaned file.
+                if let Some(info_file) = document.get(field_name).unwrap().as_document() {
+                    Self::remove_files(info_file, widget_default_value, is_image, storage)?;
+                } else {
+                    Err(Error::MissingField {
+                        model: model_name.to_string(),
+                        field: field_name.to_string(),
+                        method: "delete_file()".to_string(),
+                    })?
+                }
+            } else {
+                Err(Error::DocumentNotFound {
+                    model: model_name.to_string(),
+                    method: "delete_file()".to_string(),
+                })?
+            }
+        }
+        //
+        Ok(())
+    }
+
+    /// Validation of `unique`.
+    // *********************************************************************************************
+    async fn check_unique_async(
+        hash: &str,
+        field_name: &str,
+        bson_field_value: &mongodb::bson::Bson,
+        coll: &mongodb::Collection,
+    ) -> Result<(), Error> {
+        let object_id = mongodb::bson::oid::ObjectId::with_string(hash);
+        let mut filter = mongodb::bson::doc! { field_name: bson_field_value };
+        if let Ok(id) = object_id {
+            // If the document is will updated.
+            filter = mongodb::bson::doc! {
+                "$and": [
+                    { "_id": { "$ne": id } },
+                    filter
+                ]
+            };
+        }
+        let count: i64 = coll.count_documents(filter, None).await?;
+        if count > 0 {
+            Err(Error::InvalidValue("Is not unique.".to_string()))?
+        }
+        Ok(())
+    }
+
+    /// Get asynchronous MongoDB client by name.
+    /// ( For Models referenced by the current Model and vice versa )
+    // *********************************************************************************************
+    fn client_by_name(model_name: &str, db_client_name: &str) -> Result<mongodb::Client, Error> {
+        MONGODB_ASYNC_CLIENT_STORE
+            .read()?
+            .get(db_client_name)
+            .cloned()
+            .ok_or_else(|| Error::ClientNotFound {
+                model: model_name.to_string(),
+                client: db_client_name.to_string(),
+            })
+    }
+
+    /// Validation of references to other Models.
+    /// ( All referenced documents must exist )
+    // *********************************************************************************************
+    async fn check_ref_async(
+        object_ids: &[mongodb::bson::oid::ObjectId],
+        ref_coll: &mongodb::Collection,
+    ) -> Result<(), Error> {
+        let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = object_ids.to_vec();
+        object_ids.sort();
+        object_ids.dedup();
+        let filter = mongodb::bson::doc! { "_id": { "$in": object_ids.clone() } };
+        let count: i64 = ref_coll.count_documents(filter, None).await?;
+        if count != object_ids.len() as i64 {
+            Err(Error::InvalidValue(
+                "The referenced document does not exist.".to_string(),
+            ))?
+        }
+        Ok(())
+    }
+
+    /// Checking the Model before queries the database.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let user_profile  = UserProfile {...}
+    /// let result = user_profile.check().await?;
+    /// assert!(result.is_valid());
+    /// ```
+    ///
+    async fn check(&self) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        QPaladinsAsync::check_with_cache(self, &form_cache, &client_cache).await
+    }
+
+    /// Checking the Model with the cached data of the Model.
+    /// ( For partial updates, the cached data contains only the fields being updated )
+    // *********************************************************************************************
+    async fn check_with_cache(
+        &self,
+        form_cache: &FormCache,
+        client_cache: &mongodb::Client,
+    ) -> Result<OutputDataForm, Error> {
+        // Get Model metadata.
+        let meta: &Meta = &form_cache.meta;
+        // Determines the mode of accessing the database (insert or update).
+        let hash = self.get_hash().unwrap_or_default();
+        let hash = hash.as_str();
+        let is_update: bool = !hash.is_empty();
+        // Access the collection.
+        let coll: mongodb::Collection = client_cache
+            .database(&meta.database_name)
+            .collection(&meta.collection_name);
+        // Get the current state of the document from database.
+        let doc_from_db: Option<mongodb::bson::document::Document> = if is_update {
+            let object_id = mongodb::bson::oid::ObjectId::with_string(hash)?;
+            coll.find_one(mongodb::bson::doc! {"_id": object_id}, None)
+                .await?
+        } else {
+            None
+        };
+        // Validation of fields.
+        let (
+            mut is_err_symptom,
+            mut final_map_widgets,
+            mut final_doc,
+            unique_queue,
+            ref_queue,
+            delete_file_queue,
+        ) = self.check_fields(form_cache, doc_from_db)?;
+
+        // Delete files, if the client asked about it.
+        // -----------------------------------------------------------------------------------------
+        for (field_name, is_image) in delete_file_queue {
+            let default_value = form_cache
+                .map_widgets
+                .get(&field_name)
+                .unwrap()
+                .value
+                .clone();
+            QPaladinsAsync::delete_file(
+                self,
+                &coll,
+                meta.model_name.as_str(),
+                field_name.as_str(),
+                default_value.as_str(),
+                is_image,
+                field_storage(meta, field_name.as_str())?.as_ref(),
+            )
+            .await?;
+        }
+
+        // Validation of `unique`.
+        // -----------------------------------------------------------------------------------------
+        for (field_name, bson_field_value) in unique_queue {
+            let is_unique: bool =
+                Self::check_unique_async(hash, field_name.as_str(), &bson_field_value, &coll)
+                    .await
+                    .is_ok();
+            if !is_unique {
+                is_err_symptom = true;
+                let final_widget: &mut Widget = final_map_widgets.get_mut(&field_name).unwrap();
+                final_widget.error =
+                    Self::accumula_err(&final_widget, &"Is not unique.".to_string()).unwrap();
+            }
+        }
+
+        // Validation of references to other Models.
+        // -----------------------------------------------------------------------------------------
+        for (field_name, object_ids) in ref_queue {
+            let ref_model =
+                meta.map_ref_models
+                    .get(&field_name)
+                    .ok_or_else(|| Error::MissingField {
+                        model: meta.model_name.clone(),
+                        field: field_name.clone(),
+                        method: "check()".to_string(),
+                    })?;
+            let ref_coll: mongodb::Collection =
+                Self::client_by_name(&ref_model.model_name, &ref_model.db_client_name)?
+                    .database(&ref_model.database_name)
+                    .collection(&ref_model.collection_name);
+            if let Err(err) = Self::check_ref_async(&object_ids, &ref_coll).await {
+                is_err_symptom = true;
+                let final_widget: &mut Widget = final_map_widgets.get_mut(&field_name).unwrap();
+                final_widget.error = Self::accumula_err(&final_widget, &err.to_string()).unwrap();
+            }
+        }
+
+        // Timestamps and removal of orphaned files.
+        Self::check_epilogue(
+            is_err_symptom,
+            is_update,
+            meta,
+            &mut final_map_widgets,
+            &mut final_doc,
+        )?;
+
+        // Lifecycle hook - the final document can be changed.
+        if !is_err_symptom {
+            self.post_check(&mut final_doc, is_update)?;
+        }
+
+        // Enrich the widget map with values for dynamic widgets.
+        Self::vitaminize_async(
+            meta.project_name.as_str(),
+            meta.unique_project_key.as_str(),
+            meta.collection_name.as_str(),
+            client_cache,
+            &mut final_map_widgets,
+        )
+        .await?;
+
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        Ok(OutputDataForm::CheckModel((
+            !is_err_symptom,
+            meta.fields_name.clone(),
+            final_map_widgets,
+            final_doc,
+        )))
+    }
+
+    /// Save to database as a new document or update an existing document.
+    /// ( Used in conjunction with the `check ()` method. )
+    /// Hint: For Models with the `is_versioned = true` parameter, the update is applied only if
+    /// the stored version matches the version of the instance, otherwise `OutputDataForm::Conflict`.
+    /// For Models with the `is_soft_delete = true` parameter, a soft-deleted document
+    /// is not updated ( `OutputDataForm::Conflict` ).
+    /// Not transactional ( driver 1.x has no sessions ) - the document and its files are written
+    /// by separate operations, writes to other Models are not rolled back.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut user_profile  = UserProfile {...}
+    /// let output_data = user_profile.save(None, None).await?;
+    /// assert!(output_data.is_valid());
+    /// ```
+    ///
+    async fn save(
+        &mut self,
+        options_insert: Option<mongodb::options::InsertOneOptions>,
+        options_update: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        QPaladinsAsync::save_by(self, None, options_insert, options_update).await
+    }
+
+    /// The same as `save()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn save_by(
+        &mut self,
+        actor: Option<&str>,
+        options_insert: Option<mongodb::options::InsertOneOptions>,
+        options_update: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Lifecycle hook - normalization of values before the check.
+        self.pre_save(!self.get_hash().unwrap_or_default().is_empty())?;
+        // Get checked data from the `check()` method.
+        let verified_data: OutputDataForm = QPaladinsAsync::check(self).await?;
+        let mut is_no_error: bool = verified_data.is_valid();
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Get widget map.
+        let mut final_map_widgets: std::collections::HashMap<String, Widget> = verified_data.wig();
+        let is_update: bool = !self.get_hash().unwrap_or_default().is_empty();
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+
+        // Optimistic concurrency - the document has been changed by another query.
+        let mut is_conflict: bool = false;
+
+        // Save to database.
+        // -----------------------------------------------------------------------------------------
+        if is_no_error {
+            let mut final_doc = verified_data.doc();
+            // Optimistic concurrency - the version of the loaded document and the new version.
+            let old_version: Option<i64> = self.get_version();
+            let new_version: i64 = if is_update {
+                old_version.unwrap_or_default() + 1
+            } else {
+                1_i64
+            };
+            if meta.is_versioned {
+                final_doc.insert("version", new_version);
+            }
+            // State of the document before saving ( for the audit trail ).
+            let audit_docs: Option<(
+                Option<mongodb::bson::document::Document>,
+                mongodb::bson::document::Document,
+            )> = if meta.is_audit_log {
+                let doc_from_db = if is_update {
+                    let hash: String = self.get_hash().unwrap_or_default();
+                    let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                    coll.find_one(
+                        meta.not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id})),
+                        None,
+                    )
+                    .await?
+                } else {
+                    None
+                };
+                Some((doc_from_db, final_doc.clone()))
+            } else {
+                None
+            };
+            let result: Result<(), mongodb::error::Error> = if !is_update {
+                coll.insert_one(final_doc.clone(), options_insert)
+                    .await
+                    .map(|result| {
+                        self.set_hash(result.inserted_id.as_object_id().unwrap().to_hex())
+                    })
+            } else if !final_doc.is_empty() {
+                let hash: Option<String> = self.get_hash();
+                if hash.is_none() {
+                    Err(Error::MissingHash {
+                        model: meta.model_name.clone(),
+                        method: "save()".to_string(),
+                    })?
+                }
+                let object_id: mongodb::bson::oid::ObjectId =
+                    mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+                let mut query: mongodb::bson::document::Document = meta
+                    .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id}))
+                    .unwrap_or_default();
+                // The update is applied only if the stored version matches the version of the instance.
+                if meta.is_versioned {
+                    let old_version = match old_version {
+                        Some(old_version) => mongodb::bson::Bson::Int64(old_version),
+                        None => mongodb::bson::Bson::Null,
+                    };
+                    query.insert("version", old_version);
+                }
+                let update: mongodb::bson::document::Document = mongodb::bson::doc! {
+                    "$set": final_doc.clone(),
+                };
+                coll.update_one(query, update, options_update)
+                    .await
+                    .map(|result| {
+                        is_conflict =
+                            (meta.is_versioned || meta.is_soft_delete) && result.matched_count == 0
+                    })
+            } else {
+                Ok(())
+            };
+            // Duplicate key - the unique value was saved by a concurrent query.
+            if let Err(err) = result {
+                let fields: Vec<String> = Self::duplicate_key_fields(&meta, &err);
+                if fields.is_empty() {
+                    Err(err)?
+                }
+                is_no_error = false;
+                for field_name in fields {
+                    if let Some(final_widget) = final_map_widgets.get_mut(&field_name) {
+                        final_widget.error =
+                            Self::accumula_err(&final_widget, &"Is not unique.".to_owned())?;
+                    }
+                }
+            } else if is_conflict {
+                is_no_error = false;
+                // The new files of the document will not be saved.
+                let hash: String = self.get_hash().unwrap_or_default();
+                let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                let stored_doc: Option<mongodb::bson::document::Document> = coll
+                    .find_one(mongodb::bson::doc! {"_id": object_id}, None)
+                    .await?;
+                Self::delete_unsaved_files(
+                    &final_doc,
+                    stored_doc.as_ref(),
+                    &meta,
+                    &mut final_map_widgets,
+                )?;
+                let final_widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else {
+                if meta.is_versioned {
+                    self.set_version(new_version);
+                    final_map_widgets
+                        .get_mut(&"version".to_owned())
+                        .unwrap()
+                        .value = new_version.to_string();
+                }
+                if let Some((doc_from_db, audit_doc)) = audit_docs {
+                    AuditRecord::write_async(
+                        &meta,
+                        &client_cache,
+                        self.get_hash().unwrap_or_default().as_str(),
+                        if is_update { "update" } else { "create" },
+                        doc_from_db.as_ref(),
+                        Some(&audit_doc),
+                        actor,
+                    )
+                    .await?;
+                }
+                self.post_save(&final_doc, is_update)?;
+            }
+        }
+
+        // Add hash-line (for document identification).
+        // -----------------------------------------------------------------------------------------
+        let hash = self.get_hash().unwrap_or_default();
+        if !hash.is_empty() {
+            final_map_widgets.get_mut(&"hash".to_owned()).unwrap().value = hash.clone();
+        }
+
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        if is_conflict {
+            return Ok(OutputDataForm::Conflict((
+                meta.fields_name.clone(),
+                final_map_widgets,
+                hash,
+            )));
+        }
+        Ok(OutputDataForm::Save((
+            is_no_error,
+            meta.fields_name.clone(),
+            final_map_widgets,
+            hash,
+        )))
+    }
+
+    /// Partial update of a document - only the passed fields are checked and updated.
+    /// `values` - Json object <field name, value>, values in the same format as in the Model.
+    /// The `updated_at` field is updated. The widgets contain only the values of the passed fields.
+    /// Hint: To change the password, use the `update_password` method.
+    /// For Models with the `is_versioned = true` parameter, the update is applied only if
+    /// the version has not changed since the document was read, otherwise `OutputDataForm::Conflict`.
+    /// For Models with the `is_soft_delete = true` parameter, a soft-deleted document is not found
+    /// ( deleted after reading - `OutputDataForm::Conflict` ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let values = serde_json::json!({"username": "Rust", "age": 42});
+    /// let output_data = UserProfile::patch(hash, values, None).await?;
+    /// if !output_data.is_valid() {
+    ///     println!("{}", output_data.html());
+    /// }
+    /// ```
+    ///
+    async fn patch(
+        hash: &str,
+        values: serde_json::value::Value,
+        options: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error>
+    where
+        Self: Sized + Send + Sync + serde::de::DeserializeOwned,
+    {
+        Self::patch_by(None, hash, values, options).await
+    }
+
+    /// The same as `patch()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn patch_by(
+        actor: Option<&str>,
+        hash: &str,
+        values: serde_json::value::Value,
+        options: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<OutputDataForm, Error>
+    where
+        Self: Sized + Send + Sync + serde::de::DeserializeOwned,
+    {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: &Meta = &form_cache.meta;
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get the fields being updated.
+        let values = match values {
+            serde_json::value::Value::Object(values) => values,
+            _ => Err(Error::InvalidArgument {
+                model: meta.model_name.clone(),
+                method: "patch()".to_string(),
+                message: "The values must be a json object.".to_string(),
+            })?,
+        };
+        let mut fields_name: Vec<String> = vec!["hash".to_string()];
+        for field_name in values.keys() {
+            if field_name == "hash"
+                || (field_name == "version" && meta.is_versioned)
+                || !meta.fields_name.contains(field_name)
+            {
+                Err(Error::MissingField {
+                    model: meta.model_name.clone(),
+                    field: field_name.clone(),
+                    method: "patch()".to_string(),
+                })?
+            }
+            let widget_type: &str = form_cache.map_widgets[field_name].widget.as_str();
+            if widget_type == "inputPassword" {
+                Err(Error::UnsupportedWidget {
+                    model: meta.model_name.clone(),
+                    field: field_name.clone(),
+                    widget: widget_type.to_string(),
+                })?
+            }
+            fields_name.push(field_name.clone());
+        }
+        // Get the current state of the document from database.
+        let object_id = mongodb::bson::oid::ObjectId::with_string(hash)?;
+        let doc_from_db: mongodb::bson::document::Document = coll
+            .find_one(
+                meta.not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()})),
+                None,
+            )
+            .await?
+            .ok_or_else(|| Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "patch()".to_string(),
+            })?;
+        // Get an instance of the Model with the new values of the fields.
+        let mut instance: Self = {
+            let instance: Self = OutputDataOne::Doc((
+                Some(doc_from_db.clone()),
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                String::new(),
+                meta.map_timezones.clone(),
+            ))
+            .model::<Self>()?;
+            let mut model_json: serde_json::value::Value = instance.self_to_json()?;
+            for (field_name, value) in values {
+                model_json[field_name.as_str()] = value;
+            }
+            serde_json::from_value::<Self>(model_json)?
+        };
+        // Lifecycle hook - normalization of values before the check.
+        instance.pre_save(true)?;
+        // Check only the fields being updated.
+        let mut patch_cache: FormCache = form_cache.clone();
+        patch_cache.meta.fields_name = fields_name;
+        let verified_data: OutputDataForm =
+            QPaladinsAsync::check_with_cache(&instance, &patch_cache, &client_cache).await?;
+        let mut is_no_error: bool = verified_data.is_valid();
+        let mut final_map_widgets: std::collections::HashMap<String, Widget> = verified_data.wig();
+        // Optimistic concurrency - the document has been changed by another query.
+        let mut is_conflict: bool = false;
+
+        // Update the document.
+        // -----------------------------------------------------------------------------------------
+        if is_no_error {
+            let mut query: mongodb::bson::document::Document = meta
+                .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()}))
+                .unwrap_or_default();
+            let mut update: mongodb::bson::document::Document = mongodb::bson::doc! {
+                "$set": verified_data.doc(),
+            };
+            // The update is applied only if the stored version has not changed since it was read.
+            if meta.is_versioned {
+                let version = doc_from_db
+                    .get("version")
+                    .cloned()
+                    .unwrap_or(mongodb::bson::Bson::Null);
+                query.insert("version", version);
+                update.insert("$inc", mongodb::bson::doc! {"version": 1_i64});
+            }
+            let result = coll.update_one(query, update, options).await;
+            is_conflict = (meta.is_versioned || meta.is_soft_delete)
+                && matches!(result.as_ref(), Ok(result) if result.matched_count == 0);
+            if let Err(err) = result {
+                // Duplicate key - the unique value was saved by a concurrent query.
+                let fields: Vec<String> = Self::duplicate_key_fields(meta, &err);
+                if fields.is_empty() {
+                    Err(err)?
+                }
+                is_no_error = false;
+                Self::set_not_unique(&mut final_map_widgets, fields)?;
+            } else if is_conflict {
+                is_no_error = false;
+                // The new files of the document will not be saved.
+                let stored_doc: Option<mongodb::bson::document::Document> = coll
+                    .find_one(mongodb::bson::doc! {"_id": object_id}, None)
+                    .await?;
+                Self::delete_unsaved_files(
+                    &verified_data.doc(),
+                    stored_doc.as_ref(),
+                    meta,
+                    &mut final_map_widgets,
+                )?;
+                let final_widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else {
+                AuditRecord::write_async(
+                    meta,
+                    &client_cache,
+                    hash,
+                    "update",
+                    Some(&doc_from_db),
+                    Some(&verified_data.doc()),
+                    actor,
+                )
+                .await?;
+                instance.post_save(&verified_data.doc(), true)?;
+            }
+        }
+        final_map_widgets.get_mut("hash").unwrap().value = hash.to_string();
+
+        // Return result.
+        // -----------------------------------------------------------------------------------------
+        if is_conflict {
+            return Ok(OutputDataForm::Conflict((
+                meta.fields_name.clone(),
+                final_map_widgets,
+                hash.to_string(),
+            )));
+        }
+        Ok(OutputDataForm::Save((
+            is_no_error,
+            meta.fields_name.clone(),
+            final_map_widgets,
+            hash.to_string(),
+        )))
+    }
+
+    /// Save several Models to database - as new documents or updates of existing documents.
+    /// Each Model is checked by the `check()` method, the result is returned for each Model.
+    /// New documents are written by one `insert_many` query.
+    /// Hint: `all_or_nothing` - If at least one Model contains errors, nothing is saved.
+    /// If the write fails, the new documents are removed and the updated documents are restored
+    /// ( the driver 1.x does not support transactions - the rollback is not atomic ).
+    /// For Models with the `is_versioned = true` parameter, a version mismatch returns
+    /// `OutputDataForm::Conflict` for the Model ( also for a soft-deleted document ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut user_profiles = vec![UserProfile {...}, UserProfile {...}];
+    /// let results = UserProfile::save_many(&mut user_profiles, true).await?;
+    /// for output_data in results {
+    ///     if !output_data.is_valid() {
+    ///         println!("{}", output_data.html());
+    ///     }
+    /// }
+    /// ```
+    ///
+    async fn save_many(
+        models: &mut [Self],
+        all_or_nothing: bool,
+    ) -> Result<Vec<OutputDataForm>, Error>
+    where
+        Self: Sized + Send + Sync,
+    {
+        Self::save_many_by(None, models, all_or_nothing).await
+    }
+
+    /// The same as `save_many()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn save_many_by(
+        actor: Option<&str>,
+        models: &mut [Self],
+        all_or_nothing: bool,
+    ) -> Result<Vec<OutputDataForm>, Error>
+    where
+        Self: Sized + Send + Sync,
+    {
+        // Get checked data from the `check()` method.
+        let mut verified_data_list: Vec<OutputDataForm> = Vec::with_capacity(models.len());
+        for model in models.iter_mut() {
+            // Lifecycle hook - normalization of values before the check.
+            model.pre_save(!model.get_hash().unwrap_or_default().is_empty())?;
+            verified_data_list.push(QPaladinsAsync::check(model).await?);
+        }
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let mut statuses: Vec<bool> = verified_data_list
+            .iter()
+            .map(|verified_data| verified_data.is_valid())
+            .collect();
+        let mut map_widgets_list: Vec<std::collections::HashMap<String, Widget>> =
+            verified_data_list
+                .iter()
+                .map(|verified_data| verified_data.wig())
+                .collect();
+        // Hash-lines of new documents.
+        let mut new_hashes: Vec<Option<String>> = vec![None; models.len()];
+        let mut is_rollback: bool = all_or_nothing && statuses.contains(&false);
+        // Optimistic concurrency - the documents changed by another query.
+        let mut conflicts: Vec<bool> = vec![false; models.len()];
+
+        // Save to database.
+        // -----------------------------------------------------------------------------------------
+        if !is_rollback {
+            // Queues of new and updated documents.
+            let mut insert_queue: Vec<usize> = Vec::new();
+            let mut insert_docs: Vec<mongodb::bson::document::Document> = Vec::new();
+            let mut update_queue: Vec<(usize, mongodb::bson::oid::ObjectId)> = Vec::new();
+            for (idx, model) in models.iter().enumerate() {
+                if !statuses[idx] {
+                    continue;
+                }
+                let mut final_doc = verified_data_list[idx].doc();
+                let hash = model.get_hash().unwrap_or_default();
+                if hash.is_empty() {
+                    let object_id = mongodb::bson::oid::ObjectId::new();
+                    new_hashes[idx] = Some(object_id.to_hex());
+                    final_doc.insert("_id", object_id);
+                    if meta.is_versioned {
+                        final_doc.insert("version", 1_i64);
+                    }
+                    insert_queue.push(idx);
+                    insert_docs.push(final_doc);
+                } else if !final_doc.is_empty() {
+                    let object_id = mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+                    update_queue.push((idx, object_id));
+                }
+            }
+            // The current state of the updated documents ( for rollback and the audit trail ).
+            let originals: Vec<mongodb::bson::document::Document> =
+                if (all_or_nothing || meta.is_audit_log) && !update_queue.is_empty() {
+                    let object_ids: Vec<mongodb::bson::oid::ObjectId> = update_queue
+                        .iter()
+                        .map(|(_, object_id)| object_id.clone())
+                        .collect();
+                    let filter = meta
+                        .not_deleted_filter(Some(mongodb::bson::doc! {"_id": {"$in": object_ids}}));
+                    let cursor: mongodb::Cursor = coll.find(filter, None).await?;
+                    cursor.try_collect().await?
+                } else {
+                    Vec::new()
+                };
+            // New documents.
+            if !insert_docs.is_empty() {
+                let options = mongodb::options::InsertManyOptions::builder()
+                    .ordered(Some(all_or_nothing))
+                    .build();
+                if let Err(err) = coll.insert_many(insert_docs, Some(options)).await {
+                    let failures = Self::bulk_duplicate_key_fields(&meta, &err);
+                    if failures.is_empty() {
+                        if all_or_nothing {
+                            Self::rollback_many_async(&coll, &new_hashes, &[]).await?;
+                        }
+                        Err(err)?
+                    }
+                    for (queue_idx, fields) in failures {
+                        let idx = insert_queue[queue_idx];
+                        statuses[idx] = false;
+                        new_hashes[idx] = None;
+                        Self::set_not_unique(&mut map_widgets_list[idx], fields)?;
+                    }
+                    is_rollback = all_or_nothing;
+                }
+            }
+            // Updated documents.
+            // ( the driver 1.x does not support bulk write - one query per document )
+            let mut updated_originals: Vec<mongodb::bson::document::Document> = Vec::new();
+            for (idx, object_id) in update_queue {
+                if is_rollback {
+                    break;
+                }
+                let mut query = meta
+                    .not_deleted_filter(Some(mongodb::bson::doc! {"_id": object_id.clone()}))
+                    .unwrap_or_default();
+                let mut final_doc = verified_data_list[idx].doc();
+                if meta.is_versioned {
+                    let old_version: Option<i64> = models[idx].get_version();
+                    let old_version = match old_version {
+                        Some(old_version) => mongodb::bson::Bson::Int64(old_version),
+                        None => mongodb::bson::Bson::Null,
+                    };
+                    query.insert("version", old_version);
+                    final_doc.insert("version", models[idx].get_version().unwrap_or_default() + 1);
+                }
+                let update = mongodb::bson::doc! {"$set": final_doc};
+                match coll.update_one(query, update, None).await {
+                    Ok(result)
+                        if (meta.is_versioned || meta.is_soft_delete)
+                            && result.matched_count == 0 =>
+                    {
+                        statuses[idx] = false;
+                        conflicts[idx] = true;
+                        is_rollback = all_or_nothing;
+                        // The new files of the document will not be saved.
+                        let stored_doc: Option<mongodb::bson::document::Document> = coll
+                            .find_one(mongodb::bson::doc! {"_id": object_id}, None)
+                            .await?;
+                        Self::delete_unsaved_files(
+                            &verified_data_list[idx].doc(),
+                            stored_doc.as_ref(),
+                            &meta,
+                            &mut map_widgets_list[idx],
+                        )?;
+                    }
+                    Ok(_) => updated_originals.extend(
+                        originals
+                            .iter()
+                            .find(|doc| doc.get_object_id("_id").ok() == Some(&object_id))
+                            .cloned(),
+                    ),
+                    Err(err) => {
+                        let fields: Vec<String> = Self::duplicate_key_fields(&meta, &err);
+                        if fields.is_empty() {
+                            if all_or_nothing {
+                                Self::rollback_many_async(&coll, &new_hashes, &updated_originals)
+                                    .await?;
+                            }
+                            Err(err)?
+                        }
+                        statuses[idx] = false;
+                        Self::set_not_unique(&mut map_widgets_list[idx], fields)?;
+                        is_rollback = all_or_nothing;
+                    }
+                }
+            }
+            if is_rollback {
+                Self::rollback_many_async(&coll, &new_hashes, &updated_originals).await?;
+            }
+            // Audit trail.
+            if !is_rollback && meta.is_audit_log {
+                for (idx, model) in models.iter().enumerate() {
+                    if !statuses[idx] {
+                        continue;
+                    }
+                    let (hash, action) = match new_hashes[idx].as_ref() {
+                        Some(hash) => (hash.clone(), "create"),
+                        None => (model.get_hash().unwrap_or_default(), "update"),
+                    };
+                    let doc_from_db = originals.iter().find(|doc| {
+                        doc.get_object_id("_id").map(|item| item.to_hex()).ok()
+                            == Some(hash.clone())
+                    });
+                    AuditRecord::write_async(
+                        &meta,
+                        &client_cache,
+                        hash.as_str(),
+                        action,
+                        doc_from_db,
+                        Some(&verified_data_list[idx].doc()),
+                        actor,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        // Return results.
+        // -----------------------------------------------------------------------------------------
+        let mut results: Vec<OutputDataForm> = Vec::with_capacity(models.len());
+        for (idx, model) in models.iter_mut().enumerate() {
+            let is_update: bool = new_hashes[idx].is_none();
+            let final_map_widgets = &mut map_widgets_list[idx];
+            let final_widget: &mut Widget = final_map_widgets.get_mut(&"hash".to_owned()).unwrap();
+            if conflicts[idx] {
+                final_widget.common_msg =
+                    "The document has been changed by another user - reload it.".to_string();
+            } else if is_rollback {
+                if statuses[idx] {
+                    statuses[idx] = false;
+                    final_widget.common_msg =
+                        "It was not saved - other models contain errors.".to_string();
+                }
+            } else if statuses[idx] && meta.is_versioned {
+                let new_version: i64 = if new_hashes[idx].is_some() {
+                    1_i64
+                } else {
+                    model.get_version().unwrap_or_default() + 1
+                };
+                model.set_version(new_version);
+                final_map_widgets
+                    .get_mut(&"version".to_owned())
+                    .unwrap()
+                    .value = new_version.to_string();
+            }
+            if !is_rollback {
+                if let Some(hash) = new_hashes[idx].take() {
+                    model.set_hash(hash);
+                }
+                if statuses[idx] {
+                    model.post_save(&verified_data_list[idx].doc(), is_update)?;
+                }
+            }
+            let hash = model.get_hash().unwrap_or_default();
+            if !hash.is_empty() {
+                final_map_widgets.get_mut(&"hash".to_owned()).unwrap().value = hash.clone();
+            }
+            if conflicts[idx] {
+                results.push(OutputDataForm::Conflict((
+                    meta.fields_name.clone(),
+                    map_widgets_list[idx].clone(),
+                    hash,
+                )));
+                continue;
+            }
+            results.push(OutputDataForm::Save((
+                statuses[idx],
+                meta.fields_name.clone(),
+                map_widgets_list[idx].clone(),
+                hash,
+            )));
+        }
+        Ok(results)
+    }
+
+    /// Rollback of `save_many` - remove new documents and restore updated documents.
+    // *********************************************************************************************
+    async fn rollback_many_async(
+        coll: &mongodb::Collection,
+        new_hashes: &[Option<String>],
+        originals: &[mongodb::bson::document::Document],
+    ) -> Result<(), Error> {
+        let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = Vec::new();
+        for hash in new_hashes.iter().flatten() {
+            object_ids.push(mongodb::bson::oid::ObjectId::with_string(hash.as_str())?);
+        }
+        if !object_ids.is_empty() {
+            coll.delete_many(mongodb::bson::doc! {"_id": {"$in": object_ids}}, None)
+                .await?;
+        }
+        for original in originals {
+            let query = mongodb::bson::doc! {"_id": original.get_object_id("_id")?.clone()};
+            coll.replace_one(query, original.clone(), None).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove document from collection.
+    /// Hint: For Models with `is_soft_delete = true`, the document is marked with `deleted_at`
+    /// ( files and referring documents do not change, see `restore()` and `purge()` ).
+    /// Not transactional ( driver 1.x has no sessions ) - files are removed only after
+    /// the document has been deleted, so a failure leaves orphaned files, never missing ones.
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile.delete(None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn delete(
+        &self,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        QPaladinsAsync::delete_by(self, None, options).await
+    }
+
+    /// The same as `delete()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn delete_by(
+        &self,
+        actor: Option<&str>,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Remove the document from the collection.
+        if !meta.is_soft_delete {
+            return QPaladinsAsync::purge_by(self, actor, options).await;
+        }
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Get Model hash  for ObjectId.
+            let hash: Option<String> = self.get_hash();
+            if hash.is_none() {
+                Err(Error::MissingHash {
+                    model: meta.model_name.clone(),
+                    method: "delete()".to_string(),
+                })?
+            }
+            let object_id: mongodb::bson::oid::ObjectId =
+                mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+            // Create query.
+            let query: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(query.clone(), None).await?;
+            if document.is_none() {
+                Err(Error::DocumentNotFound {
+                    model: meta.model_name.clone(),
+                    method: "delete()".to_string(),
+                })?
+            }
+            // Mark the document as deleted.
+            // ( The time of the first deletion is kept )
+            let bson_null = &mongodb::bson::Bson::Null;
+            let document: mongodb::bson::document::Document = document.unwrap();
+            // Lifecycle hook - the deletion can be forbidden.
+            let err_msg: String = self.pre_delete(&document)?;
+            if !err_msg.is_empty() {
+                return Ok(OutputDataForm::Delete((false, err_msg)));
+            }
+            if document.get("deleted_at").unwrap_or(bson_null) == bson_null {
+                let dt: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
+                let update = mongodb::bson::doc! {
+                    "$set": {"deleted_at": mongodb::bson::Bson::DateTime(dt)}
+                };
+                coll.update_one(query, update, None).await?;
+                AuditRecord::write_async(
+                    &meta,
+                    &client_cache,
+                    self.get_hash().unwrap_or_default().as_str(),
+                    "delete",
+                    Some(&document),
+                    Some(&document),
+                    actor,
+                )
+                .await?;
+                self.post_delete(&document)?;
+            }
+            true
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Restore the soft-deleted document.
+    /// ( For Models with `is_soft_delete = true` )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let user_profile = UserProfile::find_with_deleted(Some(filter), None).await?
+    ///     .models::<UserProfile>()?
+    ///     .remove(0);
+    /// user_profile.restore().await?;
+    /// ```
+    ///
+    async fn restore(&self) -> Result<(), Error> {
+        QPaladinsAsync::restore_by(self, None).await
+    }
+
+    /// The same as `restore()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn restore_by(&self, actor: Option<&str>) -> Result<(), Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        if !meta.is_soft_delete {
+            Err(Error::PermissionDenied {
+                model: meta.model_name.clone(),
+                operation: "restore - the Model does not use soft delete".to_string(),
+            })?
+        }
+        // Access collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get Model hash  for ObjectId.
+        let hash: Option<String> = self.get_hash();
+        if hash.is_none() {
+            Err(Error::MissingHash {
+                model: meta.model_name.clone(),
+                method: "restore()".to_string(),
+            })?
+        }
+        let object_id: mongodb::bson::oid::ObjectId =
+            mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+        // Create query.
+        let query: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
+        // Execute query.
+        let update = mongodb::bson::doc! {"$unset": {"deleted_at": ""}};
+        if coll
+            .update_one(query.clone(), update, None)
+            .await?
+            .matched_count
+            == 0
+        {
+            Err(Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "restore()".to_string(),
+            })?
+        }
+        if meta.is_audit_log {
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(query, None).await?;
+            AuditRecord::write_async(
+                &meta,
+                &client_cache,
+                self.get_hash().unwrap_or_default().as_str(),
+                "restore",
+                document.as_ref(),
+                document.as_ref(),
+                actor,
+            )
+            .await?;
+        }
+        //
+        Ok(())
+    }
+
+    /// Remove document from collection and delete its files.
+    /// ( For Models with `is_soft_delete = true` - including soft-deleted documents )
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile.purge(None).await?;
+    /// if !routput_data.is_valid() {
+    ///     println!("{}", routput_data.err_msg());
+    /// }
+    /// ```
+    ///
+    async fn purge(
+        &self,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        QPaladinsAsync::purge_by(self, None, options).await
+    }
+
+    /// The same as `purge()`, with the actor id for the audit trail
+    /// ( for example, the hash of the user ).
+    // *********************************************************************************************
+    async fn purge_by(
+        &self,
+        actor: Option<&str>,
+        options: Option<mongodb::options::DeleteOptions>,
+    ) -> Result<OutputDataForm, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Get permission to delete the document.
+        let is_permission_delete: bool = meta.is_del_docs;
+        // Error message for the client.
+        // (Main use for admin panel.)
+        let mut err_msg = if is_permission_delete {
+            String::new()
+        } else {
+            "It is forbidden to perform delete.".to_string()
+        };
+        // Get a logical result.
+        let result_bool = if is_permission_delete {
+            // Access collection.
+            let coll: mongodb::Collection = client_cache
+                .database(meta.database_name.as_str())
+                .collection(meta.collection_name.as_str());
+            // Get Model hash  for ObjectId.
+            let hash: Option<String> = self.get_hash();
+            if hash.is_none() {
+                Err(Error::MissingHash {
+                    model: meta.model_name.clone(),
+                    method: "purge()".to_string(),
+                })?
+            }
+            let object_id: mongodb::bson::oid::ObjectId =
+                mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+            // Create query.
+            let query: mongodb::bson::document::Document =
+                mongodb::bson::doc! {"_id": object_id.clone()};
+            // Get the document with information about files.
+            let document: Option<mongodb::bson::document::Document> =
+                coll.find_one(query.clone(), None).await?;
+            if document.is_none() {
+                Err(Error::DocumentNotFound {
+                    model: meta.model_name.clone(),
+                    method: "purge()".to_string(),
+                })?
+            }
+            // Get the relations of Models that refer to the current Model.
+            let relations: Vec<ModelRelation> =
+                Self::db_get_relations_async(&meta, &client_cache).await?;
+            // Lifecycle hook - the deletion can be forbidden.
+            err_msg = self.pre_delete(document.as_ref().unwrap())?;
+            // Check whether deletion is forbidden by referring documents.
+            if err_msg.is_empty() {
+                err_msg = Self::check_restrict_async(&relations, &object_id).await?;
+            }
+            if err_msg.is_empty() {
+                // Execute query.
+                let is_deleted: bool = coll.delete_one(query, options).await.is_ok();
+                // Update referring documents and remove files
+                // only after the document has been deleted.
+                // ( MongoDB 1.x driver does not support transactions )
+                if is_deleted {
+                    Self::apply_on_delete_async(&relations, &object_id, actor).await?;
+                    AuditRecord::write_async(
+                        &meta,
+                        &client_cache,
+                        object_id.to_hex().as_str(),
+                        "delete",
+                        document.as_ref(),
+                        None,
+                        actor,
+                    )
+                    .await?;
+                    self.post_delete(document.as_ref().unwrap())?;
+                    Self::delete_files_from_doc(&document.unwrap(), &meta)?;
+                }
+                is_deleted
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+        Ok(OutputDataForm::Delete((result_bool, err_msg)))
+    }
+
+    /// Get the relations of Models that refer to the current Model.
+    /// ( Relations are registered in the technical database during migration )
+    // *********************************************************************************************
+    async fn db_get_relations_async(
+        meta: &Meta,
+        client: &mongodb::Client,
+    ) -> Result<Vec<ModelRelation>, Error> {
+        // Init the name of the project's technical database.
+        let db_mango_tech: String = format!(
+            "mango_tech__{}__{}",
+            meta.project_name, meta.unique_project_key
+        );
+        let filter = mongodb::bson::doc! {
+            "ref_database": &meta.database_name,
+            "ref_collection": &meta.collection_name
+        };
+        let mut cursor: mongodb::Cursor = client
+            .database(&db_mango_tech)
+            .collection("relations")
+            .find(filter, None)
+            .await?;
+        let mut relations: Vec<ModelRelation> = Vec::new();
+        while let Some(result) = cursor.try_next().await? {
+            relations.push(mongodb::bson::de::from_document(result)?);
+        }
+        //
+        Ok(relations)
+    }
+
+    /// Check whether deletion is forbidden by referring documents.
+    /// ( For relations with `on_delete = "restrict"` )
+    /// Hint: For the `cascade` policy, the Model of referring documents must be registered.
+    // *********************************************************************************************
+    ///
+    /// Returns an error message for the client, or an empty string if deletion is allowed.
+    ///
+    async fn check_restrict_async(
+        relations: &[ModelRelation],
+        object_id: &mongodb::bson::oid::ObjectId,
+    ) -> Result<String, Error> {
+        for relation in relations.iter() {
+            if relation.on_delete != "restrict" {
+                if relation.widget == "selectRef" && relation.on_delete == "cascade" {
+                    Meta::delete_fn_of(
+                        &relation.model_name,
+                        &relation.database,
+                        &relation.collection,
+                    )?;
+                }
+                continue;
+            }
+            let coll: mongodb::Collection =
+                Self::client_by_name(&relation.model_name, &relation.db_client_name)?
+                    .database(&relation.database)
+                    .collection(&relation.collection);
+            let filter = mongodb::bson::doc! { relation.field.as_str(): object_id.clone() };
+            if coll.count_documents(filter, None).await? > 0 {
+                return Ok(format!(
+                    "It is forbidden to perform delete - \
+                     the document is referenced by the Model `{}`.",
+                    relation.model_name
+                ));
+            }
+        }
+        //
+        Ok(String::new())
+    }
+
+    /// Apply the `cascade` and `set_null` policies to referring documents.
+    /// ( For `selectRefMult`, the identifier is removed from the array )
+    /// Hint: With `cascade`, each referring document is deleted by the `delete()` method
+    /// of its Model - hooks, files, soft delete, audit trail and nested relations are applied.
+    // *********************************************************************************************
+    async fn apply_on_delete_async(
+        relations: &[ModelRelation],
+        object_id: &mongodb::bson::oid::ObjectId,
+        actor: Option<&str>,
+    ) -> Result<(), Error> {
+        for relation in relations.iter() {
+            if relation.on_delete == "restrict" {
+                continue;
+            }
+            let coll: mongodb::Collection =
+                Self::client_by_name(&relation.model_name, &relation.db_client_name)?
+                    .database(&relation.database)
+                    .collection(&relation.collection);
+            let field_name: &str = relation.field.as_str();
+            let filter = mongodb::bson::doc! { field_name: object_id.clone() };
+            match (relation.widget.as_str(), relation.on_delete.as_str()) {
+                ("selectRef", "cascade") => {
+                    let delete_fn: DeleteFn = Meta::delete_fn_of(
+                        &relation.model_name,
+                        &relation.database,
+                        &relation.collection,
+                    )?;
+                    let docs: Vec<mongodb::bson::document::Document> =
+                        coll.find(filter, None).await?.try_collect().await?;
+                    for doc in docs {
+                        match delete_fn(doc, actor.map(|actor| actor.to_string())).await {
+                            Ok(output_data) if !output_data.is_valid() => {
+                                Err(Error::PermissionDenied {
+                                    model: relation.model_name.clone(),
+                                    operation: format!(
+                                        "cascade delete - {}",
+                                        output_data.err_msg()
+                                    ),
+                                })?
+                            }
+                            // The document has already been deleted by a nested relation.
+                            Ok(_) | Err(Error::DocumentNotFound { .. }) => {}
+                            Err(err) => Err(err)?,
+                        }
+                    }
+                }
+                ("selectRef", "set_null") => {
+                    let update = mongodb::bson::doc! {
+                        "$set": { field_name: mongodb::bson::Bson::Null }
+                    };
+                    coll.update_many(filter, update, None).await?;
+                }
+                ("selectRefMult", _) => {
+                    let update = mongodb::bson::doc! {
+                        "$pull": { field_name: object_id.clone() }
+                    };
+                    coll.update_many(filter, update, None).await?;
+                }
+                _ => Err(Error::UnsupportedWidget {
+                    model: relation.model_name.clone(),
+                    field: relation.field.clone(),
+                    widget: relation.widget.clone(),
+                })?,
+            }
+        }
+        //
+        Ok(())
+    }
+
+    // Operations with passwords.
+    // *********************************************************************************************
+    /// Match the password from the user to the password in the database.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let user_profile = UserProfile {...};
+    /// let password = "12345678";
+    /// assert!(user_profile.verify_password(password, None).await?);
+    /// ```
+    ///
+    async fn verify_password(
+        &self,
+        password: &str,
+        options: Option<mongodb::options::FindOneOptions>,
+    ) -> Result<bool, Error> {
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Access the collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get hash-line of Model.
+        let hash: Option<String> = self.get_hash();
+        if hash.is_none() {
+            Err(Error::MissingHash {
+                model: meta.model_name.clone(),
+                method: "verify_password()".to_string(),
+            })?
+        }
+        // Convert hash-line to ObjectId.
+        let object_id: mongodb::bson::oid::ObjectId =
+            mongodb::bson::oid::ObjectId::with_string(hash.unwrap().as_str())?;
+        // Create a filter to search for a document.
+        let filter: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
+        // An attempt to find the required document.
+        let doc = coll.find_one(filter, options).await?;
+        // We check that for the given `hash` a document is found in the database.
+        if doc.is_none() {
+            Err(Error::DocumentNotFound {
+                model: meta.model_name.clone(),
+                method: "verify_password()".to_string(),
+            })?
+        }
+        //
+        let doc = doc.unwrap();
+        // Check for the presence of the `password` field.
+        let password_hash = doc.get("password");
+        if password_hash.is_none() {
+            Err(Error::MissingField {
+                model: meta.model_name.clone(),
+                field: "password".to_string(),
+                method: "verify_password()".to_string(),
+            })?
+        }
+        // Get password hash or empty string.
+        let password_hash = password_hash.unwrap();
+        //
+        let password_hash = if password_hash != &mongodb::bson::Bson::Null {
+            password_hash.as_str().unwrap()
+        } else {
+            ""
+        };
+        // Password verification.
+        Ok(argon2::verify_encoded(password_hash, password.as_bytes())?)
+    }
+
+    /// For replace or recover password.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let user_profile = UserProfile {...};
+    /// let old_password = "12345678";
+    /// let new_password = "qBfJHCW2C9EH3_RW";
+    /// assert!(user_profile.update_password(old_password, new_password, None, None).await?);
+    /// ```
+    ///
+    async fn update_password(
+        &self,
+        old_password: &str,
+        new_password: &str,
+        options_find_old: Option<mongodb::options::FindOneOptions>,
+        options_update: Option<mongodb::options::UpdateOptions>,
+    ) -> Result<bool, Error> {
+        // Validation current password.
+        let is_valid_password: bool =
+            QPaladinsAsync::verify_password(self, old_password, options_find_old).await?;
+        if !is_valid_password {
+            return Ok(false);
+        }
+        //
+        // Get cached Model data.
+        let (form_cache, client_cache) =
+            <Self as CachingModelAsync>::get_cache_data_for_query().await?;
+        // Get Model metadata.
+        let meta: Meta = form_cache.meta;
+        // Access the collection.
+        let coll: mongodb::Collection = client_cache
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        // Get hash-line of Model.
+        let hash = self.get_hash().unwrap();
+        // Convert hash-line to ObjectId.
+        let object_id: mongodb::bson::oid::ObjectId =
+            mongodb::bson::oid::ObjectId::with_string(hash.as_str())?;
+        // Create a filter to search for a document.
+        let query: mongodb::bson::document::Document = mongodb::bson::doc! {"_id": object_id};
+        let new_password_hash = Self::create_password_hash(new_password)?;
+        let doc = mongodb::bson::doc! {"password": new_password_hash};
+        let update: mongodb::bson::document::Document = mongodb::bson::doc! {
+            "$set": doc,
+        };
+        // Update password.
+        let result: mongodb::results::UpdateResult =
+            coll.update_one(query, update, options_update).await?;
+        Ok(result.modified_count == 1_i64)
+    }
+}
+
+impl<T: QPaladins + CachingModelAsync + Send + Sync> QPaladinsAsync for T {}
+
+/// Delete the document with the `delete()` method of the Model.
+/// ( `DeleteFn` of the Model - for the `cascade` policy of referring Models )
+/// `actor` - Id of the actor for the audit trail ( the actor of the initial deletion ).
+// *************************************************************************************************
+pub fn delete_doc<T>(
+    doc: mongodb::bson::document::Document,
+    actor: Option<String>,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<OutputDataForm, Error>> + Send>>
+where
+    T: QPaladinsAsync + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    Box::pin(async move {
+        let (form_cache, _) = <T as CachingModelAsync>::get_cache_data_for_query().await?;
+        let meta: Meta = form_cache.meta;
+        let model: T = OutputDataOne::to_model::<T>(
+            doc,
+            meta.ignore_fields,
+            meta.map_widget_type,
+            meta.model_name,
+            meta.map_timezones,
+        )?;
+        QPaladinsAsync::delete_by(&model, actor.as_deref(), None).await
+    })
+}
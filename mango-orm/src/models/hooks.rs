@@ -0,0 +1,85 @@
+//! # Lifecycle hooks.
+//!
+//! Trait:
+//! `Hooks` - Methods that are called by `check()`, `save()` and `delete()`.
+//! Methods:
+//! `pre_save` - Before the check of `save()` ( normalization of values ).
+//! `post_check` - After a successful check ( the final document can be changed ).
+//! `post_save` - After the document has been saved.
+//! `pre_delete` - Before deletion ( the deletion can be forbidden ).
+//! `post_delete` - After the document has been deleted.
+//!
+
+use crate::error::Error;
+
+/// Lifecycle hooks of the Model.
+/// Hint: For custom use, add the Model attribute `is_use_hooks = true`.
+/// `is_update` - Determines the mode of accessing the database ( false - insert, true - update ).
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// impl Hooks for UserProfile {
+///     fn pre_save(&mut self, _is_update: bool) -> Result<(), Error> {
+///         self.username = self.username.as_ref().map(|item| item.trim().to_lowercase());
+///         Ok(())
+///     }
+///
+///     fn pre_delete(
+///         &self,
+///         doc_from_db: &mongodb::bson::document::Document,
+///     ) -> Result<String, Error> {
+///         if doc_from_db.get_bool("is_staff").unwrap_or_default() {
+///             return Ok("Staff accounts can not be deleted.".to_string());
+///         }
+///         Ok(String::new())
+///     }
+/// }
+/// ```
+///
+pub trait Hooks {
+    /// Called by `save()`, `save_many()` and `patch()` before the check.
+    /// ( For `patch()`, only the values of the fields being updated are saved )
+    // ---------------------------------------------------------------------------------------------
+    fn pre_save(&mut self, _is_update: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called by `check()` if the validation is positive.
+    /// ( For `patch()`, the final document contains only the fields being updated )
+    // ---------------------------------------------------------------------------------------------
+    fn post_check(
+        &self,
+        _final_doc: &mut mongodb::bson::document::Document,
+        _is_update: bool,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called by `save()`, `save_many()` and `patch()` after the document has been saved.
+    // ---------------------------------------------------------------------------------------------
+    fn post_save(
+        &self,
+        _final_doc: &mongodb::bson::document::Document,
+        _is_update: bool,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called by `delete()` and `purge()` before deletion.
+    /// Returns an error message for the client - if not empty, the deletion is forbidden.
+    // ---------------------------------------------------------------------------------------------
+    fn pre_delete(
+        &self,
+        _doc_from_db: &mongodb::bson::document::Document,
+    ) -> Result<String, Error> {
+        Ok(String::new())
+    }
+
+    /// Called by `delete()` and `purge()` after the document has been deleted.
+    // ---------------------------------------------------------------------------------------------
+    fn post_delete(&self, _doc_from_db: &mongodb::bson::document::Document) -> Result<(), Error> {
+        Ok(())
+    }
+}
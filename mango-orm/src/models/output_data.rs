@@ -2,8 +2,19 @@
 //!
 //! `OutputDataOne` - To return results after processing queries for one document.
 //! `OutputDataMany` - To return results after processing queries for many documents.
+//! `ModelsIter` - Iterator of Model instances, the documents are received lazily from one cursor.
+//! `OutputDataPage` - To return one page of documents with the data for page navigation.
 //!
 
+#[cfg(feature = "sync")]
+use crate::models::caching::CachingModel;
+use crate::{
+    error::Error,
+    forms::{DateTimeZone, Decimal, GeoPoint, TimeRange},
+    models::Meta,
+};
+use std::convert::TryFrom;
+
 /// To return results after processing queries for one document.
 // *************************************************************************************************
 #[derive(Debug, Clone)]
@@ -15,6 +26,7 @@ pub enum OutputDataOne {
             std::collections::HashMap<String, String>,
             String,
             String,
+            std::collections::HashMap<String, String>,
         ),
     ),
 }
@@ -60,7 +72,7 @@ impl OutputDataOne {
     /// }
     /// ```
     ///
-    pub fn doc(&self) -> Result<mongodb::bson::document::Document, Box<dyn std::error::Error>> {
+    pub fn doc(&self) -> Result<mongodb::bson::document::Document, Error> {
         match self {
             Self::Doc(data) => {
                 if data.0.is_some() {
@@ -69,6 +81,7 @@ impl OutputDataOne {
                         data.1.clone(),
                         data.2.clone(),
                         data.3.clone(),
+                        data.5.clone(),
                     )
                 } else {
                     Ok(mongodb::bson::document::Document::new())
@@ -91,7 +104,7 @@ impl OutputDataOne {
     /// }
     /// ```
     ///
-    pub fn json(&self) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn json(&self) -> Result<String, Error> {
         match self {
             Self::Doc(data) => {
                 if data.0.is_some() {
@@ -100,6 +113,7 @@ impl OutputDataOne {
                         data.1.clone(),
                         data.2.clone(),
                         data.3.clone(),
+                        data.5.clone(),
                     )?)
                     .into_relaxed_extjson()
                     .to_string())
@@ -124,55 +138,74 @@ impl OutputDataOne {
     /// }
     /// ```
     ///
-    pub fn model<T>(&self) -> Result<T, mongodb::bson::de::Error>
+    pub fn model<T>(&self) -> Result<T, Error>
     where
         T: serde::de::DeserializeOwned,
     {
         match self {
             Self::Doc(data) => {
                 if data.0.is_some() {
-                    let doc = Self::to_prepared_doc(
+                    Self::to_model::<T>(
                         data.0.clone().unwrap(),
                         data.1.clone(),
                         data.2.clone(),
                         data.3.clone(),
+                        data.5.clone(),
                     )
-                    .unwrap();
-                    let ignore_fields = data.1.clone();
-                    let map_widget_type = data.2.clone();
-                    let mut prepared_doc = mongodb::bson::document::Document::new();
-                    let bson_null = &mongodb::bson::Bson::Null;
-                    for (field_name, widget_type) in map_widget_type {
-                        if ignore_fields.contains(&field_name) {
-                            continue;
-                        }
-                        let bson_val = doc.get(field_name.as_str()).unwrap();
-                        if widget_type == "inputFile" || widget_type == "inputImage" {
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    let result = serde_json::to_string(
-                                        &bson_val.clone().into_relaxed_extjson(),
-                                    )
-                                    .unwrap();
-                                    mongodb::bson::Bson::String(result)
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else {
-                            prepared_doc.insert(field_name, bson_val);
-                        }
-                    }
-                    mongodb::bson::de::from_document::<T>(prepared_doc)
                 } else {
                     let prepared_doc = mongodb::bson::document::Document::new();
-                    mongodb::bson::de::from_document::<T>(prepared_doc)
+                    Ok(mongodb::bson::de::from_document::<T>(prepared_doc)?)
                 }
             }
         }
     }
 
+    /// Convert a raw document to a Model instance.
+    /// Hint: `hash` is filled from `_id`, dates are converted to the `inputDate`/`inputDateTime` format.
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_model<T>(
+        doc: mongodb::bson::document::Document,
+        ignore_fields: Vec<String>,
+        map_widget_type: std::collections::HashMap<String, String>,
+        model_name: String,
+        map_timezones: std::collections::HashMap<String, String>,
+    ) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let doc = Self::to_prepared_doc(
+            doc,
+            ignore_fields.clone(),
+            map_widget_type.clone(),
+            model_name,
+            map_timezones,
+        )?;
+        let mut prepared_doc = mongodb::bson::document::Document::new();
+        let bson_null = &mongodb::bson::Bson::Null;
+        for (field_name, widget_type) in map_widget_type {
+            if ignore_fields.contains(&field_name) {
+                continue;
+            }
+            let bson_val = doc.get(field_name.as_str()).unwrap();
+            if widget_type == "inputFile" || widget_type == "inputImage" {
+                prepared_doc.insert(
+                    field_name,
+                    if bson_val != bson_null {
+                        let result =
+                            serde_json::to_string(&bson_val.clone().into_relaxed_extjson())
+                                .unwrap();
+                        mongodb::bson::Bson::String(result)
+                    } else {
+                        mongodb::bson::Bson::Null
+                    },
+                );
+            } else {
+                prepared_doc.insert(field_name, bson_val);
+            }
+        }
+        Ok(mongodb::bson::de::from_document::<T>(prepared_doc)?)
+    }
+
     /// Get validation status (boolean)
     /// Hint: For check document availability.
     // ---------------------------------------------------------------------------------------------
@@ -215,13 +248,15 @@ impl OutputDataOne {
 
     /// Get prepared doc.
     /// Hint: Converting data types to model-friendly formats.
+    /// `map_timezones` - <field name, time zone> - Dates are rendered in the time zone of the field.
     // ---------------------------------------------------------------------------------------------
     pub fn to_prepared_doc(
         doc: mongodb::bson::document::Document,
         ignore_fields: Vec<String>,
         map_widget_type: std::collections::HashMap<String, String>,
         model_name: String,
-    ) -> Result<mongodb::bson::document::Document, Box<dyn std::error::Error>> {
+        map_timezones: std::collections::HashMap<String, String>,
+    ) -> Result<mongodb::bson::document::Document, Error> {
         let bson_null = &mongodb::bson::Bson::Null;
         let mut prepared_doc = mongodb::bson::document::Document::new();
         for (field_name, widget_type) in map_widget_type {
@@ -235,11 +270,11 @@ impl OutputDataOne {
                     if bson_val != bson_null {
                         mongodb::bson::Bson::String(bson_val.as_object_id().unwrap().to_hex())
                     } else {
-                        Err(format!(
-                            "Model: `{}` > Field: `hash` > Method: `find_one()` : \
-                                Missing document identifier `_id`.",
-                            model_name.clone()
-                        ))?
+                        Err(Error::MissingField {
+                            model: model_name.clone(),
+                            field: "_id".to_string(),
+                            method: "find_one()".to_string(),
+                        })?
                     },
                 );
             } else if widget_type == "inputPassword" {
@@ -252,25 +287,89 @@ impl OutputDataOne {
                         mongodb::bson::Bson::Null
                     },
                 );
-            } else if widget_type == "inputDate" {
+            } else if widget_type == "inputDate" || widget_type == "inputDateTime" {
                 let bson_val = doc.get(field_name.as_str()).unwrap();
+                let timezone = DateTimeZone::of_field(&map_timezones, field_name.as_str());
                 prepared_doc.insert(
                     field_name,
                     if bson_val != bson_null {
                         mongodb::bson::Bson::String(
-                            bson_val.as_datetime().unwrap().to_rfc3339()[..10].into(),
+                            timezone.render(widget_type.as_str(), bson_val.as_datetime().unwrap()),
                         )
                     } else {
                         mongodb::bson::Bson::Null
                     },
                 );
-            } else if widget_type == "inputDateTime" {
+            } else if widget_type == "inputTime" {
+                let bson_val = doc.get(field_name.as_str()).unwrap();
+                prepared_doc.insert(
+                    field_name,
+                    if bson_val != bson_null {
+                        mongodb::bson::Bson::String(TimeRange::minutes_to_time(
+                            bson_val.as_i32().unwrap(),
+                        ))
+                    } else {
+                        mongodb::bson::Bson::Null
+                    },
+                );
+            } else if widget_type == "inputTimeRange" {
                 let bson_val = doc.get(field_name.as_str()).unwrap();
                 prepared_doc.insert(
                     field_name,
                     if bson_val != bson_null {
                         mongodb::bson::Bson::String(
-                            bson_val.as_datetime().unwrap().to_rfc3339()[..16].into(),
+                            TimeRange::from_bson(bson_val).unwrap().to_value(),
+                        )
+                    } else {
+                        mongodb::bson::Bson::Null
+                    },
+                );
+            } else if widget_type.ends_with("Decimal") {
+                // The number is returned as a string, so as not to lose precision.
+                let bson_val = doc.get(field_name.as_str()).unwrap();
+                prepared_doc.insert(
+                    field_name,
+                    match Decimal::from_bson(bson_val) {
+                        Some(number) => mongodb::bson::Bson::String(number.to_value()),
+                        None => mongodb::bson::Bson::Null,
+                    },
+                );
+            } else if widget_type == "inputGeoPoint" {
+                // GeoJSON point -> "lat,lng"
+                let bson_val = doc.get(field_name.as_str()).unwrap();
+                prepared_doc.insert(
+                    field_name,
+                    match GeoPoint::from_bson(bson_val) {
+                        Some(point) => mongodb::bson::Bson::String(point.to_value()),
+                        None => mongodb::bson::Bson::Null,
+                    },
+                );
+            } else if widget_type == "selectRef" {
+                let bson_val = doc.get(field_name.as_str()).unwrap();
+                prepared_doc.insert(
+                    field_name,
+                    if bson_val != bson_null {
+                        mongodb::bson::Bson::String(bson_val.as_object_id().unwrap().to_hex())
+                    } else {
+                        mongodb::bson::Bson::Null
+                    },
+                );
+            } else if widget_type == "selectRefMult" {
+                let bson_val = doc.get(field_name.as_str()).unwrap();
+                prepared_doc.insert(
+                    field_name,
+                    if bson_val != bson_null {
+                        mongodb::bson::Bson::Array(
+                            bson_val
+                                .as_array()
+                                .unwrap()
+                                .iter()
+                                .map(|item| {
+                                    mongodb::bson::Bson::String(
+                                        item.as_object_id().unwrap().to_hex(),
+                                    )
+                                })
+                                .collect(),
                         )
                     } else {
                         mongodb::bson::Bson::Null
@@ -284,12 +383,192 @@ impl OutputDataOne {
 
         Ok(prepared_doc)
     }
+
+    /// Get prepared document with the referenced documents instead of their hash-lines.
+    /// Hint: `T` - Model referenced by the field ( widgets `selectRef`, `selectRefMult` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find_one(filter, None)?;
+    /// if output_data.is_valid() {
+    ///     println!("{:?}", output_data.populate::<User>("user")?);
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn populate<T>(&self, field_name: &str) -> Result<mongodb::bson::document::Document, Error>
+    where
+        T: CachingModel,
+    {
+        match self {
+            Self::Doc(data) => {
+                Self::check_ref_field(field_name, &data.2, &data.3)?;
+                let mut docs = vec![self.doc()?];
+                if self.is_valid() {
+                    Self::populate_docs::<T>(&mut docs, field_name)?;
+                }
+                Ok(docs.remove(0))
+            }
+        }
+    }
+
+    /// Replace the hash-lines of the field with the referenced documents.
+    /// Hint: For prepared documents. All referenced documents are received in one query.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut docs = UserProfile::find(None, None)?.docs()?;
+    /// OutputDataOne::populate_docs::<User>(&mut docs, "user")?;
+    /// OutputDataOne::populate_docs::<Tag>(&mut docs, "tags")?;
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn populate_docs<T>(
+        docs: &mut Vec<mongodb::bson::document::Document>,
+        field_name: &str,
+    ) -> Result<(), Error>
+    where
+        T: CachingModel,
+    {
+        // Get identifiers of the referenced documents.
+        let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = Vec::new();
+        for doc in docs.iter() {
+            match doc.get(field_name) {
+                Some(mongodb::bson::Bson::String(hash)) => {
+                    object_ids.push(mongodb::bson::oid::ObjectId::with_string(hash)?);
+                }
+                Some(mongodb::bson::Bson::Array(array)) => {
+                    for hash in array.iter().filter_map(|item| item.as_str()) {
+                        object_ids.push(mongodb::bson::oid::ObjectId::with_string(hash)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if object_ids.is_empty() {
+            return Ok(());
+        }
+        // Get the referenced documents.
+        // Hint: <hash, prepared document>
+        let (form_cache, client_cache) = T::get_cache_data_for_query()?;
+        let meta = form_cache.meta;
+        let coll: mongodb::sync::Collection = client_cache
+            .database(&meta.database_name)
+            .collection(&meta.collection_name);
+        let filter = mongodb::bson::doc! { "_id": { "$in": object_ids } };
+        let mut map_ref_docs: std::collections::HashMap<String, mongodb::bson::document::Document> =
+            std::collections::HashMap::new();
+        for result in coll.find(filter, None)? {
+            let ref_doc = Self::to_prepared_doc(
+                result?,
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                meta.map_timezones.clone(),
+            )?;
+            map_ref_docs.insert(ref_doc.get_str("hash")?.to_string(), ref_doc);
+        }
+        // Replace the hash-lines with the referenced documents.
+        for doc in docs.iter_mut() {
+            let bson_val = match doc.get(field_name) {
+                Some(mongodb::bson::Bson::String(hash)) => map_ref_docs
+                    .get(hash)
+                    .cloned()
+                    .map(mongodb::bson::Bson::Document)
+                    .unwrap_or(mongodb::bson::Bson::Null),
+                Some(mongodb::bson::Bson::Array(array)) => mongodb::bson::Bson::Array(
+                    array
+                        .iter()
+                        .filter_map(|item| item.as_str().and_then(|hash| map_ref_docs.get(hash)))
+                        .cloned()
+                        .map(mongodb::bson::Bson::Document)
+                        .collect(),
+                ),
+                _ => continue,
+            };
+            doc.insert(field_name, bson_val);
+        }
+        //
+        Ok(())
+    }
+
+    /// Render dates in the requested time zone ( instead of the time zones of the fields ).
+    /// Hint: Time zone - "UTC" or the name from the IANA database ( "Europe/Berlin" ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find_one(filter, None)?.with_timezone("Asia/Tokyo")?;
+    /// if output_data.is_valid() {
+    ///     println!("{:?}", output_data.doc()?);
+    /// }
+    /// ```
+    ///
+    pub fn with_timezone(mut self, timezone: &str) -> Result<Self, Error> {
+        match &mut self {
+            Self::Doc(data) => Self::set_timezone(&mut data.5, timezone)?,
+        }
+        Ok(self)
+    }
+
+    /// Set the time zone for all date fields.
+    // ---------------------------------------------------------------------------------------------
+    fn set_timezone(
+        map_timezones: &mut std::collections::HashMap<String, String>,
+        timezone: &str,
+    ) -> Result<(), Error> {
+        if DateTimeZone::new(timezone).is_none() {
+            Err(Error::UnknownTimezone {
+                timezone: timezone.to_string(),
+            })?
+        }
+        for value in map_timezones.values_mut() {
+            *value = timezone.trim().to_string();
+        }
+        Ok(())
+    }
+
+    /// Check that the field has a reference widget type.
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn check_ref_field(
+        field_name: &str,
+        map_widget_type: &std::collections::HashMap<String, String>,
+        model_name: &str,
+    ) -> Result<(), Error> {
+        match map_widget_type.get(field_name) {
+            Some(widget_type) if widget_type == "selectRef" || widget_type == "selectRefMult" => {
+                Ok(())
+            }
+            Some(widget_type) => Err(Error::UnsupportedWidget {
+                model: model_name.to_string(),
+                field: field_name.to_string(),
+                widget: widget_type.clone(),
+            }),
+            None => Err(Error::MissingField {
+                model: model_name.to_string(),
+                field: field_name.to_string(),
+                method: "populate()".to_string(),
+            }),
+        }
+    }
 }
 
 /// To return results after processing queries for many documents.
+/// `Data` - Lazy result, the query is executed when accessing the data.
+/// `Docs` - The documents have already been received from the database.
+/// ( For example, the result of an asynchronous query )
 // *************************************************************************************************
 #[derive(Debug, Clone)]
 pub enum OutputDataMany {
+    #[cfg(feature = "sync")]
     Data(
         (
             Option<mongodb::bson::document::Document>,
@@ -298,6 +577,16 @@ pub enum OutputDataMany {
             Vec<String>,
             std::collections::HashMap<String, String>,
             String,
+            std::collections::HashMap<String, String>,
+        ),
+    ),
+    Docs(
+        (
+            Vec<mongodb::bson::document::Document>,
+            Vec<String>,
+            std::collections::HashMap<String, String>,
+            String,
+            std::collections::HashMap<String, String>,
         ),
     ),
 }
@@ -318,16 +607,16 @@ impl OutputDataMany {
     /// }
     /// ```
     ///
-    pub fn raw_docs(
-        &self,
-    ) -> Result<Vec<mongodb::bson::document::Document>, Box<dyn std::error::Error>> {
+    pub fn raw_docs(&self) -> Result<Vec<mongodb::bson::document::Document>, Error> {
         match self {
+            #[cfg(feature = "sync")]
             Self::Data(data) => {
                 let cursor = data.2.find(data.0.clone(), data.1.clone())?;
                 Ok(cursor
                     .map(|item| item.unwrap())
                     .collect::<Vec<mongodb::bson::document::Document>>())
             }
+            Self::Docs(data) => Ok(data.0.clone()),
         }
     }
 
@@ -346,84 +635,20 @@ impl OutputDataMany {
     /// }
     /// ```
     ///
-    pub fn docs(
-        &self,
-    ) -> Result<Vec<mongodb::bson::document::Document>, Box<dyn std::error::Error>> {
-        match self {
-            Self::Data(data) => {
-                let mut cursor = data.2.find(data.0.clone(), data.1.clone())?;
-                let ignore_fields = data.3.clone();
-                let bson_null = &mongodb::bson::Bson::Null;
-                let mut docs: Vec<mongodb::bson::document::Document> = Vec::new();
-                while let Some(doc) = cursor.next() {
-                    let doc = doc?;
-                    let map_widget_type = data.4.clone();
-                    let mut prepared_doc = mongodb::bson::document::Document::new();
-                    for (field_name, widget_type) in map_widget_type {
-                        if ignore_fields.contains(&field_name) {
-                            continue;
-                        }
-                        if field_name == "hash" {
-                            let bson_val = doc.get("_id").unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_object_id().unwrap().to_hex(),
-                                    )
-                                } else {
-                                    Err(format!(
-                                        "Model: `{}` > Field: `hash` > Method: `find_one()` : \
-                                        Missing document identifier `_id`.",
-                                        data.5.clone()
-                                    ))?
-                                },
-                            );
-                        } else if widget_type == "inputPassword" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(String::new())
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else if widget_type == "inputDate" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_datetime().unwrap().to_rfc3339()[..10].into(),
-                                    )
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else if widget_type == "inputDateTime" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_datetime().unwrap().to_rfc3339()[..16].into(),
-                                    )
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(field_name, bson_val);
-                        }
-                    }
-                    docs.push(prepared_doc);
-                }
-
-                Ok(docs)
-            }
+    pub fn docs(&self) -> Result<Vec<mongodb::bson::document::Document>, Error> {
+        let (ignore_fields, map_widget_type, model_name, map_timezones) = self.settings();
+        let mut docs: Vec<mongodb::bson::document::Document> = Vec::new();
+        for doc in self.raw_docs()? {
+            docs.push(OutputDataOne::to_prepared_doc(
+                doc,
+                ignore_fields.clone(),
+                map_widget_type.clone(),
+                model_name.clone(),
+                map_timezones.clone(),
+            )?);
         }
+        //
+        Ok(docs)
     }
 
     /// Get json-line.
@@ -441,96 +666,26 @@ impl OutputDataMany {
     /// }
     /// ```
     ///
-    pub fn json(&self) -> Result<String, Box<dyn std::error::Error>> {
-        match self {
-            Self::Data(data) => {
-                let mut cursor = data.2.find(data.0.clone(), data.1.clone())?;
-                let ignore_fields = data.3.clone();
-                let bson_null = &mongodb::bson::Bson::Null;
-                let mut json_line = String::new();
-                while let Some(doc) = cursor.next() {
-                    let doc = doc?;
-                    let map_widget_type = data.4.clone();
-                    let mut prepared_doc = mongodb::bson::document::Document::new();
-                    for (field_name, widget_type) in map_widget_type {
-                        if ignore_fields.contains(&field_name) {
-                            continue;
-                        }
-                        if field_name == "hash" {
-                            let bson_val = doc.get("_id").unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_object_id().unwrap().to_hex(),
-                                    )
-                                } else {
-                                    Err(format!(
-                                        "Model: `{}` > Field: `hash` > Method: `find_one()` : \
-                                        Missing document identifier `_id`.",
-                                        data.5.clone()
-                                    ))?
-                                },
-                            );
-                        } else if widget_type == "inputPassword" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(String::new())
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else if widget_type == "inputDate" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_datetime().unwrap().to_rfc3339()[..10].into(),
-                                    )
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else if widget_type == "inputDateTime" {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(
-                                field_name,
-                                if bson_val != bson_null {
-                                    mongodb::bson::Bson::String(
-                                        bson_val.as_datetime().unwrap().to_rfc3339()[..16].into(),
-                                    )
-                                } else {
-                                    mongodb::bson::Bson::Null
-                                },
-                            );
-                        } else {
-                            let bson_val = doc.get(field_name.as_str()).unwrap();
-                            prepared_doc.insert(field_name, bson_val);
-                        }
-                    }
-
-                    json_line = format!(
-                        "{},{}",
-                        json_line,
-                        mongodb::bson::Bson::Document(prepared_doc)
-                            .into_relaxed_extjson()
-                            .to_string(),
-                    );
-                }
-
-                Ok(format!(
-                    "[{}]",
-                    if !json_line.is_empty() {
-                        &json_line[1..]
-                    } else {
-                        ""
-                    }
-                ))
-            }
+    pub fn json(&self) -> Result<String, Error> {
+        let mut json_line = String::new();
+        for prepared_doc in self.docs()? {
+            json_line = format!(
+                "{},{}",
+                json_line,
+                mongodb::bson::Bson::Document(prepared_doc)
+                    .into_relaxed_extjson()
+                    .to_string(),
+            );
         }
+        //
+        Ok(format!(
+            "[{}]",
+            if !json_line.is_empty() {
+                &json_line[1..]
+            } else {
+                ""
+            }
+        ))
     }
 
     /// Get validation status (boolean)
@@ -547,7 +702,7 @@ impl OutputDataMany {
     /// }
     /// ```
     ///
-    pub fn is_valid(&self) -> Result<bool, Box<dyn std::error::Error>> {
+    pub fn is_valid(&self) -> Result<bool, Error> {
         Ok(self.count()? > 0)
     }
 
@@ -566,6 +721,7 @@ impl OutputDataMany {
     ///
     pub fn count(&self) -> mongodb::error::Result<i64> {
         match self {
+            #[cfg(feature = "sync")]
             Self::Data(data) => {
                 let find_options = data.1.clone().unwrap();
                 let mut options = mongodb::options::CountOptions::default();
@@ -576,6 +732,593 @@ impl OutputDataMany {
                 options.collation = find_options.collation;
                 data.2.count_documents(data.0.clone(), Some(options))
             }
+            Self::Docs(data) => Ok(data.0.len() as i64),
+        }
+    }
+
+    /// Get prepared documents with the referenced documents instead of their hash-lines.
+    /// Hint: `T` - Model referenced by the field ( widgets `selectRef`, `selectRefMult` ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find(filter, None)?;
+    /// if output_data.is_valid()? {
+    ///     println!("{:?}", output_data.populate::<User>("user")?);
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "sync")]
+    pub fn populate<T>(
+        &self,
+        field_name: &str,
+    ) -> Result<Vec<mongodb::bson::document::Document>, Error>
+    where
+        T: CachingModel,
+    {
+        let (_ignore_fields, map_widget_type, model_name, _map_timezones) = self.settings();
+        OutputDataOne::check_ref_field(field_name, &map_widget_type, &model_name)?;
+        let mut docs = self.docs()?;
+        OutputDataOne::populate_docs::<T>(&mut docs, field_name)?;
+        //
+        Ok(docs)
+    }
+
+    /// Get an iterator of Model instances.
+    /// The documents are received lazily, from one cursor.
+    /// Hint: `hash` is filled from `_id`, dates are converted to the `inputDate`/`inputDateTime` format.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find(filter, None)?;
+    /// for user_profile in output_data.models_iter::<UserProfile>()? {
+    ///     println!("{:?}", user_profile?.username);
+    /// }
+    /// ```
+    ///
+    pub fn models_iter<T>(&self) -> Result<ModelsIter<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let (ignore_fields, map_widget_type, model_name, map_timezones) = self.settings();
+        let cursor: Box<
+            dyn Iterator<Item = mongodb::error::Result<mongodb::bson::document::Document>>,
+        > = match self {
+            #[cfg(feature = "sync")]
+            Self::Data(data) => Box::new(data.2.find(data.0.clone(), data.1.clone())?),
+            Self::Docs(data) => Box::new(data.0.clone().into_iter().map(Ok)),
+        };
+        Ok(ModelsIter {
+            cursor,
+            ignore_fields,
+            map_widget_type,
+            model_name,
+            map_timezones,
+            model_type: std::marker::PhantomData,
+        })
+    }
+
+    /// Get a list of Model instances.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find(filter, None)?;
+    /// let user_profiles: Vec<UserProfile> = output_data.models::<UserProfile>()?;
+    /// ```
+    ///
+    pub fn models<T>(&self) -> Result<Vec<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.models_iter::<T>()?.collect()
+    }
+
+    /// Render dates in the requested time zone ( instead of the time zones of the fields ).
+    /// Hint: Time zone - "UTC" or the name from the IANA database ( "Europe/Berlin" ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = doc!{};
+    /// let output_data  = UserProfile::find(filter, None)?.with_timezone("Asia/Tokyo")?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    pub fn with_timezone(mut self, timezone: &str) -> Result<Self, Error> {
+        match &mut self {
+            #[cfg(feature = "sync")]
+            Self::Data(data) => OutputDataOne::set_timezone(&mut data.6, timezone)?,
+            Self::Docs(data) => OutputDataOne::set_timezone(&mut data.4, timezone)?,
+        }
+        Ok(self)
+    }
+
+    /// Get the aggregation pipeline of the query by distance to the point.
+    /// Hint: The `$geoNear` stage is the first one, soft-deleted documents are excluded by its
+    /// `query`. `geometry` - GeoJSON Polygon or MultiPolygon for the `$geoWithin` condition.
+    /// The distance in meters is returned in the `_distance` field of the raw documents.
+    // ---------------------------------------------------------------------------------------------
+    pub fn geo_near_pipeline(
+        meta: &Meta,
+        field_name: &str,
+        point: &GeoPoint,
+        max_distance: Option<f64>,
+        geometry: Option<mongodb::bson::document::Document>,
+        filter: Option<mongodb::bson::document::Document>,
+    ) -> Result<Vec<mongodb::bson::document::Document>, Error> {
+        match meta.map_widget_type.get(field_name) {
+            Some(widget_type) if widget_type == "inputGeoPoint" => {}
+            Some(widget_type) => Err(Error::UnsupportedWidget {
+                model: meta.model_name.clone(),
+                field: field_name.to_string(),
+                widget: widget_type.clone(),
+            })?,
+            None => Err(Error::MissingField {
+                model: meta.model_name.clone(),
+                field: field_name.to_string(),
+                method: "geo_near_pipeline()".to_string(),
+            })?,
+        }
+        let mut query = meta.not_deleted_filter(filter).unwrap_or_default();
+        if let Some(geometry) = geometry {
+            let within = mongodb::bson::doc! {
+                field_name: {"$geoWithin": {"$geometry": geometry}}
+            };
+            query = if query.is_empty() {
+                within
+            } else {
+                mongodb::bson::doc! {"$and": [query, within]}
+            };
+        }
+        let mut geo_near = mongodb::bson::doc! {
+            "near": point.to_bson(),
+            "distanceField": "_distance",
+            "key": field_name,
+            "spherical": true,
+            "query": query
+        };
+        if let Some(max_distance) = max_distance {
+            geo_near.insert("maxDistance", max_distance);
+        }
+        let mut pipeline = vec![mongodb::bson::doc! {"$geoNear": geo_near}];
+        if meta.db_query_docs_limit > 0 {
+            pipeline.push(mongodb::bson::doc! {"$limit": meta.db_query_docs_limit as i64});
+        }
+        Ok(pipeline)
+    }
+
+    /// Get the settings for preparing documents.
+    /// Hint: (ignore_fields, map_widget_type, model_name, map_timezones)
+    // ---------------------------------------------------------------------------------------------
+    fn settings(
+        &self,
+    ) -> (
+        Vec<String>,
+        std::collections::HashMap<String, String>,
+        String,
+        std::collections::HashMap<String, String>,
+    ) {
+        match self {
+            #[cfg(feature = "sync")]
+            Self::Data(data) => (
+                data.3.clone(),
+                data.4.clone(),
+                data.5.clone(),
+                data.6.clone(),
+            ),
+            Self::Docs(data) => (
+                data.1.clone(),
+                data.2.clone(),
+                data.3.clone(),
+                data.4.clone(),
+            ),
+        }
+    }
+}
+
+/// Iterator of Model instances.
+/// ( The documents are received lazily, from one cursor )
+// *************************************************************************************************
+pub struct ModelsIter<T> {
+    cursor: Box<dyn Iterator<Item = mongodb::error::Result<mongodb::bson::document::Document>>>,
+    ignore_fields: Vec<String>,
+    map_widget_type: std::collections::HashMap<String, String>,
+    model_name: String,
+    map_timezones: std::collections::HashMap<String, String>,
+    model_type: std::marker::PhantomData<T>,
+}
+
+impl<T> Iterator for ModelsIter<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let doc = self.cursor.next()?;
+        Some(doc.map_err(Error::from).and_then(|doc| {
+            OutputDataOne::to_model::<T>(
+                doc,
+                self.ignore_fields.clone(),
+                self.map_widget_type.clone(),
+                self.model_name.clone(),
+                self.map_timezones.clone(),
+            )
+        }))
+    }
+}
+
+/// To return one page of documents with the data for page navigation.
+/// `docs` - Documents of the current page ( `OutputDataMany::Docs` ).
+/// `total`, `page_count` - Not calculated for keyset pagination ( are equal to 0 ).
+/// `next_cursor` - Only for keyset pagination, is passed to get the next page.
+// *************************************************************************************************
+#[derive(Debug, Clone)]
+pub struct OutputDataPage {
+    pub docs: OutputDataMany,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: i64,
+    pub page_count: u32,
+    pub has_next: bool,
+    pub has_prev: bool,
+    pub next_cursor: Option<String>,
+}
+
+impl OutputDataPage {
+    /// Get raw documents of the current page.
+    /// Hint: For non-standard operations.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::paginate(None, None, 1, 20)?;
+    /// println!("{:?}", output_data.raw_docs()?);
+    /// ```
+    ///
+    pub fn raw_docs(&self) -> Result<Vec<mongodb::bson::document::Document>, Error> {
+        self.docs.raw_docs()
+    }
+
+    /// Get prepared documents of the current page.
+    /// Hint: For page template.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::paginate(None, None, 1, 20)?;
+    /// println!("{:?}", output_data.docs()?);
+    /// ```
+    ///
+    pub fn docs(&self) -> Result<Vec<mongodb::bson::document::Document>, Error> {
+        self.docs.docs()
+    }
+
+    /// Get json-line - documents of the current page with the data for page navigation.
+    /// Hint: For Ajax and admin panel.
+    /// Format: {"docs":[...],"page":1,"per_page":20,"total":45,"page_count":3,
+    ///          "has_next":true,"has_prev":false,"next_cursor":null}
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::paginate(None, None, 1, 20)?;
+    /// println!("{}", output_data.json()?);
+    /// ```
+    ///
+    pub fn json(&self) -> Result<String, Error> {
+        let next_cursor = match self.next_cursor.as_ref() {
+            Some(cursor) => serde_json::Value::String(cursor.clone()),
+            None => serde_json::Value::Null,
+        };
+        Ok(format!(
+            "{{\"docs\":{},\"page\":{},\"per_page\":{},\"total\":{},\"page_count\":{},\"has_next\":{},\"has_prev\":{},\"next_cursor\":{}}}",
+            self.docs.json()?,
+            self.page,
+            self.per_page,
+            self.total,
+            self.page_count,
+            self.has_next,
+            self.has_prev,
+            next_cursor
+        ))
+    }
+
+    /// Render dates in the requested time zone ( instead of the time zones of the fields ).
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::paginate(None, None, 1, 20)?.with_timezone("Asia/Tokyo")?;
+    /// println!("{}", output_data.json()?);
+    /// ```
+    ///
+    pub fn with_timezone(mut self, timezone: &str) -> Result<Self, Error> {
+        self.docs = self.docs.with_timezone(timezone)?;
+        Ok(self)
+    }
+
+    /// Get the number of documents on the current page.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let output_data  = UserProfile::paginate(None, None, 1, 20)?;
+    /// println!("{}", output_data.count()?);
+    /// ```
+    ///
+    pub fn count(&self) -> mongodb::error::Result<i64> {
+        self.docs.count()
+    }
+
+    /// Get the number of documents per page.
+    /// Hint: Is limited by the `db_query_docs_limit` parameter of Model.
+    // ---------------------------------------------------------------------------------------------
+    pub fn per_page(meta: &Meta, per_page: u32) -> u32 {
+        let limit = meta.db_query_docs_limit;
+        if per_page == 0 || (limit > 0 && per_page > limit) {
+            limit.max(1)
+        } else {
+            per_page
+        }
+    }
+
+    /// Get options of query for page-based pagination.
+    /// Hint: `_id` is added to the sort to get a stable order of documents.
+    // ---------------------------------------------------------------------------------------------
+    pub fn page_options(
+        sort: Option<mongodb::bson::document::Document>,
+        page: u32,
+        per_page: u32,
+    ) -> mongodb::options::FindOptions {
+        let mut sort = sort.unwrap_or_default();
+        if !sort.contains_key("_id") {
+            sort.insert("_id", 1_i32);
+        }
+        mongodb::options::FindOptions::builder()
+            .sort(Some(sort))
+            .skip(Some((page.max(1) as i64 - 1) * per_page as i64))
+            .limit(Some(per_page as i64))
+            .build()
+    }
+
+    /// Create a page of documents for page-based pagination.
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_page(
+        meta: &Meta,
+        docs: Vec<mongodb::bson::document::Document>,
+        total: i64,
+        page: u32,
+        per_page: u32,
+    ) -> Self {
+        let page = page.max(1);
+        let page_count = ((total + per_page as i64 - 1) / per_page as i64) as u32;
+        Self {
+            docs: OutputDataMany::Docs((
+                docs,
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                meta.map_timezones.clone(),
+            )),
+            page,
+            per_page,
+            total,
+            page_count,
+            has_next: page < page_count,
+            has_prev: page > 1,
+            next_cursor: None,
+        }
+    }
+
+    /// Get filter and options of query for keyset ( cursor-based ) pagination.
+    /// Hint: The sort is by one field ( `{field: 1}` or `{field: -1}` ), `_id` is the tie-breaker.
+    /// Documents with a null or missing sort field go first for `1` and last for `-1`.
+    /// One more document than `per_page` is requested to find out if there is a next page.
+    // ---------------------------------------------------------------------------------------------
+    pub fn keyset_query(
+        filter: Option<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        after: Option<&str>,
+        per_page: u32,
+    ) -> Result<
+        (
+            Option<mongodb::bson::document::Document>,
+            mongodb::options::FindOptions,
+        ),
+        Error,
+    > {
+        let (field_name, direction) = Self::keyset_sort(sort)?;
+        let operator = if direction == 1 { "$gt" } else { "$lt" };
+        let filter = match after {
+            Some(cursor) => {
+                let (value, id) = Self::decode_cursor(cursor)?;
+                let after_filter = if field_name == "_id" {
+                    mongodb::bson::doc! {"_id": {operator: id}}
+                } else {
+                    // `$gt` and `$lt` do not match null and missing values,
+                    // they are sorted as the smallest ones.
+                    let field_name = field_name.as_str();
+                    let mut conditions: Vec<mongodb::bson::Bson> = Vec::new();
+                    if value == mongodb::bson::Bson::Null {
+                        if direction == 1 {
+                            conditions.push(
+                                mongodb::bson::doc! {field_name: {"$ne": mongodb::bson::Bson::Null}}
+                                    .into(),
+                            );
+                        }
+                    } else {
+                        conditions.push(
+                            mongodb::bson::doc! {field_name: {operator: value.clone()}}.into(),
+                        );
+                        if direction == -1 {
+                            conditions.push(
+                                mongodb::bson::doc! {field_name: mongodb::bson::Bson::Null}.into(),
+                            );
+                        }
+                    }
+                    conditions.push(
+                        mongodb::bson::doc! {field_name: value, "_id": {operator: id}}.into(),
+                    );
+                    mongodb::bson::doc! {"$or": conditions}
+                };
+                match filter {
+                    Some(filter) => Some(mongodb::bson::doc! {"$and": [filter, after_filter]}),
+                    None => Some(after_filter),
+                }
+            }
+            None => filter,
+        };
+        let mut sort = mongodb::bson::document::Document::new();
+        sort.insert(field_name.as_str(), direction);
+        if field_name != "_id" {
+            sort.insert("_id", direction);
+        }
+        let options = mongodb::options::FindOptions::builder()
+            .sort(Some(sort))
+            .limit(Some(per_page as i64 + 1))
+            .build();
+        Ok((filter, options))
+    }
+
+    /// Create a page of documents for keyset ( cursor-based ) pagination.
+    // ---------------------------------------------------------------------------------------------
+    pub fn from_keyset(
+        meta: &Meta,
+        mut docs: Vec<mongodb::bson::document::Document>,
+        sort: Option<mongodb::bson::document::Document>,
+        after: Option<&str>,
+        per_page: u32,
+    ) -> Result<Self, Error> {
+        let (field_name, _direction) = Self::keyset_sort(sort)?;
+        let has_next = docs.len() > per_page as usize;
+        docs.truncate(per_page as usize);
+        let next_cursor = if has_next {
+            let last_doc = docs.last().unwrap();
+            Some(Self::encode_cursor(
+                last_doc
+                    .get(field_name.as_str())
+                    .cloned()
+                    .unwrap_or(mongodb::bson::Bson::Null),
+                last_doc
+                    .get("_id")
+                    .cloned()
+                    .unwrap_or(mongodb::bson::Bson::Null),
+            ))
+        } else {
+            None
+        };
+        Ok(Self {
+            docs: OutputDataMany::Docs((
+                docs,
+                meta.ignore_fields.clone(),
+                meta.map_widget_type.clone(),
+                meta.model_name.clone(),
+                meta.map_timezones.clone(),
+            )),
+            page: 0,
+            per_page,
+            total: 0,
+            page_count: 0,
+            has_next,
+            has_prev: after.is_some(),
+            next_cursor,
+        })
+    }
+
+    /// Get the field name and the direction of sorting for keyset pagination.
+    // ---------------------------------------------------------------------------------------------
+    fn keyset_sort(
+        sort: Option<mongodb::bson::document::Document>,
+    ) -> Result<(String, i32), Error> {
+        // Without sorting fields, the documents are sorted by `_id`.
+        let sort = sort
+            .filter(|sort| !sort.is_empty())
+            .unwrap_or_else(|| mongodb::bson::doc! {"_id": 1_i32});
+        let mut fields = sort.iter().filter(|(key, _)| key.as_str() != "_id");
+        let (field_name, value) = match fields.next() {
+            Some(item) => item,
+            None => sort.iter().next().unwrap(),
+        };
+        if fields.next().is_some() {
+            Err(Error::Pagination(
+                "Sorting is possible by only one field ( and `_id` ).".to_string(),
+            ))?
+        }
+        let direction = match value {
+            mongodb::bson::Bson::Int32(1) | mongodb::bson::Bson::Int64(1) => 1_i32,
+            mongodb::bson::Bson::Int32(-1) | mongodb::bson::Bson::Int64(-1) => -1_i32,
+            _ => Err(Error::Pagination(format!(
+                "Field: `{}` => The sort direction must be 1 or -1.",
+                field_name
+            )))?,
+        };
+        Ok((field_name.clone(), direction))
+    }
+
+    /// Encode the value of the sort field and `_id` of the last document into a cursor.
+    /// Hint: The cursor is a hex-line of the extended json.
+    /// ( Decimal128 is not supported by the extended json of bson - it is stored as a string )
+    // ---------------------------------------------------------------------------------------------
+    fn encode_cursor(value: mongodb::bson::Bson, id: mongodb::bson::Bson) -> String {
+        let doc = match value {
+            mongodb::bson::Bson::Decimal128(_) => mongodb::bson::doc! {
+                "v": Decimal::from_bson(&value).map(|number| number.to_value()).unwrap_or_default(),
+                "id": id,
+                "decimal": true
+            },
+            _ => mongodb::bson::doc! {"v": value, "id": id},
+        };
+        mongodb::bson::Bson::Document(doc)
+            .into_canonical_extjson()
+            .to_string()
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    }
+
+    /// Decode the cursor into the value of the sort field and `_id`.
+    // ---------------------------------------------------------------------------------------------
+    fn decode_cursor(cursor: &str) -> Result<(mongodb::bson::Bson, mongodb::bson::Bson), Error> {
+        let err_msg = || Error::Pagination("Invalid cursor.".to_string());
+        if cursor.len() % 2 != 0 || !cursor.is_ascii() {
+            Err(err_msg())?
+        }
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|idx| u8::from_str_radix(&cursor[idx..idx + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| err_msg())?;
+        let json_line = String::from_utf8(bytes).map_err(|_| err_msg())?;
+        let json_value: serde_json::Value =
+            serde_json::from_str(json_line.as_str()).map_err(|_| err_msg())?;
+        let doc = match mongodb::bson::Bson::try_from(json_value) {
+            Ok(mongodb::bson::Bson::Document(doc)) => doc,
+            _ => Err(err_msg())?,
+        };
+        match (doc.get("v"), doc.get("id")) {
+            (Some(mongodb::bson::Bson::String(value)), Some(id))
+                if doc.get_bool("decimal").unwrap_or_default() =>
+            {
+                let number: Decimal = Decimal::parse(value.as_str()).ok_or_else(err_msg)?;
+                Ok((number.to_bson(), id.clone()))
+            }
+            (Some(value), Some(id)) => Ok((value.clone(), id.clone())),
+            _ => Err(err_msg())?,
         }
     }
 }
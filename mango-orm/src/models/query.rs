@@ -0,0 +1,562 @@
+//! # Typed query builder.
+//! ( The builder of each Model is generated by the `Model` macro - `UserProfile::q()` )
+//!
+//! `Query` - Filter, sort and projection of a query, with the widget types of the Model fields.
+//! `QueryBuilder` - Access to the query and query execution ( is implemented by the macro ).
+//! `QueryBuilderAsync` - Asynchronous query execution ( feature `async` ).
+//! `QueryField` - Conditions, sorting and projection for one field of the Model.
+//!
+
+#[cfg(feature = "async")]
+use crate::models::db_query_api::common_async::QCommonAsync;
+use crate::{
+    error::Error,
+    forms::{DateTimeZone, Decimal, GeoPoint, TimeRange},
+    models::{
+        db_query_api::common::QCommon,
+        output_data::{OutputDataMany, OutputDataOne, OutputDataPage},
+    },
+};
+use std::convert::TryFrom;
+
+/// Filter, sort and projection of a query.
+/// Hint: The values are converted to the representation stored in the database,
+/// according to the widget type of the field ( dates - DateTime, `hash` and references - ObjectId,
+/// time - minutes since midnight, decimal numbers - Decimal128, geo points - GeoJSON ).
+/// Dates without offset are in the time zone of the field.
+// *************************************************************************************************
+#[derive(Default, Clone, Debug)]
+pub struct Query {
+    pub model_name: String,
+    pub map_widget_type: std::collections::HashMap<String, String>,
+    pub map_timezones: std::collections::HashMap<String, String>,
+    pub filter: mongodb::bson::document::Document,
+    pub sort: mongodb::bson::document::Document,
+    pub projection: mongodb::bson::document::Document,
+    pub skip: Option<i64>,
+    pub limit: Option<i64>,
+    pub errors: Vec<String>,
+}
+
+impl Query {
+    /// Create a query for the Model.
+    /// Hint: `fields` - <field name, widget type>
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(model_name: &str, fields: &[(&str, &str)]) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            map_widget_type: fields
+                .iter()
+                .map(|(field_name, widget_type)| (field_name.to_string(), widget_type.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Add the time zones of the `inputDate` and `inputDateTime` fields.
+    /// Hint: `timezones` - <field name, time zone>
+    // ---------------------------------------------------------------------------------------------
+    pub fn with_timezones(mut self, timezones: &[(&str, &str)]) -> Self {
+        self.map_timezones = timezones
+            .iter()
+            .map(|(field_name, timezone)| (field_name.to_string(), timezone.to_string()))
+            .collect();
+        self
+    }
+
+    /// Get the name of the field in the database.
+    // ---------------------------------------------------------------------------------------------
+    pub fn db_field_name(field_name: &str) -> &str {
+        if field_name == "hash" {
+            "_id"
+        } else {
+            field_name
+        }
+    }
+
+    /// Add the condition for the field.
+    /// ( Several conditions for the same field are combined )
+    // ---------------------------------------------------------------------------------------------
+    pub fn add_condition(&mut self, field_name: &str, operator: &str, value: mongodb::bson::Bson) {
+        let db_field_name = Self::db_field_name(field_name);
+        match self.filter.get_mut(db_field_name) {
+            Some(mongodb::bson::Bson::Document(conditions)) => {
+                conditions.insert(operator, value);
+            }
+            _ => {
+                self.filter
+                    .insert(db_field_name, mongodb::bson::doc! {operator: value});
+            }
+        }
+    }
+
+    /// Convert the value to the representation stored in the database.
+    /// ( In case of an error, the error is added to the query and Null is returned )
+    // ---------------------------------------------------------------------------------------------
+    pub fn to_bson(
+        &mut self,
+        field_name: &str,
+        value: serde_json::value::Value,
+    ) -> mongodb::bson::Bson {
+        match self.value_to_bson(field_name, value) {
+            Ok(bson_value) => bson_value,
+            Err(err) => {
+                self.errors.push(format!(
+                    "Model: `{}` > Field: `{}` : {}",
+                    self.model_name, field_name, err
+                ));
+                mongodb::bson::Bson::Null
+            }
+        }
+    }
+
+    /// Convert the value according to the widget type of the field.
+    // ---------------------------------------------------------------------------------------------
+    fn value_to_bson(
+        &self,
+        field_name: &str,
+        value: serde_json::value::Value,
+    ) -> Result<mongodb::bson::Bson, Error> {
+        if value.is_null() {
+            return Ok(mongodb::bson::Bson::Null);
+        }
+        let widget_type: &str = self
+            .map_widget_type
+            .get(field_name)
+            .map(|widget_type| widget_type.as_str())
+            .unwrap_or_default();
+        Ok(match widget_type {
+            _ if field_name == "hash" || widget_type.starts_with("selectRef") => {
+                let hash: &str = value.as_str().unwrap_or_default();
+                mongodb::bson::Bson::ObjectId(mongodb::bson::oid::ObjectId::with_string(hash)?)
+            }
+            "inputDate" | "inputDateTime" => {
+                let value: &str = value.as_str().unwrap_or_default();
+                let dt_value: chrono::DateTime<chrono::Utc> =
+                    DateTimeZone::of_field(&self.map_timezones, field_name)
+                        .parse(widget_type, value)
+                        .map_err(|err| Error::InvalidValue(err.to_string().replace("<br>", " ")))?;
+                mongodb::bson::Bson::DateTime(dt_value)
+            }
+            "inputTime" => {
+                let value: &str = value.as_str().unwrap_or_default();
+                let minutes: i32 = TimeRange::time_to_minutes(value).ok_or_else(|| {
+                    Error::InvalidValue("Incorrect time format. Example: 09:00".to_string())
+                })?;
+                mongodb::bson::Bson::Int32(minutes)
+            }
+            "inputTimeRange" => {
+                let value: &str = value.as_str().unwrap_or_default();
+                TimeRange::parse(value)
+                    .ok_or_else(|| {
+                        Error::InvalidValue(
+                            "Incorrect time range format. Example: 09:00-18:00".to_string(),
+                        )
+                    })?
+                    .to_bson()
+            }
+            "numberDecimal" | "rangeDecimal" | "hiddenDecimal" => {
+                let value: String = match value.as_str() {
+                    Some(value) => value.to_string(),
+                    None => value.to_string(),
+                };
+                Decimal::parse(value.as_str())
+                    .ok_or_else(|| {
+                        Error::InvalidValue("Incorrect number format. Example: 1234.56".to_string())
+                    })?
+                    .to_bson()
+            }
+            "inputGeoPoint" => {
+                let value: &str = value.as_str().unwrap_or_default();
+                GeoPoint::check_value(value)
+                    .map_err(|err| Error::InvalidValue(err.to_string().replace("<br>", " ")))?
+                    .to_bson()
+            }
+            _ if widget_type.contains("I32") => {
+                mongodb::bson::Bson::Int32(value.as_i64().unwrap_or_default() as i32)
+            }
+            _ if widget_type.contains("U32") || widget_type.contains("I64") => {
+                mongodb::bson::Bson::Int64(value.as_i64().unwrap_or_default())
+            }
+            _ if widget_type.contains("F64") => {
+                mongodb::bson::Bson::Double(value.as_f64().unwrap_or_default())
+            }
+            _ => {
+                mongodb::bson::Bson::try_from(value).map_err(|err| Error::Bson(err.to_string()))?
+            }
+        })
+    }
+
+    /// Get the filter of the query.
+    // ---------------------------------------------------------------------------------------------
+    pub fn get_filter(&self) -> Result<Option<mongodb::bson::document::Document>, Error> {
+        if !self.errors.is_empty() {
+            Err(Error::InvalidArgument {
+                model: self.model_name.clone(),
+                method: "filter()".to_string(),
+                message: self.errors.join("\n"),
+            })?
+        }
+        Ok(Some(self.filter.clone()))
+    }
+
+    /// Get the options of the query ( sort, projection, skip, limit ).
+    /// Hint: Without a limit, the `db_query_docs_limit` parameter of Model is applied.
+    // ---------------------------------------------------------------------------------------------
+    pub fn get_options(&self) -> mongodb::options::FindOptions {
+        mongodb::options::FindOptions::builder()
+            .sort(if !self.sort.is_empty() {
+                Some(self.sort.clone())
+            } else {
+                None
+            })
+            .projection(if !self.projection.is_empty() {
+                Some(self.projection.clone())
+            } else {
+                None
+            })
+            .skip(self.skip)
+            .limit(Some(self.limit.unwrap_or(0_i64)))
+            .build()
+    }
+}
+
+/// Access to the query and query execution.
+/// ( Is implemented by the `Model` macro for the query builder of the Model )
+/// Hint: The methods of the fields take precedence over the methods of this trait -
+/// if the Model has a field with the same name, use `QueryBuilder::find(builder)`.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// let output_data = UserProfile::q()
+///     .age().gte(18)
+///     .age().lt(65)
+///     .username().regex("^R", "i")
+///     .birthday().desc()
+///     .limit(20)
+///     .find()?;
+/// ```
+///
+pub trait QueryBuilder: Sized {
+    /// Model for which the query is built.
+    type Model: QCommon;
+
+    /// Get the query.
+    // ---------------------------------------------------------------------------------------------
+    fn query(&self) -> &Query;
+    fn query_mut(&mut self) -> &mut Query;
+
+    /// Skip the number of documents.
+    // ---------------------------------------------------------------------------------------------
+    fn skip(mut self, skip: i64) -> Self {
+        self.query_mut().skip = Some(skip);
+        self
+    }
+
+    /// Limit the number of documents.
+    // ---------------------------------------------------------------------------------------------
+    fn limit(mut self, limit: i64) -> Self {
+        self.query_mut().limit = Some(limit);
+        self
+    }
+
+    /// Get the filter of the query.
+    /// Hint: For the query methods of `QCommon` and `QCommonAsync`.
+    // ---------------------------------------------------------------------------------------------
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let filter = UserProfile::q().age().gt(18).filter()?;
+    /// let count = UserProfile::count_documents(filter, None)?;
+    /// ```
+    ///
+    fn filter(&self) -> Result<Option<mongodb::bson::document::Document>, Error> {
+        self.query().get_filter()
+    }
+
+    /// Get the sort of the query.
+    // ---------------------------------------------------------------------------------------------
+    fn sort(&self) -> Option<mongodb::bson::document::Document> {
+        let sort = &self.query().sort;
+        if !sort.is_empty() {
+            Some(sort.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Get the options of the query ( sort, projection, skip, limit ).
+    // ---------------------------------------------------------------------------------------------
+    fn options(&self) -> mongodb::options::FindOptions {
+        self.query().get_options()
+    }
+
+    /// Finds the documents in the collection matching the query.
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn find(&self) -> Result<OutputDataMany, Error> {
+        <Self::Model as QCommon>::find(self.filter()?, Some(self.options()))
+    }
+
+    /// Finds a single document in the collection matching the query.
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn find_one(&self) -> Result<OutputDataOne, Error> {
+        let options = self.options();
+        let options = mongodb::options::FindOneOptions::builder()
+            .sort(options.sort)
+            .projection(options.projection)
+            .skip(options.skip)
+            .build();
+        <Self::Model as QCommon>::find_one(self.filter()?, Some(options))
+    }
+
+    /// Gets the number of documents matching the query.
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn count(&self) -> Result<i64, Error> {
+        <Self::Model as QCommon>::count_documents(self.filter()?, None)
+    }
+
+    /// Gets one page of documents matching the query.
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn paginate(&self, page: u32, per_page: u32) -> Result<OutputDataPage, Error> {
+        <Self::Model as QCommon>::paginate(self.filter()?, self.sort(), page, per_page)
+    }
+}
+
+/// Asynchronous query execution ( feature `async` ).
+/// Hint: Is implemented for all query builders, the Model must implement `QCommonAsync`.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// use mango_orm::models::query::QueryBuilderAsync;
+/// let output_data = UserProfile::q().age().gte(18).limit(20).find().await?;
+/// let count = UserProfile::q().is_active().eq(true).count().await?;
+/// ```
+///
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait QueryBuilderAsync: QueryBuilder + Sync
+where
+    Self::Model: QCommonAsync,
+{
+    /// Finds the documents in the collection matching the query.
+    // ---------------------------------------------------------------------------------------------
+    async fn find(&self) -> Result<OutputDataMany, Error> {
+        <Self::Model as QCommonAsync>::find(self.filter()?, Some(self.options())).await
+    }
+
+    /// Finds a single document in the collection matching the query.
+    // ---------------------------------------------------------------------------------------------
+    async fn find_one(&self) -> Result<OutputDataOne, Error> {
+        let options = self.options();
+        let options = mongodb::options::FindOneOptions::builder()
+            .sort(options.sort)
+            .projection(options.projection)
+            .skip(options.skip)
+            .build();
+        <Self::Model as QCommonAsync>::find_one(self.filter()?, Some(options)).await
+    }
+
+    /// Gets the number of documents matching the query.
+    // ---------------------------------------------------------------------------------------------
+    async fn count(&self) -> Result<i64, Error> {
+        <Self::Model as QCommonAsync>::count_documents(self.filter()?, None).await
+    }
+
+    /// Gets one page of documents matching the query.
+    // ---------------------------------------------------------------------------------------------
+    async fn paginate(&self, page: u32, per_page: u32) -> Result<OutputDataPage, Error> {
+        <Self::Model as QCommonAsync>::paginate(self.filter()?, self.sort(), page, per_page).await
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: QueryBuilder + Sync> QueryBuilderAsync for T where T::Model: QCommonAsync {}
+
+/// Conditions, sorting and projection for one field of the Model.
+/// `T` - Type of the field value ( for multiple widgets - type of the array element ).
+// *************************************************************************************************
+pub struct QueryField<B, T> {
+    builder: B,
+    field_name: &'static str,
+    value_type: std::marker::PhantomData<T>,
+}
+
+impl<B, T> QueryField<B, T>
+where
+    B: QueryBuilder,
+    T: serde::Serialize,
+{
+    /// Create a field of the query builder.
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(builder: B, field_name: &'static str) -> Self {
+        Self {
+            builder,
+            field_name,
+            value_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Add the condition with a value of the field type.
+    // ---------------------------------------------------------------------------------------------
+    fn condition<V: Into<T>>(mut self, operator: &str, value: V) -> B {
+        let value = serde_json::to_value(value.into()).unwrap_or_default();
+        let query = self.builder.query_mut();
+        let bson_value = query.to_bson(self.field_name, value);
+        query.add_condition(self.field_name, operator, bson_value);
+        self.builder
+    }
+
+    /// Add the condition with a list of values of the field type.
+    // ---------------------------------------------------------------------------------------------
+    fn list_condition<V: Into<T>>(mut self, operator: &str, values: Vec<V>) -> B {
+        let query = self.builder.query_mut();
+        let mut bson_values: Vec<mongodb::bson::Bson> = Vec::new();
+        for value in values {
+            let value = serde_json::to_value(value.into()).unwrap_or_default();
+            bson_values.push(query.to_bson(self.field_name, value));
+        }
+        query.add_condition(
+            self.field_name,
+            operator,
+            mongodb::bson::Bson::Array(bson_values),
+        );
+        self.builder
+    }
+
+    /// Matches values that are equal to a specified value.
+    /// Hint: For multiple widgets - the array contains the value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn eq<V: Into<T>>(self, value: V) -> B {
+        self.condition("$eq", value)
+    }
+
+    /// Matches all values that are not equal to a specified value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn ne<V: Into<T>>(self, value: V) -> B {
+        self.condition("$ne", value)
+    }
+
+    /// Matches values that are greater than a specified value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn gt<V: Into<T>>(self, value: V) -> B {
+        self.condition("$gt", value)
+    }
+
+    /// Matches values that are greater than or equal to a specified value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn gte<V: Into<T>>(self, value: V) -> B {
+        self.condition("$gte", value)
+    }
+
+    /// Matches values that are less than a specified value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn lt<V: Into<T>>(self, value: V) -> B {
+        self.condition("$lt", value)
+    }
+
+    /// Matches values that are less than or equal to a specified value.
+    // ---------------------------------------------------------------------------------------------
+    pub fn lte<V: Into<T>>(self, value: V) -> B {
+        self.condition("$lte", value)
+    }
+
+    /// Matches any of the values specified in a list.
+    // ---------------------------------------------------------------------------------------------
+    pub fn is_in<V: Into<T>>(self, values: Vec<V>) -> B {
+        self.list_condition("$in", values)
+    }
+
+    /// Matches none of the values specified in a list.
+    // ---------------------------------------------------------------------------------------------
+    pub fn not_in<V: Into<T>>(self, values: Vec<V>) -> B {
+        self.list_condition("$nin", values)
+    }
+
+    /// Matches documents where the field is empty ( null or missing ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn is_null(mut self) -> B {
+        self.builder
+            .query_mut()
+            .add_condition(self.field_name, "$eq", mongodb::bson::Bson::Null);
+        self.builder
+    }
+
+    /// Matches documents where the field is not empty.
+    // ---------------------------------------------------------------------------------------------
+    pub fn is_not_null(mut self) -> B {
+        self.builder
+            .query_mut()
+            .add_condition(self.field_name, "$ne", mongodb::bson::Bson::Null);
+        self.builder
+    }
+
+    /// Sort in ascending order.
+    // ---------------------------------------------------------------------------------------------
+    pub fn asc(mut self) -> B {
+        self.builder
+            .query_mut()
+            .sort
+            .insert(Query::db_field_name(self.field_name), 1_i32);
+        self.builder
+    }
+
+    /// Sort in descending order.
+    // ---------------------------------------------------------------------------------------------
+    pub fn desc(mut self) -> B {
+        self.builder
+            .query_mut()
+            .sort
+            .insert(Query::db_field_name(self.field_name), -1_i32);
+        self.builder
+    }
+
+    /// Include the field in the returned documents.
+    /// Hint: With a projection, use `raw_docs()` - prepared documents require all the fields.
+    // ---------------------------------------------------------------------------------------------
+    pub fn include(mut self) -> B {
+        self.builder
+            .query_mut()
+            .projection
+            .insert(Query::db_field_name(self.field_name), 1_i32);
+        self.builder
+    }
+
+    /// Exclude the field from the returned documents.
+    // ---------------------------------------------------------------------------------------------
+    pub fn exclude(mut self) -> B {
+        self.builder
+            .query_mut()
+            .projection
+            .insert(Query::db_field_name(self.field_name), 0_i32);
+        self.builder
+    }
+}
+
+impl<B> QueryField<B, String>
+where
+    B: QueryBuilder,
+{
+    /// Matches values by the regular expression.
+    /// Hint: `options` - "i", "m", "x", "s" ( or an empty string ).
+    // ---------------------------------------------------------------------------------------------
+    pub fn regex(mut self, pattern: &str, options: &str) -> B {
+        self.builder.query_mut().add_condition(
+            self.field_name,
+            "$regex",
+            mongodb::bson::Bson::RegularExpression(mongodb::bson::Regex {
+                pattern: pattern.to_string(),
+                options: options.to_string(),
+            }),
+        );
+        self.builder
+    }
+}
@@ -0,0 +1,206 @@
+//! # Upload ingestion.
+//!
+//! Trait:
+//! `Upload` - Moves uploaded files into the storage of the `inputFile` and `inputImage` fields.
+//! Methods:
+//! `upload_bytes` - Ingest the contents of the uploaded file.
+//! `upload_file` - Ingest a temporary file ( the temporary file is removed ).
+//! `sniff_mime` - Determine the MIME type and extension by the content of the file.
+//! `is_accepted` - Check the MIME type against the `accept` parameter of the widget.
+//!
+
+use crate::{
+    error::Error,
+    forms::{FileData, ImageData, Widget},
+    models::{Meta, ToModel},
+    storage::field_storage,
+};
+use image::GenericImageView;
+use rand::Rng;
+use std::{convert::TryFrom, fs};
+
+/// Ingestion of uploaded files.
+/// Files are stored as `<upload root of the storage>/<YYYY>/<MM>/<DD>/<generated name>`.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// let photo: String = UserProfile::upload_file("photo", "/tmp/upload-0f3a2c")?;
+/// let mut user_profile = UserProfile {
+///     photo: Some(photo),
+///     ..Default::default()
+/// };
+/// let output_data = user_profile.save(None, None)?;
+/// ```
+///
+pub trait Upload: ToModel {
+    /// Ingest the contents of the uploaded file.
+    /// Checks the size ( `maxsize` ) and the MIME type ( `accept` ) of the file,
+    /// then returns a json-line of `FileData` or `ImageData` - the value of the field.
+    /// Hint: The MIME type and the extension are determined only by the content
+    /// ( unrecognized text - `text/plain` and `.txt`, unrecognized binary -
+    /// `application/octet-stream` without extension ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let photo: String = UserProfile::upload_bytes("photo", &bytes)?;
+    /// ```
+    ///
+    fn upload_bytes(field_name: &str, data: &[u8]) -> Result<String, Error> {
+        let meta: Meta = Self::meta()?;
+        let model_name: &str = meta.model_name.as_str();
+        let map_widgets = Self::widgets()?;
+        let widget: &Widget = map_widgets
+            .get(field_name)
+            .ok_or_else(|| Error::MissingField {
+                model: model_name.to_string(),
+                field: field_name.to_string(),
+                method: "upload_bytes()".to_string(),
+            })?;
+        let rejected = |message: String| Error::InvalidUpload {
+            model: model_name.to_string(),
+            field: field_name.to_string(),
+            message,
+        };
+        // Validation of file size.
+        if data.is_empty() {
+            Err(rejected("The file is empty.".to_string()))?
+        }
+        let size: u32 = u32::try_from(data.len())
+            .map_err(|_| rejected(format!("The file size exceeds {} bytes.", u32::MAX)))?;
+        if widget.maxsize > 0 && data.len() as u64 > widget.maxsize as u64 {
+            Err(rejected(format!(
+                "The file size exceeds {} bytes.",
+                widget.maxsize
+            )))?
+        }
+        // The name of the uploaded file is not used - it is controlled by the client.
+        let (mime_type, extension) = Self::sniff_mime(data);
+        // Validation of MIME type.
+        if !Self::is_accepted(
+            widget.accept.as_str(),
+            mime_type.as_str(),
+            extension.as_str(),
+        ) {
+            Err(rejected(format!(
+                "The file type `{}` is not accepted.",
+                mime_type
+            )))?
+        }
+        // Generate a collision-free key of the file.
+        let storage = field_storage(&meta, field_name)?;
+        let now = chrono::Utc::now();
+        let dir: String = format!("{}/{}", storage.upload_root(), now.format("%Y/%m/%d"));
+        let (key, name) = loop {
+            let mut name = format!(
+                "{:x}{:08x}",
+                now.timestamp_millis(),
+                rand::thread_rng().gen::<u32>()
+            );
+            if !extension.is_empty() {
+                name = format!("{}.{}", name, extension);
+            }
+            let key = format!("{}/{}", dir, name);
+            if !storage.exists(key.as_str())? {
+                break (key, name);
+            }
+        };
+        // Fill in the file data.
+        match widget.widget.as_str() {
+            "inputFile" => {
+                storage.put(key.as_str(), data)?;
+                let file_data = FileData {
+                    url: storage.url(key.as_str()),
+                    path: key,
+                    name,
+                    size,
+                };
+                Ok(serde_json::to_string(&file_data)?)
+            }
+            "inputImage" => {
+                let img = if mime_type.starts_with("image/") {
+                    image::load_from_memory(data).ok()
+                } else {
+                    None
+                };
+                let img = img.ok_or_else(|| rejected("The file is not an image.".to_string()))?;
+                storage.put(key.as_str(), data)?;
+                let dimensions: (u32, u32) = img.dimensions();
+                let image_data = ImageData {
+                    url: storage.url(key.as_str()),
+                    path: key,
+                    name,
+                    size,
+                    width: dimensions.0,
+                    height: dimensions.1,
+                    ..Default::default()
+                };
+                Ok(serde_json::to_string(&image_data)?)
+            }
+            _ => Err(Error::UnsupportedWidget {
+                model: model_name.to_string(),
+                field: field_name.to_string(),
+                widget: widget.widget.clone(),
+            })?,
+        }
+    }
+
+    /// Ingest a temporary file ( after ingestion the temporary file is removed ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let file: String = UserProfile::upload_file("resume", "/tmp/upload-0f3a2c")?;
+    /// ```
+    ///
+    fn upload_file(field_name: &str, temp_path: &str) -> Result<String, Error> {
+        let data: Vec<u8> = fs::read(temp_path)?;
+        let result: String = Self::upload_bytes(field_name, data.as_slice())?;
+        fs::remove_file(temp_path)?;
+        Ok(result)
+    }
+
+    /// Determine the MIME type and extension by the content of the file.
+    /// ( Unrecognized text - `text/plain` and `txt`,
+    /// unrecognized binary - `application/octet-stream` without extension )
+    // *********************************************************************************************
+    fn sniff_mime(data: &[u8]) -> (String, String) {
+        match infer::get(data) {
+            Some(kind) => (kind.mime_type().to_string(), kind.extension().to_string()),
+            None if std::str::from_utf8(data).is_ok() => {
+                ("text/plain".to_string(), "txt".to_string())
+            }
+            None => ("application/octet-stream".to_string(), String::new()),
+        }
+    }
+
+    /// Check the MIME type against the `accept` parameter of the widget.
+    /// Hint: accept="image/jpeg,image/png" or accept="image/*" or accept=".pdf,.odt"
+    /// ( An empty `accept` parameter accepts any file )
+    // *********************************************************************************************
+    fn is_accepted(accept: &str, mime_type: &str, extension: &str) -> bool {
+        if accept.trim().is_empty() {
+            return true;
+        }
+        accept
+            .split(',')
+            .map(|item| item.trim().to_lowercase())
+            .filter(|item| !item.is_empty())
+            .any(|item| {
+                if item == "*/*" {
+                    true
+                } else if let Some(group) = item.strip_suffix("/*") {
+                    mime_type.starts_with(format!("{}/", group).as_str())
+                } else if let Some(ext) = item.strip_prefix('.') {
+                    !extension.is_empty()
+                        && (ext == extension || (ext == "jpeg" && extension == "jpg"))
+                } else {
+                    item == mime_type
+                }
+            })
+    }
+}
@@ -8,6 +8,10 @@
 //! `accumula_err` - Accumulation of errors.
 //! `regex_validation` - Validation in regular expression (email, password, etc...).
 //! `check_unique` - Validation of `unique`.
+//! `duplicate_key_fields` - Get the fields of the unique index from a duplicate key error.
+//! `bulk_duplicate_key_fields` - Get the fields of the unique indexes from a duplicate key error of a bulk write.
+//! `index_fields` - Get the fields of the index whose name is in the error message.
+//! `check_ref` - Validation of references to other Models.
 //!
 //! Trait:
 //! `AdditionalValidation` - Methods for additional validation.
@@ -16,8 +20,13 @@
 //!
 
 use crate::{
+    error::Error,
     forms::Widget,
-    store::{REGEX_IS_COLOR_CODE, REGEX_IS_DATE, REGEX_IS_DATETIME, REGEX_IS_PASSWORD},
+    models::Meta,
+    store::{
+        REGEX_IS_COLOR_CODE, REGEX_IS_DATE, REGEX_IS_DATETIME, REGEX_IS_PASSWORD, REGEX_IS_TIME,
+        REGEX_IS_TIME_RANGE,
+    },
 };
 
 /// Validating Model fields for save and update.
@@ -25,25 +34,31 @@ use crate::{
 pub trait ValidationModel {
     /// Validation of `minlength`.
     // ---------------------------------------------------------------------------------------------
-    fn check_minlength(minlength: usize, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn check_minlength(minlength: usize, value: &str) -> Result<(), Error> {
         if minlength > 0 && value.encode_utf16().count() < minlength {
-            Err(format!("Exceeds limit, minlength={}.", minlength))?
+            Err(Error::InvalidValue(format!(
+                "Exceeds limit, minlength={}.",
+                minlength
+            )))?
         }
         Ok(())
     }
 
     /// Validation of `maxlength`.
     // ---------------------------------------------------------------------------------------------
-    fn check_maxlength(maxlength: usize, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn check_maxlength(maxlength: usize, value: &str) -> Result<(), Error> {
         if maxlength > 0 && value.encode_utf16().count() > maxlength {
-            Err(format!("Exceeds limit, maxlength={}.", maxlength))?
+            Err(Error::InvalidValue(format!(
+                "Exceeds limit, maxlength={}.",
+                maxlength
+            )))?
         }
         Ok(())
     }
 
     /// Accumulation of errors.
     // ---------------------------------------------------------------------------------------------
-    fn accumula_err(widget: &Widget, err: &String) -> Result<String, Box<dyn std::error::Error>> {
+    fn accumula_err(widget: &Widget, err: &String) -> Result<String, Error> {
         let mut tmp = widget.error.clone();
         tmp = if !tmp.is_empty() {
             format!("{}<br>", tmp)
@@ -55,53 +70,76 @@ pub trait ValidationModel {
 
     /// Validation in regular expression (email, password, etc...).
     // ---------------------------------------------------------------------------------------------
-    fn regex_validation(field_type: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn regex_validation(field_type: &str, value: &str) -> Result<(), Error> {
         match field_type {
             "inputEmail" => {
                 if !validator::validate_email(value) {
-                    Err("Invalid email address.")?
+                    Err(Error::InvalidValue("Invalid email address.".to_string()))?
                 }
             }
             "inputColor" => {
                 if !REGEX_IS_COLOR_CODE.is_match(value) {
-                    Err("Invalid Color code.")?
+                    Err(Error::InvalidValue("Invalid Color code.".to_string()))?
                 }
             }
             "inputUrl" => {
                 if !validator::validate_url(value) {
-                    Err("Invalid Url.")?
+                    Err(Error::InvalidValue("Invalid Url.".to_string()))?
                 }
             }
             "inputIP" => {
                 if !validator::validate_ip(value) {
-                    Err("Invalid IP address.")?
+                    Err(Error::InvalidValue("Invalid IP address.".to_string()))?
                 }
             }
             "inputIPv4" => {
                 if !validator::validate_ip_v4(value) {
-                    Err("Invalid IPv4 address.")?
+                    Err(Error::InvalidValue("Invalid IPv4 address.".to_string()))?
                 }
             }
             "inputIPv6" => {
                 if !validator::validate_ip_v6(value) {
-                    Err("Invalid IPv6 address.")?
+                    Err(Error::InvalidValue("Invalid IPv6 address.".to_string()))?
                 }
             }
             "inputPassword" => {
                 if !REGEX_IS_PASSWORD.is_match(value) {
-                    Err("Size 8-256 chars ; Allowed chars: a-z A-Z 0-9 @ # $ % ^ & + = * ! ~ ) (")?
+                    Err(Error::InvalidValue(
+                        "Size 8-256 chars ; Allowed chars: a-z A-Z 0-9 @ # $ % ^ & + = * ! ~ ) ("
+                            .to_string(),
+                    ))?
                 }
             }
             "inputDate" => {
                 if !REGEX_IS_DATE.is_match(value) {
-                    Err("Incorrect date format.<br>\
-                         Example: 1970-02-28")?
+                    Err(Error::InvalidValue(
+                        "Incorrect date format.<br>\
+                         Example: 1970-02-28"
+                            .to_string(),
+                    ))?
                 }
             }
             "inputDateTime" => {
                 if !REGEX_IS_DATETIME.is_match(value) {
-                    Err("Incorrect date and time format.<br>\
-                         Example: 1970-02-28T00:00")?
+                    Err(Error::InvalidValue(
+                        "Incorrect date and time format.<br>\
+                         Example: 1970-02-28T00:00"
+                            .to_string(),
+                    ))?
+                }
+            }
+            "inputTime" => {
+                if !REGEX_IS_TIME.is_match(value) {
+                    Err(Error::InvalidValue(
+                        "Incorrect time format.<br>Example: 09:00".to_string(),
+                    ))?
+                }
+            }
+            "inputTimeRange" => {
+                if !REGEX_IS_TIME_RANGE.is_match(value) {
+                    Err(Error::InvalidValue(
+                        "Incorrect time range format.<br>Example: 09:00-18:00".to_string(),
+                    ))?
                 }
             }
             _ => return Ok(()),
@@ -111,12 +149,13 @@ pub trait ValidationModel {
 
     /// Validation of `unique`.
     // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
     fn check_unique(
         hash: &str,
         field_name: &str,
         bson_field_value: &mongodb::bson::Bson,
         coll: &mongodb::sync::Collection,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         let object_id = mongodb::bson::oid::ObjectId::with_string(hash);
         let mut filter = mongodb::bson::doc! { field_name: bson_field_value };
         if let Ok(id) = object_id {
@@ -130,7 +169,80 @@ pub trait ValidationModel {
         }
         let count: i64 = coll.count_documents(filter, None)?;
         if count > 0 {
-            Err("Is not unique.")?
+            Err(Error::InvalidValue("Is not unique.".to_string()))?
+        }
+        Ok(())
+    }
+
+    /// Get the fields of the unique index from a duplicate key error.
+    /// ( The value became non-unique after `check_unique` - a concurrent query saved it first )
+    // ---------------------------------------------------------------------------------------------
+    fn duplicate_key_fields(meta: &Meta, err: &mongodb::error::Error) -> Vec<String> {
+        let message: &str = match err.kind.as_ref() {
+            mongodb::error::ErrorKind::WriteError(mongodb::error::WriteFailure::WriteError(
+                write_error,
+            )) if write_error.code == 11000 => write_error.message.as_str(),
+            _ => return Vec::new(),
+        };
+        Self::index_fields(meta, message)
+    }
+
+    /// Get the fields of the unique indexes from a duplicate key error of a bulk write.
+    /// Returns: <index of document in the query, fields of the unique index>
+    // ---------------------------------------------------------------------------------------------
+    fn bulk_duplicate_key_fields(
+        meta: &Meta,
+        err: &mongodb::error::Error,
+    ) -> Vec<(usize, Vec<String>)> {
+        match err.kind.as_ref() {
+            mongodb::error::ErrorKind::BulkWriteError(failure) => failure
+                .write_errors
+                .as_ref()
+                .map(|write_errors| {
+                    write_errors
+                        .iter()
+                        .filter(|write_error| write_error.code == 11000)
+                        .map(|write_error| {
+                            (
+                                write_error.index,
+                                Self::index_fields(meta, write_error.message.as_str()),
+                            )
+                        })
+                        .filter(|(_, fields)| !fields.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the fields of the index whose name is in the error message.
+    // ---------------------------------------------------------------------------------------------
+    fn index_fields(meta: &Meta, message: &str) -> Vec<String> {
+        meta.indexes
+            .iter()
+            .find(|index| message.contains(format!("index: {} ", index.name).as_str()))
+            .map(|index| index.keys.iter().map(|(field, _)| field.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Validation of references to other Models.
+    /// ( All referenced documents must exist )
+    // ---------------------------------------------------------------------------------------------
+    #[cfg(feature = "sync")]
+    fn check_ref(
+        object_ids: &[mongodb::bson::oid::ObjectId],
+        ref_coll: &mongodb::sync::Collection,
+    ) -> Result<(), Error> {
+        let mut object_ids: Vec<mongodb::bson::oid::ObjectId> = object_ids.to_vec();
+        object_ids.sort();
+        object_ids.dedup();
+        let filter = mongodb::bson::doc! { "_id": { "$in": object_ids.clone() } };
+        let count: i64 = ref_coll.count_documents(filter, None)?;
+        if count != object_ids.len() as i64 {
+            Err(Error::InvalidValue(
+                "The referenced document does not exist.".to_string(),
+            ))?
         }
         Ok(())
     }
@@ -147,7 +259,7 @@ pub trait ValidationModel {
 /// impl AdditionalValidation for UserProfile {
 ///     fn add_validation<'a>(
 ///         &self,
-///     ) -> Result<std::collections::HashMap<&'a str, &'a str>, Box<dyn std::error::Error>> {
+///     ) -> Result<std::collections::HashMap<&'a str, &'a str>, Error> {
 ///         // Hint: error_map.insert("field_name", "Error message.")
 ///         let mut error_map: std::collections::HashMap<&'a str, &'a str> =
 ///             std::collections::HashMap::new();
@@ -182,9 +294,7 @@ pub trait ValidationModel {
 ///
 pub trait AdditionalValidation {
     // Default implementation as a stub.
-    fn add_validation<'a>(
-        &self,
-    ) -> Result<std::collections::HashMap<&'a str, &'a str>, Box<dyn std::error::Error>> {
+    fn add_validation<'a>(&self) -> Result<std::collections::HashMap<&'a str, &'a str>, Error> {
         // error_map.insert("field_name", "Error message.")
         let error_map: std::collections::HashMap<&'a str, &'a str> =
             std::collections::HashMap::new();
@@ -0,0 +1,276 @@
+//! # File storage
+//!
+//! `Storage` - Storage backend for files of the `inputFile` and `inputImage` widgets.
+//! `LocalStorage` - Local file system ( the default storage ).
+//! `S3Storage` - S3-compatible object store ( feature `s3` ).
+//! `field_storage` - Get the storage of the Model field.
+//!
+//! Storages are registered in `STORAGE_STORE` by name and are selected by the Model parameter
+//! `storage` or by the field parameter `storage` ( the storage `default` is `LocalStorage` ).
+//!
+
+use crate::{error::Error, models::Meta, store::STORAGE_STORE};
+use std::{fs, path::Path, sync::Arc};
+
+/// Storage backend for files.
+/// `key` - The value of the `path` attribute of `FileData` and `ImageData`.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// STORAGE_STORE.write()?.insert(
+///     "media".to_string(),
+///     std::sync::Arc::new(LocalStorage::new("./media", "/media")),
+/// );
+/// ```
+///
+pub trait Storage: Send + Sync {
+    /// Write the file.
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), Error>;
+    /// Read the file.
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error>;
+    /// Remove the file ( a missing file is not an error ).
+    fn delete(&self, key: &str) -> Result<(), Error>;
+    /// Check whether the file exists.
+    fn exists(&self, key: &str) -> Result<bool, Error>;
+    /// Get the public URL of the file.
+    fn url(&self, key: &str) -> String;
+    /// Get the file size in bytes.
+    fn size(&self, key: &str) -> Result<u64, Error> {
+        Ok(self.get(key)?.len() as u64)
+    }
+    /// Get the directory for uploaded files ( `Upload` trait ).
+    fn upload_root(&self) -> String {
+        "uploads".to_string()
+    }
+}
+
+/// Local file system.
+/// `media_root` - Directory of the files, `media_url` - URL prefix for this directory.
+// *************************************************************************************************
+#[derive(Clone, Debug)]
+pub struct LocalStorage {
+    pub media_root: String,
+    pub media_url: String,
+}
+
+impl Default for LocalStorage {
+    fn default() -> Self {
+        LocalStorage {
+            media_root: "./media".to_string(),
+            media_url: "/media".to_string(),
+        }
+    }
+}
+
+impl LocalStorage {
+    /// Create a local storage.
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(media_root: &str, media_url: &str) -> Self {
+        LocalStorage {
+            media_root: media_root.trim_end_matches('/').to_string(),
+            media_url: media_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl Storage for LocalStorage {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), Error> {
+        let path = Path::new(key);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        Ok(fs::read(key)?)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        let path = Path::new(key);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> Result<bool, Error> {
+        Ok(Path::new(key).is_file())
+    }
+
+    fn url(&self, key: &str) -> String {
+        match key.strip_prefix(self.media_root.as_str()) {
+            Some(rest) => format!("{}{}", self.media_url, rest),
+            None => key.to_string(),
+        }
+    }
+
+    fn size(&self, key: &str) -> Result<u64, Error> {
+        Ok(Path::new(key).metadata()?.len())
+    }
+
+    fn upload_root(&self) -> String {
+        format!("{}/uploads", self.media_root)
+    }
+}
+
+/// S3-compatible object store ( AWS S3, MinIO, etc. ).
+/// Keys are used as object names without the leading `./` and `/`.
+// *************************************************************************************************
+///
+/// # Example:
+///
+/// ```
+/// let storage = S3Storage::new(
+///     "media",
+///     "us-east-1",
+///     "http://localhost:9000",
+///     "minioadmin",
+///     "minioadmin",
+///     "http://localhost:9000/media",
+/// )?;
+/// STORAGE_STORE.write()?.insert("s3".to_string(), std::sync::Arc::new(storage));
+/// ```
+///
+#[cfg(feature = "s3")]
+pub struct S3Storage {
+    bucket: s3::bucket::Bucket,
+    public_url: String,
+}
+
+#[cfg(feature = "s3")]
+impl S3Storage {
+    /// Create a storage for the bucket ( path-style requests ).
+    /// `public_url` - URL prefix for the objects of the bucket.
+    // ---------------------------------------------------------------------------------------------
+    pub fn new(
+        bucket_name: &str,
+        region: &str,
+        endpoint: &str,
+        access_key: &str,
+        secret_key: &str,
+        public_url: &str,
+    ) -> Result<Self, Error> {
+        let region = s3::region::Region::Custom {
+            region: region.to_string(),
+            endpoint: endpoint.to_string(),
+        };
+        let credentials =
+            s3::creds::Credentials::new(Some(access_key), Some(secret_key), None, None, None)
+                .map_err(|err| Self::error(bucket_name, err))?;
+        let bucket = s3::bucket::Bucket::new_with_path_style(bucket_name, region, credentials)
+            .map_err(|err| Self::error(bucket_name, err))?;
+        Ok(S3Storage {
+            bucket,
+            public_url: public_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Get the name of the object.
+    // ---------------------------------------------------------------------------------------------
+    fn object_name(key: &str) -> &str {
+        key.trim_start_matches("./").trim_start_matches('/')
+    }
+
+    /// Get the storage error.
+    // ---------------------------------------------------------------------------------------------
+    fn error<T: std::fmt::Display>(key: &str, err: T) -> Error {
+        Error::Storage {
+            key: key.to_string(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Check the status code of the response.
+    // ---------------------------------------------------------------------------------------------
+    fn check_status(key: &str, code: u16) -> Result<(), Error> {
+        if !(200..300).contains(&code) {
+            Err(Self::error(key, format!("Status code - {}.", code)))?
+        }
+        Ok(())
+    }
+
+    /// Get the size of the object ( None - the object does not exist ).
+    // ---------------------------------------------------------------------------------------------
+    fn object_size(&self, key: &str) -> Result<Option<u64>, Error> {
+        let name = Self::object_name(key);
+        let results = self
+            .bucket
+            .list_blocking(name.to_string(), None)
+            .map_err(|err| Self::error(key, err))?;
+        for (result, code) in results {
+            Self::check_status(key, code)?;
+            if let Some(object) = result.contents.iter().find(|object| object.key == name) {
+                return Ok(Some(object.size));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "s3")]
+impl Storage for S3Storage {
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), Error> {
+        let (_, code) = self
+            .bucket
+            .put_object_blocking(Self::object_name(key), data)
+            .map_err(|err| Self::error(key, err))?;
+        Self::check_status(key, code)
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let (data, code) = self
+            .bucket
+            .get_object_blocking(Self::object_name(key))
+            .map_err(|err| Self::error(key, err))?;
+        Self::check_status(key, code)?;
+        Ok(data)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        let (_, code) = self
+            .bucket
+            .delete_object_blocking(Self::object_name(key))
+            .map_err(|err| Self::error(key, err))?;
+        if code != 404 {
+            Self::check_status(key, code)?;
+        }
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> Result<bool, Error> {
+        Ok(self.object_size(key)?.is_some())
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, Self::object_name(key))
+    }
+
+    fn size(&self, key: &str) -> Result<u64, Error> {
+        self.object_size(key)?
+            .ok_or_else(|| Self::error(key, "The object does not exist."))
+    }
+}
+
+/// Get the storage of the Model field.
+/// ( The storage `default` is `LocalStorage`, if it is not registered )
+// *************************************************************************************************
+pub fn field_storage(meta: &Meta, field_name: &str) -> Result<Arc<dyn Storage>, Error> {
+    let storage_name: &str = meta
+        .map_storages
+        .get(field_name)
+        .map_or("default", |item| item.as_str());
+    if let Some(storage) = STORAGE_STORE.read()?.get(storage_name) {
+        return Ok(storage.clone());
+    }
+    if storage_name == "default" {
+        return Ok(Arc::new(LocalStorage::default()));
+    }
+    Err(Error::StorageNotFound {
+        model: meta.model_name.clone(),
+        storage: storage_name.to_string(),
+    })
+}
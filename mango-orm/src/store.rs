@@ -3,6 +3,9 @@
 //! `FormCache` - Structure for caching map of widgets, json and html, for mango models.
 //! `FORM_STORE` - Storage of settings for mango models.
 //! `MONGODB_CLIENT_STORE` - Storage for Clients of MongoDB.
+//! `MONGODB_ASYNC_CLIENT_STORE` - Storage for asynchronous Clients of MongoDB ( feature `async` ).
+//! `STORAGE_STORE` - Storage for file storages ( `inputFile` and `inputImage` widgets ).
+//! `DELETE_FN_STORE` - Functions for deleting documents of Models ( `cascade` policy ).
 //!
 
 use lazy_static::lazy_static;
@@ -20,6 +23,20 @@ pub struct FormCache {
     pub form_html: String,
 }
 
+// Caching clients MongoDB
+// ( `lazy_static!` does not pass the `cfg` attribute to the generated impls - separate blocks )
+#[cfg(feature = "sync")]
+lazy_static! {
+    pub static ref MONGODB_CLIENT_STORE: RwLock<std::collections::HashMap<String, mongodb::sync::Client>> =
+        RwLock::new(std::collections::HashMap::new());
+}
+// Caching asynchronous clients MongoDB
+#[cfg(feature = "async")]
+lazy_static! {
+    pub static ref MONGODB_ASYNC_CLIENT_STORE: RwLock<std::collections::HashMap<String, mongodb::Client>> =
+        RwLock::new(std::collections::HashMap::new());
+}
+
 // Store
 lazy_static! {
     // Storage of settings for mango models
@@ -27,16 +44,24 @@ lazy_static! {
     pub static ref FORM_STORE: RwLock<std::collections::HashMap<String, FormCache>> = {
         RwLock::new(std::collections::HashMap::new())
     };
-    // Caching clients MongoDB
+    // File storages ( the storage `default` is `LocalStorage`, if it is not registered )
+    // ---------------------------------------------------------------------------------------------
+    pub static ref STORAGE_STORE: RwLock<std::collections::HashMap<String, std::sync::Arc<dyn crate::storage::Storage>>> = {
+        RwLock::new(std::collections::HashMap::new())
+    };
+    // Functions for deleting documents of Models ( `cascade` policy )
+    // Hint: <"database.collection", DeleteFn>
     // ---------------------------------------------------------------------------------------------
-    pub static ref MONGODB_CLIENT_STORE: RwLock<std::collections::HashMap<String, mongodb::sync::Client>> = {
+    pub static ref DELETE_FN_STORE: RwLock<std::collections::HashMap<String, crate::models::DeleteFn>> = {
         RwLock::new(std::collections::HashMap::new())
     };
     // Regular expressions
     // ---------------------------------------------------------------------------------------------
     pub static ref REGEX_IS_COLOR_CODE: Regex = RegexBuilder::new(r"^(?:#|0x)(?:[a-f0-9]{3}|[a-f0-9]{6}|[a-f0-9]{8})\b|(?:rgb|hsl)a?\([^\)]*\)$").case_insensitive(true).build().unwrap();
     pub static ref REGEX_IS_DATE: Regex = Regex::new(r"^(?:[1-9]\d{3}-(?:(?:0[1-9]|1[0-2])-(?:0[1-9]|1\d|2[0-8])|(?:0[13-9]|1[0-2])-(?:29|30)|(?:0[13578]|1[02])-31)|(?:[1-9]\d(?:0[48]|[2468][048]|[13579][26])|(?:[2468][048]|[13579][26])00)-02-29)$").unwrap();
-    pub static ref REGEX_IS_DATETIME: Regex = Regex::new(r"^(?:[1-9]\d{3}-(?:(?:0[1-9]|1[0-2])-(?:0[1-9]|1\d|2[0-8])|(?:0[13-9]|1[0-2])-(?:29|30)|(?:0[13578]|1[02])-31)|(?:[1-9]\d(?:0[48]|[2468][048]|[13579][26])|(?:[2468][048]|[13579][26])00)-02-29)T(?:[01]\d|2[0-3]):[0-5]\d$").unwrap();
+    pub static ref REGEX_IS_DATETIME: Regex = Regex::new(r"^(?:[1-9]\d{3}-(?:(?:0[1-9]|1[0-2])-(?:0[1-9]|1\d|2[0-8])|(?:0[13-9]|1[0-2])-(?:29|30)|(?:0[13578]|1[02])-31)|(?:[1-9]\d(?:0[48]|[2468][048]|[13579][26])|(?:[2468][048]|[13579][26])00)-02-29)T(?:[01]\d|2[0-3]):[0-5]\d(?::[0-5]\d(?:\.\d{1,9})?)?(?:[zZ]|[+-](?:[01]\d|2[0-3]):[0-5]\d)?$").unwrap();
+    pub static ref REGEX_IS_DECIMAL: Regex = Regex::new(r"^[+-]?(?:\d+(?:\.\d*)?|\.\d+)(?:[eE][+-]?\d+)?$").unwrap();
     pub static ref REGEX_IS_PASSWORD: Regex = Regex::new(r"^[a-zA-Z0-9@#$%^&+=*!~)(]{8,256}$").unwrap();
     pub static ref REGEX_IS_TIME: Regex = Regex::new(r"^(?:[01]\d|2[0-3]):[0-5]\d$").unwrap();
+    pub static ref REGEX_IS_TIME_RANGE: Regex = Regex::new(r"^(?:[01]\d|2[0-3]):[0-5]\d-(?:[01]\d|2[0-3]):[0-5]\d$").unwrap();
 }
@@ -0,0 +1,102 @@
+// DECIMAL NUMBERS
+// #################################################################################################
+#[cfg(test)]
+mod tests {
+    use mango_orm::forms::Decimal;
+    use mongodb::bson::{doc, Bson};
+
+    // Get the bits of Decimal128 ( without the `decimal128` feature of bson ).
+    fn decimal128_bits(value: &Bson) -> u128 {
+        let mut buffer: Vec<u8> = Vec::new();
+        doc! {"v": value.clone()}.to_writer(&mut buffer).unwrap();
+        let mut bytes: [u8; 16] = [0; 16];
+        bytes.copy_from_slice(&buffer[7..23]);
+        u128::from_le_bytes(bytes)
+    }
+
+    #[test]
+    fn decimal_parse() {
+        let number = Decimal::parse("1.2345E+3").unwrap();
+        assert_eq!("1234.5", number.to_value());
+        assert_eq!("1234.50", number.with_scale(2).unwrap().to_value());
+        assert!(number.with_scale(0).is_none());
+        assert_eq!("-0.05", Decimal::parse("-5E-2").unwrap().to_value());
+        assert_eq!("1200", Decimal::parse("12E2").unwrap().to_value());
+        // invalids
+        assert!(Decimal::parse("12,5").is_none());
+        assert!(Decimal::parse("").is_none());
+        assert!(Decimal::parse("1E-35").is_none());
+        assert!(Decimal::parse(&"9".repeat(35)).is_none());
+    }
+
+    #[test]
+    fn decimal_to_bson() {
+        // Sign - bit 127, exponent with the bias 6176 - bits 113-126, coefficient - bits 0-112.
+        let value = Decimal::parse("1").unwrap().to_bson();
+        assert!(matches!(value, Bson::Decimal128(_)));
+        assert_eq!(0x3040 << 112 | 1, decimal128_bits(&value));
+        let value = Decimal::parse("-0.05").unwrap().to_bson();
+        assert_eq!(0xB03C << 112 | 5, decimal128_bits(&value));
+        let value = Decimal::parse("0").unwrap().to_bson();
+        assert_eq!(0x3040 << 112, decimal128_bits(&value));
+        let value = Decimal::parse("1234.50").unwrap().to_bson();
+        assert_eq!(0x303C << 112 | 123450, decimal128_bits(&value));
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        for value in [
+            // negative numbers
+            "-0.05",
+            "-1234.50",
+            "-1.5E-30",
+            // zero
+            "0",
+            "0.00",
+            "-0",
+            // scale 0
+            "42",
+            "1E+3",
+            // scale 34
+            "0.0000000000000000000000000000000001",
+            "-0.1234567890123456789012345678901234",
+            // 34-digit coefficients
+            "9999999999999999999999999999999999",
+            "-9999999999999999999999999999999999",
+            "99999999999999999999999999.99999999",
+        ]
+        .iter()
+        {
+            let number = Decimal::parse(value).unwrap();
+            assert_eq!(
+                Some(number.clone()),
+                Decimal::from_bson(&number.to_bson()),
+                "{}",
+                value
+            );
+        }
+        let number = Decimal::parse("0.0000000000000000000000000000000001").unwrap();
+        assert_eq!(34, number.scale);
+        let number = Decimal::parse(&"9".repeat(34)).unwrap();
+        assert_eq!(34, number.digits());
+        assert_eq!(0, number.scale);
+    }
+
+    #[test]
+    fn decimal_from_bson() {
+        assert_eq!(
+            "42",
+            Decimal::from_bson(&Bson::Int32(42)).unwrap().to_value()
+        );
+        assert_eq!(
+            "-42",
+            Decimal::from_bson(&Bson::Int64(-42)).unwrap().to_value()
+        );
+        assert_eq!(
+            "2.5",
+            Decimal::from_bson(&Bson::Double(2.5)).unwrap().to_value()
+        );
+        assert!(Decimal::from_bson(&Bson::String("2.5".to_string())).is_none());
+        assert!(Decimal::from_bson(&Bson::Null).is_none());
+    }
+}
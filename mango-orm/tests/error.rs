@@ -0,0 +1,44 @@
+// ERRORS
+// #################################################################################################
+#[cfg(test)]
+mod tests {
+    use mango_orm::Error;
+
+    #[test]
+    fn error_display() {
+        let err = Error::DocumentNotFound {
+            model: "UserProfile".to_string(),
+            method: "delete()".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Model: `UserProfile` > Method: `delete()` : Document not found."
+        );
+        let err = Error::FileNotFound {
+            model: "UserProfile".to_string(),
+            field: "resume".to_string(),
+            path: "./media/resume.docx".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Model: `UserProfile` > Field: `resume` : File is missing - ./media/resume.docx"
+        );
+    }
+
+    #[test]
+    fn error_conversion() {
+        let err: Error = std::io::Error::new(std::io::ErrorKind::NotFound, "no file").into();
+        assert!(matches!(err, Error::Io(_)));
+        let err: Error = serde_json::from_str::<Vec<u32>>("{").unwrap_err().into();
+        assert!(matches!(err, Error::Json(_)));
+        let err: Error = String::from("Some message.").into();
+        assert!(matches!(err, Error::Other(_)));
+        // Compatibility with `Box<dyn std::error::Error>`.
+        let err: Box<dyn std::error::Error> = Error::MissingHash {
+            model: "UserProfile".to_string(),
+            method: "save()".to_string(),
+        }
+        .into();
+        assert!(err.to_string().contains("`hash`"));
+    }
+}
@@ -0,0 +1,86 @@
+// SOFT DELETE
+// #################################################################################################
+#[cfg(test)]
+mod tests {
+    use mango_orm::models::Meta;
+    use mongodb::bson::doc;
+
+    fn soft_delete_meta() -> Meta {
+        Meta {
+            is_soft_delete: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn not_deleted_filter() {
+        let meta = soft_delete_meta();
+        assert_eq!(
+            Some(doc! {"rank": 1, "deleted_at": null}),
+            meta.not_deleted_filter(Some(doc! {"rank": 1}))
+        );
+        // A filter with `deleted_at` is used as is.
+        let filter = doc! {"deleted_at": {"$ne": null}};
+        assert_eq!(Some(filter.clone()), meta.not_deleted_filter(Some(filter)));
+        // Without soft delete.
+        assert_eq!(None, Meta::default().not_deleted_filter(None));
+    }
+
+    #[test]
+    fn not_deleted_pipeline() {
+        let meta = soft_delete_meta();
+        let not_deleted = doc! {"$match": {"deleted_at": null}};
+        // `$match` is the first stage.
+        let pipeline = meta.not_deleted_pipeline(vec![doc! {"$sort": {"rank": 1}}]);
+        assert_eq!(
+            vec![not_deleted.clone(), doc! {"$sort": {"rank": 1}}],
+            pipeline
+        );
+        assert_eq!(
+            vec![not_deleted.clone()],
+            meta.not_deleted_pipeline(Vec::new())
+        );
+        // The filter is added to the query of `$geoNear`.
+        let geo_near = doc! {
+            "$geoNear": {
+                "near": {"type": "Point", "coordinates": [-73.99, 40.73]},
+                "distanceField": "distance",
+                "query": {"rank": 1}
+            }
+        };
+        let pipeline = meta.not_deleted_pipeline(vec![geo_near]);
+        assert_eq!(1, pipeline.len());
+        assert_eq!(
+            &doc! {"rank": 1, "deleted_at": null},
+            pipeline[0]
+                .get_document("$geoNear")
+                .unwrap()
+                .get_document("query")
+                .unwrap()
+        );
+        let geo_near = doc! {"$geoNear": {"near": [-73.99, 40.73], "distanceField": "distance"}};
+        let pipeline = meta.not_deleted_pipeline(vec![geo_near]);
+        assert_eq!(
+            &doc! {"deleted_at": null},
+            pipeline[0]
+                .get_document("$geoNear")
+                .unwrap()
+                .get_document("query")
+                .unwrap()
+        );
+        // The stages that must be first.
+        for stage in [
+            doc! {"$collStats": {"count": {}}},
+            doc! {"$indexStats": {}},
+            doc! {"$search": {"text": {"query": "name", "path": "name"}}},
+        ]
+        .iter()
+        {
+            let pipeline = meta.not_deleted_pipeline(vec![stage.clone()]);
+            assert_eq!(vec![stage.clone(), not_deleted.clone()], pipeline);
+        }
+        // Without soft delete.
+        let pipeline = Meta::default().not_deleted_pipeline(vec![doc! {"$sort": {"rank": 1}}]);
+        assert_eq!(vec![doc! {"$sort": {"rank": 1}}], pipeline);
+    }
+}
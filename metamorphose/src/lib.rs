@@ -3,6 +3,7 @@
 //! `Model` - Macro for converting Structure to mango-orm Model.
 //! The model can access the database.
 //! The model can create, update, and delete documents in collections.
+//! For each Model, a typed query builder is generated - `<Model>Query` ( `Model::q()` ).
 //!
 //! `Form` - Macro for converting Structure to mango-orm Form.
 //! The form does not have access to the database.
@@ -182,6 +183,22 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
     let mut map_default_values: std::collections::HashMap<String, (String, String)> =
         std::collections::HashMap::new();
     let mut add_trait_custom_valid = quote! {impl AdditionalValidation for #model_name {}};
+    let mut add_trait_hooks = quote! {impl Hooks for #model_name {}};
+    // References to other Models.
+    // Hint: (field_name, ref_model, on_delete)
+    let mut ref_models: Vec<(String, String, String)> = Vec::new();
+    // Embedded documents.
+    // Hint: (field_name, widget_type, embedded_type)
+    let mut embedded_docs: Vec<(String, String, String)> = Vec::new();
+    // Indexes of the collection.
+    // Hint: "field, -field; unique sparse: field, field; text: field, field"
+    let mut indexes_param = String::new();
+    // Indexes declared in the field attributes.
+    let mut field_indexes: Vec<Index> = Vec::new();
+    // Storage of files for the widgets `inputFile` and `inputImage`.
+    let mut storage_param = String::from("default");
+    // Time zone for the widgets `inputDate` and `inputDateTime`.
+    let mut timezone_param = String::from("UTC");
 
     // Get Model attributes.
     // *********************************************************************************************
@@ -248,6 +265,36 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                             model_name.to_string(),
                         )
                     }
+                } else if mnv.path.is_ident("is_soft_delete") {
+                    if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                        trans_meta.is_soft_delete = lit_bool.value;
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `is_soft_delete`. Use the `bool` type.",
+                            model_name.to_string(),
+                        )
+                    }
+                } else if mnv.path.is_ident("is_audit_log") {
+                    if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                        trans_meta.is_audit_log = lit_bool.value;
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `is_audit_log`. Use the `bool` type.",
+                            model_name.to_string(),
+                        )
+                    }
+                } else if mnv.path.is_ident("is_versioned") {
+                    if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                        trans_meta.is_versioned = lit_bool.value;
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `is_versioned`. Use the `bool` type.",
+                            model_name.to_string(),
+                        )
+                    }
                 } else if mnv.path.is_ident("ignore_fields") {
                     if let syn::Lit::Str(lit_str) = &mnv.lit {
                         let mut value = lit_str.value();
@@ -265,6 +312,37 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                             model_name.to_string(),
                         )
                     }
+                } else if mnv.path.is_ident("indexes") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        indexes_param = lit_str.value().trim().to_string();
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `indexes`. Use the type `&str` in \
+                            the format - <field, -field; unique: field, field>.",
+                            model_name.to_string(),
+                        )
+                    }
+                } else if mnv.path.is_ident("storage") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        storage_param = lit_str.value().trim().to_string();
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `storage`. Use the type `&str`.",
+                            model_name.to_string(),
+                        )
+                    }
+                } else if mnv.path.is_ident("timezone") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        timezone_param = lit_str.value().trim().to_string();
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `timezone`. Use the type `&str`.",
+                            model_name.to_string(),
+                        )
+                    }
                 } else if mnv.path.is_ident("is_use_add_valid") {
                     if let syn::Lit::Bool(lit_bool) = &mnv.lit {
                         if lit_bool.value {
@@ -277,6 +355,18 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                             model_name.to_string(),
                         )
                     }
+                } else if mnv.path.is_ident("is_use_hooks") {
+                    if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                        if lit_bool.value {
+                            add_trait_hooks = quote! {};
+                        }
+                    } else {
+                        panic!(
+                            "Model: `{}` : Could not determine value for \
+                            parameter `is_use_hooks`. Use the `bool` type.",
+                            model_name.to_string(),
+                        )
+                    }
                 }
             }
         }
@@ -296,6 +386,16 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
             let new_field = new_field.named.first().unwrap().to_owned();
             &fields.push(new_field);
 
+            // Add new field `version` ( optimistic concurrency ).
+            if trans_meta.is_versioned {
+                let new_field: syn::FieldsNamed = syn::parse2(quote! {
+                    {#[serde(default)] #[field_attrs(widget = "hiddenI64")] pub version: Option<i64>}
+                })
+                .unwrap_or_else(|err| panic!("{}", err.to_string()));
+                let new_field = new_field.named.first().unwrap().to_owned();
+                fields.push(new_field);
+            }
+
             // Get the number of fields.
             trans_meta.fields_count = fields.len();
 
@@ -354,6 +454,17 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                 };
                 // Allow Validation - Whether the Widget supports the current field type.
                 let mut check_field_type = true;
+                // Parameters of reference widgets (`selectRef`, `selectRefMult`).
+                let mut ref_model = String::new();
+                let mut on_delete = String::new();
+                // Index parameters (`index`, `sparse`, `expire_after`).
+                let mut index_order = String::new();
+                let mut index_sparse = false;
+                let mut expire_after = 0_u32;
+                // Storage of files (`inputFile`, `inputImage`).
+                let mut field_storage = String::new();
+                // Time zone (`inputDate`, `inputDateTime`).
+                let mut field_timezone = String::new();
 
                 // Get field attributes.
                 if attrs.is_some() {
@@ -365,16 +476,122 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                                         if let syn::Meta::NameValue(mnv) = meta {
                                             let attr_name =
                                                 &mnv.path.get_ident().unwrap().to_string()[..];
-                                            get_param_value(
-                                                attr_name,
-                                                &mnv,
-                                                &mut widget,
-                                                model_name.to_string().as_ref(),
-                                                field_name.as_ref(),
-                                                field_type.as_ref(),
-                                                &mut check_field_type,
-                                                "Model",
-                                            );
+                                            match attr_name {
+                                                "ref_model" => {
+                                                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                                                        ref_model =
+                                                            lit_str.value().trim().to_string();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `ref_model`. \
+                                                            Example: \"User\"",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "on_delete" => {
+                                                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                                                        on_delete =
+                                                            lit_str.value().trim().to_string();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `on_delete`. \
+                                                            Example: \"cascade\"",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "index" => {
+                                                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                                                        index_order =
+                                                            lit_str.value().trim().to_string();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `index`. \
+                                                            Example: \"asc\"",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "sparse" => {
+                                                    if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                                                        index_sparse = lit_bool.value;
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `sparse`. \
+                                                            Use the `bool` type.",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "storage" => {
+                                                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                                                        field_storage =
+                                                            lit_str.value().trim().to_string();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `storage`. \
+                                                            Example: \"s3\"",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "timezone" => {
+                                                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                                                        field_timezone =
+                                                            lit_str.value().trim().to_string();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `timezone`. \
+                                                            Example: \"Europe/Berlin\"",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                "expire_after" => {
+                                                    if let syn::Lit::Int(lit_int) = &mnv.lit {
+                                                        expire_after =
+                                                            lit_int.base10_parse::<u32>().unwrap();
+                                                    } else {
+                                                        panic!(
+                                                            "Model: `{}` > Field: `{}` : \
+                                                            Could not determine value for \
+                                                            parameter `expire_after`. \
+                                                            Use the `u32` type ( seconds ).",
+                                                            model_name.to_string(),
+                                                            field_name
+                                                        )
+                                                    }
+                                                }
+                                                _ => get_param_value(
+                                                    attr_name,
+                                                    &mnv,
+                                                    &mut widget,
+                                                    model_name.to_string().as_ref(),
+                                                    field_name.as_ref(),
+                                                    field_type.as_ref(),
+                                                    &mut check_field_type,
+                                                    "Model",
+                                                ),
+                                            }
                                         }
                                     }
                                 }
@@ -407,6 +624,179 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                         )
                     }
                 }
+                // Validation of parameters for embedded document widgets.
+                if widget.widget.starts_with("embedded") {
+                    let embedded_type = get_embedded_type(
+                        widget.widget.as_ref(),
+                        field_type.as_ref(),
+                        model_name.to_string().as_ref(),
+                        field_name.as_ref(),
+                        "Model",
+                    );
+                    if widget.unique {
+                        panic!(
+                            "Model: `{}` > Field: `{}` : \
+                            Embedded document widgets do not support the `unique` parameter.",
+                            model_name, field_name
+                        )
+                    }
+                    if widget.widget == "embeddedDocMult" {
+                        widget.value = "[]".to_string();
+                    }
+                    embedded_docs.push((field_name.clone(), widget.widget.clone(), embedded_type));
+                }
+                // Validation of parameters for reference widgets.
+                if widget.widget.contains("Ref") {
+                    if ref_model.is_empty() {
+                        panic!(
+                            "Model: `{}` > Field: `{}` : \
+                            For reference widgets, the `ref_model` parameter is required.",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    if syn::parse_str::<syn::Path>(ref_model.as_str()).is_err() {
+                        panic!(
+                            "Model: `{}` > Field: `{}` > Parameter: `ref_model` : \
+                            Invalid Model name. Example: \"User\"",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    if on_delete.is_empty() {
+                        on_delete = "restrict".to_string();
+                    } else if !["restrict", "cascade", "set_null"].contains(&on_delete.as_str()) {
+                        panic!(
+                            "Model: `{}` > Field: `{}` > Parameter: `on_delete` : \
+                            Valid values - `restrict`, `cascade`, `set_null`.",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    ref_models.push((field_name.clone(), ref_model, on_delete));
+                } else if !ref_model.is_empty() || !on_delete.is_empty() {
+                    panic!(
+                        "Model: `{}` > Field: `{}` : \
+                        The `ref_model` and `on_delete` parameters are only \
+                        allowed for reference widgets.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
+                // Validation of index parameters.
+                if widget.widget == "inputGeoPoint" {
+                    if !index_order.is_empty() || widget.unique || expire_after > 0 {
+                        panic!(
+                            "Model: `{}` > Field: `{}` : \
+                            The `index`, `unique` and `expire_after` parameters are not allowed \
+                            for the `inputGeoPoint` widget ( the `2dsphere` index is created \
+                            automatically ).",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    // Geospatial queries require a `2dsphere` index.
+                    if !trans_meta.ignore_fields.contains(&field_name) {
+                        index_order = "2dsphere".to_string();
+                    }
+                } else if !index_order.is_empty()
+                    && !["asc", "desc", "text"].contains(&index_order.as_str())
+                {
+                    panic!(
+                        "Model: `{}` > Field: `{}` > Parameter: `index` : \
+                        Valid values - `asc`, `desc`, `text`.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
+                if expire_after > 0 {
+                    if !["inputDate", "inputDateTime"].contains(&widget.widget.as_str()) {
+                        panic!(
+                            "Model: `{}` > Field: `{}` : \
+                            The `expire_after` parameter is only allowed for \
+                            widgets `inputDate` and `inputDateTime`.",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    if index_order == "text" {
+                        panic!(
+                            "Model: `{}` > Field: `{}` : \
+                            The `expire_after` parameter is not compatible with the text index.",
+                            model_name.to_string(),
+                            field_name
+                        )
+                    }
+                    if index_order.is_empty() {
+                        index_order = "asc".to_string();
+                    }
+                }
+                if index_sparse && index_order.is_empty() && !widget.unique {
+                    panic!(
+                        "Model: `{}` > Field: `{}` : \
+                        The `sparse` parameter requires the `index` or `unique` parameter.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
+                if !index_order.is_empty() && trans_meta.ignore_fields.contains(&field_name) {
+                    panic!(
+                        "Model: `{}` > Field: `{}` : \
+                        Indexes for ignored fields are not allowed.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
+                // Unique fields are backed by a unique index.
+                let is_unique_index: bool = widget.unique
+                    && widget.widget != "inputPassword"
+                    && !trans_meta.ignore_fields.contains(&field_name);
+                if !index_order.is_empty() || is_unique_index {
+                    if index_order.is_empty() {
+                        index_order = "asc".to_string();
+                    }
+                    field_indexes.push(Index {
+                        keys: vec![(field_name.clone(), index_order)],
+                        unique: is_unique_index,
+                        sparse: index_sparse,
+                        expire_after,
+                        ..Default::default()
+                    });
+                }
+                // Storage of files.
+                if ["inputFile", "inputImage"].contains(&widget.widget.as_str()) {
+                    if field_storage.is_empty() {
+                        field_storage = storage_param.clone();
+                    }
+                    trans_meta
+                        .map_storages
+                        .insert(field_name.clone(), field_storage);
+                } else if !field_storage.is_empty() {
+                    panic!(
+                        "Model: `{}` > Field: `{}` : \
+                        The `storage` parameter is only allowed for \
+                        widgets `inputFile` and `inputImage`.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
+                // Time zone of dates.
+                if ["inputDate", "inputDateTime"].contains(&widget.widget.as_str()) {
+                    if field_timezone.is_empty() {
+                        field_timezone = timezone_param.clone();
+                    }
+                    trans_meta
+                        .map_timezones
+                        .insert(field_name.clone(), field_timezone);
+                } else if !field_timezone.is_empty() {
+                    panic!(
+                        "Model: `{}` > Field: `{}` : \
+                        The `timezone` parameter is only allowed for \
+                        widgets `inputDate` and `inputDateTime`.",
+                        model_name.to_string(),
+                        field_name
+                    )
+                }
                 // Validation the `min` and` max` parameters for date and time.
                 if widget.widget == "inputDate".to_string() {
                     let re_valid_date = regex::RegexBuilder::new(
@@ -444,7 +834,7 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                 }
                 if widget.widget == "inputDateTime".to_string() {
                     let re_valid_datetime = regex::RegexBuilder::new(
-                    r"^(?:[1-9]\d{3}-(?:(?:0[1-9]|1[0-2])-(?:0[1-9]|1\d|2[0-8])|(?:0[13-9]|1[0-2])-(?:29|30)|(?:0[13578]|1[02])-31)|(?:[1-9]\d(?:0[48]|[2468][048]|[13579][26])|(?:[2468][048]|[13579][26])00)-02-29)T(?:[01]\d|2[0-3]):[0-5]\d$"
+                    r"^(?:[1-9]\d{3}-(?:(?:0[1-9]|1[0-2])-(?:0[1-9]|1\d|2[0-8])|(?:0[13-9]|1[0-2])-(?:29|30)|(?:0[13578]|1[02])-31)|(?:[1-9]\d(?:0[48]|[2468][048]|[13579][26])|(?:[2468][048]|[13579][26])00)-02-29)T(?:[01]\d|2[0-3]):[0-5]\d(?::[0-5]\d(?:\.\d{1,9})?)?(?:[zZ]|[+-](?:[01]\d|2[0-3]):[0-5]\d)?$"
                         )
                         .build()
                         .unwrap();
@@ -476,6 +866,35 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                         }
                     }
                 }
+                // Validation the `default`, `min` and` max` parameters for time.
+                check_time_widget(
+                    &mut widget,
+                    "Model",
+                    model_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the `precision`, `scale`, `default`, `min`, `max` and `step`
+                // parameters for decimal numbers.
+                check_decimal_widget(
+                    &mut widget,
+                    "Model",
+                    model_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the `default` parameter for geospatial point.
+                check_geo_point_widget(
+                    &mut widget,
+                    "Model",
+                    model_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the parameters of list widgets.
+                check_list_widget(
+                    &mut widget,
+                    "Model",
+                    model_name.to_string().as_ref(),
+                    &field_name,
+                );
                 // Add field name and widget name to the map.
                 trans_meta
                     .map_widget_type
@@ -540,6 +959,24 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                 )
             }
         }
+        // For reference widgets, the default is invalid.
+        if widget.widget.contains("Ref") {
+            if !widget.value.is_empty() {
+                panic!(
+                    "Model: `{}` > Field: `{}` : \
+                    For reference widgets, it is unacceptable to use default values.",
+                    model_name.to_string(),
+                    field_name,
+                )
+            } else if trans_meta.ignore_fields.contains(&widget.name) {
+                panic!(
+                    "Model: `{}` > Field: `{}` : \
+                    Reference widgets for ignored fields are not allowed.",
+                    model_name.to_string(),
+                    field_name,
+                )
+            }
+        }
         // File fields must not be ignored.
         match widget.widget.as_str() {
             "inputFile" | "inputImage" if trans_meta.ignore_fields.contains(field_name) => {
@@ -589,6 +1026,77 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
         );
     }
     trans_meta.map_default_values = map_default_values;
+    // Collect the indexes of the collection.
+    trans_meta.indexes = get_indexes(
+        indexes_param.as_ref(),
+        field_indexes,
+        &trans_meta,
+        model_name.to_string().as_ref(),
+    );
+
+    // Getter and Setter for field `version` ( optimistic concurrency ).
+    // ---------------------------------------------------------------------------------------------
+    let version_methods = if trans_meta.is_versioned {
+        quote! {
+            fn get_version(&self) -> Option<i64> {
+                self.version
+            }
+            fn set_version(&mut self, value: i64) {
+                self.version = Some(value);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Typed query builder.
+    // ( Methods for the fields stored in the database, the value type is the type of the field )
+    // ---------------------------------------------------------------------------------------------
+    let query_name = Ident::new(format!("{}Query", model_name).as_str(), model_name.span());
+    let query_vis = &ast.vis;
+    let query_fields_info: Vec<(String, String)> = trans_meta
+        .fields_name
+        .iter()
+        .filter(|field_name| !trans_meta.ignore_fields.contains(field_name))
+        .map(|field_name| {
+            (
+                field_name.clone(),
+                trans_meta.map_widget_type.get(field_name).unwrap().clone(),
+            )
+        })
+        .filter(|(_, widget_type)| {
+            ![
+                "inputPassword",
+                "inputFile",
+                "inputImage",
+                "embeddedDoc",
+                "embeddedDocMult",
+            ]
+            .contains(&widget_type.as_str())
+        })
+        .collect();
+    let query_fields = query_fields_info.iter().map(|(field_name, widget_type)| {
+        let method_name = Ident::new(field_name.as_str(), model_name.span());
+        let value_type = get_widget_info(widget_type.as_str()).unwrap().0;
+        let value_type: syn::Type = syn::parse_str(
+            value_type
+                .trim_start_matches("Vec < ")
+                .trim_end_matches(" >"),
+        )
+        .unwrap();
+        quote! {
+            pub fn #method_name(self) -> QueryField<Self, #value_type> {
+                QueryField::new(self, #field_name)
+            }
+        }
+    });
+    let query_map_widget_type = query_fields_info
+        .iter()
+        .map(|(field_name, widget_type)| quote! {(#field_name, #widget_type)});
+    let query_map_timezones = trans_meta
+        .map_timezones
+        .iter()
+        .map(|(field_name, timezone)| quote! {(#field_name, #timezone)});
 
     // trans_meta to Json-line.
     // ---------------------------------------------------------------------------------------------
@@ -602,6 +1110,49 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
         Err(err) => panic!("Model: `{}` : {}", model_name.to_string(), err.to_string()),
     };
 
+    // Adding information about references to other Models.
+    // ( Resolved at runtime, from the metadata of the referenced Model )
+    // ---------------------------------------------------------------------------------------------
+    let add_ref_models = ref_models.iter().map(|(field_name, ref_model, on_delete)| {
+        let ref_path: syn::Path = syn::parse_str(ref_model.as_str()).unwrap();
+        quote! {
+            let ref_meta: Meta = <#ref_path as ToModel>::meta_base()?;
+            meta.map_ref_models.insert(
+                #field_name.to_string(),
+                RefModel {
+                    model_name: ref_meta.model_name,
+                    db_client_name: ref_meta.db_client_name,
+                    database_name: ref_meta.database_name,
+                    collection_name: ref_meta.collection_name,
+                    on_delete: #on_delete.to_string(),
+                },
+            );
+        }
+    });
+
+    // Adding information about embedded documents.
+    // ( Resolved at runtime, from the Form of the embedded document )
+    // ---------------------------------------------------------------------------------------------
+    let add_embedded_docs = embedded_docs.iter().map(|(field_name, _, embedded_type)| {
+        let embedded_path: syn::Path = syn::parse_str(embedded_type.as_str()).unwrap();
+        quote! {
+            meta.map_embedded.insert(
+                #field_name.to_string(),
+                <#embedded_path as ToForm>::embedded_doc()?,
+            );
+        }
+    });
+    let add_embedded_widgets = embedded_docs
+        .iter()
+        .filter(|(_, widget_type, _)| widget_type == "embeddedDoc")
+        .map(|(field_name, _, embedded_type)| {
+            let embedded_path: syn::Path = syn::parse_str(embedded_type.as_str()).unwrap();
+            quote! {
+                map_widgets.get_mut(#field_name).unwrap().value =
+                    serde_json::to_string(&vec![<#embedded_path as ToForm>::embedded_doc()?])?;
+            }
+        });
+
     // Implementation of methods.
     // *********************************************************************************************
     let output = quote! {
@@ -626,7 +1177,19 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
 
             // Get metadata of Model.
             // -------------------------------------------------------------------------------------
-            fn meta() -> Result<Meta, Box<dyn std::error::Error>> {
+            fn meta() -> Result<Meta, mango_orm::Error> {
+                let mut meta = Self::meta_base()?;
+                // Add information about references to other Models.
+                #(#add_ref_models)*
+                // Add the function for deleting documents ( `cascade` policy ).
+                meta.delete_fn = Some(mango_orm::models::delete_doc::<Self>);
+
+                Ok(meta)
+            }
+
+            // Get metadata of Model without information about references to other Models.
+            // -------------------------------------------------------------------------------------
+            fn meta_base() -> Result<Meta, mango_orm::Error> {
                 let re = regex::Regex::new(r"(?P<upper_chr>[A-Z])").unwrap();
                 let mut meta = serde_json::from_str::<Meta>(&#trans_meta)?;
                 let service_name: String = SERVICE_NAME.trim().to_string();
@@ -659,6 +1222,8 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                     re.replace_all(&meta.model_name[..], "_$upper_chr")
                 )
                 .to_lowercase();
+                // Add information about embedded documents.
+                #(#add_embedded_docs)*
 
                 Ok(meta)
             }
@@ -667,8 +1232,14 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
             // Hint: <field name, Widget>
             // -------------------------------------------------------------------------------------
             fn widgets() -> Result<std::collections::HashMap<String, Widget>,
-                Box<dyn std::error::Error>> {
-                Ok(serde_json::from_str::<TransMapWidgets>(&#trans_map_widgets)?.map_widgets)
+                mango_orm::Error> {
+                #[allow(unused_mut)]
+                let mut map_widgets =
+                    serde_json::from_str::<TransMapWidgets>(&#trans_map_widgets)?.map_widgets;
+                // Add the default state of embedded documents.
+                #(#add_embedded_widgets)*
+
+                Ok(map_widgets)
             }
 
             // Getter and Setter for field `hash`.
@@ -680,10 +1251,14 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                 self.hash = Some(value);
             }
 
+            // Getter and Setter for field `version`.
+            // -------------------------------------------------------------------------------------
+            #version_methods
+
             // Serialize model to json-line.
             // -------------------------------------------------------------------------------------
             fn self_to_json(&self)
-                -> Result<serde_json::value::Value, Box<dyn std::error::Error>> {
+                -> Result<serde_json::value::Value, mango_orm::Error> {
                 Ok(serde_json::to_value(self)?)
             }
         }
@@ -700,16 +1275,62 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
         // *****************************************************************************************
         #add_trait_custom_valid
 
+        // Lifecycle hooks ( pre/post save, pre/post delete, post check ).
+        // *****************************************************************************************
+        #add_trait_hooks
+
         // Database Query API
         // *****************************************************************************************
         // Common database query methods.
         impl QCommon for #model_name {}
         // Query methods for a Model instance.
         impl QPaladins for #model_name {}
+        // Query methods for the history of documents ( audit trail ).
+        impl QAudit for #model_name {}
 
         // Rendering HTML-controls code for Form.
         // *****************************************************************************************
         impl HtmlControls for #model_name {}
+
+        // Ingestion of uploaded files ( `inputFile` and `inputImage` ).
+        // *****************************************************************************************
+        impl Upload for #model_name {}
+
+        // Typed query builder.
+        // *****************************************************************************************
+        #[derive(Clone, Debug)]
+        #query_vis struct #query_name {
+            query: Query,
+        }
+
+        impl QueryBuilder for #query_name {
+            type Model = #model_name;
+
+            fn query(&self) -> &Query {
+                &self.query
+            }
+            fn query_mut(&mut self) -> &mut Query {
+                &mut self.query
+            }
+        }
+
+        impl #query_name {
+            #(#query_fields)*
+        }
+
+        impl #model_name {
+            // Get typed query builder.
+            // -------------------------------------------------------------------------------------
+            pub fn q() -> #query_name {
+                #query_name {
+                    query: Query::new(
+                        stringify!(#model_name),
+                        &[#(#query_map_widget_type),*],
+                    )
+                    .with_timezones(&[#(#query_map_timezones),*]),
+                }
+            }
+        }
     };
 
     // Hand the output tokens back to the compiler.
@@ -792,6 +1413,9 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
     let mut trans_map_widgets: TransMapWidgets = Default::default();
     let mut fields_name: Vec<String> = Vec::new();
     let mut add_trait_custom_valid = quote! {impl AdditionalValidation for #form_name {}};
+    // Embedded documents.
+    // Hint: (field_name, widget_type, embedded_type)
+    let mut embedded_docs: Vec<(String, String, String)> = Vec::new();
 
     // Get Form attributes.
     // *********************************************************************************************
@@ -914,6 +1538,56 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
                         )
                     }
                 }
+                // Validation of parameters for embedded document widgets.
+                if widget.widget.starts_with("embedded") {
+                    let embedded_type = get_embedded_type(
+                        widget.widget.as_ref(),
+                        field_type.as_ref(),
+                        form_name.to_string().as_ref(),
+                        field_name.as_ref(),
+                        "Form",
+                    );
+                    if widget.unique {
+                        panic!(
+                            "Form: `{}` > Field: `{}` : \
+                            Embedded document widgets do not support the `unique` parameter.",
+                            form_name, field_name
+                        )
+                    }
+                    if widget.widget == "embeddedDocMult" {
+                        widget.value = "[]".to_string();
+                    }
+                    embedded_docs.push((field_name.clone(), widget.widget.clone(), embedded_type));
+                }
+                // Validation the `default`, `min` and` max` parameters for time.
+                check_time_widget(
+                    &mut widget,
+                    "Form",
+                    form_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the `precision`, `scale`, `default`, `min`, `max` and `step`
+                // parameters for decimal numbers.
+                check_decimal_widget(
+                    &mut widget,
+                    "Form",
+                    form_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the `default` parameter for geospatial point.
+                check_geo_point_widget(
+                    &mut widget,
+                    "Form",
+                    form_name.to_string().as_ref(),
+                    &field_name,
+                );
+                // Validation the parameters of list widgets.
+                check_list_widget(
+                    &mut widget,
+                    "Form",
+                    form_name.to_string().as_ref(),
+                    &field_name,
+                );
                 // Add widget to map.
                 trans_map_widgets
                     .map_widgets
@@ -943,6 +1617,14 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
                 field_name,
             )
         }
+        if widget.widget.contains("Ref") {
+            panic!(
+                "Form: `{}` > Field: `{}` : \
+                Forms are not supported by reference widgets.",
+                form_name.to_string(),
+                field_name,
+            )
+        }
     }
     // TransMapWidgets to Json-string
     let trans_map_widgets: String = match serde_json::to_string(&trans_map_widgets) {
@@ -954,6 +1636,27 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
         Ok(json_string) => json_string,
         Err(err) => panic!("Form: `{}` : {}", form_name.to_string(), err),
     };
+    // Adding information about embedded documents.
+    // ( Resolved at runtime, from the Form of the embedded document )
+    let add_embedded_docs = embedded_docs.iter().map(|(field_name, _, embedded_type)| {
+        let embedded_path: syn::Path = syn::parse_str(embedded_type.as_str()).unwrap();
+        quote! {
+            map_embedded.insert(
+                #field_name.to_string(),
+                <#embedded_path as ToForm>::embedded_doc()?,
+            );
+        }
+    });
+    let add_embedded_widgets = embedded_docs
+        .iter()
+        .filter(|(_, widget_type, _)| widget_type == "embeddedDoc")
+        .map(|(field_name, _, embedded_type)| {
+            let embedded_path: syn::Path = syn::parse_str(embedded_type.as_str()).unwrap();
+            quote! {
+                map_widgets.get_mut(#field_name).unwrap().value =
+                    serde_json::to_string(&vec![<#embedded_path as ToForm>::embedded_doc()?])?;
+            }
+        });
 
     // Implementation of methods.
     // *********************************************************************************************
@@ -983,7 +1686,7 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
 
             // Get fields name list.
             // -------------------------------------------------------------------------------------
-            fn fields_name() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+            fn fields_name() -> Result<Vec<String>, mango_orm::Error> {
                 Ok(serde_json::from_str::<Vec<String>>(#fields_name)?)
             }
 
@@ -991,14 +1694,33 @@ fn impl_create_form(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStrea
             // Hint: <field name, Widget>
             // -------------------------------------------------------------------------------------
             fn widgets() -> Result<std::collections::HashMap<String, Widget>,
-                Box<dyn std::error::Error>> {
-                Ok(serde_json::from_str::<TransMapWidgets>(&#trans_map_widgets)?.map_widgets)
+                mango_orm::Error> {
+                #[allow(unused_mut)]
+                let mut map_widgets =
+                    serde_json::from_str::<TransMapWidgets>(&#trans_map_widgets)?.map_widgets;
+                // Add the default state of embedded documents.
+                #(#add_embedded_widgets)*
+
+                Ok(map_widgets)
+            }
+
+            // Get map of embedded documents for form fields.
+            // Hint: <field name, EmbeddedDoc>
+            // -------------------------------------------------------------------------------------
+            fn embedded_docs() -> Result<std::collections::HashMap<String, EmbeddedDoc>,
+                mango_orm::Error> {
+                #[allow(unused_mut)]
+                let mut map_embedded: std::collections::HashMap<String, EmbeddedDoc> =
+                    std::collections::HashMap::new();
+                #(#add_embedded_docs)*
+
+                Ok(map_embedded)
             }
 
             // Serialize Form to json-line.
             // -------------------------------------------------------------------------------------
             fn self_to_json(&self)
-                -> Result<serde_json::value::Value, Box<dyn std::error::Error>> {
+                -> Result<serde_json::value::Value, mango_orm::Error> {
                 Ok(serde_json::to_value(self)?)
             }
         }
@@ -1064,12 +1786,28 @@ struct Meta {
     pub is_add_docs: bool,
     pub is_up_docs: bool,
     pub is_del_docs: bool,
+    // `delete()` marks the document with `deleted_at` instead of removing it.
+    pub is_soft_delete: bool,
+    // Save and delete are recorded in the audit trail ( `audit_log` collection ).
+    pub is_audit_log: bool,
+    // Optimistic concurrency - `save()` checks and increments the `version` field.
+    pub is_versioned: bool,
     pub map_field_type: std::collections::HashMap<String, String>,
     pub map_widget_type: std::collections::HashMap<String, String>,
     // <field_name, (widget_type, value)>
     pub map_default_values: std::collections::HashMap<String, (String, String)>,
     // List of field names that will not be saved to the database.
     pub ignore_fields: Vec<String>,
+    // <field_name, RefModel> ( The value is determined automatically )
+    pub map_ref_models: std::collections::HashMap<String, String>,
+    // <field_name, EmbeddedDoc> ( The value is determined automatically )
+    pub map_embedded: std::collections::HashMap<String, String>,
+    // <field_name, storage name> - For widgets `inputFile` and `inputImage`.
+    pub map_storages: std::collections::HashMap<String, String>,
+    // <field_name, time zone> - For widgets `inputDate` and `inputDateTime`.
+    pub map_timezones: std::collections::HashMap<String, String>,
+    // Indexes of the collection.
+    pub indexes: Vec<Index>,
 }
 
 impl Default for Meta {
@@ -1088,15 +1826,35 @@ impl Default for Meta {
             is_add_docs: true,
             is_up_docs: true,
             is_del_docs: true,
+            is_soft_delete: false,
+            is_audit_log: false,
+            is_versioned: false,
             map_field_type: std::collections::HashMap::new(),
             map_widget_type: std::collections::HashMap::new(),
             map_default_values: std::collections::HashMap::new(),
             // List of field names that will not be saved to the database
             ignore_fields: Vec::new(),
+            map_ref_models: std::collections::HashMap::new(),
+            map_embedded: std::collections::HashMap::new(),
+            map_storages: std::collections::HashMap::new(),
+            map_timezones: std::collections::HashMap::new(),
+            indexes: Vec::new(),
         }
     }
 }
 
+// Index of the collection.
+// *************************************************************************************************
+#[derive(Default, Serialize)]
+struct Index {
+    pub name: String, // The value is determined automatically
+    // <field_name, `asc` | `desc` | `text`>
+    pub keys: Vec<(String, String)>,
+    pub unique: bool,
+    pub sparse: bool,
+    pub expire_after: u32, // TTL in seconds ( 0 - without TTL )
+}
+
 // Widget attributes.
 // *************************************************************************************************
 #[derive(Serialize)]
@@ -1122,6 +1880,12 @@ struct Widget {
     pub max: String,
     pub options: Vec<(String, String)>, // Hint: <value, Title> - <option value="value1">Title 1</option>
     pub thumbnails: Vec<(String, u32)>,
+    pub maxsize: u32,        // Maximum file size in bytes ( 0 - without limit )
+    pub precision: u32,      // Maximum number of digits of the decimal number ( 1-34 )
+    pub scale: u32,          // Number of digits after the decimal point
+    pub min_items: u32,      // Minimum number of items of the list ( 0 - without limit )
+    pub max_items: u32,      // Maximum number of items of the list ( 0 - without limit )
+    pub unique_items: bool,  // The items of the list must not be repeated
     pub other_attrs: String, // "autofocus tabindex=\"some number\" size=\"some number\" ..."
     pub css_classes: String, // "class-name class-name ..."
     pub hint: String,
@@ -1154,6 +1918,12 @@ impl Default for Widget {
             max: String::new(),
             options: Vec::new(),
             thumbnails: Vec::new(),
+            maxsize: 0_u32,
+            precision: 34_u32,
+            scale: 2_u32,
+            min_items: 0_u32,
+            max_items: 0_u32,
+            unique_items: false,
             other_attrs: String::new(),
             css_classes: String::new(),
             hint: String::new(),
@@ -1172,6 +1942,294 @@ struct TransMapWidgets {
     pub map_widgets: std::collections::HashMap<String, Widget>,
 }
 
+// Validation the `default`, `min` and` max` parameters for `inputTime` and `inputTimeRange`.
+// ( For `inputTimeRange`, the `pattern` parameter is added for client-side validation )
+// *************************************************************************************************
+fn check_time_widget(widget: &mut Widget, model_or_form: &str, model_name: &str, field_name: &str) {
+    if widget.widget != "inputTime" && widget.widget != "inputTimeRange" {
+        return;
+    }
+    let re_valid_time = regex::RegexBuilder::new(r"^(?:[01]\d|2[0-3]):[0-5]\d$")
+        .build()
+        .unwrap();
+    let re_valid_time_range =
+        regex::RegexBuilder::new(r"^(?:[01]\d|2[0-3]):[0-5]\d-(?:[01]\d|2[0-3]):[0-5]\d$")
+            .build()
+            .unwrap();
+    if widget.widget == "inputTime" {
+        if !widget.value.is_empty() && !re_valid_time.is_match(&widget.value) {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                Incorrect time format. Example: \"09:00\"",
+                model_or_form, model_name, field_name
+            )
+        }
+    } else {
+        if !widget.value.is_empty() && !re_valid_time_range.is_match(&widget.value) {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                Incorrect time range format. Example: \"09:00-18:00\"",
+                model_or_form, model_name, field_name
+            )
+        }
+        if widget.pattern.is_empty() {
+            widget.pattern = r"(?:[01]\d|2[0-3]):[0-5]\d-(?:[01]\d|2[0-3]):[0-5]\d".to_string();
+        }
+    }
+    if !widget.min.is_empty() && !re_valid_time.is_match(&widget.min) {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `min` : \
+            Incorrect time format. Example: \"09:00\"",
+            model_or_form, model_name, field_name
+        )
+    }
+    if !widget.max.is_empty() && !re_valid_time.is_match(&widget.max) {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `max` : \
+            Incorrect time format. Example: \"18:00\"",
+            model_or_form, model_name, field_name
+        )
+    }
+}
+
+// Validation the `precision`, `scale`, `default`, `min`, `max` and `step` parameters
+// for `numberDecimal`, `rangeDecimal` and `hiddenDecimal`.
+// ( Numbers are brought to the `scale`, the default `step` is one unit of the last digit )
+// *************************************************************************************************
+fn check_decimal_widget(
+    widget: &mut Widget,
+    model_or_form: &str,
+    model_name: &str,
+    field_name: &str,
+) {
+    if !widget.widget.ends_with("Decimal") {
+        if widget.precision != 34 {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `precision` : \
+                The `precision` parameter is only allowed for decimal widgets.",
+                model_or_form, model_name, field_name
+            )
+        }
+        return;
+    }
+    if widget.precision == 0 || widget.precision > 34 {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `precision` : \
+            The value must be in the range from 1 to 34.",
+            model_or_form, model_name, field_name
+        )
+    }
+    if widget.scale > widget.precision {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `scale` : \
+            The value must not exceed `precision`.",
+            model_or_form, model_name, field_name
+        )
+    }
+    if widget.step == "1" && widget.scale > 0 {
+        widget.step = format!("0.{}1", "0".repeat(widget.scale as usize - 1));
+    }
+    let scale: usize = widget.scale as usize;
+    let precision: usize = widget.precision as usize;
+    let re_valid_decimal = regex::RegexBuilder::new(r"^-?(\d+)(?:\.(\d+))?$")
+        .build()
+        .unwrap();
+    // Bring the number to the `scale` ( "9.5" -> "9.50" ).
+    let to_scale = |value: &str, param: &str| -> String {
+        let caps = re_valid_decimal.captures(value).unwrap_or_else(|| {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `{}` : \
+                Incorrect number format. Example: \"1234.56\"",
+                model_or_form, model_name, field_name, param
+            )
+        });
+        let int_part: &str = caps.get(1).unwrap().as_str();
+        let frac_part: &str = caps.get(2).map_or("", |item| item.as_str());
+        let frac_part: &str = frac_part.trim_end_matches('0');
+        if frac_part.len() > scale {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `{}` : \
+                No more than {} digits after the decimal point.",
+                model_or_form, model_name, field_name, param, scale
+            )
+        }
+        if int_part.trim_start_matches('0').len() + scale > precision {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `{}` : \
+                No more than {} digits.",
+                model_or_form, model_name, field_name, param, precision
+            )
+        }
+        let sign: &str = if value.starts_with('-') { "-" } else { "" };
+        let int_part: &str = match int_part.trim_start_matches('0') {
+            "" => "0",
+            int_part => int_part,
+        };
+        if scale == 0 {
+            format!("{}{}", sign, int_part)
+        } else {
+            format!("{}{}.{:0<width$}", sign, int_part, frac_part, width = scale)
+        }
+    };
+    if !widget.value.is_empty() {
+        widget.value = to_scale(widget.value.as_str(), "default");
+    }
+    if !widget.min.is_empty() {
+        widget.min = to_scale(widget.min.as_str(), "min");
+    }
+    if !widget.max.is_empty() {
+        widget.max = to_scale(widget.max.as_str(), "max");
+    }
+    widget.step = to_scale(widget.step.as_str(), "step");
+    if widget.step.starts_with('-') || !widget.step.chars().any(|c| ('1'..='9').contains(&c)) {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `step` : \
+            The value must be greater than zero.",
+            model_or_form, model_name, field_name
+        )
+    }
+}
+
+// Validation the `default` parameter for `inputGeoPoint` - "lat,lng".
+// ( The `pattern` parameter is added for client-side validation )
+// *************************************************************************************************
+fn check_geo_point_widget(
+    widget: &mut Widget,
+    model_or_form: &str,
+    model_name: &str,
+    field_name: &str,
+) {
+    if widget.widget != "inputGeoPoint" {
+        return;
+    }
+    if !widget.value.is_empty() {
+        let coordinates: Vec<f64> = widget
+            .value
+            .split(',')
+            .filter_map(|item| item.trim().parse::<f64>().ok())
+            .collect();
+        if coordinates.len() != 2
+            || widget.value.split(',').count() != 2
+            || !(-90_f64..=90_f64).contains(&coordinates[0])
+            || !(-180_f64..=180_f64).contains(&coordinates[1])
+        {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                Incorrect coordinates ( latitude from -90 to 90, longitude from -180 to 180 ). \
+                Example: \"52.520008,13.404954\"",
+                model_or_form, model_name, field_name
+            )
+        }
+    }
+    if widget.pattern.is_empty() {
+        widget.pattern = r"\s*-?\d+(?:\.\d+)?\s*,\s*-?\d+(?:\.\d+)?\s*".to_string();
+    }
+}
+
+// Validation the `min_items`, `max_items`, `unique_items`, `default`, `min`, `max` and `pattern`
+// parameters for `listText`, `listI32`, `listU32`, `listI64` and `listF64`.
+// ( The `default` parameter is a json-line of the array - "[\"rust\",\"mongodb\"]" )
+// *************************************************************************************************
+fn check_list_widget(widget: &mut Widget, model_or_form: &str, model_name: &str, field_name: &str) {
+    if !widget.widget.starts_with("list") {
+        if widget.min_items != 0 || widget.max_items != 0 || widget.unique_items {
+            panic!(
+                "{}: `{}` > Field: `{}` : \
+                The `min_items`, `max_items` and `unique_items` parameters \
+                are only allowed for list widgets.",
+                model_or_form, model_name, field_name
+            )
+        }
+        return;
+    }
+    if widget.unique {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `unique` : \
+            List widgets do not support the `unique` parameter, use `unique_items`.",
+            model_or_form, model_name, field_name
+        )
+    }
+    if widget.max_items > 0 && widget.min_items > widget.max_items {
+        panic!(
+            "{}: `{}` > Field: `{}` > Parameter: `min_items` : \
+            The value must not exceed `max_items`.",
+            model_or_form, model_name, field_name
+        )
+    }
+    let is_text: bool = widget.widget == "listText";
+    if is_text {
+        if !widget.min.is_empty() || !widget.max.is_empty() {
+            panic!(
+                "{}: `{}` > Field: `{}` : \
+                For the `listText` widget, use the `minlength` and `maxlength` parameters \
+                instead of `min` and `max`.",
+                model_or_form, model_name, field_name
+            )
+        }
+        if !widget.pattern.is_empty()
+            && regex::Regex::new(format!("^(?:{})$", widget.pattern).as_str()).is_err()
+        {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `pattern` : \
+                Invalid regular expression.",
+                model_or_form, model_name, field_name
+            )
+        }
+    }
+    if !widget.value.is_empty() {
+        let items: Vec<serde_json::Value> = serde_json::from_str(widget.value.as_str())
+            .ok()
+            .filter(|items: &Vec<serde_json::Value>| {
+                items.iter().all(|item| match widget.widget.as_str() {
+                    "listText" => item.is_string(),
+                    "listI32" => item.as_i64().map_or(false, |number| {
+                        number >= i32::MIN as i64 && number <= i32::MAX as i64
+                    }),
+                    "listU32" => item
+                        .as_u64()
+                        .map_or(false, |number| number <= u32::MAX as u64),
+                    "listI64" => item.is_i64(),
+                    _ => item.is_number(),
+                })
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                    Incorrect list format. Example: {}",
+                    model_or_form,
+                    model_name,
+                    field_name,
+                    if is_text {
+                        "\"[\\\"rust\\\",\\\"mongodb\\\"]\""
+                    } else {
+                        "\"[1,2,3]\""
+                    }
+                )
+            });
+        let count: u32 = items.len() as u32;
+        if count < widget.min_items || (widget.max_items > 0 && count > widget.max_items) {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                The number of items is out of range between `min_items` and `max_items`.",
+                model_or_form, model_name, field_name
+            )
+        }
+        if widget.unique_items
+            && items
+                .iter()
+                .enumerate()
+                .any(|(idx, item)| items[..idx].contains(item))
+        {
+            panic!(
+                "{}: `{}` > Field: `{}` > Parameter: `default` : \
+                The items must not be repeated.",
+                model_or_form, model_name, field_name
+            )
+        }
+        widget.value = serde_json::to_string(&items).unwrap();
+    }
+}
+
 // Get widget info.
 // *************************************************************************************************
 fn get_widget_info<'a>(
@@ -1182,6 +2240,9 @@ fn get_widget_info<'a>(
         "inputColor" => ("String", "color"),
         "inputDate" => ("String", "date"),
         "inputDateTime" => ("String", "datetime"),
+        "inputTime" => ("String", "time"),
+        "inputTimeRange" => ("String", "text"),
+        "inputGeoPoint" => ("String", "text"),
         "inputEmail" => ("String", "email"),
         "inputFile" => ("String", "file"),
         "inputImage" => ("String", "file"),
@@ -1189,6 +2250,7 @@ fn get_widget_info<'a>(
         "numberU32" => ("u32", "number"),
         "numberI64" => ("i64", "number"),
         "numberF64" => ("f64", "number"),
+        "numberDecimal" => ("String", "number"),
         "inputPassword" => ("String", "password"),
         "radioText" => ("String", "radio"),
         "radioI32" => ("i32", "radio"),
@@ -1199,6 +2261,7 @@ fn get_widget_info<'a>(
         "rangeU32" => ("u32", "range"),
         "rangeI64" => ("i64", "range"),
         "rangeF64" => ("f64", "range"),
+        "rangeDecimal" => ("String", "range"),
         "inputPhone" => ("String", "tel"),
         "inputText" => ("String", "text"),
         "inputUrl" => ("String", "url"),
@@ -1226,16 +2289,216 @@ fn get_widget_info<'a>(
         "selectF64Dyn" => ("f64", "select"),
         "selectF64Mult" => ("Vec < f64 >", "select"),
         "selectF64MultDyn" => ("Vec < f64 >", "select"),
+        "selectRef" => ("String", "select"),
+        "selectRefMult" => ("Vec < String >", "select"),
+        "listText" => ("Vec < String >", "list"),
+        "listI32" => ("Vec < i32 >", "list"),
+        "listU32" => ("Vec < u32 >", "list"),
+        "listI64" => ("Vec < i64 >", "list"),
+        "listF64" => ("Vec < f64 >", "list"),
         "hiddenText" => ("String", "hidden"),
         "hiddenI32" => ("i32", "hidden"),
         "hiddenU32" => ("u32", "hidden"),
         "hiddenI64" => ("i64", "hidden"),
         "hiddenF64" => ("f64", "hidden"),
+        "hiddenDecimal" => ("String", "hidden"),
+        "embeddedDoc" => ("", "embedded"),
+        "embeddedDocMult" => ("", "embedded"),
         _ => Err("Invalid widget type.")?,
     };
     Ok(info)
 }
 
+// Get the indexes of the collection.
+// Hint: `indexes` - "field, -field; unique sparse: field, field; text: field, field"
+// *************************************************************************************************
+fn get_indexes<'a>(
+    indexes_param: &'a str,
+    field_indexes: Vec<Index>,
+    trans_meta: &'a Meta,
+    model_name: &'a str,
+) -> Vec<Index> {
+    let mut indexes: Vec<Index> = Vec::new();
+    // Indexes from the Model parameter `indexes`.
+    for group in indexes_param.split(';').map(|item| item.trim()) {
+        if group.is_empty() {
+            continue;
+        }
+        let (options, fields): (&str, &str) = match group.find(':') {
+            Some(pos) => (&group[..pos], &group[pos + 1..]),
+            None => ("", group),
+        };
+        let mut index = Index::default();
+        let mut is_text = false;
+        for option in options.split_whitespace() {
+            match option {
+                "unique" => index.unique = true,
+                "sparse" => index.sparse = true,
+                "text" => is_text = true,
+                _ => panic!(
+                    "Model: `{}` > Parameter: `indexes` : Undefined index option `{}`. \
+                    Valid options - `unique`, `sparse`, `text`.",
+                    model_name, option
+                ),
+            }
+        }
+        for field in fields.split(',').map(|item| item.trim()) {
+            let (field_name, order): (&str, &str) = if field.starts_with('-') {
+                (&field[1..], "desc")
+            } else if is_text {
+                (field, "text")
+            } else {
+                (field, "asc")
+            };
+            let is_model_field: bool = trans_meta.fields_name.contains(&field_name.to_string())
+                && field_name != "hash"
+                && !trans_meta.ignore_fields.contains(&field_name.to_string());
+            if !is_model_field && !["created_at", "updated_at"].contains(&field_name) {
+                panic!(
+                    "Model: `{}` > Parameter: `indexes` : \
+                    Model does not have a field named `{}` ( ignored fields are not allowed ).",
+                    model_name, field_name
+                )
+            }
+            if is_text {
+                let field_type: &str = trans_meta
+                    .map_field_type
+                    .get(field_name)
+                    .map(|item| item.as_str())
+                    .unwrap_or_default();
+                if order != "text" || !["String", "Vec < String >"].contains(&field_type) {
+                    panic!(
+                        "Model: `{}` > Parameter: `indexes` > Field: `{}` : \
+                        The text index is only allowed for fields of type \
+                        `String` and `Vec < String >`, without the `-` prefix.",
+                        model_name, field_name
+                    )
+                }
+            }
+            index.keys.push((field_name.to_string(), order.to_string()));
+        }
+        indexes.push(index);
+    }
+    // Indexes from the field attributes.
+    for field_index in field_indexes {
+        let (field_name, order) = &field_index.keys[0];
+        if order == "text" {
+            let field_type: &str = trans_meta.map_field_type.get(field_name).unwrap().as_str();
+            if !["String", "Vec < String >"].contains(&field_type) {
+                panic!(
+                    "Model: `{}` > Field: `{}` > Parameter: `index` : \
+                    The text index is only allowed for fields of type \
+                    `String` and `Vec < String >`.",
+                    model_name, field_name
+                )
+            }
+        }
+        match indexes
+            .iter_mut()
+            .find(|index| index.keys == field_index.keys)
+        {
+            Some(index) => {
+                index.unique = index.unique || field_index.unique;
+                index.sparse = index.sparse || field_index.sparse;
+                index.expire_after = field_index.expire_after;
+            }
+            None => indexes.push(field_index),
+        }
+    }
+    // Add index names and check for conflicts.
+    let mut index_names: Vec<String> = Vec::new();
+    let mut text_index_count = 0_usize;
+    for index in indexes.iter_mut() {
+        let is_text: bool = index.keys.iter().any(|(_, order)| order == "text");
+        if is_text {
+            text_index_count += 1;
+            if index.unique {
+                panic!("Model: `{}` : The text index cannot be unique.", model_name)
+            }
+        }
+        index.name = format!(
+            "mango__{}",
+            index
+                .keys
+                .iter()
+                .map(|(field_name, order)| format!("{}_{}", field_name, order))
+                .collect::<Vec<String>>()
+                .join("__")
+        );
+        if index_names.contains(&index.name) {
+            panic!(
+                "Model: `{}` : Duplicate index `{}`.",
+                model_name, index.name
+            )
+        }
+        index_names.push(index.name.clone());
+    }
+    if text_index_count > 1 {
+        panic!(
+            "Model: `{}` : A collection can have only one text index. \
+            Use the Model parameter - indexes = \"text: field, field\".",
+            model_name
+        )
+    }
+    //
+    indexes
+}
+
+// Get the type of embedded document.
+// Hint: `embeddedDoc` - Option<SomeForm>, `embeddedDocMult` - Option<Vec<SomeForm>>
+// *************************************************************************************************
+fn get_embedded_type<'a>(
+    widget_name: &'a str,
+    field_type: &'a str,
+    model_name: &'a str,
+    field_name: &'a str,
+    model_or_form: &'a str,
+) -> String {
+    let re_vec_type = regex::RegexBuilder::new(r"^Vec < ([a-z\d]+) >$")
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+    let embedded_type: String = if widget_name == "embeddedDocMult" {
+        match re_vec_type.captures(field_type) {
+            Some(cap) => cap[1].to_string(),
+            None => panic!(
+                "{}: `{}` > Field: `{}` : \
+                For the `embeddedDocMult` widget, use the field type `Option < Vec < SomeForm > >`.",
+                model_or_form, model_name, field_name
+            ),
+        }
+    } else if field_type.starts_with("Vec") {
+        panic!(
+            "{}: `{}` > Field: `{}` : \
+            For a list of embedded documents, use the `embeddedDocMult` widget.",
+            model_or_form, model_name, field_name
+        )
+    } else {
+        field_type.to_string()
+    };
+    match embedded_type.as_str() {
+        "String" | "bool" | "i32" | "u32" | "i64" | "f64" => panic!(
+            "{}: `{}` > Field: `{}` : \
+            The type of an embedded document must be a structure with the `Form` macro.",
+            model_or_form, model_name, field_name
+        ),
+        _ if embedded_type == model_name => panic!(
+            "{}: `{}` > Field: `{}` : \
+            The structure cannot be embedded in itself.",
+            model_or_form, model_name, field_name
+        ),
+        _ => {}
+    }
+    if syn::parse_str::<syn::Path>(embedded_type.as_str()).is_err() {
+        panic!(
+            "{}: `{}` > Field: `{}` : \
+            Invalid type of embedded document.",
+            model_or_form, model_name, field_name
+        )
+    }
+    embedded_type
+}
+
 // Get parameter value from model field attribute.
 // *************************************************************************************************
 fn get_param_value<'a>(
@@ -1248,6 +2511,11 @@ fn get_param_value<'a>(
     check_field_type: &mut bool,
     model_or_form: &'a str,
 ) {
+    // Type of items for `step`, `min` and `max` of list widgets ( `Vec < i32 >` -> `i32` ).
+    let item_type: &str = field_type
+        .strip_prefix("Vec < ")
+        .and_then(|item| item.strip_suffix(" >"))
+        .unwrap_or(field_type);
     match attr_name {
         "label" => {
             if let syn::Lit::Str(lit_str) = &mnv.lit {
@@ -1285,7 +2553,8 @@ fn get_param_value<'a>(
                         err.to_string()
                     )
                 });
-                if widget_info.0 != field_type {
+                // The type of embedded documents is checked separately.
+                if !widget_name.starts_with("embedded") && widget_info.0 != field_type {
                     panic!(
                         "{}: `{}` > Field: `{}` : \
                         The widget type is not the same as the field type.",
@@ -1365,6 +2634,18 @@ fn get_param_value<'a>(
                     )
                 }
             }
+            _ if field_type.starts_with("Vec") => {
+                if let syn::Lit::Str(lit_str) = &mnv.lit {
+                    widget.value = lit_str.value().trim().to_string()
+                } else {
+                    panic!(
+                        "{}: `{}` > Field: `{}` > Type: {} : \
+                        Could not determine value for parameter `value`. \
+                        Example: r#\"[\"rust\",\"mongodb\"]\"#",
+                        model_or_form, model_name, field_name, field_type
+                    )
+                }
+            }
             _ => panic!(
                 "{}: `{}` > Field: `{}` > Type: {} : \
                 Unsupported field type for `default` parameter.",
@@ -1482,7 +2763,7 @@ fn get_param_value<'a>(
                 )
             }
         }
-        "step" => match field_type {
+        "step" => match item_type {
             "i32" => {
                 if let syn::Lit::Int(lit_int) = &mnv.lit {
                     widget.step = lit_int.base10_parse::<i32>().unwrap().to_string();
@@ -1549,7 +2830,7 @@ fn get_param_value<'a>(
                 model_or_form, model_name, field_name, field_type
             ),
         },
-        "min" => match field_type {
+        "min" => match item_type {
             "i32" => {
                 if let syn::Lit::Int(lit_int) = &mnv.lit {
                     widget.min = lit_int.base10_parse::<i32>().unwrap().to_string();
@@ -1616,7 +2897,7 @@ fn get_param_value<'a>(
                 model_or_form, model_name, field_name, field_type
             ),
         },
-        "max" => match field_type {
+        "max" => match item_type {
             "i32" => {
                 if let syn::Lit::Int(lit_int) = &mnv.lit {
                     widget.max = lit_int.base10_parse::<i32>().unwrap().to_string();
@@ -1831,6 +3112,78 @@ fn get_param_value<'a>(
                 )
             }
         }
+        "maxsize" => {
+            if let syn::Lit::Int(lit_int) = &mnv.lit {
+                widget.maxsize = lit_int.base10_parse::<u32>().unwrap();
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `maxsize`. \
+                    Example: 1048576",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
+        "precision" => {
+            if let syn::Lit::Int(lit_int) = &mnv.lit {
+                widget.precision = lit_int.base10_parse::<u32>().unwrap();
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `precision`. \
+                    Example: 10",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
+        "scale" => {
+            if let syn::Lit::Int(lit_int) = &mnv.lit {
+                widget.scale = lit_int.base10_parse::<u32>().unwrap();
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `scale`. \
+                    Example: 2",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
+        "min_items" => {
+            if let syn::Lit::Int(lit_int) = &mnv.lit {
+                widget.min_items = lit_int.base10_parse::<u32>().unwrap();
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `min_items`. \
+                    Example: 1",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
+        "max_items" => {
+            if let syn::Lit::Int(lit_int) = &mnv.lit {
+                widget.max_items = lit_int.base10_parse::<u32>().unwrap();
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `max_items`. \
+                    Example: 10",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
+        "unique_items" => {
+            if let syn::Lit::Bool(lit_bool) = &mnv.lit {
+                widget.unique_items = lit_bool.value;
+            } else {
+                panic!(
+                    "{}: `{}` > Field: `{}` : \
+                    Could not determine value for parameter `unique_items`. \
+                    Example: true. Default = false.",
+                    model_or_form, model_name, field_name
+                )
+            }
+        }
         "other_attrs" => {
             if let syn::Lit::Str(lit_str) = &mnv.lit {
                 widget.other_attrs = lit_str.value().trim().to_string();
@@ -131,9 +131,7 @@ pub struct User {
 }
 
 impl AdditionalValidation for User {
-    fn add_validation<'a>(
-        &self,
-    ) -> Result<std::collections::HashMap<&'a str, &'a str>, Box<dyn std::error::Error>> {
+    fn add_validation<'a>(&self) -> Result<std::collections::HashMap<&'a str, &'a str>, Error> {
         // Hint: error_map.insert("field_name", "Error message.")
         let mut error_map: std::collections::HashMap<&'a str, &'a str> =
             std::collections::HashMap::new();
@@ -283,9 +281,7 @@ pub struct UserProfile {
 impl AdditionalValidation for UserProfile {
     // Example of additional validation for ignored fields
     // ---------------------------------------------------------------------------------------------
-    fn add_validation<'a>(
-        &self,
-    ) -> Result<std::collections::HashMap<&'a str, &'a str>, Box<dyn std::error::Error>> {
+    fn add_validation<'a>(&self) -> Result<std::collections::HashMap<&'a str, &'a str>, Error> {
         // Hint: error_map.insert("field_name", "Error message.")
         let mut error_map: std::collections::HashMap<&'a str, &'a str> =
             std::collections::HashMap::new();
@@ -343,9 +339,7 @@ pub struct UserForm {
 impl AdditionalValidation for UserForm {
     // Example of additional validation for ignored fields
     // ---------------------------------------------------------------------------------------------
-    fn add_validation<'a>(
-        &self,
-    ) -> Result<std::collections::HashMap<&'a str, &'a str>, Box<dyn std::error::Error>> {
+    fn add_validation<'a>(&self) -> Result<std::collections::HashMap<&'a str, &'a str>, Error> {
         // error_map.insert("field_name", "Error message.")
         let mut error_map: std::collections::HashMap<&'a str, &'a str> =
             std::collections::HashMap::new();
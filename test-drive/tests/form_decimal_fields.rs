@@ -0,0 +1,71 @@
+use mango_orm::*;
+use metamorphose::Form;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const UNIQUE_PROJECT_KEY: &str = "Dw5nHc8RtKq2MzV";
+    pub const SERVICE_NAME: &str = "service_name";
+
+    // Create form
+    // *********************************************************************************************
+    #[Form]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestForm {
+        #[serde(default)]
+        #[field_attrs(widget = "numberDecimal", min = "0.5", max = "100", required = true)]
+        pub amount: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "rangeDecimal", precision = 3, scale = 1, max = "10")]
+        pub rate: Option<String>,
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_form_decimal_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut test_form = app_name::TestForm {
+        amount: Some("12.3".to_string()),
+        rate: Some("2.5".to_string()),
+    };
+
+    // Check
+    // ---------------------------------------------------------------------------------------------
+    let result = test_form.check()?;
+    // Validating
+    assert!(result.is_valid());
+    let map_wigets = result.wig();
+    assert_eq!("12.30".to_string(), map_wigets.get("amount").unwrap().value);
+    assert_eq!("2.5".to_string(), map_wigets.get("rate").unwrap().value);
+    let map_wigets = app_name::TestForm::form_wig()?;
+    assert_eq!("number", map_wigets.get("amount").unwrap().input_type);
+    assert_eq!("0.50", map_wigets.get("amount").unwrap().min);
+    assert_eq!("0.1", map_wigets.get("rate").unwrap().step);
+    // Html
+    let html = app_name::TestForm::form_html()?;
+    assert!(html.contains("step=\"0.01\""));
+    assert!(html.contains("min=\"0.50\""));
+    assert!(html.contains("max=\"10.0\""));
+
+    // Validation
+    // ---------------------------------------------------------------------------------------------
+    test_form.amount = Some("0.25".to_string());
+    assert!(!test_form.check()?.is_valid());
+    test_form.amount = Some("1.005".to_string());
+    assert!(!test_form.check()?.is_valid());
+    test_form.amount = None;
+    assert!(!test_form.check()?.is_valid());
+    test_form.amount = Some("100".to_string());
+    test_form.rate = Some("10.5".to_string());
+    assert!(!test_form.check()?.is_valid());
+    test_form.rate = None;
+    assert!(test_form.check()?.is_valid());
+
+    Ok(())
+}
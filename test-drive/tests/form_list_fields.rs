@@ -0,0 +1,75 @@
+use mango_orm::*;
+use metamorphose::Form;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const UNIQUE_PROJECT_KEY: &str = "Ln3vKs9PdRw6YgF";
+    pub const SERVICE_NAME: &str = "service_name";
+
+    // Create form
+    // *********************************************************************************************
+    #[Form]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestForm {
+        #[serde(default)]
+        #[field_attrs(widget = "listText", maxlength = 10, max_items = 2, required = true)]
+        pub emails: Option<Vec<String>>,
+        #[serde(default)]
+        #[field_attrs(widget = "listI64", min = 0, unique_items = true)]
+        pub offsets: Option<Vec<i64>>,
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_form_list_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut test_form = app_name::TestForm {
+        emails: Some(vec![" a@b.cd ".to_string()]),
+        offsets: Some(vec![0, 5, 10]),
+    };
+
+    // Check
+    // ---------------------------------------------------------------------------------------------
+    let result = test_form.check()?;
+    // Validating
+    assert!(result.is_valid());
+    let map_wigets = result.wig();
+    assert_eq!(r#"["a@b.cd"]"#, map_wigets.get("emails").unwrap().value);
+    assert_eq!("[0,5,10]", map_wigets.get("offsets").unwrap().value);
+    let map_wigets = app_name::TestForm::form_wig()?;
+    assert_eq!("list", map_wigets.get("emails").unwrap().input_type);
+    assert_eq!(2, map_wigets.get("emails").unwrap().max_items);
+    assert_eq!("0", map_wigets.get("offsets").unwrap().min);
+    // Html
+    let html = app_name::TestForm::form_html()?;
+    assert!(html.contains("name=\"emails[]\""));
+    assert!(html.contains("maxlength=\"10\""));
+    assert!(html.contains("min=\"0\""));
+
+    // Validation
+    // ---------------------------------------------------------------------------------------------
+    test_form.emails = Some(vec!["a@b.cd".to_string(), "01234567890".to_string()]);
+    assert!(!test_form.check()?.is_valid());
+    test_form.emails = Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    assert!(!test_form.check()?.is_valid());
+    test_form.emails = Some(Vec::new());
+    assert!(!test_form.check()?.is_valid());
+    test_form.emails = None;
+    assert!(!test_form.check()?.is_valid());
+    test_form.emails = Some(vec!["a".to_string(), "b".to_string()]);
+    test_form.offsets = Some(vec![-1]);
+    assert!(!test_form.check()?.is_valid());
+    test_form.offsets = Some(vec![1, 1]);
+    assert!(!test_form.check()?.is_valid());
+    test_form.offsets = None;
+    assert!(test_form.check()?.is_valid());
+
+    Ok(())
+}
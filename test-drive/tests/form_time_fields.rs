@@ -0,0 +1,71 @@
+use mango_orm::*;
+use metamorphose::Form;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const UNIQUE_PROJECT_KEY: &str = "Yc6mRk2WvNt8PsD";
+    pub const SERVICE_NAME: &str = "service_name";
+
+    // Create form
+    // *********************************************************************************************
+    #[Form]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestForm {
+        #[serde(default)]
+        #[field_attrs(widget = "inputTime", value = "09:00", min = "08:00", max = "20:00")]
+        pub time: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputTimeRange", required = true)]
+        pub time_range: Option<String>,
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_form_time_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut test_form = app_name::TestForm {
+        time: Some("12:45".to_string()),
+        time_range: Some("09:00-18:00".to_string()),
+        ..Default::default()
+    };
+
+    // Check
+    // ---------------------------------------------------------------------------------------------
+    let result = test_form.check()?;
+    // Validating
+    assert!(result.is_valid());
+    let map_wigets = result.wig();
+    assert_eq!("12:45".to_string(), map_wigets.get("time").unwrap().value);
+    assert_eq!(
+        "09:00-18:00".to_string(),
+        map_wigets.get("time_range").unwrap().value
+    );
+    let map_wigets = app_name::TestForm::form_wig()?;
+    assert_eq!("09:00".to_string(), map_wigets.get("time").unwrap().value);
+    assert_eq!("time", map_wigets.get("time").unwrap().input_type);
+    assert!(!map_wigets.get("time_range").unwrap().pattern.is_empty());
+    // Html
+    let html = app_name::TestForm::form_html()?;
+    assert!(html.contains("type=\"time\""));
+    assert!(html.contains("min=\"08:00\""));
+
+    // Validation
+    // ---------------------------------------------------------------------------------------------
+    test_form.time = Some("21:00".to_string());
+    assert!(!test_form.check()?.is_valid());
+    test_form.time = Some("25:00".to_string());
+    assert!(!test_form.check()?.is_valid());
+    test_form.time = None;
+    assert!(test_form.check()?.is_valid());
+    test_form.time_range = None;
+    assert!(!test_form.check()?.is_valid());
+
+    Ok(())
+}
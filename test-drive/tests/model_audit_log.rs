@@ -0,0 +1,178 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Js4kVd9PqHw2ReM";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(is_audit_log = true)]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true, unique = true)]
+        pub username: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputEmail", required = true)]
+        pub email: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32", min = 0, max = 150)]
+        pub age: Option<i32>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputPassword")]
+        pub password: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_audit_log() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        username: Some("user_1".to_string()),
+        email: Some("user_1@test.net".to_string()),
+        age: Some(20),
+        password: Some("j2972K4R3uQeVFPF".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save_by(Some("admin"), None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let hash = test_model.hash.clone().unwrap();
+    let history = app_name::TestModel::history(hash.as_str())?;
+    assert_eq!(1, history.len());
+    assert_eq!("create", history[0].action);
+    assert_eq!(Some("admin".to_string()), history[0].actor);
+    assert_eq!(hash, history[0].hash);
+    assert_eq!(
+        "user_1",
+        history[0]
+            .changes
+            .get_document("username")?
+            .get_str("new")?
+    );
+    assert!(history[0].changes.get_document("username")?.is_null("old"));
+    // `inputPassword` fields are not recorded.
+    assert!(!history[0].changes.contains_key("password"));
+    assert!(!history[0].snapshot.contains_key("password"));
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    test_model.age = Some(30);
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let history = app_name::TestModel::history(hash.as_str())?;
+    assert_eq!(2, history.len());
+    assert_eq!("update", history[1].action);
+    assert_eq!(None, history[1].actor);
+    assert_eq!(
+        vec!["age"],
+        history[1].changes.keys().collect::<Vec<&String>>()
+    );
+    assert_eq!(20, history[1].changes.get_document("age")?.get_i32("old")?);
+    assert_eq!(30, history[1].changes.get_document("age")?.get_i32("new")?);
+    // Saving without changes is not recorded.
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(2, app_name::TestModel::history(hash.as_str())?.len());
+    // Partial update
+    let values = serde_json::json!({"email": "user_2@test.net"});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let history = app_name::TestModel::history(hash.as_str())?;
+    assert_eq!(3, history.len());
+    assert_eq!(
+        "user_2@test.net",
+        history[2].changes.get_document("email")?.get_str("new")?
+    );
+
+    // Revert
+    // ---------------------------------------------------------------------------------------------
+    app_name::TestModel::revert(hash.as_str(), history[0].id.as_str())?;
+    let test_model = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?
+        .model::<app_name::TestModel>()?;
+    assert_eq!(Some(20), test_model.age);
+    assert_eq!(Some("user_1@test.net".to_string()), test_model.email);
+    let history = app_name::TestModel::history(hash.as_str())?;
+    assert_eq!(4, history.len());
+    assert_eq!("revert", history[3].action);
+    // Unknown record
+    assert!(app_name::TestModel::revert(hash.as_str(), hash.as_str()).is_err());
+
+    // Delete
+    // ---------------------------------------------------------------------------------------------
+    let output_data = test_model.delete_by(Some("admin"), None)?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    let history = app_name::TestModel::history(hash.as_str())?;
+    assert_eq!(5, history.len());
+    assert_eq!("delete", history[4].action);
+    assert_eq!(Some("admin".to_string()), history[4].actor);
+    assert!(history[4].changes.get_document("age")?.is_null("new"));
+    assert_eq!(20, history[4].snapshot.get_i32("age")?);
+    // Revert of a removed document - inserted again with the same hash.
+    app_name::TestModel::revert(hash.as_str(), history[1].id.as_str())?;
+    let output_data = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?;
+    assert!(output_data.is_valid());
+    let test_model = output_data.model::<app_name::TestModel>()?;
+    assert_eq!(Some(hash.clone()), test_model.hash);
+    assert_eq!(Some(30), test_model.age);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Mk7vRq3WzTn5HcP";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(
+            widget = "numberDecimal",
+            precision = 8,
+            min = "0",
+            max = "100000",
+            value = "9.5"
+        )]
+        pub price: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "rangeDecimal",
+            scale = 1,
+            min = "0",
+            max = "50",
+            step = "2.5"
+        )]
+        pub discount: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "hiddenDecimal", scale = 0, unique = true)]
+        pub code: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_decimal_fields() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Parameters of the widgets.
+    // ---------------------------------------------------------------------------------------------
+    let map_wigets = app_name::TestModel::widgets()?;
+    assert_eq!("9.50", map_wigets["price"].value);
+    assert_eq!("0.00", map_wigets["price"].min);
+    assert_eq!("0.01", map_wigets["price"].step);
+    assert_eq!("2.5", map_wigets["discount"].step);
+    assert_eq!("1", map_wigets["code"].step);
+    let meta = app_name::TestModel::meta()?;
+    assert_eq!(
+        ("numberDecimal".to_string(), "9.50".to_string()),
+        meta.map_default_values["price"]
+    );
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        price: Some("1234.5".to_string()),
+        discount: Some("7.5".to_string()),
+        code: Some("42".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!("1234.50", result.wig()["price"].value);
+    // Numbers are stored in Decimal128.
+    let output_data = app_name::TestModel::find_one(None, None)?;
+    let raw_doc = output_data.raw_doc();
+    let price = raw_doc.get("price");
+    assert!(matches!(price, Some(mongodb::bson::Bson::Decimal128(_))));
+    assert_eq!(
+        Some("1234.50".to_string()),
+        price
+            .and_then(forms::Decimal::from_bson)
+            .map(|item| item.to_value())
+    );
+    // Numbers are returned as strings.
+    let doc = output_data.doc()?;
+    assert_eq!("1234.50", doc.get_str("price")?);
+    assert_eq!("7.5", doc.get_str("discount")?);
+    assert_eq!("42", doc.get_str("code")?);
+    let instance = output_data.model::<app_name::TestModel>()?;
+    assert_eq!(Some("1234.50".to_string()), instance.price);
+    assert!(output_data.json()?.contains(r#""price":"1234.50""#));
+    // Query
+    assert_eq!(1, app_name::TestModel::q().price().gte("1234.5").count()?);
+    assert_eq!(0, app_name::TestModel::q().price().gt("1234.50").count()?);
+
+    // Validation
+    // ---------------------------------------------------------------------------------------------
+    // Too many digits after the decimal point.
+    test_model.price = Some("10.005".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Too many digits ( `precision` ).
+    test_model.price = Some("1000000.00".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Out of range between `min` and `max`.
+    test_model.price = Some("-1".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Does not match the step of 2.5.
+    test_model.price = Some("10".to_string());
+    test_model.discount = Some("8".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Incorrect format.
+    test_model.discount = Some("five".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Not unique.
+    test_model.discount = None;
+    let test_model_2 = app_name::TestModel {
+        code: Some("42.0".to_string()),
+        ..Default::default()
+    };
+    assert!(!test_model_2.check()?.is_valid());
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let doc = app_name::TestModel::find_one(None, None)?.doc()?;
+    assert_eq!("10.00", doc.get_str("price")?);
+    assert_eq!(
+        mongodb::bson::Bson::Null,
+        doc.get("discount").unwrap().clone()
+    );
+
+    // Keyset pagination by a decimal field
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model_3 = app_name::TestModel {
+        price: Some("9.5".to_string()),
+        code: Some("43".to_string()),
+        ..Default::default()
+    };
+    let result = test_model_3.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let sort = mongodb::bson::doc! {"price": 1};
+    let output_data = app_name::TestModel::paginate_keyset(None, Some(sort.clone()), None, 1)?;
+    assert!(output_data.has_next);
+    assert_eq!("9.50", output_data.docs()?[0].get_str("price")?);
+    let after: Option<String> = output_data.next_cursor.clone();
+    let output_data = app_name::TestModel::paginate_keyset(None, Some(sort), after.as_deref(), 1)?;
+    assert!(!output_data.has_next);
+    assert_eq!("10.00", output_data.docs()?[0].get_str("price")?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::{Form, Model};
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    sync::Client,
+};
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Xb7kRw2MzeLq9Tn";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create embedded document
+    // *********************************************************************************************
+    #[Form]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct Address {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true, maxlength = 20)]
+        pub city: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", maxlength = 5)]
+        pub zip: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberU32", max = 1000)]
+        pub house: Option<u32>,
+    }
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "embeddedDoc", label = "Address")]
+        pub address: Option<Address>,
+        #[serde(default)]
+        #[field_attrs(widget = "embeddedDocMult", label = "Other addresses")]
+        pub addresses: Option<Vec<Address>>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgects map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_embedded_fields() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Invalid embedded documents
+    // ---------------------------------------------------------------------------------------------
+    // Missing required nested field
+    let mut test_model = app_name::TestModel {
+        address: Some(app_name::Address {
+            zip: Some("75001".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(!result.is_valid());
+    assert!(!result.wig().get("address").unwrap().error.is_empty());
+    // Nested field exceeds the maximum
+    let mut test_model = app_name::TestModel {
+        addresses: Some(vec![app_name::Address {
+            city: Some("Paris".to_string()),
+            house: Some(1001),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(!result.is_valid());
+    assert!(!result.wig().get("addresses").unwrap().error.is_empty());
+    assert!(result.hash()?.is_empty());
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        address: Some(app_name::Address {
+            city: Some("Paris".to_string()),
+            zip: Some("75001".to_string()),
+            house: Some(10),
+        }),
+        addresses: Some(vec![
+            app_name::Address {
+                city: Some("Lyon".to_string()),
+                ..Default::default()
+            },
+            app_name::Address {
+                city: Some("Nice".to_string()),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert!(result.html().contains("<fieldset"));
+
+    // Validating values in database
+    {
+        let form_store = FORM_STORE.read()?;
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let form_cache: &FormCache = form_store.get(&app_name::TestModel::key()[..]).unwrap();
+        let meta: &Meta = &form_cache.meta;
+        let client: &Client = client_store.get(meta.db_client_name.as_str()).unwrap();
+        let object_id = ObjectId::with_string(test_model.hash.clone().unwrap().as_str())?;
+        let coll = client
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let filter = doc! {"_id": object_id};
+        let doc = coll.find_one(filter, None)?.unwrap();
+        assert_eq!(1_i64, coll.count_documents(None, None)?);
+        let address = doc.get_document("address")?;
+        assert_eq!("Paris", address.get_str("city")?);
+        assert_eq!("75001", address.get_str("zip")?);
+        assert_eq!(10_i64, address.get_i64("house")?);
+        let addresses = doc.get_array("addresses")?;
+        assert_eq!(2, addresses.len());
+        assert_eq!("Nice", addresses[1].as_document().unwrap().get_str("city")?);
+    }
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    test_model.address.as_mut().unwrap().city = Some("Marseille".to_string());
+    test_model.addresses = None;
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let object_id = ObjectId::with_string(test_model.hash.clone().unwrap().as_str())?;
+    let doc = app_name::TestModel::find_one(Some(doc! {"_id": object_id}), None)?.raw_doc();
+    assert_eq!("Marseille", doc.get_document("address")?.get_str("city")?);
+    assert!(doc.is_null("addresses"));
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
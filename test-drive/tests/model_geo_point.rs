@@ -0,0 +1,196 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Kp6wBz3NtRv8LmQ";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub title: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputGeoPoint", required = true)]
+        pub location: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_geo_point() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // 2dsphere index.
+    // ---------------------------------------------------------------------------------------------
+    let meta = app_name::TestModel::meta()?;
+    let index = meta
+        .indexes
+        .iter()
+        .find(|index| index.name == "mango__location_2dsphere")
+        .unwrap();
+    assert_eq!(
+        vec![("location".to_string(), "2dsphere".to_string())],
+        index.keys
+    );
+
+    // Invalid values.
+    // ---------------------------------------------------------------------------------------------
+    for location in [
+        "52.52",
+        "52.52,13.40,1",
+        "north,east",
+        "90.5,13.40",
+        "52.52,-181",
+    ]
+    .iter()
+    {
+        let mut test_model = app_name::TestModel {
+            location: Some(location.to_string()),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(!result.is_valid(), "{}", location);
+        assert!(!result.wig()["location"].error.is_empty());
+    }
+    let mut test_model = app_name::TestModel::default();
+    let result = test_model.save(None, None)?;
+    assert!(!result.is_valid());
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    for (title, location) in [
+        ("Berlin", " 52.520008, 13.404954 "),
+        ("Potsdam", "52.390569,13.064473"),
+        ("Hamburg", "53.551086,9.993682"),
+    ]
+    .iter()
+    {
+        let mut test_model = app_name::TestModel {
+            title: Some(title.to_string()),
+            location: Some(location.to_string()),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+    }
+    // The point is stored in GeoJSON format.
+    let output_data = app_name::TestModel::q().title().eq("Berlin").find_one()?;
+    let raw_doc = output_data.raw_doc();
+    let location = raw_doc.get_document("location")?;
+    assert_eq!("Point", location.get_str("type")?);
+    assert_eq!(
+        Some(GeoPoint {
+            lat: 52.520008,
+            lng: 13.404954
+        }),
+        GeoPoint::from_bson(raw_doc.get("location").unwrap())
+    );
+    assert_eq!(
+        "52.520008,13.404954",
+        output_data.doc()?.get_str("location")?
+    );
+    // Query values are "lat,lng".
+    assert_eq!(
+        1,
+        app_name::TestModel::q()
+            .location()
+            .eq("52.390569,13.064473")
+            .count()?
+    );
+
+    // Near queries.
+    // ---------------------------------------------------------------------------------------------
+    let point = GeoPoint::new(52.4, 13.1).unwrap();
+    let output_data = app_name::TestModel::find_near("location", &point, None, None)?;
+    let docs = output_data.raw_docs()?;
+    let titles: Vec<&str> = docs
+        .iter()
+        .map(|doc| doc.get_str("title").unwrap())
+        .collect();
+    assert_eq!(vec!["Potsdam", "Berlin", "Hamburg"], titles);
+    assert!(docs[0].get_f64("_distance")? < docs[1].get_f64("_distance")?);
+    assert_eq!(3, output_data.count()?);
+    // Maximum distance in meters.
+    let output_data = app_name::TestModel::find_near("location", &point, Some(50000.0), None)?;
+    assert_eq!(2, output_data.count()?);
+    let filter = mongodb::bson::doc! {"title": "Berlin"};
+    let output_data = app_name::TestModel::find_near("location", &point, None, Some(filter))?;
+    assert_eq!(1, output_data.count()?);
+    assert!(app_name::TestModel::find_near("title", &point, None, None).is_err());
+    assert!(app_name::TestModel::find_near("address", &point, None, None).is_err());
+
+    // Queries within the area.
+    // ---------------------------------------------------------------------------------------------
+    let area = GeoPoint::polygon(&[
+        GeoPoint::new(52.7, 12.9).unwrap(),
+        GeoPoint::new(52.7, 13.7).unwrap(),
+        GeoPoint::new(52.2, 13.7).unwrap(),
+        GeoPoint::new(52.2, 12.9).unwrap(),
+    ]);
+    let point = GeoPoint::new(52.6, 13.5).unwrap();
+    let docs = app_name::TestModel::find_within("location", area, &point, None)?.docs()?;
+    let titles: Vec<&str> = docs
+        .iter()
+        .map(|doc| doc.get_str("title").unwrap())
+        .collect();
+    assert_eq!(vec!["Berlin", "Potsdam"], titles);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
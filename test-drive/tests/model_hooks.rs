@@ -0,0 +1,189 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Bq7nXt4KwRm2VhD";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(is_use_hooks = true)]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true, unique = true)]
+        pub username: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub slug: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "checkBox")]
+        pub is_staff: Option<bool>,
+    }
+
+    // Number of calls of the `post_save` and `post_delete` hooks.
+    pub static SAVE_EVENTS: AtomicUsize = AtomicUsize::new(0);
+    pub static DELETE_EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+    impl Hooks for TestModel {
+        fn pre_save(&mut self, _is_update: bool) -> Result<(), Error> {
+            self.username = self
+                .username
+                .as_ref()
+                .map(|item| item.trim().to_lowercase());
+            Ok(())
+        }
+
+        fn post_check(
+            &self,
+            final_doc: &mut mongodb::bson::document::Document,
+            is_update: bool,
+        ) -> Result<(), Error> {
+            if !is_update {
+                let slug = final_doc.get_str("username")?.replace("_", "-");
+                final_doc.insert("slug", slug);
+            }
+            Ok(())
+        }
+
+        fn post_save(
+            &self,
+            _final_doc: &mongodb::bson::document::Document,
+            _is_update: bool,
+        ) -> Result<(), Error> {
+            SAVE_EVENTS.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn pre_delete(
+            &self,
+            doc_from_db: &mongodb::bson::document::Document,
+        ) -> Result<String, Error> {
+            if doc_from_db.get_bool("is_staff").unwrap_or_default() {
+                return Ok("Staff accounts can not be deleted.".to_string());
+            }
+            Ok(String::new())
+        }
+
+        fn post_delete(
+            &self,
+            _doc_from_db: &mongodb::bson::document::Document,
+        ) -> Result<(), Error> {
+            DELETE_EVENTS.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_hooks() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Save
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        username: Some("  User_1 ".to_string()),
+        is_staff: Some(true),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(Some("user_1".to_string()), test_model.username);
+    assert_eq!(1, app_name::SAVE_EVENTS.load(Ordering::SeqCst));
+    let doc = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?;
+    assert!(doc.is_valid());
+    let test_model_2 = doc.model::<app_name::TestModel>()?;
+    assert_eq!(Some("user-1".to_string()), test_model_2.slug);
+    // Invalid data - the document is not saved.
+    let mut test_model_3 = app_name::TestModel {
+        username: Some(" USER_1".to_string()),
+        ..Default::default()
+    };
+    let result = test_model_3.save(None, None)?;
+    assert!(!result.is_valid());
+    assert_eq!(1, app_name::SAVE_EVENTS.load(Ordering::SeqCst));
+
+    // Partial update - `pre_save` normalizes the values being updated.
+    // ---------------------------------------------------------------------------------------------
+    let hash = test_model.hash.clone().unwrap();
+    let values = serde_json::json!({"username": " User_2 "});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(2, app_name::SAVE_EVENTS.load(Ordering::SeqCst));
+    let doc = app_name::TestModel::find_one(Some(doc! {"username": "user_2"}), None)?;
+    assert!(doc.is_valid());
+    test_model.username = Some("user_2".to_string());
+
+    // Delete
+    // ---------------------------------------------------------------------------------------------
+    let result = test_model.delete(None)?;
+    assert!(!result.is_valid());
+    assert_eq!("Staff accounts can not be deleted.", result.err_msg());
+    assert_eq!(0, app_name::DELETE_EVENTS.load(Ordering::SeqCst));
+    test_model.is_staff = Some(false);
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(3, app_name::SAVE_EVENTS.load(Ordering::SeqCst));
+    let result = test_model.delete(None)?;
+    assert!(result.is_valid(), "{}", result.err_msg());
+    assert_eq!(1, app_name::DELETE_EVENTS.load(Ordering::SeqCst));
+    assert_eq!(0, app_name::TestModel::count_documents(None, None)?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
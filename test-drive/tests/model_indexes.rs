@@ -0,0 +1,249 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, document::Document},
+    sync::Client,
+};
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Ry4mTb7WkPz2HxD";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // ( Two versions of the same Model )
+    // *********************************************************************************************
+    pub mod v1 {
+        use super::*;
+
+        #[Model(indexes = "unique: first_name, last_name; text: bio")]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub first_name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub last_name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputEmail", unique = true)]
+            pub email: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "textArea")]
+            pub bio: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "numberI32", index = "desc")]
+            pub age: Option<i32>,
+        }
+    }
+
+    pub mod v2 {
+        use super::*;
+
+        #[Model(indexes = "unique: first_name, last_name; text: bio")]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub first_name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub last_name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputEmail", unique = true, index = "asc", sparse = true)]
+            pub email: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "textArea")]
+            pub bio: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "numberI32")]
+            pub age: Option<i32>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputDateTime", expire_after = 3600)]
+            pub last_visit: Option<String>,
+        }
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Monitor initialization
+    pub fn monitor<'a>(models: Vec<Meta>) -> Monitor<'a> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE
+            .write()
+            .unwrap()
+            .entry("default".to_string())
+            .or_insert_with(|| {
+                mongodb::sync::Client::with_uri_str("mongodb://localhost:27017").unwrap()
+            });
+        Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models,
+        }
+    }
+
+    // Get the indexes of the collection
+    pub fn indexes(meta: &Meta) -> Result<Vec<Document>, Box<dyn std::error::Error>> {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let client: &Client = client_store.get("default").unwrap();
+        let result: Document = client
+            .database(meta.database_name.as_str())
+            .run_command(doc! {"listIndexes": meta.collection_name.as_str()}, None)?;
+        Ok(result
+            .get_document("cursor")?
+            .get_array("firstBatch")?
+            .iter()
+            .filter_map(|item| item.as_document().cloned())
+            .collect())
+    }
+
+    // Get the index by name
+    pub fn index(meta: &Meta, name: &str) -> Result<Option<Document>, Box<dyn std::error::Error>> {
+        Ok(indexes(meta)?
+            .into_iter()
+            .find(|item| item.get_str("name").ok() == Some(name)))
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let model_list = vec![app_name::v1::TestModel::meta()?];
+    let meta: &Meta = &model_list[0];
+    let monitor = app_name::monitor(model_list.clone());
+    // Remove test databases
+    // ( Test databases may remain in case of errors )
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Index declarations
+    // ---------------------------------------------------------------------------------------------
+    let names: Vec<&str> = meta.indexes.iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(
+        vec![
+            "mango__first_name_asc__last_name_asc",
+            "mango__bio_text",
+            "mango__email_asc",
+            "mango__age_desc",
+        ],
+        names
+    );
+    assert!(meta.indexes[0].unique);
+    assert!(meta.indexes[2].unique);
+    assert!(!meta.indexes[3].unique);
+
+    // Migration - the indexes are created
+    // ---------------------------------------------------------------------------------------------
+    let plan = monitor.plan()?;
+    assert_eq!(4, plan.models[0].created_indexes.len());
+    monitor.migrat()?;
+    assert!(monitor.plan()?.is_empty(), "{}", monitor.plan()?.to_json()?);
+    let index = app_name::index(meta, "mango__email_asc")?.unwrap();
+    assert!(index.get_bool("unique")?);
+    assert_eq!(
+        "string",
+        index
+            .get_document("partialFilterExpression")?
+            .get_document("email")?
+            .get_str("$type")?
+    );
+    assert_eq!(
+        -1_i32,
+        app_name::index(meta, "mango__age_desc")?
+            .unwrap()
+            .get_document("key")?
+            .get_i32("age")?
+    );
+    assert!(app_name::index(meta, "mango__bio_text")?.is_some());
+
+    // Unique indexes ignore empty fields
+    // ---------------------------------------------------------------------------------------------
+    app_name::v1::TestModel::to_cache()?;
+    let mut test_model = app_name::v1::TestModel {
+        first_name: Some("John".to_string()),
+        last_name: Some("Smith".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let mut test_model_2 = app_name::v1::TestModel {
+        first_name: Some("John".to_string()),
+        last_name: Some("Doe".to_string()),
+        ..Default::default()
+    };
+    let result = test_model_2.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+
+    // Duplicate key is returned as the error of the fields
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model_3 = app_name::v1::TestModel {
+        first_name: Some("John".to_string()),
+        last_name: Some("Smith".to_string()),
+        ..Default::default()
+    };
+    let result = test_model_3.save(None, None)?;
+    assert!(!result.is_valid());
+    assert!(test_model_3.hash.is_none());
+    let map_widgets = result.wig();
+    assert_eq!(
+        "Is not unique.",
+        map_widgets.get("first_name").unwrap().error
+    );
+    assert_eq!(
+        "Is not unique.",
+        map_widgets.get("last_name").unwrap().error
+    );
+    assert!(map_widgets.get("email").unwrap().error.is_empty());
+
+    // Changed indexes - created, recreated and dropped
+    // ---------------------------------------------------------------------------------------------
+    let model_list = vec![app_name::v2::TestModel::meta()?];
+    let meta: &Meta = &model_list[0];
+    let monitor = app_name::monitor(model_list.clone());
+    let plan = monitor.plan()?;
+    let model_plan = &plan.models[0];
+    assert_eq!(
+        vec!["mango__last_visit_asc".to_string()],
+        model_plan.created_indexes
+    );
+    assert_eq!(
+        vec!["mango__age_desc".to_string()],
+        model_plan.dropped_indexes
+    );
+    monitor.migrat()?;
+    assert!(monitor.plan()?.is_empty(), "{}", monitor.plan()?.to_json()?);
+    assert!(app_name::index(meta, "mango__age_desc")?.is_none());
+    let index = app_name::index(meta, "mango__last_visit_asc")?.unwrap();
+    assert_eq!("3600", index.get("expireAfterSeconds").unwrap().to_string());
+    // The existing indexes are not touched
+    assert!(app_name::index(meta, "_id_")?.is_some());
+    assert_eq!(5, app_name::indexes(meta)?.len());
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
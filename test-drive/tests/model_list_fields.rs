@@ -0,0 +1,218 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Tq4mXr7HwNc2BvZ";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(
+            widget = "listText",
+            value = r#"["rust"]"#,
+            minlength = 2,
+            maxlength = 16,
+            pattern = "[a-z][a-z0-9-]*",
+            max_items = 3,
+            unique_items = true
+        )]
+        pub tags: Option<Vec<String>>,
+        #[serde(default)]
+        #[field_attrs(widget = "listI32", min = 1, max = 5, min_items = 2)]
+        pub ratings: Option<Vec<i32>>,
+        #[serde(default)]
+        #[field_attrs(widget = "listU32")]
+        pub counters: Option<Vec<u32>>,
+        #[serde(default)]
+        #[field_attrs(widget = "listF64", unique_items = true)]
+        pub weights: Option<Vec<f64>>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_list_fields() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Widgets
+    // ---------------------------------------------------------------------------------------------
+    let map_wigets = app_name::TestModel::form_wig()?;
+    assert_eq!("list", map_wigets["tags"].input_type);
+    assert_eq!(r#"["rust"]"#, map_wigets["tags"].value);
+    assert_eq!(2, map_wigets["ratings"].min_items);
+    assert!(map_wigets["weights"].unique_items);
+    // Html - one input per item and one input for a new item.
+    let html = app_name::TestModel::form_html()?;
+    assert!(html.contains(r#"name="tags[]" value="rust""#));
+    assert!(html.contains(r#"name="tags[]" value="""#));
+    assert!(html.contains(r#"pattern="[a-z][a-z0-9-]*""#));
+    assert!(html.contains(r#"type="number" name="ratings[]""#));
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        tags: Some(vec![" rust ".to_string(), "mongodb".to_string()]),
+        ratings: Some(vec![5, 3, 5]),
+        counters: Some(vec![0, 4294967295]),
+        weights: Some(vec![0.5, 1.5]),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let map_wigets = result.wig();
+    assert_eq!(r#"["rust","mongodb"]"#, map_wigets["tags"].value);
+    assert_eq!("[5,3,5]", map_wigets["ratings"].value);
+    // Values in the database.
+    let output_data = app_name::TestModel::q()
+        .hash()
+        .eq(test_model.hash.clone().unwrap().as_str())
+        .find_one()?;
+    let raw_doc = output_data.raw_doc();
+    assert_eq!(2, raw_doc.get_array("tags")?.len());
+    assert_eq!(Some(5), raw_doc.get_array("ratings")?[0].as_i32());
+    assert_eq!(Some(4294967295), raw_doc.get_array("counters")?[1].as_i64());
+    let model: app_name::TestModel = output_data.model()?;
+    assert_eq!(Some(vec![0, 4294967295]), model.counters);
+    // Query - the list contains the item.
+    assert_eq!(1, app_name::TestModel::q().tags().eq("mongodb").count()?);
+    assert_eq!(
+        1,
+        app_name::TestModel::q().ratings().is_in(vec![3]).count()?
+    );
+    assert_eq!(0, app_name::TestModel::q().tags().eq("python").count()?);
+
+    // Default value and empty lists.
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        ratings: Some(vec![1, 2]),
+        weights: Some(Vec::new()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert!(result.wig()["weights"].value.is_empty());
+    assert_eq!(2_i64, app_name::TestModel::estimated_document_count(None)?);
+
+    // Invalid values.
+    // ---------------------------------------------------------------------------------------------
+    let invalid_models = vec![
+        // Item does not match the pattern.
+        app_name::TestModel {
+            tags: Some(vec!["Rust".to_string()]),
+            ..Default::default()
+        },
+        // Item is too short.
+        app_name::TestModel {
+            tags: Some(vec!["r".to_string()]),
+            ..Default::default()
+        },
+        // Item is empty.
+        app_name::TestModel {
+            tags: Some(vec!["rust".to_string(), "  ".to_string()]),
+            ..Default::default()
+        },
+        // Too many items.
+        app_name::TestModel {
+            tags: Some(vec![
+                "a1".to_string(),
+                "b2".to_string(),
+                "c3".to_string(),
+                "d4".to_string(),
+            ]),
+            ..Default::default()
+        },
+        // Items are repeated ( after trimming ).
+        app_name::TestModel {
+            tags: Some(vec!["rust".to_string(), "rust ".to_string()]),
+            ..Default::default()
+        },
+        // Too few items.
+        app_name::TestModel {
+            ratings: Some(vec![4]),
+            ..Default::default()
+        },
+        // Item is out of range.
+        app_name::TestModel {
+            ratings: Some(vec![4, 6]),
+            ..Default::default()
+        },
+        // Items are repeated.
+        app_name::TestModel {
+            weights: Some(vec![1.0, 2.0, 1.0]),
+            ..Default::default()
+        },
+    ];
+    for mut test_model in invalid_models {
+        let result = test_model.save(None, None)?;
+        assert!(!result.is_valid());
+    }
+    let mut test_model = app_name::TestModel {
+        ratings: Some(vec![4, 6]),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert_eq!(
+        "Item 2: Number 6 is out of range (min=1 <> max=5).",
+        result.wig()["ratings"].error
+    );
+    assert_eq!(2_i64, app_name::TestModel::estimated_document_count(None)?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
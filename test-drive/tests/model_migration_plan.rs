@@ -0,0 +1,172 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::sync::Client;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Hq3vZm8RcYw5LtB";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // ( Two versions of the same Model )
+    // *********************************************************************************************
+    pub mod v1 {
+        use super::*;
+
+        #[Model]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub age: Option<String>,
+        }
+    }
+
+    pub mod v2 {
+        use super::*;
+
+        #[Model]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "numberI32")]
+            pub age: Option<i32>,
+        }
+    }
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestOrphan {
+        #[serde(default)]
+        #[field_attrs(widget = "selectTextDyn")]
+        pub tag: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Monitor initialization
+    pub fn monitor<'a>(models: Vec<Meta>) -> Monitor<'a> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE
+            .write()
+            .unwrap()
+            .entry("default".to_string())
+            .or_insert_with(|| {
+                mongodb::sync::Client::with_uri_str("mongodb://localhost:27017").unwrap()
+            });
+        Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models,
+        }
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_migration_plan() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let model_list = vec![
+        app_name::v1::TestModel::meta()?,
+        app_name::TestOrphan::meta()?,
+    ];
+    let monitor = app_name::monitor(model_list.clone());
+    // Remove test databases
+    // ( Test databases may remain in case of errors )
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Plan for new Models
+    // ---------------------------------------------------------------------------------------------
+    let plan = monitor.plan()?;
+    assert!(!plan.is_empty());
+    assert_eq!(2, plan.models.len());
+    assert!(plan.models.iter().all(|item| item.is_new));
+    assert!(plan.dropped_collections.is_empty());
+    // The database has not been touched
+    {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let client: &Client = client_store.get("default").unwrap();
+        let database_names: Vec<String> = client.list_database_names(None, None)?;
+        assert!(!database_names.contains(&monitor.mango_tech_name()?));
+        assert!(!database_names.contains(&model_list[0].database_name));
+    }
+
+    // Migration
+    // ---------------------------------------------------------------------------------------------
+    monitor.migrat()?;
+    assert!(monitor.plan()?.is_empty(), "{}", monitor.plan()?.to_json()?);
+    app_name::v1::TestModel::to_cache()?;
+    let mut test_model = app_name::v1::TestModel {
+        name: Some("Alex".to_string()),
+        age: Some("33".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+
+    // Plan for changed Model and orphaned collection
+    // ---------------------------------------------------------------------------------------------
+    let monitor = app_name::monitor(vec![app_name::v2::TestModel::meta()?]);
+    let plan = monitor.plan()?;
+    assert!(!plan.is_empty());
+    let model_plan = &plan.models[0];
+    assert!(!model_plan.is_new);
+    assert_eq!(1, model_plan.changed_fields.len());
+    assert_eq!("age", model_plan.changed_fields[0].field);
+    assert_eq!("inputText", model_plan.changed_fields[0].old_widget);
+    assert_eq!("numberI32", model_plan.changed_fields[0].new_widget);
+    assert_eq!(1_i64, model_plan.docs_to_rewrite);
+    assert_eq!(1, plan.dropped_collections.len());
+    let dropped = &plan.dropped_collections[0];
+    assert_eq!(model_list[1].collection_name, dropped.collection);
+    assert_eq!(0_i64, dropped.docs_count);
+    assert_eq!(vec!["tag".to_string()], dropped.dyn_fields);
+    assert!(plan.to_json()?.contains("\"numberI32\""));
+    // The database has not been touched
+    {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let client: &Client = client_store.get("default").unwrap();
+        let collection_names: Vec<String> = client
+            .database(model_list[1].database_name.as_str())
+            .list_collection_names(None)?;
+        assert!(collection_names.contains(&model_list[1].collection_name));
+        let coll = client
+            .database(model_list[0].database_name.as_str())
+            .collection(model_list[0].collection_name.as_str());
+        let doc = coll.find_one(None, None)?.unwrap();
+        assert_eq!("33", doc.get_str("age")?);
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, Bson},
+    sync::Client,
+};
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "pT4wNcV8sKd2GhR";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // ( Two versions of the same Model )
+    // *********************************************************************************************
+    pub mod v1 {
+        use super::*;
+
+        #[Model]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub age: Option<String>,
+        }
+    }
+
+    pub mod v2 {
+        use super::*;
+
+        #[Model]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub username: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "numberI32")]
+            pub age: Option<i32>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub full_name: Option<String>,
+        }
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Migration steps
+    pub fn migrations() -> Vec<MigrationStep> {
+        vec![
+            MigrationStep::rename_field("TestModel", 1, "name", "username"),
+            // Fails if applied twice - the value is already a number.
+            MigrationStep::convert_field("TestModel", 2, "age", |value| {
+                Ok(Bson::Int32(value.as_str().ok_or("Not a string.")?.parse()?))
+            }),
+            MigrationStep::transform("TestModel", 3, &["full_name"], |mut doc| {
+                let full_name = format!("Mr. {}", doc.get_str("username")?);
+                doc.insert("full_name", full_name);
+                Ok(doc)
+            }),
+        ]
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration(
+        models: Vec<Meta>,
+        migrations: Vec<MigrationStep>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models,
+        }
+        .with_migrations(migrations);
+        monitor.migrat()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_migration_steps() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    // Remove test databases
+    // ( Test databases may remain in case of errors )
+    MONGODB_CLIENT_STORE.write()?.insert(
+        "default".to_string(),
+        mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+    );
+    let model_list = vec![app_name::v1::TestModel::meta()?];
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    app_name::mango_migration(model_list.clone(), Vec::new())?;
+    app_name::v1::TestModel::to_cache()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Create documents of the first version
+    // ---------------------------------------------------------------------------------------------
+    for (name, age) in vec![("Alex", "33"), ("Bob", "42")] {
+        let mut test_model = app_name::v1::TestModel {
+            name: Some(name.to_string()),
+            age: Some(age.to_string()),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+    }
+
+    // Unregistered Model in the migration steps
+    // ---------------------------------------------------------------------------------------------
+    let result = app_name::mango_migration(
+        vec![app_name::v2::TestModel::meta()?],
+        vec![MigrationStep::rename_field(
+            "Unknown", 1, "name", "username",
+        )],
+    );
+    assert!(result.is_err());
+
+    // Apply the migration steps
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration(
+        vec![app_name::v2::TestModel::meta()?],
+        app_name::migrations(),
+    )?;
+    // The steps are applied once
+    app_name::mango_migration(
+        vec![app_name::v2::TestModel::meta()?],
+        app_name::migrations(),
+    )?;
+
+    // Validating values in database
+    {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let meta: Meta = app_name::v2::TestModel::meta()?;
+        let client: &Client = client_store.get(meta.db_client_name.as_str()).unwrap();
+        let coll = client
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        assert_eq!(2_i64, coll.count_documents(None, None)?);
+        let doc = coll.find_one(doc! {"username": "Bob"}, None)?.unwrap();
+        assert!(!doc.contains_key("name"));
+        assert_eq!(42, doc.get_i32("age")?);
+        assert_eq!("Mr. Bob", doc.get_str("full_name")?);
+        // Records about the applied steps
+        let mango_tech_name = format!(
+            "mango_tech__{}__{}",
+            app_name::PROJECT_NAME,
+            app_name::UNIQUE_PROJECT_KEY
+        );
+        let coll = client
+            .database(mango_tech_name.as_str())
+            .collection("migrations");
+        assert_eq!(3_i64, coll.count_documents(None, None)?);
+    }
+
+    // Step older than the applied ones
+    // ---------------------------------------------------------------------------------------------
+    let mut migrations = app_name::migrations();
+    migrations.push(MigrationStep::rename_field(
+        "TestModel",
+        0,
+        "username",
+        "full_name",
+    ));
+    let result = app_name::mango_migration(vec![app_name::v2::TestModel::meta()?], migrations);
+    assert!(result.is_err());
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &vec![app_name::v2::TestModel::meta()?],
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
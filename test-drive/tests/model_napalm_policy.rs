@@ -0,0 +1,169 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::sync::Client;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Nw6pKs2XfRm9VtJ";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub name: Option<String>,
+    }
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestOrphan {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub title: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Monitor initialization
+    pub fn monitor<'a>(models: Vec<Meta>, napalm_policy: NapalmPolicy) -> ConfiguredMonitor<'a> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE
+            .write()
+            .unwrap()
+            .entry("default".to_string())
+            .or_insert_with(|| {
+                mongodb::sync::Client::with_uri_str("mongodb://localhost:27017").unwrap()
+            });
+        Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models,
+        }
+        .with_napalm_policy(napalm_policy)
+    }
+
+    // Get the number of documents in the collection
+    pub fn docs_count(database: &str, collection: &str) -> Result<i64, Box<dyn std::error::Error>> {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let client: &Client = client_store.get("default").unwrap();
+        let collection_names: Vec<String> =
+            client.database(database).list_collection_names(None)?;
+        if !collection_names.contains(&collection.to_string()) {
+            return Ok(-1_i64);
+        }
+        Ok(client
+            .database(database)
+            .collection(collection)
+            .count_documents(None, None)?)
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_napalm_policy() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let model_list = vec![app_name::TestModel::meta()?, app_name::TestOrphan::meta()?];
+    let database: &str = model_list[1].database_name.as_str();
+    let collection: &str = model_list[1].collection_name.as_str();
+    // Remove test databases
+    // ( Test databases may remain in case of errors )
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Migration with two Models
+    // ---------------------------------------------------------------------------------------------
+    app_name::monitor(model_list.clone(), NapalmPolicy::Drop).migrat()?;
+    app_name::TestOrphan::to_cache()?;
+    let mut test_orphan = app_name::TestOrphan {
+        title: Some("Title".to_string()),
+        ..Default::default()
+    };
+    let result = test_orphan.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(1_i64, app_name::docs_count(database, collection)?);
+
+    // Refuse - the migration returns an error, the collection is kept
+    // ---------------------------------------------------------------------------------------------
+    let monitor = app_name::monitor(vec![app_name::TestModel::meta()?], NapalmPolicy::Refuse);
+    let plan = monitor.plan()?;
+    assert_eq!(NapalmPolicy::Refuse, plan.napalm_policy);
+    assert_eq!(1, plan.dropped_collections.len());
+    assert!(monitor.migrat().is_err());
+    assert_eq!(1_i64, app_name::docs_count(database, collection)?);
+
+    // Keep - the collection and its state are kept
+    // ---------------------------------------------------------------------------------------------
+    let monitor = app_name::monitor(vec![app_name::TestModel::meta()?], NapalmPolicy::Keep);
+    let warnings: Vec<String> = monitor.migrat()?;
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].contains(collection));
+    assert_eq!(1_i64, app_name::docs_count(database, collection)?);
+    assert_eq!(1, monitor.plan()?.dropped_collections.len());
+
+    // Archive - the collection is renamed
+    // ---------------------------------------------------------------------------------------------
+    let monitor = app_name::monitor(vec![app_name::TestModel::meta()?], NapalmPolicy::Archive);
+    monitor.migrat()?;
+    assert_eq!(-1_i64, app_name::docs_count(database, collection)?);
+    assert!(monitor.plan()?.dropped_collections.is_empty());
+    let archives = monitor.archives()?;
+    assert_eq!(1, archives.len());
+    assert_eq!(collection, archives[0].collection);
+    assert!(archives[0].archive.starts_with(collection));
+    // Names of archives created within one second are unique.
+    let now = chrono::Utc::now();
+    assert_ne!(
+        ArchivedCollection::archive_name(collection, &now),
+        ArchivedCollection::archive_name(collection, &now)
+    );
+    assert_eq!(1_i64, app_name::docs_count(database, &archives[0].archive)?);
+
+    // Restore - the collection is renamed back
+    // ---------------------------------------------------------------------------------------------
+    assert!(monitor.restore("unknown_archive").is_err());
+    monitor.restore(&archives[0].archive)?;
+    assert!(monitor.archives()?.is_empty());
+    assert_eq!(
+        -1_i64,
+        app_name::docs_count(database, &archives[0].archive)?
+    );
+    let monitor = app_name::monitor(model_list.clone(), NapalmPolicy::Refuse);
+    monitor.migrat()?;
+    assert_eq!(1_i64, app_name::docs_count(database, collection)?);
+    {
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let client: &Client = client_store.get("default").unwrap();
+        let coll = client.database(database).collection(collection);
+        let doc = coll.find_one(None, None)?.unwrap();
+        assert_eq!("Title", doc.get_str("title")?);
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &model_list,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Kd5nWq8TzLr3VmC";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub name: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32")]
+        pub rank: Option<i32>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+
+    // Get the names of all documents page by page ( keyset pagination )
+    pub fn keyset_names(
+        sort: mongodb::bson::document::Document,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut names: Vec<String> = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let output_data =
+                TestModel::paginate_keyset(None, Some(sort.clone()), after.as_deref(), 4)?;
+            for doc in output_data.raw_docs()? {
+                names.push(doc.get_str("name")?.to_string());
+            }
+            if !output_data.has_next {
+                break;
+            }
+            after = output_data.next_cursor.clone();
+        }
+        Ok(names)
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_pagination() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // 25 documents, `rank` - 0, 0, 1, 1, 2, 2 ...
+    for idx in 0..25 {
+        let mut test_model = app_name::TestModel {
+            name: Some(format!("Name {}", idx)),
+            rank: Some(idx / 2),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+    }
+
+    // Page-based pagination
+    // ---------------------------------------------------------------------------------------------
+    let sort = doc! {"rank": -1};
+    let output_data = app_name::TestModel::paginate(None, Some(sort.clone()), 1, 10)?;
+    assert_eq!(10, output_data.count()?);
+    assert_eq!(25, output_data.total);
+    assert_eq!(3, output_data.page_count);
+    assert!(output_data.has_next);
+    assert!(!output_data.has_prev);
+    assert_eq!(12, output_data.raw_docs()?[0].get_i32("rank")?);
+    //
+    let output_data = app_name::TestModel::paginate(None, Some(sort.clone()), 3, 10)?;
+    assert_eq!(5, output_data.count()?);
+    assert!(!output_data.has_next);
+    assert!(output_data.has_prev);
+    assert_eq!(0, output_data.raw_docs()?[4].get_i32("rank")?);
+    // Page outside the range
+    let output_data = app_name::TestModel::paginate(None, Some(sort.clone()), 4, 10)?;
+    assert_eq!(0, output_data.count()?);
+    assert!(!output_data.has_next);
+    // With filter
+    let filter = doc! {"rank": {"$lt": 3}};
+    let output_data = app_name::TestModel::paginate(Some(filter), None, 1, 4)?;
+    assert_eq!(6, output_data.total);
+    assert_eq!(2, output_data.page_count);
+    // `per_page` is limited by `db_query_docs_limit`
+    let output_data = app_name::TestModel::paginate(None, None, 1, 0)?;
+    assert_eq!(1000, output_data.per_page);
+    assert_eq!(25, output_data.count()?);
+    // Json for the admin panel
+    let output_data = app_name::TestModel::paginate(None, None, 2, 10)?;
+    let json: serde_json::Value = serde_json::from_str(output_data.json()?.as_str())?;
+    assert_eq!(10, json["docs"].as_array().unwrap().len());
+    assert_eq!(2, json["page"]);
+    assert_eq!(25, json["total"]);
+    assert_eq!(true, json["has_next"]);
+    assert_eq!(true, json["has_prev"]);
+    assert!(json["next_cursor"].is_null());
+
+    // Keyset pagination
+    // ---------------------------------------------------------------------------------------------
+    let mut names: Vec<String> = Vec::new();
+    let mut after: Option<String> = None;
+    let mut pages = 0;
+    loop {
+        let output_data =
+            app_name::TestModel::paginate_keyset(None, Some(sort.clone()), after.as_deref(), 10)?;
+        assert_eq!(after.is_some(), output_data.has_prev);
+        pages += 1;
+        for doc in output_data.raw_docs()? {
+            names.push(doc.get_str("name")?.to_string());
+        }
+        if !output_data.has_next {
+            assert!(output_data.next_cursor.is_none());
+            break;
+        }
+        after = output_data.next_cursor.clone();
+    }
+    assert_eq!(3, pages);
+    assert_eq!(25, names.len());
+    names.sort();
+    names.dedup();
+    assert_eq!(25, names.len());
+    // Invalid cursor and sorting
+    assert!(app_name::TestModel::paginate_keyset(None, None, Some("zz"), 10).is_err());
+    let sort = doc! {"rank": 1, "name": 1};
+    assert!(app_name::TestModel::paginate_keyset(None, Some(sort), None, 10).is_err());
+    // Empty sorting - by `_id`
+    assert_eq!(25, app_name::keyset_names(doc! {})?.len());
+    // Documents with a null `rank` are not lost
+    for idx in 25..30 {
+        let mut test_model = app_name::TestModel {
+            name: Some(format!("Name {}", idx)),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+    }
+    let null_names: Vec<String> = (25..30).map(|idx| format!("Name {}", idx)).collect();
+    // Ascending - go first
+    let names = app_name::keyset_names(doc! {"rank": 1})?;
+    assert_eq!(30, names.len());
+    assert!(names[..5].iter().all(|name| null_names.contains(name)));
+    // Descending - go last
+    let names = app_name::keyset_names(doc! {"rank": -1})?;
+    assert_eq!(30, names.len());
+    assert!(names[25..].iter().all(|name| null_names.contains(name)));
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    sync::Client,
+};
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Tb3hYv9NsKw6PzF";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true, unique = true)]
+        pub username: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputEmail", required = true)]
+        pub email: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32", min = 0, max = 150)]
+        pub age: Option<i32>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputPassword")]
+        pub password: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_patch() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    let mut test_model = app_name::TestModel {
+        username: Some("user_1".to_string()),
+        email: Some("user_1@test.net".to_string()),
+        age: Some(20),
+        password: Some("j2972K4R3uQeVFPF".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let hash = test_model.hash.clone().unwrap();
+    let mut test_model_2 = app_name::TestModel {
+        username: Some("user_2".to_string()),
+        email: Some("user_2@test.net".to_string()),
+        password: Some("j2972K4R3uQeVFPF".to_string()),
+        ..Default::default()
+    };
+    let result = test_model_2.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+
+    // Get the document from database
+    let get_doc = || -> Result<mongodb::bson::document::Document, Box<dyn std::error::Error>> {
+        let form_store = FORM_STORE.read()?;
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let form_cache: &FormCache = form_store.get(&app_name::TestModel::key()[..]).unwrap();
+        let meta: &Meta = &form_cache.meta;
+        let client: &Client = client_store.get(meta.db_client_name.as_str()).unwrap();
+        let coll = client
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let filter = doc! {"_id": ObjectId::with_string(hash.as_str())?};
+        Ok(coll.find_one(filter, None)?.unwrap())
+    };
+    let doc_before = get_doc()?;
+
+    // Patch one field
+    // ---------------------------------------------------------------------------------------------
+    let values = serde_json::json!({"age": 30});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(hash, result.hash()?);
+    let doc = get_doc()?;
+    assert_eq!(30, doc.get_i32("age")?);
+    assert_eq!("user_1", doc.get_str("username")?);
+    assert_eq!("user_1@test.net", doc.get_str("email")?);
+    assert_eq!(doc_before.get_str("password")?, doc.get_str("password")?);
+    assert_eq!(
+        doc_before.get_datetime("created_at")?,
+        doc.get_datetime("created_at")?
+    );
+    assert!(doc.get_datetime("updated_at")? >= doc_before.get_datetime("updated_at")?);
+
+    // Only the passed fields are validated
+    // ---------------------------------------------------------------------------------------------
+    let values = serde_json::json!({"age": 200});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(!result.is_valid());
+    let map_widgets = result.wig();
+    assert!(!map_widgets.get("age").unwrap().error.is_empty());
+    assert!(map_widgets.get("username").unwrap().error.is_empty());
+    assert_eq!(30, get_doc()?.get_i32("age")?);
+    //
+    let values = serde_json::json!({"username": "user_2", "email": "user_1"});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(!result.is_valid());
+    let map_widgets = result.wig();
+    assert_eq!("Is not unique.", map_widgets.get("username").unwrap().error);
+    assert!(!map_widgets.get("email").unwrap().error.is_empty());
+    assert!(map_widgets.get("age").unwrap().error.is_empty());
+    assert_eq!("user_1", get_doc()?.get_str("username")?);
+    //
+    let values = serde_json::json!({"username": "user_3", "email": null});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(!result.is_valid());
+    assert!(!result.wig().get("email").unwrap().error.is_empty());
+    //
+    let values = serde_json::json!({"username": "user_3"});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let doc = get_doc()?;
+    assert_eq!("user_3", doc.get_str("username")?);
+    assert_eq!(30, doc.get_i32("age")?);
+
+    // Invalid fields
+    // ---------------------------------------------------------------------------------------------
+    let values = serde_json::json!({"nickname": "Rust"});
+    assert!(app_name::TestModel::patch(hash.as_str(), values, None).is_err());
+    let values = serde_json::json!({"password": "j2972K4R3uQeVFPF"});
+    assert!(app_name::TestModel::patch(hash.as_str(), values, None).is_err());
+    let values = serde_json::json!({"age": 40});
+    let hash_2 = ObjectId::new().to_hex();
+    assert!(app_name::TestModel::patch(hash_2.as_str(), values, None).is_err());
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Gv2sHn7QkXr4MbW";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub username: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32")]
+        pub age: Option<i32>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberF64")]
+        pub score: Option<f64>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputDate")]
+        pub birthday: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "selectTextMult",
+            options = r#"[
+                ["rust","Rust"],
+                ["python","Python"],
+                ["go","Go"]
+            ]"#
+        )]
+        pub languages: Option<Vec<String>>,
+        #[serde(default)]
+        #[field_attrs(widget = "checkBox")]
+        pub is_active: Option<bool>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_query_builder() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    let mut hashes: Vec<String> = Vec::new();
+    for idx in 0..5 {
+        let mut test_model = app_name::TestModel {
+            username: Some(format!("user_{}", idx)),
+            age: Some(15 + idx * 5),
+            score: Some(idx as f64 / 2.0),
+            birthday: Some(format!("{}-06-15", 2005 - idx * 5)),
+            languages: Some(if idx % 2 == 0 {
+                vec!["rust".to_string()]
+            } else {
+                vec!["python".to_string(), "go".to_string()]
+            }),
+            is_active: Some(idx != 3),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+        hashes.push(test_model.hash.clone().unwrap());
+    }
+
+    // Filter
+    // ---------------------------------------------------------------------------------------------
+    let filter = app_name::TestModel::q()
+        .age()
+        .gt(18)
+        .age()
+        .lt(30)
+        .username()
+        .ne("user_2")
+        .filter()?;
+    assert_eq!(
+        Some(doc! {"age": {"$gt": 18, "$lt": 30}, "username": {"$ne": "user_2"}}),
+        filter
+    );
+    assert_eq!(
+        2,
+        app_name::TestModel::q().age().gt(18).age().lt(30).count()?
+    );
+    assert_eq!(2, app_name::TestModel::q().score().gte(1.5).count()?);
+    assert_eq!(4, app_name::TestModel::q().is_active().eq(true).count()?);
+    assert_eq!(3, app_name::TestModel::q().languages().eq("rust").count()?);
+    assert_eq!(
+        2,
+        app_name::TestModel::q()
+            .languages()
+            .is_in(vec!["go", "python"])
+            .count()?
+    );
+    assert_eq!(
+        5,
+        app_name::TestModel::q()
+            .username()
+            .regex("^USER_", "i")
+            .count()?
+    );
+    // Dates are compared as DateTime
+    assert_eq!(
+        3,
+        app_name::TestModel::q()
+            .birthday()
+            .lt("2000-01-01")
+            .count()?
+    );
+    assert_eq!(
+        1,
+        app_name::TestModel::q()
+            .birthday()
+            .eq("2005-06-15")
+            .count()?
+    );
+    // `hash` is converted to ObjectId
+    let output_data = app_name::TestModel::q()
+        .hash()
+        .eq(hashes[2].as_str())
+        .find_one()?;
+    assert!(output_data.is_valid());
+    assert_eq!("user_2", output_data.raw_doc().get_str("username")?);
+    assert_eq!(
+        4,
+        app_name::TestModel::q()
+            .hash()
+            .not_in(vec![hashes[0].as_str()])
+            .count()?
+    );
+
+    // Sort, skip, limit and projection
+    // ---------------------------------------------------------------------------------------------
+    let docs = app_name::TestModel::q()
+        .age()
+        .desc()
+        .skip(1)
+        .limit(2)
+        .find()?
+        .raw_docs()?;
+    assert_eq!(2, docs.len());
+    assert_eq!(30, docs[0].get_i32("age")?);
+    assert_eq!(25, docs[1].get_i32("age")?);
+    let doc = app_name::TestModel::q()
+        .age()
+        .asc()
+        .username()
+        .include()
+        .find_one()?
+        .raw_doc();
+    assert_eq!("user_0", doc.get_str("username")?);
+    assert!(doc.get("age").is_none());
+    let page = app_name::TestModel::q()
+        .is_active()
+        .eq(true)
+        .age()
+        .desc()
+        .paginate(1, 3)?;
+    assert_eq!(4, page.total);
+    assert_eq!(35, page.raw_docs()?[0].get_i32("age")?);
+
+    // Invalid values
+    // ---------------------------------------------------------------------------------------------
+    assert!(app_name::TestModel::q()
+        .birthday()
+        .eq("15-06-2005")
+        .filter()
+        .is_err());
+    assert!(app_name::TestModel::q().hash().eq("123").find().is_err());
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    sync::Client,
+};
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "qfhLb7tvBVUgE3m";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestTarget {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub name: Option<String>,
+    }
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestRestrict {
+        #[serde(default)]
+        #[field_attrs(widget = "selectRef", ref_model = "TestTarget")]
+        pub target: Option<String>,
+    }
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestCascade {
+        #[serde(default)]
+        #[field_attrs(widget = "selectRef", ref_model = "TestTarget", on_delete = "cascade")]
+        pub target: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "selectRefMult",
+            ref_model = "TestTarget",
+            on_delete = "set_null"
+        )]
+        pub targets: Option<Vec<String>>,
+    }
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestNested {
+        #[serde(default)]
+        #[field_attrs(widget = "selectRef", ref_model = "TestCascade", on_delete = "cascade")]
+        pub parent: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![
+            TestTarget::meta()?,
+            TestRestrict::meta()?,
+            TestCascade::meta()?,
+            TestNested::meta()?,
+        ])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgects map to cache.
+        TestTarget::to_cache()?;
+        TestRestrict::to_cache()?;
+        TestCascade::to_cache()?;
+        TestNested::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_ref_fields() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    let mut test_target = app_name::TestTarget {
+        name: Some("Target".to_string()),
+        ..Default::default()
+    };
+    let result = test_target.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let target_hash = test_target.hash.clone().unwrap();
+
+    // Reference to a non-existent document
+    // ---------------------------------------------------------------------------------------------
+    let mut test_cascade = app_name::TestCascade {
+        target: Some(ObjectId::new().to_hex()),
+        ..Default::default()
+    };
+    let result = test_cascade.save(None, None)?;
+    assert!(!result.is_valid());
+    assert!(!result.wig().get("target").unwrap().error.is_empty());
+    // Invalid hash-line
+    let mut test_cascade = app_name::TestCascade {
+        targets: Some(vec!["123".to_string()]),
+        ..Default::default()
+    };
+    let result = test_cascade.save(None, None)?;
+    assert!(!result.is_valid());
+    assert!(!result.wig().get("targets").unwrap().error.is_empty());
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_cascade = app_name::TestCascade {
+        target: Some(target_hash.clone()),
+        targets: Some(vec![target_hash.clone()]),
+        ..Default::default()
+    };
+    let result = test_cascade.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let mut test_set_null = app_name::TestCascade {
+        targets: Some(vec![target_hash.clone()]),
+        ..Default::default()
+    };
+    let result = test_set_null.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+
+    // Validating values in database
+    {
+        let form_store = FORM_STORE.read()?;
+        let client_store = MONGODB_CLIENT_STORE.read()?;
+        let form_cache: &FormCache = form_store.get(&app_name::TestCascade::key()[..]).unwrap();
+        let meta: &Meta = &form_cache.meta;
+        let client: &Client = client_store.get(meta.db_client_name.as_str()).unwrap();
+        let object_id = ObjectId::with_string(test_cascade.hash.clone().unwrap().as_str())?;
+        let coll = client
+            .database(meta.database_name.as_str())
+            .collection(meta.collection_name.as_str());
+        let filter = doc! {"_id": object_id};
+        let doc = coll.find_one(filter, None)?.unwrap();
+        assert_eq!(2_i64, coll.count_documents(None, None)?);
+        assert_eq!(target_hash, doc.get_object_id("target")?.to_hex());
+        assert_eq!(1, doc.get_array("targets")?.len());
+    }
+
+    // Populate
+    // ---------------------------------------------------------------------------------------------
+    let object_id = ObjectId::with_string(test_cascade.hash.clone().unwrap().as_str())?;
+    let output_data = app_name::TestCascade::find_one(Some(doc! {"_id": object_id}), None)?;
+    assert_eq!(
+        target_hash,
+        output_data.doc()?.get_str("target")?.to_string()
+    );
+    let doc = output_data.populate::<app_name::TestTarget>("target")?;
+    assert_eq!("Target", doc.get_document("target")?.get_str("name")?);
+    let docs =
+        app_name::TestCascade::find(None, None)?.populate::<app_name::TestTarget>("targets")?;
+    assert_eq!(2, docs.len());
+    for doc in docs {
+        assert_eq!(
+            "Target",
+            doc.get_array("targets")?[0]
+                .as_document()
+                .unwrap()
+                .get_str("name")?
+        );
+    }
+
+    // Nested cascade
+    let mut test_nested = app_name::TestNested {
+        parent: test_cascade.hash.clone(),
+        ..Default::default()
+    };
+    let result = test_nested.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+
+    // On delete - restrict
+    // ---------------------------------------------------------------------------------------------
+    let mut test_restrict = app_name::TestRestrict {
+        target: Some(target_hash.clone()),
+        ..Default::default()
+    };
+    let result = test_restrict.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let result = test_target.delete(None)?;
+    assert!(!result.is_valid());
+    assert!(!result.err_msg().is_empty());
+    assert_eq!(1_i64, app_name::TestTarget::count_documents(None, None)?);
+
+    // On delete - cascade, set_null
+    // ---------------------------------------------------------------------------------------------
+    let result = test_restrict.delete(None)?;
+    assert!(result.is_valid(), "{}", result.err_msg());
+    let result = test_target.delete(None)?;
+    assert!(result.is_valid(), "{}", result.err_msg());
+    assert_eq!(0_i64, app_name::TestTarget::count_documents(None, None)?);
+    assert_eq!(1_i64, app_name::TestCascade::count_documents(None, None)?);
+    assert_eq!(0_i64, app_name::TestNested::count_documents(None, None)?);
+    let object_id = ObjectId::with_string(test_set_null.hash.clone().unwrap().as_str())?;
+    let doc = app_name::TestCascade::find_one(Some(doc! {"_id": object_id}), None)?.raw_doc();
+    assert!(doc.get_array("targets")?.is_empty());
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
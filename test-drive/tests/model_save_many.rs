@@ -0,0 +1,184 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Wm7cGx4RtPn2LsQ";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true)]
+        pub name: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputEmail", unique = true)]
+        pub email: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+
+    // Create an instance of the Model
+    pub fn test_model(name: &str, email: &str) -> TestModel {
+        TestModel {
+            name: Some(name.to_string()),
+            email: Some(email.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_save_many() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_models = vec![
+        app_name::test_model("Name 1", "one@test.net"),
+        app_name::test_model("Name 2", "two@test.net"),
+    ];
+    let results = app_name::TestModel::save_many(&mut test_models, true)?;
+    assert_eq!(2, results.len());
+    for (result, test_model) in results.iter().zip(test_models.iter()) {
+        assert!(result.is_valid(), "{}", result.hash()?);
+        assert_eq!(result.hash()?, test_model.hash.clone().unwrap());
+    }
+    assert_eq!(2_i64, app_name::TestModel::count_documents(None, None)?);
+
+    // All or nothing - one of the Models contains errors
+    // ---------------------------------------------------------------------------------------------
+    let mut test_models = vec![
+        app_name::test_model("Name 3", "three@test.net"),
+        app_name::test_model("", "four@test.net"),
+    ];
+    let results = app_name::TestModel::save_many(&mut test_models, true)?;
+    assert!(!results[0].is_valid());
+    assert!(!results[0].wig().get("hash").unwrap().common_msg.is_empty());
+    assert!(test_models[0].hash.is_none());
+    assert!(!results[1].is_valid());
+    assert!(!results[1].wig().get("name").unwrap().error.is_empty());
+    assert_eq!(2_i64, app_name::TestModel::count_documents(None, None)?);
+    // The valid Models are saved
+    let results = app_name::TestModel::save_many(&mut test_models, false)?;
+    assert!(results[0].is_valid(), "{}", results[0].hash()?);
+    assert!(test_models[0].hash.is_some());
+    assert!(!results[1].is_valid());
+    assert!(test_models[1].hash.is_none());
+    assert_eq!(3_i64, app_name::TestModel::count_documents(None, None)?);
+
+    // Duplicate values inside the batch
+    // ---------------------------------------------------------------------------------------------
+    let mut test_models = vec![
+        app_name::test_model("Name 5", "five@test.net"),
+        app_name::test_model("Name 6", "five@test.net"),
+    ];
+    let results = app_name::TestModel::save_many(&mut test_models, true)?;
+    assert!(!results[0].is_valid());
+    assert!(!results[1].is_valid());
+    assert_eq!(
+        "Is not unique.",
+        results[1].wig().get("email").unwrap().error
+    );
+    assert_eq!(3_i64, app_name::TestModel::count_documents(None, None)?);
+    //
+    let results = app_name::TestModel::save_many(&mut test_models, false)?;
+    assert!(results[0].is_valid(), "{}", results[0].hash()?);
+    assert!(!results[1].is_valid());
+    assert_eq!(
+        "Is not unique.",
+        results[1].wig().get("email").unwrap().error
+    );
+    assert_eq!(4_i64, app_name::TestModel::count_documents(None, None)?);
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    let mut test_models = vec![
+        app_name::test_model("Name 7", "seven@test.net"),
+        app_name::test_model("Name 8", "eight@test.net"),
+    ];
+    app_name::TestModel::save_many(&mut test_models, true)?;
+    let hashes: Vec<String> = test_models
+        .iter()
+        .map(|test_model| test_model.hash.clone().unwrap())
+        .collect();
+    test_models[0].name = Some("Name 77".to_string());
+    test_models[1].email = Some("one@test.net".to_string());
+    // The second Model is not valid - nothing is updated
+    let results = app_name::TestModel::save_many(&mut test_models, true)?;
+    assert!(!results[0].is_valid());
+    assert!(!results[1].is_valid());
+    let filter = doc! {"name": "Name 77"};
+    assert_eq!(
+        0_i64,
+        app_name::TestModel::count_documents(Some(filter), None)?
+    );
+    //
+    test_models[1].email = Some("eight@test.net".to_string());
+    let results = app_name::TestModel::save_many(&mut test_models, true)?;
+    assert!(results[0].is_valid(), "{}", results[0].hash()?);
+    assert!(results[1].is_valid(), "{}", results[1].hash()?);
+    assert_eq!(hashes[0], test_models[0].hash.clone().unwrap());
+    let filter = doc! {"name": "Name 77"};
+    assert_eq!(
+        1_i64,
+        app_name::TestModel::count_documents(Some(filter), None)?
+    );
+    assert_eq!(6_i64, app_name::TestModel::count_documents(None, None)?);
+
+    // ---------------------------------------------------------------------------------------------
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Zf6tBq2WmJr8NxK";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(is_soft_delete = true)]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub name: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32")]
+        pub rank: Option<i32>,
+    }
+
+    // The same Model with the changed type of the `rank` field
+    pub mod v2 {
+        use super::*;
+
+        #[Model(is_soft_delete = true)]
+        #[derive(Serialize, Deserialize, Default)]
+        pub struct TestModel {
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub name: Option<String>,
+            #[serde(default)]
+            #[field_attrs(widget = "inputText")]
+            pub rank: Option<String>,
+        }
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+    // Migration of the changed Model
+    pub fn migrat_v2() -> Result<(), Box<dyn std::error::Error>> {
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: vec![v2::TestModel::meta()?],
+        };
+        monitor.migrat()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_soft_delete() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    let mut hash_list: Vec<String> = Vec::new();
+    for idx in 0..3 {
+        let mut test_model = app_name::TestModel {
+            name: Some(format!("Name {}", idx)),
+            rank: Some(idx),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+        hash_list.push(result.hash()?);
+    }
+
+    // Soft delete
+    // ---------------------------------------------------------------------------------------------
+    let test_model = app_name::TestModel::find_one(Some(doc! {"rank": 0}), None)?
+        .model::<app_name::TestModel>()?;
+    let output_data = test_model.delete(None)?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    // The document remains in the collection.
+    let output_data = app_name::TestModel::find_with_deleted(None, None)?;
+    assert_eq!(3, output_data.count()?);
+    let filter = doc! {"deleted_at": {"$ne": null}};
+    let output_data = app_name::TestModel::find_with_deleted(Some(filter.clone()), None)?;
+    assert_eq!(1, output_data.count()?);
+    assert_eq!(
+        hash_list[0],
+        output_data.raw_docs()?[0].get_object_id("_id")?.to_hex()
+    );
+    // Read methods exclude soft-deleted documents.
+    assert_eq!(2, app_name::TestModel::find(None, None)?.count()?);
+    assert_eq!(2, app_name::TestModel::count_documents(None, None)?);
+    assert_eq!(2, app_name::TestModel::estimated_document_count(None)?);
+    assert_eq!(2, app_name::TestModel::distinct("rank", None, None)?.len());
+    assert_eq!(2, app_name::TestModel::aggregate(Vec::new(), None)?.len());
+    assert_eq!(2, app_name::TestModel::paginate(None, None, 1, 10)?.total);
+    assert_eq!(
+        2,
+        app_name::TestModel::paginate_keyset(None, None, None, 10)?.count()?
+    );
+    assert!(!app_name::TestModel::find_one(Some(doc! {"rank": 0}), None)?.is_valid());
+    // A filter with `deleted_at` is used as is.
+    assert_eq!(
+        1,
+        app_name::TestModel::count_documents(Some(filter.clone()), None)?
+    );
+
+    // Soft-deleted documents are not updated.
+    let mut deleted_model = app_name::TestModel {
+        hash: test_model.hash.clone(),
+        name: Some("New name".to_string()),
+        ..Default::default()
+    };
+    assert!(deleted_model.save(None, None)?.is_conflict());
+    let mut deleted_models = vec![deleted_model];
+    assert!(app_name::TestModel::save_many(&mut deleted_models, false)?[0].is_conflict());
+    assert!(app_name::TestModel::patch(
+        hash_list[0].as_str(),
+        serde_json::json!({"name": "New name"}),
+        None
+    )
+    .is_err());
+    let unchanged = doc! {"rank": 0, "name": "Name 0"};
+    assert_eq!(
+        1,
+        app_name::TestModel::find_with_deleted(Some(unchanged), None)?.count()?
+    );
+
+    // Restore
+    // ---------------------------------------------------------------------------------------------
+    test_model.restore()?;
+    assert_eq!(3, app_name::TestModel::count_documents(None, None)?);
+    assert!(app_name::TestModel::find_one(Some(doc! {"rank": 0}), None)?.is_valid());
+
+    // Purge
+    // ---------------------------------------------------------------------------------------------
+    test_model.delete(None)?;
+    let output_data = test_model.purge(None)?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    assert_eq!(
+        2,
+        app_name::TestModel::find_with_deleted(None, None)?.count()?
+    );
+    assert!(test_model.restore().is_err());
+
+    // Bulk delete methods mark the documents as deleted
+    // ---------------------------------------------------------------------------------------------
+    let output_data = app_name::TestModel::delete_one(doc! {"rank": 1}, None)?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    assert_eq!(1, app_name::TestModel::count_documents(None, None)?);
+    let output_data = app_name::TestModel::find_one_and_delete(doc! {"rank": 2}, None)?;
+    assert!(output_data.is_valid());
+    assert_eq!(2, output_data.raw_doc().get_i32("rank")?);
+    assert_eq!(0, app_name::TestModel::count_documents(None, None)?);
+    let output_data = app_name::TestModel::delete_many(doc! {}, None)?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    assert_eq!(
+        2,
+        app_name::TestModel::find_with_deleted(None, None)?.count()?
+    );
+
+    // Migration with live and soft-deleted documents
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        name: Some("Name 3".to_string()),
+        rank: Some(3),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    app_name::migrat_v2()?;
+    let output_data = app_name::TestModel::find_with_deleted(None, None)?;
+    assert_eq!(3, output_data.count()?);
+    for doc in output_data.raw_docs()? {
+        assert!(doc.get_i32("rank").is_err());
+    }
+    assert_eq!(1, app_name::TestModel::count_documents(None, None)?);
+    assert_eq!(2, app_name::TestModel::count_documents(Some(filter), None)?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
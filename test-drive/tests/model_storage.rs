@@ -0,0 +1,166 @@
+use mango_orm::*;
+use mango_orm::{forms::FileData, migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Vr5kNp8WqTz3HcM";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Storage of files in memory.
+    // *********************************************************************************************
+    #[derive(Default)]
+    pub struct MemoryStorage {
+        pub files: RwLock<HashMap<String, Vec<u8>>>,
+    }
+
+    impl Storage for MemoryStorage {
+        fn put(&self, key: &str, data: &[u8]) -> Result<(), Error> {
+            self.files.write()?.insert(key.to_string(), data.to_vec());
+            Ok(())
+        }
+        fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+            self.files
+                .read()?
+                .get(key)
+                .cloned()
+                .ok_or_else(|| Error::Other(format!("{} - not found.", key)))
+        }
+        fn delete(&self, key: &str) -> Result<(), Error> {
+            self.files.write()?.remove(key);
+            Ok(())
+        }
+        fn exists(&self, key: &str) -> Result<bool, Error> {
+            Ok(self.files.read()?.contains_key(key))
+        }
+        fn url(&self, key: &str) -> String {
+            format!("/memory/{}", key)
+        }
+    }
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(storage = "memory")]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputFile")]
+        pub file: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "inputFile",
+            storage = "default",
+            value = r#"{
+                "path":"./media/hello_world.odt",
+                "url":"/media/hello_world.odt"
+            }"#
+        )]
+        pub local_file: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration(storage: Arc<MemoryStorage>) -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Register storages
+        STORAGE_STORE.write()?.insert("memory".to_string(), storage);
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_storage() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let storage = Arc::new(app_name::MemoryStorage::default());
+    app_name::mango_migration(storage.clone())?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Storages of the fields.
+    // ---------------------------------------------------------------------------------------------
+    let meta = app_name::TestModel::meta()?;
+    assert_eq!("memory", meta.map_storages["file"]);
+    assert_eq!("default", meta.map_storages["local_file"]);
+    let local_storage = field_storage(&meta, "local_file")?;
+    assert!(local_storage.exists("./media/hello_world.odt")?);
+    assert_eq!(9741, local_storage.size("./media/hello_world.odt")?);
+    assert_eq!(
+        "/media/hello_world.odt",
+        LocalStorage::default().url("./media/hello_world.odt")
+    );
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        file: Some(
+            r#"{"path":"uploads/report.txt","url":"/memory/uploads/report.txt"}"#.to_string(),
+        ),
+        ..Default::default()
+    };
+    // The file is missing in the storage.
+    assert!(test_model.save(None, None).is_err());
+    storage.put("uploads/report.txt", b"Hello, storage!")?;
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let file_data: FileData = serde_json::from_str(result.wig()["file"].value.as_str())?;
+    assert_eq!("report.txt", file_data.name);
+    assert_eq!(15, file_data.size);
+    let local_file: FileData = serde_json::from_str(result.wig()["local_file"].value.as_str())?;
+    assert_eq!(9741, local_file.size);
+
+    // Delete - files are removed from the storage of the field.
+    // ---------------------------------------------------------------------------------------------
+    let result = test_model.delete(None)?;
+    assert!(result.is_valid(), "{}", result.err_msg());
+    assert!(!storage.exists("uploads/report.txt")?);
+    // Files by default are not removed.
+    assert!(local_storage.exists("./media/hello_world.odt")?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
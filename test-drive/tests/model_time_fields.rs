@@ -0,0 +1,156 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Qn4wTz7HbLm2RcX";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(
+            widget = "inputTime",
+            value = "09:00",
+            min = "08:00",
+            max = "20:00",
+            step = "900"
+        )]
+        pub opens_at: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputTimeRange", value = "09:00-18:00")]
+        pub opening_hours: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_time_fields() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Conversion of time.
+    // ---------------------------------------------------------------------------------------------
+    assert_eq!(Some(555), forms::TimeRange::time_to_minutes("09:15"));
+    assert_eq!(None, forms::TimeRange::time_to_minutes("24:00"));
+    assert_eq!("09:15", forms::TimeRange::minutes_to_time(555));
+    let range = forms::TimeRange::parse("22:00-06:00").unwrap();
+    assert_eq!((1320, 360), (range.start, range.end));
+    assert_eq!("22:00-06:00", range.to_value());
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        opens_at: Some("10:30".to_string()),
+        opening_hours: Some("22:00-06:00".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!("10:30", result.wig()["opens_at"].value);
+    // Time is stored in minutes since midnight.
+    let output_data = app_name::TestModel::find_one(None, None)?;
+    let raw_doc = output_data.raw_doc();
+    assert_eq!(630, raw_doc.get_i32("opens_at")?);
+    assert_eq!(
+        &doc! {"start": 1320, "end": 360},
+        raw_doc.get_document("opening_hours")?
+    );
+    // Conversion to the field format.
+    let doc = output_data.doc()?;
+    assert_eq!("10:30", doc.get_str("opens_at")?);
+    assert_eq!("22:00-06:00", doc.get_str("opening_hours")?);
+    let instance = output_data.model::<app_name::TestModel>()?;
+    assert_eq!(Some("22:00-06:00".to_string()), instance.opening_hours);
+    // Query
+    assert_eq!(1, app_name::TestModel::q().opens_at().gte("10:00").count()?);
+    assert_eq!(0, app_name::TestModel::q().opens_at().gt("10:30").count()?);
+    assert_eq!(
+        1,
+        app_name::TestModel::q()
+            .opening_hours()
+            .eq("22:00-06:00")
+            .count()?
+    );
+
+    // Validation
+    // ---------------------------------------------------------------------------------------------
+    // Out of range between `min` and `max`.
+    test_model.opens_at = Some("07:45".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Does not match the step of 15 minutes.
+    test_model.opens_at = Some("10:20".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // Incorrect format.
+    test_model.opens_at = Some("10:30".to_string());
+    test_model.opening_hours = Some("9:00-18:00".to_string());
+    assert!(!test_model.check()?.is_valid());
+    // The start and the end of the range are the same.
+    test_model.opening_hours = Some("09:00-09:00".to_string());
+    assert!(!test_model.check()?.is_valid());
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    test_model.opening_hours = Some("08:00-17:30".to_string());
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let doc = app_name::TestModel::find_one(None, None)?.doc()?;
+    assert_eq!("08:00-17:30", doc.get_str("opening_hours")?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Fs9kWm3RtXn6QbL";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(timezone = "Europe/Berlin")]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputDate")]
+        pub day: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "inputDateTime",
+            min = "2021-01-01T00:00",
+            max = "2021-12-31T23:59"
+        )]
+        pub starts_at: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputDateTime", timezone = "America/New_York")]
+        pub ends_at: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputDateTime", timezone = "UTC")]
+        pub logged_at: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_timezone() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Time zones of the fields.
+    // ---------------------------------------------------------------------------------------------
+    let meta = app_name::TestModel::meta()?;
+    assert_eq!("Europe/Berlin", meta.map_timezones["day"]);
+    assert_eq!("Europe/Berlin", meta.map_timezones["starts_at"]);
+    assert_eq!("America/New_York", meta.map_timezones["ends_at"]);
+    assert_eq!("UTC", meta.map_timezones["logged_at"]);
+    assert!(DateTimeZone::new("Mars/Olympus").is_none());
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        day: Some("2021-07-01".to_string()),
+        starts_at: Some("2021-07-01T09:30".to_string()),
+        ends_at: Some("2021-07-01T14:00:15+02:00".to_string()),
+        logged_at: Some("2021-07-01T07:30:15.250Z".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    // Values are returned in the time zone of the field.
+    let map_wigets = result.wig();
+    assert_eq!("2021-07-01", map_wigets["day"].value);
+    assert_eq!("2021-07-01T09:30", map_wigets["starts_at"].value);
+    assert_eq!("2021-07-01T08:00:15", map_wigets["ends_at"].value);
+    assert_eq!("2021-07-01T07:30:15", map_wigets["logged_at"].value);
+
+    // Values in the database are in UTC.
+    // ---------------------------------------------------------------------------------------------
+    let utc = DateTimeZone::default();
+    let hash = test_model.hash.clone().unwrap();
+    let output_data = app_name::TestModel::q()
+        .hash()
+        .eq(hash.as_str())
+        .find_one()?;
+    let raw_doc = output_data.raw_doc();
+    assert_eq!(
+        "2021-06-30T22:00",
+        utc.render("inputDateTime", raw_doc.get_datetime("day")?)
+    );
+    assert_eq!(
+        "2021-07-01T07:30",
+        utc.render("inputDateTime", raw_doc.get_datetime("starts_at")?)
+    );
+    assert_eq!(
+        "2021-07-01T12:00:15",
+        utc.render("inputDateTime", raw_doc.get_datetime("ends_at")?)
+    );
+
+    // Rendering in the configured and in the requested time zone.
+    // ---------------------------------------------------------------------------------------------
+    let doc = output_data.doc()?;
+    assert_eq!("2021-07-01", doc.get_str("day")?);
+    assert_eq!("2021-07-01T09:30", doc.get_str("starts_at")?);
+    assert_eq!("2021-07-01T08:00:15", doc.get_str("ends_at")?);
+    let model: app_name::TestModel = output_data.model()?;
+    assert_eq!(Some("2021-07-01T09:30".to_string()), model.starts_at);
+    let doc = output_data.clone().with_timezone("Asia/Tokyo")?.doc()?;
+    assert_eq!("2021-07-01", doc.get_str("day")?);
+    assert_eq!("2021-07-01T16:30", doc.get_str("starts_at")?);
+    assert_eq!("2021-07-01T21:00:15", doc.get_str("ends_at")?);
+    assert!(output_data.with_timezone("Mars/Olympus").is_err());
+    let docs = app_name::TestModel::find(None, None)?
+        .with_timezone("UTC")?
+        .docs()?;
+    assert_eq!("2021-06-30", docs[0].get_str("day")?);
+    assert_eq!("2021-07-01T07:30", docs[0].get_str("starts_at")?);
+    let json_line = app_name::TestModel::find(None, None)?.json()?;
+    assert!(json_line.contains(r#""starts_at":"2021-07-01T09:30""#));
+
+    // Query values are in the time zone of the field.
+    // ---------------------------------------------------------------------------------------------
+    assert_eq!(
+        1,
+        app_name::TestModel::q()
+            .starts_at()
+            .eq("2021-07-01T09:30")
+            .count()?
+    );
+    assert_eq!(
+        1,
+        app_name::TestModel::q()
+            .starts_at()
+            .eq("2021-07-01T07:30:00Z")
+            .count()?
+    );
+    assert_eq!(1, app_name::TestModel::q().day().eq("2021-07-01").count()?);
+
+    // Invalid values.
+    // ---------------------------------------------------------------------------------------------
+    // The time does not exist in the time zone ( transition to summer time ).
+    let mut test_model = app_name::TestModel {
+        starts_at: Some("2021-03-28T02:30".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(!result.is_valid());
+    // `min` and `max` are in the time zone of the field.
+    let mut test_model = app_name::TestModel {
+        starts_at: Some("2020-12-31T23:30:00Z".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!("2021-01-01T00:30", result.wig()["starts_at"].value);
+    let mut test_model = app_name::TestModel {
+        starts_at: Some("2020-12-31T22:30:00Z".to_string()),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(!result.is_valid());
+    assert_eq!(2_i64, app_name::TestModel::estimated_document_count(None)?);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use mango_orm::*;
+use mango_orm::{migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Pc8rLw3YtGn5KsZ";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText")]
+        pub name: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32")]
+        pub rank: Option<i32>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputDate")]
+        pub date: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputDateTime")]
+        pub datetime: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_typed_results() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    let mut hash_list: Vec<String> = Vec::new();
+    for idx in 0..5 {
+        let mut test_model = app_name::TestModel {
+            name: Some(format!("Name {}", idx)),
+            rank: Some(idx),
+            date: Some("1970-02-28".to_string()),
+            datetime: Some("1970-02-28T12:30".to_string()),
+            ..Default::default()
+        };
+        let result = test_model.save(None, None)?;
+        assert!(result.is_valid(), "{}", result.hash()?);
+        hash_list.push(result.hash()?);
+    }
+
+    // Lazy iterator of Model instances
+    // ---------------------------------------------------------------------------------------------
+    let find_options = mongodb::options::FindOptions::builder()
+        .sort(Some(doc! {"rank": 1}))
+        .build();
+    let output_data = app_name::TestModel::find(None, Some(find_options))?;
+    let mut count = 0;
+    for (idx, test_model) in output_data
+        .models_iter::<app_name::TestModel>()?
+        .enumerate()
+    {
+        let test_model = test_model?;
+        assert_eq!(Some(hash_list[idx].clone()), test_model.hash);
+        assert_eq!(Some(idx as i32), test_model.rank);
+        assert_eq!(Some("1970-02-28".to_string()), test_model.date);
+        assert_eq!(Some("1970-02-28T12:30".to_string()), test_model.datetime);
+        count += 1;
+    }
+    assert_eq!(5, count);
+    // Partial consumption
+    let output_data = app_name::TestModel::find(Some(doc! {"rank": {"$gte": 3}}), None)?;
+    let test_model = output_data
+        .models_iter::<app_name::TestModel>()?
+        .next()
+        .unwrap()?;
+    assert!(test_model.rank.unwrap() >= 3);
+
+    // List of Model instances
+    // ---------------------------------------------------------------------------------------------
+    let output_data = app_name::TestModel::find(None, None)?;
+    let test_models: Vec<app_name::TestModel> = output_data.models::<app_name::TestModel>()?;
+    assert_eq!(5, test_models.len());
+    assert!(test_models.iter().all(|item| item.hash.is_some()));
+    // Empty result
+    let output_data = app_name::TestModel::find(Some(doc! {"rank": 100}), None)?;
+    assert!(output_data.models::<app_name::TestModel>()?.is_empty());
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use mango_orm::*;
+use mango_orm::{
+    forms::{FileData, ImageData},
+    migration::Monitor,
+    test_tool::del_test_db,
+};
+use metamorphose::Model;
+use serde::{Deserialize, Serialize};
+use std::{fs, sync::Arc};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Hx8pMv3TrKn5WqD";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(storage = "upload")]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputFile", accept = "text/plain,.pdf", maxsize = 64)]
+        pub file: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputFile", accept = ".txt")]
+        pub note: Option<String>,
+        #[serde(default)]
+        #[field_attrs(
+            widget = "inputImage",
+            accept = "image/*",
+            thumbnails = r#"[["xs",150]]"#
+        )]
+        pub image: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration(media_root: &str) -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Register storages
+        STORAGE_STORE.write()?.insert(
+            "upload".to_string(),
+            Arc::new(LocalStorage::new(media_root, "/media")),
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_upload() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let media_root = std::env::temp_dir().join("mango_orm_upload_Hx8pMv3TrKn5WqD");
+    let media_root = media_root.to_str().unwrap();
+    app_name::mango_migration(media_root)?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // MIME type by the content of the file.
+    // ---------------------------------------------------------------------------------------------
+    let jpeg: Vec<u8> = fs::read("./media/beautiful-mountains.jpg")?;
+    let (mime_type, extension) = app_name::TestModel::sniff_mime(jpeg.as_slice());
+    assert_eq!("image/jpeg", mime_type);
+    assert_eq!("jpg", extension);
+    assert_eq!(
+        ("text/plain".to_string(), String::new()),
+        app_name::TestModel::sniff_mime(b"Hello, upload!")
+    );
+    assert!(app_name::TestModel::is_accepted(
+        "",
+        "application/pdf",
+        "pdf"
+    ));
+    assert!(app_name::TestModel::is_accepted(
+        "image/*",
+        "image/png",
+        "png"
+    ));
+    assert!(app_name::TestModel::is_accepted(
+        ".jpeg",
+        "image/jpeg",
+        "jpg"
+    ));
+    assert!(!app_name::TestModel::is_accepted(
+        "image/png",
+        "image/jpeg",
+        "jpg"
+    ));
+
+    // Rejected uploads.
+    // ---------------------------------------------------------------------------------------------
+    // The file is empty.
+    assert!(app_name::TestModel::upload_bytes("file", b"").is_err());
+    // The file size exceeds `maxsize`.
+    assert!(app_name::TestModel::upload_bytes("file", &[b'a'; 65]).is_err());
+    // The MIME type is determined by the content.
+    assert!(app_name::TestModel::upload_bytes("file", &jpeg[..64]).is_err());
+    assert!(app_name::TestModel::upload_bytes("image", b"Hello, upload!").is_err());
+    // The field is not a file field.
+    assert!(app_name::TestModel::upload_bytes("hash", b"Hello, upload!").is_err());
+
+    // Upload of bytes.
+    // ---------------------------------------------------------------------------------------------
+    let file = app_name::TestModel::upload_bytes("file", b"Hello, upload!")?;
+    let file_data: FileData = serde_json::from_str(file.as_str())?;
+    assert!(file_data.path.starts_with(media_root));
+    assert!(file_data.path.ends_with(".txt"));
+    assert!(file_data.url.starts_with("/media/uploads/"));
+    assert_eq!(14, file_data.size);
+    assert_eq!(
+        b"Hello, upload!".to_vec(),
+        fs::read(file_data.path.as_str())?
+    );
+    // Names are collision-free.
+    let file_2 = app_name::TestModel::upload_bytes("file", b"Hello, upload!")?;
+    let file_data_2: FileData = serde_json::from_str(file_2.as_str())?;
+    assert_ne!(file_data.path, file_data_2.path);
+    fs::remove_file(file_data_2.path)?;
+    // Unrecognized text - `text/plain` and `.txt`.
+    let note = app_name::TestModel::upload_bytes("note", b"Hello, upload!")?;
+    let note_data: FileData = serde_json::from_str(note.as_str())?;
+    assert!(note_data.path.ends_with(".txt"));
+    fs::remove_file(note_data.path)?;
+    // Unrecognized binary - `application/octet-stream` without extension.
+    assert!(app_name::TestModel::upload_bytes("note", b"\xff\xfe\x00\x01").is_err());
+
+    // Upload of a temporary file.
+    // ---------------------------------------------------------------------------------------------
+    let temp_path = std::env::temp_dir().join("mango_orm_upload_Hx8pMv3TrKn5WqD.tmp");
+    let temp_path = temp_path.to_str().unwrap();
+    fs::write(temp_path, jpeg.as_slice())?;
+    let image = app_name::TestModel::upload_file("image", temp_path)?;
+    assert!(!std::path::Path::new(temp_path).exists());
+    let image_data: ImageData = serde_json::from_str(image.as_str())?;
+    assert!(image_data.path.ends_with(".jpg"));
+    assert_eq!(jpeg.len() as u32, image_data.size);
+    assert!(image_data.width > 0 && image_data.height > 0);
+
+    // Save
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        file: Some(file),
+        image: Some(image),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let image_data: ImageData = serde_json::from_str(result.wig()["image"].value.as_str())?;
+    assert!(!image_data.path_xs.is_empty());
+
+    // Delete - uploaded files are removed.
+    // ---------------------------------------------------------------------------------------------
+    let result = test_model.delete(None)?;
+    assert!(result.is_valid(), "{}", result.err_msg());
+    assert!(!std::path::Path::new(file_data.path.as_str()).exists());
+    assert!(!std::path::Path::new(image_data.path.as_str()).exists());
+    fs::remove_dir_all(media_root)?;
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
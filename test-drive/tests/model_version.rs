@@ -0,0 +1,141 @@
+use mango_orm::*;
+use mango_orm::{forms::FileData, migration::Monitor, test_tool::del_test_db};
+use metamorphose::Model;
+use mongodb::bson::doc;
+use serde::{Deserialize, Serialize};
+
+// APP NAME
+// #################################################################################################
+mod app_name {
+    use super::*;
+
+    // Test application settings
+    // *********************************************************************************************
+    pub const PROJECT_NAME: &str = "project_name";
+    pub const UNIQUE_PROJECT_KEY: &str = "Lm3xQr8VbTn6WpC";
+    pub const SERVICE_NAME: &str = "service_name";
+    pub const DATABASE_NAME: &str = "database_name";
+    pub const DB_CLIENT_NAME: &str = "default";
+    const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+
+    // Create models
+    // *********************************************************************************************
+    #[Model(is_versioned = true)]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        #[serde(default)]
+        #[field_attrs(widget = "inputText", required = true)]
+        pub username: Option<String>,
+        #[serde(default)]
+        #[field_attrs(widget = "numberI32", min = 0, max = 150)]
+        pub age: Option<i32>,
+        #[serde(default)]
+        #[field_attrs(widget = "inputFile", accept = "text/plain")]
+        pub file: Option<String>,
+    }
+
+    // Test migration
+    // *********************************************************************************************
+    // Model list
+    pub fn model_list() -> Result<Vec<Meta>, Box<dyn std::error::Error>> {
+        Ok(vec![TestModel::meta()?])
+    }
+    // Test, migration service `Mango`
+    pub fn mango_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // Caching MongoDB clients
+        MONGODB_CLIENT_STORE.write()?.insert(
+            "default".to_string(),
+            mongodb::sync::Client::with_uri_str("mongodb://localhost:27017")?,
+        );
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(PROJECT_NAME, UNIQUE_PROJECT_KEY, &model_list()?)?;
+        // Migration
+        let monitor = Monitor {
+            project_name: PROJECT_NAME,
+            unique_project_key: UNIQUE_PROJECT_KEY,
+            // Register models
+            models: model_list()?,
+        };
+        monitor.migrat()?;
+        // Add metadata and widgets map to cache.
+        TestModel::to_cache()?;
+        //
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_model_version() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    app_name::mango_migration()?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Create
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = app_name::TestModel {
+        username: Some("user_1".to_string()),
+        age: Some(20),
+        ..Default::default()
+    };
+    let result = test_model.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert!(!result.is_conflict());
+    assert_eq!(Some(1), test_model.version);
+    let hash = test_model.hash.clone().unwrap();
+
+    // Update
+    // ---------------------------------------------------------------------------------------------
+    let mut instance_1 = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?
+        .model::<app_name::TestModel>()?;
+    let mut instance_2 = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?
+        .model::<app_name::TestModel>()?;
+    assert_eq!(Some(1), instance_1.version);
+    // The first save is applied and increments the version.
+    instance_1.age = Some(30);
+    let result = instance_1.save(None, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    assert_eq!(Some(2), instance_1.version);
+    assert_eq!("2", result.wig()["version"].value);
+    // The second instance is outdated.
+    instance_2.age = Some(40);
+    let file = app_name::TestModel::upload_bytes("file", b"Hello, version!")?;
+    let file_data: FileData = serde_json::from_str(file.as_str())?;
+    instance_2.file = Some(file);
+    let result = instance_2.save(None, None)?;
+    assert!(result.is_conflict());
+    // The uploaded file is not saved - it is removed.
+    assert!(!std::path::Path::new(file_data.path.as_str()).exists());
+    assert!(result.wig()["file"].value.is_empty());
+    assert!(!result.is_valid());
+    assert!(!result.wig()["hash"].common_msg.is_empty());
+    assert_eq!(hash, result.hash()?);
+    assert_eq!(Some(1), instance_2.version);
+    let test_model = app_name::TestModel::find_one(Some(doc! {"username": "user_1"}), None)?
+        .model::<app_name::TestModel>()?;
+    assert_eq!(Some(30), test_model.age);
+    assert_eq!(Some(2), test_model.version);
+
+    // Partial update - the version is incremented.
+    // ---------------------------------------------------------------------------------------------
+    let values = serde_json::json!({"age": 50});
+    let result = app_name::TestModel::patch(hash.as_str(), values, None)?;
+    assert!(result.is_valid(), "{}", result.hash()?);
+    let result = instance_1.save(None, None)?;
+    assert!(result.is_conflict());
+    // The version can not be changed directly.
+    let values = serde_json::json!({"version": 10});
+    assert!(app_name::TestModel::patch(hash.as_str(), values, None).is_err());
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        app_name::PROJECT_NAME,
+        app_name::UNIQUE_PROJECT_KEY,
+        &app_name::model_list()?,
+    )?;
+
+    Ok(())
+}
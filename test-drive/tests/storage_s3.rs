@@ -0,0 +1,63 @@
+//! Integration test of `S3Storage` ( feature `s3` ).
+//! Requires an S3-compatible server, for example MinIO:
+//! `minio server /tmp/minio` and `mc mb local/mango-orm-test`.
+//! The test is run by: `cargo test --features s3 --test storage_s3`.
+#![cfg(feature = "s3")]
+
+use mango_orm::*;
+
+// Test settings
+// #################################################################################################
+const BUCKET_NAME: &str = "mango-orm-test";
+const REGION: &str = "us-east-1";
+const ENDPOINT: &str = "http://localhost:9000";
+const ACCESS_KEY: &str = "minioadmin";
+const SECRET_KEY: &str = "minioadmin";
+const PUBLIC_URL: &str = "http://localhost:9000/mango-orm-test/";
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_storage_s3() -> Result<(), Box<dyn std::error::Error>> {
+    // ---------------------------------------------------------------------------------------------
+    let storage = S3Storage::new(
+        BUCKET_NAME,
+        REGION,
+        ENDPOINT,
+        ACCESS_KEY,
+        SECRET_KEY,
+        PUBLIC_URL,
+    )?;
+    let key = "./media/uploads/storage_s3/Zk4mQw7RtNp2XcV.txt";
+    // Remove the object in case of errors of the previous run.
+    storage.delete(key)?;
+    // ^ ^ ^ ---------------------------------------------------------------------------------------
+
+    // Put
+    // ---------------------------------------------------------------------------------------------
+    assert!(!storage.exists(key)?);
+    assert!(storage.size(key).is_err());
+    storage.put(key, b"Hello, S3!")?;
+    assert!(storage.exists(key)?);
+    assert_eq!(10, storage.size(key)?);
+    assert_eq!(b"Hello, S3!".to_vec(), storage.get(key)?);
+    // The prefix of the key does not match another object.
+    assert!(!storage.exists("./media/uploads/storage_s3/Zk4mQw7RtNp2XcV")?);
+
+    // Url
+    // ---------------------------------------------------------------------------------------------
+    assert_eq!(
+        "http://localhost:9000/mango-orm-test/media/uploads/storage_s3/Zk4mQw7RtNp2XcV.txt",
+        storage.url(key)
+    );
+
+    // Delete
+    // ---------------------------------------------------------------------------------------------
+    storage.delete(key)?;
+    assert!(!storage.exists(key)?);
+    assert!(storage.get(key).is_err());
+    // Deleting a missing object is not an error.
+    storage.delete(key)?;
+
+    Ok(())
+}